@@ -26,7 +26,7 @@ pub use ockam_macros::{node, test};
 // ---
 
 // Export node implementation
-pub use ockam_node::{Context, DelayedEvent, Executor, NodeBuilder, WorkerBuilder};
+pub use ockam_node::{Context, DelayedEvent, Executor, NodeBuilder, OverflowPolicy, WorkerBuilder};
 // ---
 
 mod delay;
@@ -67,6 +67,9 @@ pub mod access_control {
     pub use ockam_node::access_control::*;
 }
 
+/// Node metrics
+pub use ockam_node::metrics;
+
 /// Mark an Ockam Worker implementation.
 ///
 /// This is currently implemented as a re-export of the `async_trait` macro, but
@@ -123,6 +126,9 @@ pub mod authenticated_storage {
 #[cfg(feature = "ockam_transport_tcp")]
 pub use ockam_transport_tcp::{TcpTransport, TCP};
 
+#[cfg(all(unix, feature = "ockam_transport_tcp"))]
+pub use ockam_transport_tcp::{UdsTransport, UDS};
+
 #[cfg(feature = "ockam_transport_tcp")]
 /// Tcp
 pub mod tcp {
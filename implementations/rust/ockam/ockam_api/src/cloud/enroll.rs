@@ -108,8 +108,6 @@ mod node {
             cloud_route: Route,
             body: AuthenticateToken<'_>,
         ) -> Result<Vec<u8>> {
-            // TODO: add AuthenticateAuth0Token to schema.cddl and use it here
-            let schema = None;
             let api_service;
             match body {
                 AuthenticateToken::Auth0(body) => {
@@ -118,7 +116,7 @@ mod node {
                     self.request_controller(
                         ctx,
                         api_service,
-                        schema,
+                        "authenticate_auth0_token",
                         cloud_route,
                         api_service,
                         req_builder,
@@ -131,7 +129,7 @@ mod node {
                     self.request_controller(
                         ctx,
                         api_service,
-                        schema,
+                        "authenticate_enrollment_token",
                         cloud_route,
                         api_service,
                         req_builder,
@@ -217,8 +215,8 @@ pub mod enrollment_token {
 
     // Main req/res types
 
-    #[derive(Encode, Debug)]
-    #[cfg_attr(test, derive(Decode, Clone))]
+    #[derive(Encode, Decode, Debug)]
+    #[cfg_attr(test, derive(Clone))]
     #[rustfmt::skip]
     #[cbor(map)]
     pub struct RequestEnrollmentToken<'a> {
@@ -258,8 +256,8 @@ pub mod enrollment_token {
         }
     }
 
-    #[derive(Encode, Debug)]
-    #[cfg_attr(test, derive(Decode, Clone))]
+    #[derive(Encode, Decode, Debug)]
+    #[cfg_attr(test, derive(Clone))]
     #[rustfmt::skip]
     #[cbor(map)]
     pub struct AuthenticateEnrollmentToken<'a> {
@@ -282,7 +280,9 @@ pub mod enrollment_token {
 #[cfg(test)]
 #[allow(non_snake_case)]
 pub(crate) mod tests {
-    use quickcheck::{Arbitrary, Gen};
+    use quickcheck::{quickcheck, Arbitrary, Gen, TestResult};
+
+    use crate::schema::validate;
 
     use crate::cloud::enroll::enrollment_token::{AuthenticateEnrollmentToken, EnrollmentToken};
     use crate::cloud::enroll::Token;
@@ -317,6 +317,12 @@ pub(crate) mod tests {
                 }))
             }
         }
+
+        quickcheck! {
+            fn authenticate_auth0_token(o: RandomAuthorizedAuth0Token) -> TestResult {
+                validate("authenticate_auth0_token", &o.0)
+            }
+        }
     }
 
     mod enrollment_token {
@@ -332,6 +338,16 @@ pub(crate) mod tests {
                 ))
             }
         }
+
+        quickcheck! {
+            fn enrollment_token(o: Token<'static>) -> TestResult {
+                validate("enrollment_token", &EnrollmentToken::new(o))
+            }
+
+            fn authenticate_enrollment_token(o: RandomAuthorizedEnrollmentToken) -> TestResult {
+                validate("authenticate_enrollment_token", &o.0)
+            }
+        }
     }
 
     impl Arbitrary for Token<'static> {
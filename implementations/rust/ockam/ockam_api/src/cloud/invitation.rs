@@ -11,6 +11,7 @@ use ockam_core::TypeTag;
 #[cbor(map)]
 pub struct Invitation<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<7088378>,
     #[b(1)] pub id: CowStr<'a>,
     #[b(2)] pub inviter: CowStr<'a>,
@@ -72,13 +73,12 @@ mod node {
     use minicbor::Decoder;
     use tracing::trace;
 
+    use ockam_core::api::Request;
     use ockam_core::{self, Result};
     use ockam_node::Context;
 
     use crate::cloud::{BareCloudRequestWrapper, CloudRequestWrapper};
     use crate::nodes::NodeManager;
-    use crate::request;
-    use crate::{Request, Response, Status};
 
     use super::*;
 
@@ -88,7 +88,6 @@ mod node {
         pub(crate) async fn create_invitation(
             &mut self,
             ctx: &mut Context,
-            req: &Request<'_>,
             dec: &mut Decoder<'_>,
         ) -> Result<Vec<u8>> {
             let req_wrapper: CloudRequestWrapper<CreateInvitation> = dec.decode()?;
@@ -104,27 +103,21 @@ mod node {
                 "creating invitation"
             };
 
-            let sc = self.secure_channel(cloud_route).await?;
-            let route = self.cloud_service_route(&sc.to_string(), "invitations");
-
-            let req_builder = Request::post("v0/").body(req_body);
-            let res = match request(ctx, label, "create_invitation", route, req_builder).await {
-                Ok(r) => Ok(r),
-                Err(err) => {
-                    error!(?err, "Failed to create invitation");
-                    Ok(Response::builder(req.id(), Status::InternalServerError)
-                        .body(err.to_string())
-                        .to_vec()?)
-                }
-            };
-            self.delete_secure_channel(ctx, sc).await?;
-            res
+            let req_builder = Request::post("/v0/").body(req_body);
+            self.request_controller(
+                ctx,
+                label,
+                "create_invitation",
+                cloud_route,
+                "invitations",
+                req_builder,
+            )
+            .await
         }
 
         pub(crate) async fn list_invitations(
             &mut self,
             ctx: &mut Context,
-            req: &Request<'_>,
             dec: &mut Decoder<'_>,
         ) -> Result<Vec<u8>> {
             let req_wrapper: BareCloudRequestWrapper = dec.decode()?;
@@ -133,27 +126,14 @@ mod node {
             let label = "list_invitations";
             trace!(target: TARGET, "listing invitations");
 
-            let sc = self.secure_channel(cloud_route).await?;
-            let route = self.cloud_service_route(&sc.to_string(), "invitations");
-
-            let req_builder = Request::get("v0/");
-            let res = match request(ctx, label, None, route, req_builder).await {
-                Ok(r) => Ok(r),
-                Err(err) => {
-                    error!(?err, "Failed to retrieve invitations");
-                    Ok(Response::builder(req.id(), Status::InternalServerError)
-                        .body(err.to_string())
-                        .to_vec()?)
-                }
-            };
-            self.delete_secure_channel(ctx, sc).await?;
-            res
+            let req_builder = Request::get("/v0/");
+            self.request_controller(ctx, label, None, cloud_route, "invitations", req_builder)
+                .await
         }
 
         pub(crate) async fn accept_invitation(
             &mut self,
             ctx: &mut Context,
-            req: &Request<'_>,
             dec: &mut Decoder<'_>,
             id: &str,
         ) -> Result<Vec<u8>> {
@@ -161,29 +141,16 @@ mod node {
             let cloud_route = req_wrapper.route()?;
 
             let label = "accept_invitation";
-            trace!(target: TARGET, %id, "accepting invitation");
-
-            let sc = self.secure_channel(cloud_route).await?;
-            let route = self.cloud_service_route(&sc.to_string(), "invitations");
-
-            let req_builder = Request::put(format!("v0/{id}"));
-            let res = match request(ctx, label, None, route, req_builder).await {
-                Ok(r) => Ok(r),
-                Err(err) => {
-                    error!(?err, "Failed to accept invitation");
-                    Ok(Response::builder(req.id(), Status::InternalServerError)
-                        .body(err.to_string())
-                        .to_vec()?)
-                }
-            };
-            self.delete_secure_channel(ctx, sc).await?;
-            res
+            trace!(target: TARGET, invitation = %id, "accepting invitation");
+
+            let req_builder = Request::put(format!("/v0/{id}"));
+            self.request_controller(ctx, label, None, cloud_route, "invitations", req_builder)
+                .await
         }
 
         pub(crate) async fn reject_invitation(
             &mut self,
             ctx: &mut Context,
-            req: &Request<'_>,
             dec: &mut Decoder<'_>,
             id: &str,
         ) -> Result<Vec<u8>> {
@@ -191,23 +158,11 @@ mod node {
             let cloud_route = req_wrapper.route()?;
 
             let label = "reject_invitation";
-            trace!(target: TARGET, %id, "rejecting invitation");
-
-            let sc = self.secure_channel(cloud_route).await?;
-            let route = self.cloud_service_route(&sc.to_string(), "invitations");
-
-            let req_builder = Request::delete(format!("v0/{id}"));
-            let res = match request(ctx, label, None, route, req_builder).await {
-                Ok(r) => Ok(r),
-                Err(err) => {
-                    error!(?err, "Failed to reject invitation");
-                    Ok(Response::builder(req.id(), Status::InternalServerError)
-                        .body(err.to_string())
-                        .to_vec()?)
-                }
-            };
-            self.delete_secure_channel(ctx, sc).await?;
-            res
+            trace!(target: TARGET, invitation = %id, "rejecting invitation");
+
+            let req_builder = Request::delete(format!("/v0/{id}"));
+            self.request_controller(ctx, label, None, cloud_route, "invitations", req_builder)
+                .await
         }
     }
 }
@@ -217,19 +172,17 @@ mod tests {
     use minicbor::Decoder;
     use quickcheck::{Arbitrary, Gen};
 
+    use ockam_core::api::{Method, Request, Response};
     use ockam_core::compat::collections::HashMap;
     use ockam_core::{Routed, Worker};
     use ockam_node::Context;
 
-    use crate::{Method, Request, Response};
-
     use super::*;
 
     mod schema {
-        use cddl_cat::validate_cbor_bytes;
         use quickcheck::{quickcheck, TestResult};
 
-        use crate::SCHEMA;
+        use crate::schema::validate;
 
         use super::*;
 
@@ -288,53 +241,58 @@ mod tests {
 
         quickcheck! {
             fn invitation(o: In) -> TestResult {
-                let cbor = minicbor::to_vec(&o.0).unwrap();
-                if let Err(e) = validate_cbor_bytes("invitation", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("invitation", &o.0)
             }
 
             fn invitations(o: Vec<In>) -> TestResult {
-                let empty: Vec<Invitation> = vec![];
-                let cbor = minicbor::to_vec(&empty).unwrap();
-                if let Err(e) = validate_cbor_bytes("invitations", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed();
-
                 let o: Vec<Invitation> = o.into_iter().map(|p| p.0).collect();
-                let cbor = minicbor::to_vec(&o).unwrap();
-                if let Err(e) = validate_cbor_bytes("invitations", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("invitations", &o)
             }
 
             fn create_invitation(o: CIn) -> TestResult {
-                let cbor = minicbor::to_vec(&o.0).unwrap();
-                if let Err(e) = validate_cbor_bytes("create_invitation", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("create_invitation", &o.0)
             }
         }
     }
 
     mod node_api {
+        use ockam_core::api::Status;
+        use ockam_core::{route, Route};
+        use ockam_identity::authenticated_storage::mem::InMemoryStorage;
+        use ockam_identity::{Identity, TrustEveryonePolicy};
+        use ockam_multiaddr::MultiAddr;
+        use ockam_vault::Vault;
+
         use crate::cloud::CloudRequestWrapper;
         use crate::nodes::NodeManager;
-        use crate::{route_to_multiaddr, Status};
-        use ockam_core::route;
+        use crate::route_to_multiaddr;
 
         use super::*;
 
-        #[ockam_macros::test]
-        async fn accept(ctx: &mut Context) -> ockam_core::Result<()> {
-            // Create node manager to handle requests
+        /// Serve an `InvitationServer` as the controller behind a secure
+        /// channel listener at "cloud", and start a node manager which
+        /// trusts it
+        async fn setup(ctx: &Context) -> ockam_core::Result<(Route, MultiAddr)> {
+            let controller = Identity::create(ctx, &Vault::create()).await?;
+            controller
+                .create_secure_channel_listener(
+                    "cloud",
+                    TrustEveryonePolicy,
+                    &InMemoryStorage::new(),
+                )
+                .await?;
+            ctx.start_worker("invitations", InvitationServer::default())
+                .await?;
             let route =
-                NodeManager::test_create(ctx, "invitations", InvitationServer::default()).await?;
+                NodeManager::test_create_with_controller(ctx, controller.identifier().clone())
+                    .await?;
             let cloud_route = route_to_multiaddr(&route!["cloud"]).unwrap();
+            Ok((route, cloud_route))
+        }
+
+        #[ockam_macros::test]
+        async fn accept(ctx: &mut Context) -> ockam_core::Result<()> {
+            let (route, cloud_route) = setup(ctx).await?;
 
             // Create invitation
             let req = CreateInvitation::new("invitee", "s1", Some("p1"));
@@ -345,12 +303,8 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
             let i = dec.decode::<Invitation>()?;
-            // TODO: the inviter must be the `client_identity` created, as that's the identity invoking
-            //       the API. But the simple test server here doesn't retrieve the peer identity from the
-            //       secure channel metadata.
-            //assert_eq!(&i.inviter, client_identity.id);
             assert_eq!(&i.invitee, &req.invitee);
             assert_eq!(&i.space_id, &req.space_id);
             assert_eq!(&i.project_id, &req.project_id);
@@ -366,7 +320,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
             let list = dec.decode::<Vec<Invitation>>()?;
             assert_eq!(list.len(), 1);
             assert_eq!(&list[0].id.to_string(), &i_id);
@@ -379,7 +333,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
 
             // Check that status has changed
             let mut buf = vec![];
@@ -389,7 +343,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
             let list = dec.decode::<Vec<Invitation>>()?;
             assert_eq!(list.len(), 1);
             assert_eq!(&list[0].id.to_string(), &i_id);
@@ -403,17 +357,14 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::BadRequest));
+            assert_eq!(header.status(), Some(Status::BadRequest));
 
             ctx.stop().await
         }
 
         #[ockam_macros::test]
         async fn reject(ctx: &mut Context) -> ockam_core::Result<()> {
-            // Create node manager to handle requests
-            let route =
-                NodeManager::test_create(ctx, "invitations", InvitationServer::default()).await?;
-            let cloud_route = route_to_multiaddr(&route!["cloud"]).unwrap();
+            let (route, cloud_route) = setup(ctx).await?;
 
             // Create invitation
             let req = CreateInvitation::new("invitee", "s1", Some("p1"));
@@ -424,12 +375,8 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
             let i = dec.decode::<Invitation>()?;
-            // TODO: the inviter must be the `client_identity` created, as that's the identity invoking
-            //       the API. But the simple test server here doesn't retrieve the peer identity from the
-            //       secure channel metadata.
-            //assert_eq!(&i.inviter, client_identity.id);
             assert_eq!(&i.invitee, &req.invitee);
             assert_eq!(&i.space_id, &req.space_id);
             assert_eq!(&i.project_id, &req.project_id);
@@ -445,7 +392,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
             let list = dec.decode::<Vec<Invitation>>()?;
             assert_eq!(list.len(), 1);
             assert_eq!(&list[0].id.to_string(), &i_id);
@@ -458,7 +405,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
 
             // Check that status has changed
             let mut buf = vec![];
@@ -468,7 +415,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::Ok));
+            assert_eq!(header.status(), Some(Status::Ok));
             let list = dec.decode::<Vec<Invitation>>()?;
             assert_eq!(list.len(), 1);
             assert_eq!(&list[0].id.to_string(), &i_id);
@@ -482,7 +429,7 @@ mod tests {
             let response: Vec<u8> = ctx.send_and_receive(route.clone(), buf).await?;
             let mut dec = Decoder::new(&response);
             let header = dec.decode::<Response>()?;
-            assert_eq!(header.status, Some(Status::BadRequest));
+            assert_eq!(header.status(), Some(Status::BadRequest));
 
             ctx.stop().await
         }
@@ -10,6 +10,7 @@ use ockam_multiaddr::MultiAddr;
 use crate::error::ApiError;
 
 pub mod enroll;
+pub mod invitation;
 pub mod project;
 pub mod space;
 pub mod subscription;
@@ -150,3 +151,25 @@ mod node {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::cloud::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn cloud_request_wrapper(r: String) -> TestResult {
+                let route = "/dnsaddr/localhost/tcp/4000/service/api".parse().unwrap();
+                validate("cloud_request_wrapper", &CloudRequestWrapper::new(r, &route))
+            }
+
+            fn bare_cloud_request_wrapper() -> TestResult {
+                let route = "/dnsaddr/localhost/tcp/4000/service/api".parse().unwrap();
+                validate("cloud_request_wrapper", &BareCloudRequestWrapper::bare(&route))
+            }
+        }
+    }
+}
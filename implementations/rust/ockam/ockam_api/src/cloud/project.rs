@@ -375,50 +375,49 @@ mod tests {
                 name: String::arbitrary(g).into(),
                 services: vec![String::arbitrary(g).into(), String::arbitrary(g).into()],
                 users: vec![String::arbitrary(g).into(), String::arbitrary(g).into()],
-                enforce_credentials: None,
+                enforce_credentials: Option::<bool>::arbitrary(g),
             })
         }
     }
 
     mod schema {
-        use cddl_cat::validate_cbor_bytes;
         use quickcheck::{quickcheck, TestResult};
 
-        use ockam_core::api::SCHEMA;
+        use crate::schema::validate;
 
         use super::*;
 
         quickcheck! {
             fn project(o: Pr) -> TestResult {
-                let cbor = minicbor::to_vec(&o.0).unwrap();
-                if let Err(e) = validate_cbor_bytes("project", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("project", &o.0)
             }
 
             fn projects(o: Vec<Pr>) -> TestResult {
-                let empty: Vec<Project> = vec![];
-                let cbor = minicbor::to_vec(&empty).unwrap();
-                if let Err(e) = validate_cbor_bytes("projects", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed();
-
                 let o: Vec<Project> = o.into_iter().map(|p| p.0).collect();
-                let cbor = minicbor::to_vec(&o).unwrap();
-                if let Err(e) = validate_cbor_bytes("projects", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("projects", &o)
             }
 
             fn create_project(o: CPr) -> TestResult {
-                let cbor = minicbor::to_vec(&o.0).unwrap();
-                if let Err(e) = validate_cbor_bytes("create_project", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("create_project", &o.0)
+            }
+
+            fn add_enroller(i: String, d: Option<String>) -> TestResult {
+                validate("add_enroller", &AddEnroller::new(i, d))
+            }
+
+            fn enrollers(o: Vec<(String, Option<String>, String, String)>) -> TestResult {
+                let o: Vec<Enroller> = o
+                    .into_iter()
+                    .map(|(i, d, a, c)| Enroller {
+                        #[cfg(feature = "tag")]
+                        tag: Default::default(),
+                        identity_id: i.into(),
+                        description: d.map(|d| d.into()),
+                        added_by: a.into(),
+                        created_at: c.into(),
+                    })
+                    .collect();
+                validate("enrollers", &o)
             }
         }
     }
@@ -157,10 +157,9 @@ pub mod tests {
     use super::*;
 
     mod schema {
-        use cddl_cat::validate_cbor_bytes;
         use quickcheck::{quickcheck, TestResult};
 
-        use ockam_core::api::SCHEMA;
+        use crate::schema::validate;
 
         use super::*;
 
@@ -195,35 +194,16 @@ pub mod tests {
 
         quickcheck! {
             fn space(o: Sp) -> TestResult {
-                let cbor = minicbor::to_vec(&o.0).unwrap();
-                if let Err(e) = validate_cbor_bytes("space", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("space", &o.0)
             }
 
             fn spaces(o: Vec<Sp>) -> TestResult {
-                let empty: Vec<Space> = vec![];
-                let cbor = minicbor::to_vec(&empty).unwrap();
-                if let Err(e) = validate_cbor_bytes("spaces", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed();
-
                 let o: Vec<Space> = o.into_iter().map(|p| p.0).collect();
-                let cbor = minicbor::to_vec(&o).unwrap();
-                if let Err(e) = validate_cbor_bytes("spaces", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("spaces", &o)
             }
 
             fn create_space(o: CSp) -> TestResult {
-                let cbor = minicbor::to_vec(&o.0).unwrap();
-                if let Err(e) = validate_cbor_bytes("create_space", SCHEMA, &cbor) {
-                    return TestResult::error(e.to_string())
-                }
-                TestResult::passed()
+                validate("create_space", &o.0)
             }
         }
     }
@@ -213,3 +213,44 @@ mod node {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::cloud::subscription::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn activate_existing(i: String, d: String) -> TestResult {
+                validate("activate_request", &ActivateSubscription::existing(i, d))
+            }
+
+            fn activate_create(n: String, e: Vec<String>, d: String) -> TestResult {
+                validate("activate_request", &ActivateSubscription::create(n, &e, d))
+            }
+
+            fn subscriptions(o: Vec<(Vec<String>, Option<String>)>) -> TestResult {
+                let o: Vec<Subscription> = o
+                    .into_iter()
+                    .map(|(f, s)| {
+                        let f = |i: usize| f.get(i).cloned().unwrap_or_default().into();
+                        Subscription {
+                            #[cfg(feature = "tag")]
+                            tag: Default::default(),
+                            id: f(0),
+                            marketplace: f(1),
+                            status: f(2),
+                            entitlements: f(3),
+                            metadata: f(4),
+                            contact_info: f(5),
+                            space_id: s.map(|s| s.into()),
+                        }
+                    })
+                    .collect();
+                validate("subscriptions", &o)
+            }
+        }
+    }
+}
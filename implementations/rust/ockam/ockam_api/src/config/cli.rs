@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::{
     env,
+    net::SocketAddr,
     path::{Path, PathBuf},
 };
 
@@ -117,6 +118,31 @@ pub struct NodeConfig {
 
     pub pid: Option<i32>,
     pub state_dir: Option<PathBuf>,
+
+    #[serde(default)]
+    pub metrics_address: Option<SocketAddr>,
+
+    /// Unix domain socket serving the node manager API, if enabled
+    #[serde(default)]
+    pub api_socket: Option<PathBuf>,
+
+    /// Whether the node manager API is also served over TCP when it
+    /// is served on a Unix domain socket
+    #[serde(default)]
+    pub tcp_api: bool,
+
+    /// The systemd unit running the node, if it is service-managed
+    #[serde(default)]
+    pub service: Option<NodeService>,
+}
+
+/// A systemd unit running a node
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeService {
+    /// Name of the unit
+    pub unit: String,
+    /// Whether this is a system unit rather than a user unit
+    pub system: bool,
 }
 
 fn default_name() -> String {
@@ -0,0 +1,726 @@
+//! A self-hosted controller
+//!
+//! Serves the spaces, projects and enrollment token APIs of the Ockam
+//! Orchestrator controller from a node, so that installations without
+//! access to the hosted controller can point their nodes at it with
+//! `OCKAM_CONTROLLER_ADDR` and `OCKAM_CONTROLLER_IDENTITY_ID`.
+//!
+//! The state is stored as JSON in a local directory.  Every project
+//! is run by a node of its own, started by a [`ProjectLauncher`],
+//! which acts as the project's authority with the direct
+//! authenticator.
+//!
+//! The default secure channel listener of a node only accepts the
+//! node's own identity, identities enrolling with a token must reach
+//! the controller through a listener which accepts them.
+
+mod store;
+
+use crate::cloud::enroll::enrollment_token::{
+    AuthenticateEnrollmentToken, EnrollmentToken, RequestEnrollmentToken,
+};
+use crate::cloud::enroll::Token;
+use crate::cloud::project::{AddEnroller, CreateProject, Enroller, Project};
+use crate::cloud::space::{CreateSpace, Space};
+use crate::HexByteVec;
+use minicbor::Decoder;
+use ockam_core::api::{self, Method, Request, Response};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::rand;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Address, CowStr, Result, Routed, Worker};
+use ockam_identity::credential::Timestamp;
+use ockam_identity::{IdentityIdentifier, IdentitySecureChannelLocalInfo};
+use ockam_node::Context;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use store::{EnrollerRecord, ProjectRecord, SpaceRecord, Store, TokenRecord, User};
+use tracing::{trace, warn};
+
+#[cfg(feature = "tag")]
+use ockam_core::TypeTag;
+
+/// Address of the spaces API
+pub const SPACES: &str = "spaces";
+/// Address of the projects API, which also generates enrollment tokens
+pub const PROJECTS: &str = "projects";
+/// Address of the API authenticating enrollment tokens
+pub const ENROLLMENT_TOKEN_AUTHENTICATOR: &str = "enrollment_token_authenticator";
+
+const TARGET: &str = "ockam_api::controller";
+
+/// Starts and stops the nodes running projects
+#[async_trait]
+pub trait ProjectLauncher: Send + Sync + 'static {
+    /// Start the node of a project
+    ///
+    /// The node must run the direct authenticator for `project_id`,
+    /// reading its enrollers from the file at `enrollers`.
+    async fn launch(
+        &self,
+        ctx: &Context,
+        project_id: &str,
+        enrollers: &Path,
+    ) -> Result<ProjectNode>;
+
+    /// Stop the node of a project
+    async fn stop(&self, ctx: &Context, project_id: &str) -> Result<()>;
+}
+
+/// A node running a project, and the project's authority
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectNode {
+    /// Route to the secure channel listener of the node
+    pub access_route: String,
+    /// Identifier of the node's identity
+    pub identity: IdentityIdentifier,
+    /// The node's exported identity, which issues credentials
+    pub authority_identity: HexByteVec,
+}
+
+/// The controller APIs
+///
+/// The worker is started at [`SPACES`], [`PROJECTS`] and
+/// [`ENROLLMENT_TOKEN_AUTHENTICATOR`], and only answers requests
+/// received over a secure channel.
+pub struct Server {
+    store: Store,
+    launcher: Arc<dyn ProjectLauncher>,
+}
+
+#[ockam_core::worker]
+impl Worker for Server {
+    type Context = Context;
+    type Message = Vec<u8>;
+
+    async fn handle_message(&mut self, c: &mut Context, m: Routed<Self::Message>) -> Result<()> {
+        if let Ok(i) = IdentitySecureChannelLocalInfo::find_info(m.local_message()) {
+            let service = m.msg_addr();
+            let r = self
+                .on_request(c, service.address(), i.their_identity_id(), m.as_body())
+                .await?;
+            c.send(m.return_route(), r).await
+        } else {
+            let mut dec = Decoder::new(m.as_body());
+            let req: Request = dec.decode()?;
+            let res = api::forbidden(&req, "secure channel required").to_vec()?;
+            c.send(m.return_route(), res).await
+        }
+    }
+}
+
+impl Server {
+    /// Create a controller storing its state in `dir`
+    ///
+    /// The `admins` are allowed to use the controller in addition to
+    /// the identities which enrolled with a token.
+    pub fn new(
+        dir: &Path,
+        admins: &[IdentityIdentifier],
+        launcher: Arc<dyn ProjectLauncher>,
+    ) -> Result<Self> {
+        let mut store = Store::load(dir)?;
+        for admin in admins {
+            store.state.users.entry(admin.to_string()).or_default();
+        }
+        store.save()?;
+        Ok(Server { store, launcher })
+    }
+
+    /// The addresses the controller must be started at
+    pub fn addresses() -> Vec<Address> {
+        vec![
+            SPACES.into(),
+            PROJECTS.into(),
+            ENROLLMENT_TOKEN_AUTHENTICATOR.into(),
+        ]
+    }
+
+    async fn on_request(
+        &mut self,
+        ctx: &Context,
+        service: &str,
+        from: &IdentityIdentifier,
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        let mut dec = Decoder::new(data);
+        let req: Request = dec.decode()?;
+
+        trace! {
+            target: TARGET,
+            from    = %from,
+            service = %service,
+            id      = %req.id(),
+            method  = ?req.method(),
+            path    = %req.path(),
+            body    = %req.has_body(),
+            "request"
+        }
+
+        let from = from.to_string();
+        if service != ENROLLMENT_TOKEN_AUTHENTICATOR && !self.store.state.users.contains_key(&from)
+        {
+            warn!(target: TARGET, %from, "unknown identity");
+            return Ok(api::forbidden(&req, "unknown identity").to_vec()?);
+        }
+
+        // The controller paths may or may not have a trailing slash
+        let path: Vec<&str> = req.path().split('/').filter(|s| !s.is_empty()).collect();
+        let res = match (service, req.method(), path.as_slice()) {
+            (SPACES, Some(Method::Post), ["v0"]) => {
+                let body: CreateSpace = dec.decode()?;
+                self.create_space(&req, &from, body)?
+            }
+            (SPACES, Some(Method::Get), ["v0"]) => self.list_spaces(&req, &from)?,
+            (SPACES, Some(Method::Get), ["v0", id]) => self.get_space(&req, &from, id)?,
+            (SPACES, Some(Method::Delete), ["v0", id]) => {
+                self.delete_space(ctx, &req, &from, id).await?
+            }
+
+            (PROJECTS, Some(Method::Post), ["v0"]) => {
+                let body: RequestEnrollmentToken = dec.decode()?;
+                self.create_token(&req, &from, body)?
+            }
+            (PROJECTS, Some(Method::Post), ["v0", space_id]) => {
+                let body: CreateProject = dec.decode()?;
+                self.create_project(ctx, &req, &from, space_id, body)
+                    .await?
+            }
+            (PROJECTS, Some(Method::Get), ["v0"]) => self.list_projects(&req, &from)?,
+            (PROJECTS, Some(Method::Get), ["v0", id]) => self.get_project(&req, &from, id)?,
+            (PROJECTS, Some(Method::Delete), ["v0", space_id, id]) => {
+                self.delete_project(ctx, &req, &from, space_id, id).await?
+            }
+            (PROJECTS, Some(Method::Post), ["v0", id, "enrollers"]) => {
+                let body: AddEnroller = dec.decode()?;
+                self.add_enroller(&req, &from, id, body)?
+            }
+            (PROJECTS, Some(Method::Get), ["v0", id, "enrollers"]) => {
+                self.list_enrollers(&req, &from, id)?
+            }
+            (PROJECTS, Some(Method::Delete), ["v0", id, "enrollers", enroller]) => {
+                self.delete_enroller(&req, &from, id, enroller)?
+            }
+
+            (ENROLLMENT_TOKEN_AUTHENTICATOR, Some(Method::Post), ["v0", "enroll"]) => {
+                let body: AuthenticateEnrollmentToken = dec.decode()?;
+                self.authenticate_token(&req, from, body)?
+            }
+
+            (_, Some(_), _) => api::unknown_path(&req).to_vec()?,
+            (_, None, _) => api::invalid_method(&req).to_vec()?,
+        };
+        Ok(res)
+    }
+
+    fn create_space(
+        &mut self,
+        req: &Request<'_>,
+        from: &str,
+        body: CreateSpace,
+    ) -> Result<Vec<u8>> {
+        let state = &mut self.store.state;
+        if state.spaces.values().any(|s| s.name == *body.name) {
+            return Ok(api::bad_request(req, "a space with this name exists").to_vec()?);
+        }
+        let id = random_id();
+        let mut users = vec![from.to_string()];
+        for u in body.users.iter() {
+            if !users.iter().any(|x| x == &**u) {
+                users.push(u.to_string());
+            }
+        }
+        state.spaces.insert(
+            id.clone(),
+            SpaceRecord {
+                name: body.name.to_string(),
+                users,
+            },
+        );
+        self.store.save()?;
+        let space = space(&id, &self.store.state.spaces[&id]);
+        Ok(Response::ok(req.id()).body(space).to_vec()?)
+    }
+
+    fn list_spaces(&self, req: &Request<'_>, from: &str) -> Result<Vec<u8>> {
+        let spaces: Vec<Space> = self
+            .store
+            .state
+            .spaces
+            .iter()
+            .filter(|(_, s)| s.users.iter().any(|u| u == from))
+            .map(|(id, s)| space(id, s))
+            .collect();
+        Ok(Response::ok(req.id()).body(spaces).to_vec()?)
+    }
+
+    fn get_space(&self, req: &Request<'_>, from: &str, id: &str) -> Result<Vec<u8>> {
+        match self.member_space(from, id) {
+            Some(s) => Ok(Response::ok(req.id()).body(space(id, s)).to_vec()?),
+            None => Ok(Response::not_found(req.id()).to_vec()?),
+        }
+    }
+
+    async fn delete_space(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        from: &str,
+        id: &str,
+    ) -> Result<Vec<u8>> {
+        if self.member_space(from, id).is_none() {
+            return Ok(Response::not_found(req.id()).to_vec()?);
+        }
+        let projects: Vec<String> = self
+            .store
+            .state
+            .projects
+            .iter()
+            .filter(|(_, p)| p.space_id == id)
+            .map(|(pid, _)| pid.clone())
+            .collect();
+        for pid in projects {
+            self.remove_project(ctx, &pid).await;
+        }
+        self.store.state.spaces.remove(id);
+        self.store.save()?;
+        Ok(Response::ok(req.id()).to_vec()?)
+    }
+
+    async fn create_project(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        from: &str,
+        space_id: &str,
+        body: CreateProject<'_>,
+    ) -> Result<Vec<u8>> {
+        let space_users = match self.member_space(from, space_id) {
+            Some(s) => s.users.clone(),
+            None => return Ok(Response::not_found(req.id()).to_vec()?),
+        };
+        if self
+            .store
+            .state
+            .projects
+            .values()
+            .any(|p| p.name == *body.name)
+        {
+            return Ok(api::bad_request(req, "a project with this name exists").to_vec()?);
+        }
+
+        // The creator of a project can enroll its members
+        let id = random_id();
+        let mut enrollers = BTreeMap::new();
+        enrollers.insert(
+            from.to_string(),
+            EnrollerRecord {
+                description: Some("project creator".to_string()),
+                added_by: from.to_string(),
+                created_at: now(),
+            },
+        );
+        let path = self.store.write_enrollers(&id, &enrollers)?;
+        let node = match self.launcher.launch(ctx, &id, &path).await {
+            Ok(node) => node,
+            Err(e) => {
+                warn!(target: TARGET, project = %id, "failed to launch project node: {e}");
+                self.store.remove_project_dir(&id);
+                return Ok(api::internal_error(req, "failed to launch the project node").to_vec()?);
+            }
+        };
+
+        let mut users = space_users;
+        for u in body.users.iter() {
+            if !users.iter().any(|x| x == &**u) {
+                users.push(u.to_string());
+            }
+        }
+        self.store.state.projects.insert(
+            id.clone(),
+            ProjectRecord {
+                name: body.name.to_string(),
+                space_id: space_id.to_string(),
+                services: body.services.iter().map(|s| s.to_string()).collect(),
+                users,
+                node,
+                enrollers,
+            },
+        );
+        self.store.save()?;
+        let project = self.project(&id).expect("project was just added");
+        Ok(Response::ok(req.id()).body(project).to_vec()?)
+    }
+
+    fn list_projects(&self, req: &Request<'_>, from: &str) -> Result<Vec<u8>> {
+        let projects: Vec<Project> = self
+            .store
+            .state
+            .projects
+            .iter()
+            .filter(|(_, p)| self.member_space(from, &p.space_id).is_some())
+            .filter_map(|(id, _)| self.project(id))
+            .collect();
+        Ok(Response::ok(req.id()).body(projects).to_vec()?)
+    }
+
+    fn get_project(&self, req: &Request<'_>, from: &str, id: &str) -> Result<Vec<u8>> {
+        match self.member_project(from, id) {
+            Some(_) => match self.project(id) {
+                Some(p) => Ok(Response::ok(req.id()).body(p).to_vec()?),
+                None => Ok(Response::not_found(req.id()).to_vec()?),
+            },
+            None => Ok(Response::not_found(req.id()).to_vec()?),
+        }
+    }
+
+    async fn delete_project(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        from: &str,
+        space_id: &str,
+        id: &str,
+    ) -> Result<Vec<u8>> {
+        match self.member_project(from, id) {
+            Some(p) if p.space_id == space_id => {}
+            _ => return Ok(Response::not_found(req.id()).to_vec()?),
+        }
+        self.remove_project(ctx, id).await;
+        self.store.save()?;
+        Ok(Response::ok(req.id()).to_vec()?)
+    }
+
+    /// Stop the node of a project and forget the project
+    async fn remove_project(&mut self, ctx: &Context, id: &str) {
+        if let Err(e) = self.launcher.stop(ctx, id).await {
+            warn!(target: TARGET, project = %id, "failed to stop project node: {e}");
+        }
+        self.store.state.projects.remove(id);
+        self.store.remove_project_dir(id);
+    }
+
+    fn add_enroller(
+        &mut self,
+        req: &Request<'_>,
+        from: &str,
+        id: &str,
+        body: AddEnroller,
+    ) -> Result<Vec<u8>> {
+        if self.member_project(from, id).is_none() {
+            return Ok(Response::not_found(req.id()).to_vec()?);
+        }
+        if IdentityIdentifier::try_from(&*body.identity_id).is_err() {
+            return Ok(api::bad_request(req, "invalid identity identifier").to_vec()?);
+        }
+        let record = EnrollerRecord {
+            description: body.description.as_ref().map(|d| d.to_string()),
+            added_by: from.to_string(),
+            created_at: now(),
+        };
+        let enroller = enroller(&body.identity_id, &record);
+        let project = self
+            .store
+            .state
+            .projects
+            .get_mut(id)
+            .expect("project exists");
+        project
+            .enrollers
+            .insert(body.identity_id.to_string(), record);
+        let enrollers = &self.store.state.projects[id].enrollers;
+        self.store.write_enrollers(id, enrollers)?;
+        self.store.save()?;
+        Ok(Response::ok(req.id()).body(enroller).to_vec()?)
+    }
+
+    fn list_enrollers(&self, req: &Request<'_>, from: &str, id: &str) -> Result<Vec<u8>> {
+        match self.member_project(from, id) {
+            Some(p) => {
+                let enrollers: Vec<Enroller> = p
+                    .enrollers
+                    .iter()
+                    .map(|(identity, e)| enroller(identity, e))
+                    .collect();
+                Ok(Response::ok(req.id()).body(enrollers).to_vec()?)
+            }
+            None => Ok(Response::not_found(req.id()).to_vec()?),
+        }
+    }
+
+    fn delete_enroller(
+        &mut self,
+        req: &Request<'_>,
+        from: &str,
+        id: &str,
+        identity: &str,
+    ) -> Result<Vec<u8>> {
+        if self.member_project(from, id).is_none() {
+            return Ok(Response::not_found(req.id()).to_vec()?);
+        }
+        let project = self
+            .store
+            .state
+            .projects
+            .get_mut(id)
+            .expect("project exists");
+        if project.enrollers.remove(identity).is_none() {
+            return Ok(Response::not_found(req.id()).to_vec()?);
+        }
+        let enrollers = &self.store.state.projects[id].enrollers;
+        self.store.write_enrollers(id, enrollers)?;
+        self.store.save()?;
+        Ok(Response::ok(req.id()).to_vec()?)
+    }
+
+    fn create_token(
+        &mut self,
+        req: &Request<'_>,
+        from: &str,
+        body: RequestEnrollmentToken,
+    ) -> Result<Vec<u8>> {
+        let token = random_id();
+        let attributes = body
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.to_string(), HexByteVec::from(v.to_vec())))
+            .collect();
+        self.store.state.tokens.insert(
+            token.clone(),
+            TokenRecord {
+                issued_by: from.to_string(),
+                attributes,
+            },
+        );
+        self.store.save()?;
+        let body = EnrollmentToken::new(Token::new(token));
+        Ok(Response::ok(req.id()).body(body).to_vec()?)
+    }
+
+    /// Enroll the identity presenting a token, which can only be used
+    /// once
+    fn authenticate_token(
+        &mut self,
+        req: &Request<'_>,
+        from: String,
+        body: AuthenticateEnrollmentToken,
+    ) -> Result<Vec<u8>> {
+        let token = match self.store.state.tokens.remove(&*body.token.0) {
+            Some(t) => t,
+            None => return Ok(api::forbidden(req, "unknown enrollment token").to_vec()?),
+        };
+        trace!(target: TARGET, identity = %from, issued_by = %token.issued_by, "enrolled");
+        self.store.state.users.insert(
+            from,
+            User {
+                attributes: token.attributes,
+            },
+        );
+        self.store.save()?;
+        Ok(Response::ok(req.id()).to_vec()?)
+    }
+
+    /// The space with the given id, if `from` is one of its users
+    fn member_space(&self, from: &str, id: &str) -> Option<&SpaceRecord> {
+        self.store
+            .state
+            .spaces
+            .get(id)
+            .filter(|s| s.users.iter().any(|u| u == from))
+    }
+
+    /// The project with the given id, if `from` is a user of its space
+    fn member_project(&self, from: &str, id: &str) -> Option<&ProjectRecord> {
+        self.store
+            .state
+            .projects
+            .get(id)
+            .filter(|p| self.member_space(from, &p.space_id).is_some())
+    }
+
+    fn project(&self, id: &str) -> Option<Project<'static>> {
+        let p = self.store.state.projects.get(id)?;
+        let space = self.store.state.spaces.get(&p.space_id)?;
+        Some(Project {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            id: id.to_string().into(),
+            name: p.name.clone().into(),
+            space_name: space.name.clone().into(),
+            services: p.services.iter().map(|s| s.clone().into()).collect(),
+            access_route: p.node.access_route.clone().into(),
+            users: p.users.iter().map(|u| u.clone().into()).collect(),
+            space_id: p.space_id.clone().into(),
+            identity: Some(p.node.identity.clone()),
+            authority_access_route: Some(p.node.access_route.clone().into()),
+            authority_identity: Some(hex::encode(p.node.authority_identity.as_slice()).into()),
+        })
+    }
+}
+
+fn space(id: &str, s: &SpaceRecord) -> Space<'static> {
+    Space {
+        #[cfg(feature = "tag")]
+        tag: TypeTag,
+        id: id.to_string().into(),
+        name: s.name.clone().into(),
+        users: s.users.iter().map(|u| u.clone().into()).collect(),
+    }
+}
+
+fn enroller<'a>(identity: &str, e: &EnrollerRecord) -> Enroller<'a> {
+    Enroller {
+        #[cfg(feature = "tag")]
+        tag: TypeTag,
+        identity_id: CowStr::from(identity.to_string()),
+        description: e.description.clone().map(CowStr::from),
+        added_by: e.added_by.clone().into(),
+        created_at: e.created_at.clone().into(),
+    }
+}
+
+fn random_id() -> String {
+    hex::encode(rand::random::<[u8; 16]>())
+}
+
+/// The current time, in seconds since the Unix epoch
+fn now() -> String {
+    Timestamp::now()
+        .map(|t| u64::from(t).to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud::enroll::enrollment_token::EnrollmentToken;
+    use minicbor::Encode;
+    use ockam_core::api::{RequestBuilder, Status};
+    use ockam_identity::credential::Attributes;
+
+    /// Pretends to start project nodes
+    struct FakeLauncher;
+
+    #[async_trait]
+    impl ProjectLauncher for FakeLauncher {
+        async fn launch(&self, _: &Context, _: &str, enrollers: &Path) -> Result<ProjectNode> {
+            assert!(enrollers.exists());
+            Ok(ProjectNode {
+                access_route: "/dnsaddr/127.0.0.1/tcp/4000/service/api".to_string(),
+                identity: id("project"),
+                authority_identity: vec![1, 2, 3].into(),
+            })
+        }
+
+        async fn stop(&self, _: &Context, _: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn id(s: &str) -> IdentityIdentifier {
+        IdentityIdentifier::try_from(format!("P{}", hex::encode(s))).unwrap()
+    }
+
+    async fn call<T: Encode<()>>(
+        server: &mut Server,
+        ctx: &Context,
+        service: &str,
+        from: &IdentityIdentifier,
+        req: RequestBuilder<'_, T>,
+    ) -> Result<Vec<u8>> {
+        server.on_request(ctx, service, from, &req.to_vec()?).await
+    }
+
+    fn status(res: &[u8]) -> Option<Status> {
+        Decoder::new(res)
+            .decode::<api::Response>()
+            .unwrap()
+            .status()
+    }
+
+    #[ockam_macros::test]
+    async fn spaces_projects_and_tokens(ctx: &mut Context) -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let admin = id("admin");
+        let member = id("member");
+        let mut server = Server::new(
+            dir.path(),
+            std::slice::from_ref(&admin),
+            Arc::new(FakeLauncher),
+        )?;
+
+        // Unknown identities can't use the controller
+        let res = call(&mut server, ctx, SPACES, &member, Request::get("/v0/")).await?;
+        assert_eq!(status(&res), Some(Status::Forbidden));
+
+        let users: [&str; 0] = [];
+        let req = Request::post("/v0/").body(CreateSpace::new("s", &users));
+        let res = call(&mut server, ctx, SPACES, &admin, req).await?;
+        let mut dec = Decoder::new(&res);
+        assert_eq!(dec.decode::<api::Response>()?.status(), Some(Status::Ok));
+        let space: Space = dec.decode()?;
+        assert_eq!(space.users, vec![admin.to_string()]);
+
+        let path = format!("/v0/{}", space.id);
+        let req = Request::post(path).body(CreateProject::new("p", None, &users, &users));
+        let res = call(&mut server, ctx, PROJECTS, &admin, req).await?;
+        let mut dec = Decoder::new(&res);
+        assert_eq!(dec.decode::<api::Response>()?.status(), Some(Status::Ok));
+        let project: Project = dec.decode()?;
+        assert_eq!(project.space_id, space.id);
+        assert_eq!(project.authority_identity.as_deref(), Some("010203"));
+
+        // The creator of a project is its first enroller
+        let enrollers = dir
+            .path()
+            .join("projects")
+            .join(&*project.id)
+            .join("enrollers.json");
+        let enrollers = std::fs::read_to_string(enrollers).unwrap();
+        assert!(enrollers.contains(&admin.to_string()));
+
+        // An enrollment token can be used once, and makes its holder
+        // a user of the controller
+        let mut attrs = Attributes::new();
+        attrs.put("role", b"member");
+        let req = Request::post("v0/").body(RequestEnrollmentToken::new(attrs));
+        let res = call(&mut server, ctx, PROJECTS, &admin, req).await?;
+        let mut dec = Decoder::new(&res);
+        assert_eq!(dec.decode::<api::Response>()?.status(), Some(Status::Ok));
+        let token: EnrollmentToken = dec.decode()?;
+        let body = AuthenticateEnrollmentToken::new(token);
+        for expected in [Status::Ok, Status::Forbidden] {
+            let req = Request::post("v0/enroll").body(body.clone());
+            let res = call(
+                &mut server,
+                ctx,
+                ENROLLMENT_TOKEN_AUTHENTICATOR,
+                &member,
+                req,
+            )
+            .await?;
+            assert_eq!(status(&res), Some(expected));
+        }
+
+        // Members only see the spaces they belong to
+        let res = call(&mut server, ctx, SPACES, &member, Request::get("/v0/")).await?;
+        let mut dec = Decoder::new(&res);
+        assert_eq!(dec.decode::<api::Response>()?.status(), Some(Status::Ok));
+        assert!(dec.decode::<Vec<Space>>()?.is_empty());
+
+        // The state survives a restart
+        drop(server);
+        let mut server = Server::new(dir.path(), &[], Arc::new(FakeLauncher))?;
+        let res = call(&mut server, ctx, PROJECTS, &admin, Request::get("/v0")).await?;
+        let mut dec = Decoder::new(&res);
+        assert_eq!(dec.decode::<api::Response>()?.status(), Some(Status::Ok));
+        assert_eq!(dec.decode::<Vec<Project>>()?.len(), 1);
+
+        let path = format!("/v0/{}/{}", space.id, project.id);
+        let res = call(&mut server, ctx, PROJECTS, &admin, Request::delete(path)).await?;
+        assert_eq!(status(&res), Some(Status::Ok));
+        assert!(!dir.path().join("projects").join(&*project.id).exists());
+
+        ctx.stop().await
+    }
+}
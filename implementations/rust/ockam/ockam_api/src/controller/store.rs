@@ -0,0 +1,128 @@
+use crate::controller::ProjectNode;
+use crate::HexByteVec;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "controller.json";
+
+/// The state of a controller, persisted as JSON
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct State {
+    /// Identities allowed to use the controller, by identifier
+    #[serde(default)]
+    pub(super) users: BTreeMap<String, User>,
+    #[serde(default)]
+    pub(super) spaces: BTreeMap<String, SpaceRecord>,
+    #[serde(default)]
+    pub(super) projects: BTreeMap<String, ProjectRecord>,
+    /// Unused enrollment tokens
+    #[serde(default)]
+    pub(super) tokens: BTreeMap<String, TokenRecord>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct User {
+    /// Attributes of the enrollment token the user enrolled with
+    #[serde(default)]
+    pub(super) attributes: BTreeMap<String, HexByteVec>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct SpaceRecord {
+    pub(super) name: String,
+    pub(super) users: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct ProjectRecord {
+    pub(super) name: String,
+    pub(super) space_id: String,
+    pub(super) services: Vec<String>,
+    pub(super) users: Vec<String>,
+    pub(super) node: ProjectNode,
+    #[serde(default)]
+    pub(super) enrollers: BTreeMap<String, EnrollerRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct EnrollerRecord {
+    pub(super) description: Option<String>,
+    pub(super) added_by: String,
+    pub(super) created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct TokenRecord {
+    pub(super) issued_by: String,
+    pub(super) attributes: BTreeMap<String, HexByteVec>,
+}
+
+/// The state of a controller and the directory it is stored in
+pub(super) struct Store {
+    dir: PathBuf,
+    pub(super) state: State,
+}
+
+impl Store {
+    /// Load the state stored in `dir`, or start with an empty state
+    pub(super) fn load(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(io_error)?;
+        let path = dir.join(STATE_FILE);
+        let state = if path.exists() {
+            let contents = fs::read_to_string(&path).map_err(io_error)?;
+            serde_json::from_str(&contents)
+                .map_err(|e| ockam_core::Error::new(Origin::Application, Kind::Invalid, e))?
+        } else {
+            State::default()
+        };
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            state,
+        })
+    }
+
+    /// Write the state, replacing the previous file atomically
+    pub(super) fn save(&self) -> Result<()> {
+        let contents = serde_json::to_string_pretty(&self.state)
+            .map_err(|e| ockam_core::Error::new(Origin::Application, Kind::Serialization, e))?;
+        let tmp = self.dir.join(format!("{STATE_FILE}.tmp"));
+        fs::write(&tmp, contents).map_err(io_error)?;
+        fs::rename(&tmp, self.dir.join(STATE_FILE)).map_err(io_error)
+    }
+
+    /// The directory of a project's files
+    pub(super) fn project_dir(&self, project_id: &str) -> PathBuf {
+        self.dir.join("projects").join(project_id)
+    }
+
+    /// Write the enrollers file read by the authenticator of a project
+    pub(super) fn write_enrollers(
+        &self,
+        project_id: &str,
+        enrollers: &BTreeMap<String, EnrollerRecord>,
+    ) -> Result<PathBuf> {
+        let dir = self.project_dir(project_id);
+        fs::create_dir_all(&dir).map_err(io_error)?;
+        let enrollers: BTreeMap<&str, serde_json::Value> = enrollers
+            .keys()
+            .map(|id| (id.as_str(), serde_json::json!({})))
+            .collect();
+        let contents = serde_json::to_string_pretty(&enrollers)
+            .map_err(|e| ockam_core::Error::new(Origin::Application, Kind::Serialization, e))?;
+        let path = dir.join("enrollers.json");
+        fs::write(&path, contents).map_err(io_error)?;
+        Ok(path)
+    }
+
+    pub(super) fn remove_project_dir(&self, project_id: &str) {
+        let _ = fs::remove_dir_all(self.project_dir(project_id));
+    }
+}
+
+fn io_error(e: std::io::Error) -> ockam_core::Error {
+    ockam_core::Error::new(Origin::Application, Kind::Io, e)
+}
@@ -2,6 +2,7 @@ pub mod auth;
 pub mod authenticator;
 pub mod cloud;
 pub mod config;
+pub mod controller;
 pub mod echoer;
 pub mod error;
 pub mod identity;
@@ -14,6 +15,9 @@ mod session;
 mod util;
 pub use util::*;
 
+#[cfg(test)]
+mod schema;
+
 #[cfg(feature = "lmdb")]
 pub mod lmdb;
 
@@ -28,6 +32,7 @@ impl DefaultAddress {
     pub const AUTHENTICATED_SERVICE: &'static str = "authenticated";
     pub const UPPERCASE_SERVICE: &'static str = "uppercase";
     pub const ECHO_SERVICE: &'static str = "echo";
+    pub const FORWARDING_SERVICE: &'static str = "forwarding_service";
     pub const CREDENTIAL_SERVICE: &'static str = "credentials";
     pub const SECURE_CHANNEL_LISTENER: &'static str = "api";
     pub const AUTHENTICATOR: &'static str = "authenticator";
@@ -1,4 +1,7 @@
 use crate::config::ConfigValues;
+use crate::nodes::models::transport::{TransportMode, TransportType};
+use ockam_identity::IdentityIdentifier;
+use ockam_multiaddr::MultiAddr;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -12,6 +15,9 @@ pub struct NodeManConfig {
     pub identity: Option<Vec<u8>>,
     /// Identity was overridden
     pub identity_was_overridden: bool,
+    /// Resources created at runtime, in creation order
+    #[serde(default)]
+    pub resources: Vec<PersistedResource>,
 }
 
 impl ConfigValues for NodeManConfig {
@@ -19,3 +25,66 @@ impl ConfigValues for NodeManConfig {
         Self::default()
     }
 }
+
+/// A resource created on the node, which is re-created when the
+/// node restarts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PersistedResource {
+    Transport {
+        tt: TransportType,
+        tm: TransportMode,
+        /// Bound socket address for listeners, peer address otherwise
+        addr: String,
+    },
+    SecureChannelListener {
+        addr: String,
+        authorized_identifiers: Option<Vec<IdentityIdentifier>>,
+    },
+    Service {
+        kind: ServiceKind,
+        addr: String,
+    },
+    Inlet {
+        alias: String,
+        bind_addr: String,
+        outlet_route: String,
+        check_credential: bool,
+    },
+    Outlet {
+        alias: String,
+        tcp_addr: String,
+        worker_addr: String,
+        check_credential: bool,
+    },
+    Forwarder {
+        address: MultiAddr,
+        alias: Option<String>,
+        at_rust_node: bool,
+        authorized: Option<IdentityIdentifier>,
+    },
+}
+
+/// The services which can be started on a node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceKind {
+    Vault,
+    Identity,
+    Authenticated,
+    Uppercase,
+    Echoer,
+    Verifier,
+    Forwarding,
+    Credentials {
+        oneway: bool,
+    },
+    Authenticator {
+        path: PathBuf,
+        project: Vec<u8>,
+    },
+    Controller {
+        path: PathBuf,
+        admins: Vec<IdentityIdentifier>,
+    },
+}
@@ -0,0 +1,123 @@
+//! Recent log records of a node, and control over its log filter
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use ockam_node::tokio::sync::watch;
+
+/// Number of log records kept in memory
+const MAX_RECORDS: usize = 1000;
+
+type SetFilter = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+
+/// Log records of a node, shared between the tracing subscriber
+/// which writes them and the node manager which serves them
+#[derive(Clone)]
+pub struct NodeLogs {
+    records: Arc<Mutex<Records>>,
+    set_filter: Option<Arc<SetFilter>>,
+    /// Sequence number of the next record, updated on every record
+    next: Arc<watch::Sender<u64>>,
+}
+
+impl Default for NodeLogs {
+    fn default() -> Self {
+        NodeLogs {
+            records: Default::default(),
+            set_filter: None,
+            next: Arc::new(watch::channel(0).0),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Records {
+    /// Sequence number of the next record
+    next: u64,
+    lines: VecDeque<String>,
+}
+
+impl NodeLogs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `f` to replace the tracing filter of the node with new
+    /// `RUST_LOG`-style directives
+    pub fn with_filter<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.set_filter = Some(Arc::new(f));
+        self
+    }
+
+    /// Record a log line, dropping the oldest one if the buffer is full
+    pub fn push(&self, line: impl Into<String>) {
+        let mut r = self.records.lock().unwrap();
+        if r.lines.len() == MAX_RECORDS {
+            r.lines.pop_front();
+        }
+        r.lines.push_back(line.into());
+        r.next += 1;
+        // There may be no one watching the records
+        let _ = self.next.send(r.next);
+    }
+
+    /// Get notified of new records
+    ///
+    /// The receiver sees a change once a record is written after it
+    /// was created.
+    pub fn watch(&self) -> watch::Receiver<u64> {
+        self.next.subscribe()
+    }
+
+    /// The records from sequence number `since` on, or from the oldest
+    /// one still kept, along with the sequence number of the next record
+    pub fn since(&self, since: u64) -> (u64, Vec<String>) {
+        let r = self.records.lock().unwrap();
+        let first = r.next - r.lines.len() as u64;
+        let skip = since.saturating_sub(first) as usize;
+        (r.next, r.lines.iter().skip(skip).cloned().collect())
+    }
+
+    pub fn set_filter(&self, directives: &str) -> Result<(), String> {
+        match &self.set_filter {
+            Some(f) => f(directives),
+            None => Err("the log filter of this node can not be changed".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_read_from_a_sequence_number() {
+        let logs = NodeLogs::new();
+        for i in 0..MAX_RECORDS + 2 {
+            logs.push(i.to_string());
+        }
+        let (next, lines) = logs.since(0);
+        assert_eq!(next, MAX_RECORDS as u64 + 2);
+        assert_eq!(lines.len(), MAX_RECORDS);
+        assert_eq!(lines[0], "2");
+
+        let (_, lines) = logs.since(next - 1);
+        assert_eq!(lines, vec![(MAX_RECORDS + 1).to_string()]);
+        assert!(logs.since(next).1.is_empty());
+    }
+
+    #[test]
+    fn watchers_see_new_records() {
+        let logs = NodeLogs::new();
+        logs.push("before");
+        let changes = logs.watch();
+        assert!(!changes.has_changed().unwrap());
+
+        logs.push("after");
+        assert!(changes.has_changed().unwrap());
+        assert_eq!(*changes.borrow(), 2);
+    }
+}
@@ -1,4 +1,5 @@
 mod config;
+pub mod logs;
 pub mod registry;
 
 pub mod service;
@@ -10,3 +11,5 @@ pub const NODEMANAGER_ADDR: &str = "_internal.nodemanager";
 
 /// The main node-manager service running on remote nodes
 pub use service::{IdentityOverride, NodeManager};
+
+pub use logs::NodeLogs;
@@ -13,11 +13,12 @@ use ockam_core::TypeTag;
 ///////////////////-!  RESPONSE BODIES
 
 /// Response body for a node status
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct NodeStatus<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<6586555>,
     #[n(1)] pub node_name: Cow<'a, str>,
     #[n(2)] pub status: Cow<'a, str>,
@@ -45,3 +46,240 @@ impl<'a> NodeStatus<'a> {
         }
     }
 }
+
+/// Response body for the metrics of a node
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct NodeMetrics<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<4215673>,
+    /// Metrics in the OpenMetrics text format
+    #[b(1)] pub text: Cow<'a, str>,
+}
+
+impl<'a> NodeMetrics<'a> {
+    pub fn new(text: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            text: text.into(),
+        }
+    }
+}
+
+/// Response body for the health of a node
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct NodeHealth<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<1825493>,
+    #[n(1)] pub node_name: Cow<'a, str>,
+    /// `Running`, or `Degraded` if a session is down or the credential
+    /// can not be refreshed
+    #[n(2)] pub status: Cow<'a, str>,
+    /// Whether every session of the node is up
+    #[n(3)] pub ready: bool,
+    #[n(4)] pub version: Cow<'a, str>,
+    #[n(5)] pub uptime_secs: u64,
+    #[n(6)] pub pid: i32,
+    #[n(7)] pub workers: u32,
+    #[n(8)] pub transports: Vec<TransportHealth<'a>>,
+    #[n(9)] pub secure_channels: Vec<SecureChannelHealth<'a>>,
+    #[n(10)] pub sessions: Vec<SessionHealth<'a>>,
+    /// Unix timestamp at which the credential of the node expires
+    #[n(11)] pub credential_expires_at: Option<u64>,
+    /// Most recent errors, oldest first
+    #[n(12)] pub recent_errors: Vec<RecentError<'a>>,
+    /// Unix timestamp of the next attempt to refresh the credential
+    #[n(13)] pub credential_refresh_at: Option<u64>,
+    /// Number of consecutive failed attempts to refresh the credential
+    #[n(14)] pub credential_refresh_failures: u32,
+    /// Error of the last failed attempt to refresh the credential
+    #[n(15)] pub credential_refresh_error: Option<Cow<'a, str>>,
+}
+
+impl<'a> NodeHealth<'a> {
+    /// A healthy node, without any transport, secure channel or session
+    pub fn new(
+        node_name: impl Into<Cow<'a, str>>,
+        version: impl Into<Cow<'a, str>>,
+        uptime_secs: u64,
+        pid: i32,
+        workers: u32,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            node_name: node_name.into(),
+            status: Cow::Borrowed("Running"),
+            ready: true,
+            version: version.into(),
+            uptime_secs,
+            pid,
+            workers,
+            transports: Vec::new(),
+            secure_channels: Vec::new(),
+            sessions: Vec::new(),
+            credential_expires_at: None,
+            recent_errors: Vec::new(),
+            credential_refresh_at: None,
+            credential_refresh_failures: 0,
+            credential_refresh_error: None,
+        }
+    }
+}
+
+/// Number of transports of a given type and mode
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct TransportHealth<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<7730261>,
+    #[n(1)] pub transport_type: Cow<'a, str>,
+    #[n(2)] pub mode: Cow<'a, str>,
+    #[n(3)] pub count: u32,
+}
+
+impl<'a> TransportHealth<'a> {
+    pub fn new(
+        transport_type: impl Into<Cow<'a, str>>,
+        mode: impl Into<Cow<'a, str>>,
+        count: u32,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            transport_type: transport_type.into(),
+            mode: mode.into(),
+            count,
+        }
+    }
+}
+
+/// State of a secure channel created by a node
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct SecureChannelHealth<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<4076348>,
+    #[n(1)] pub address: Cow<'a, str>,
+    #[n(2)] pub route: Cow<'a, str>,
+    /// `open`, or `closed` if its worker is gone
+    #[n(3)] pub state: Cow<'a, str>,
+}
+
+impl<'a> SecureChannelHealth<'a> {
+    pub fn new(
+        address: impl Into<Cow<'a, str>>,
+        route: impl Into<Cow<'a, str>>,
+        state: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            address: address.into(),
+            route: route.into(),
+            state: state.into(),
+        }
+    }
+}
+
+/// State of a session monitored by a node
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct SessionHealth<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<9164270>,
+    #[n(1)] pub key: Cow<'a, str>,
+    #[n(2)] pub address: Cow<'a, str>,
+    #[n(3)] pub up: bool,
+}
+
+impl<'a> SessionHealth<'a> {
+    pub fn new(key: impl Into<Cow<'a, str>>, address: impl Into<Cow<'a, str>>, up: bool) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            key: key.into(),
+            address: address.into(),
+            up,
+        }
+    }
+}
+
+/// A request a node failed to handle
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RecentError<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<2938514>,
+    /// Unix timestamp of the failure
+    #[n(1)] pub time: u64,
+    #[n(2)] pub path: Cow<'a, str>,
+    #[n(3)] pub message: Cow<'a, str>,
+}
+
+impl<'a> RecentError<'a> {
+    pub fn new(time: u64, path: impl Into<Cow<'a, str>>, message: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            time,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::base::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn node_status(n: String, s: String, w: u32, p: i32, t: u32) -> TestResult {
+                validate("node_status", &NodeStatus::new(&n, &s, w, p, t))
+            }
+
+            fn node_metrics(t: String) -> TestResult {
+                validate("node_metrics", &NodeMetrics::new(t))
+            }
+
+            fn node_health(
+                n: String,
+                v: String,
+                t: Vec<(String, String, u32)>,
+                c: Vec<(String, String, String)>,
+                s: Vec<(String, String, bool)>,
+                x: (Option<u64>, Option<u64>, u32, Option<String>),
+                e: Vec<(u64, String, String)>
+            ) -> TestResult {
+                let mut h = NodeHealth::new(n, v, 1, 2, 3);
+                h.transports = t.into_iter().map(|(a, b, c)| TransportHealth::new(a, b, c)).collect();
+                h.secure_channels = c.into_iter().map(|(a, b, c)| SecureChannelHealth::new(a, b, c)).collect();
+                h.sessions = s.into_iter().map(|(a, b, c)| SessionHealth::new(a, b, c)).collect();
+                h.credential_expires_at = x.0;
+                h.recent_errors = e.into_iter().map(|(a, b, c)| RecentError::new(a, b, c)).collect();
+                h.credential_refresh_at = x.1;
+                h.credential_refresh_failures = x.2;
+                h.credential_refresh_error = x.3.map(Cow::Owned);
+                validate("node_health", &h)
+            }
+        }
+    }
+}
@@ -46,3 +46,24 @@ impl<'a> PresentCredentialRequest<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::credentials::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn get_credential_request(o: bool) -> TestResult {
+                validate("get_credential_request", &GetCredentialRequest::new(o))
+            }
+
+            fn present_credential_request(o: bool) -> TestResult {
+                let route = "/dnsaddr/localhost/tcp/4000/service/api".parse().unwrap();
+                validate("present_credential_request", &PresentCredentialRequest::new(&route, o))
+            }
+        }
+    }
+}
@@ -72,6 +72,31 @@ impl<'a> CreateForwarder<'a> {
     }
 }
 
+/// Request body when instructing a node to delete a forwarder
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct DeleteForwarder<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<4417902>,
+    /// Remote address of the forwarder
+    #[b(1)] remote_address: CowStr<'a>,
+}
+
+impl<'a> DeleteForwarder<'a> {
+    pub fn new(remote_address: impl Into<CowStr<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: Default::default(),
+            remote_address: remote_address.into(),
+        }
+    }
+
+    pub fn remote_address(&self) -> &str {
+        &self.remote_address
+    }
+}
+
 /// Response body when creating a forwarder
 #[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
@@ -93,10 +118,20 @@ impl<'a> ForwarderInfo<'a> {
     pub fn remote_address(&'a self) -> &'a str {
         &self.remote_address
     }
+
+    pub fn worker_address(&'a self) -> &'a str {
+        &self.worker_address
+    }
 }
 
 impl<'a> From<RemoteForwarderInfo> for ForwarderInfo<'a> {
     fn from(inner: RemoteForwarderInfo) -> Self {
+        Self::from(&inner)
+    }
+}
+
+impl<'a> From<&RemoteForwarderInfo> for ForwarderInfo<'a> {
+    fn from(inner: &RemoteForwarderInfo) -> Self {
         Self {
             #[cfg(feature = "tag")]
             tag: Default::default(),
@@ -191,4 +226,34 @@ mod tests {
             ctx.send(msg.return_route(), msg.body()).await
         }
     }
+
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::forwarder::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn create_forwarder(a: Option<String>, r: bool, i: Option<String>) -> TestResult {
+                let addr = "/dnsaddr/localhost/tcp/4000".parse().unwrap();
+                let i = i.map(|i| IdentityIdentifier::from_key_id(&i));
+                validate("create_forwarder", &CreateForwarder::at_node(addr, a, r, i))
+            }
+
+            fn delete_forwarder(a: String) -> TestResult {
+                validate("delete_forwarder", &DeleteForwarder::new(a))
+            }
+
+            fn forwarder_info(f: String, r: String, w: String) -> TestResult {
+                let info = ForwarderInfo {
+                    #[cfg(feature = "tag")]
+                    tag: Default::default(),
+                    forwarding_route: f.into(),
+                    remote_address: r.into(),
+                    worker_address: w.into(),
+                };
+                validate("forwarder_info", &info)
+            }
+        }
+    }
 }
@@ -7,11 +7,12 @@ use ockam_core::CowBytes;
 use ockam_core::TypeTag;
 
 /// Response body when instructing a node to create a Secure Channel
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct CreateIdentityResponse<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<2187575>,
     #[b(1)] pub identity_id: Cow<'a, str>,
 }
@@ -45,11 +46,12 @@ impl<'a> LongIdentityResponse<'a> {
     }
 }
 
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct ShortIdentityResponse<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<5773131>,
     #[b(1)] pub identity_id: Cow<'a, str>,
 }
@@ -63,3 +65,27 @@ impl<'a> ShortIdentityResponse<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::identity::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn create_identity_response(i: String) -> TestResult {
+                validate("create_identity_response", &CreateIdentityResponse::new(i))
+            }
+
+            fn long_identity_response(i: Vec<u8>) -> TestResult {
+                validate("long_identity_response", &LongIdentityResponse::new(i))
+            }
+
+            fn short_identity_response(i: String) -> TestResult {
+                validate("short_identity_response", &ShortIdentityResponse::new(i))
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use minicbor::{Decode, Encode};
+use ockam_core::CowStr;
+
+#[cfg(feature = "tag")]
+use ockam_core::TypeTag;
+
+/// Request body to get the log records of a node
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct GetLogs {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<5529118>,
+    /// Sequence number of the first record to return
+    #[n(1)] pub since: u64,
+}
+
+impl GetLogs {
+    pub fn new(since: u64) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            since,
+        }
+    }
+}
+
+/// Response body with the log records of a node
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct LogRecords<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<3174906>,
+    /// Sequence number of the next record
+    #[n(1)] pub next: u64,
+    #[b(2)] pub lines: Vec<CowStr<'a>>,
+}
+
+impl<'a> LogRecords<'a> {
+    pub fn new(next: u64, lines: Vec<CowStr<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            next,
+            lines,
+        }
+    }
+}
+
+/// Request body to replace the log filter of a node
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct SetLogFilter<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<8861327>,
+    /// `RUST_LOG`-style directives, e.g. `ockam_node=debug,info`
+    #[b(1)] pub directives: CowStr<'a>,
+}
+
+impl<'a> SetLogFilter<'a> {
+    pub fn new(directives: impl Into<CowStr<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            directives: directives.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::logs::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn get_logs(s: u64) -> TestResult {
+                validate("get_logs", &GetLogs::new(s))
+            }
+
+            fn log_records(n: u64, l: Vec<String>) -> TestResult {
+                let l = l.into_iter().map(|s| s.into()).collect();
+                validate("log_records", &LogRecords::new(n, l))
+            }
+
+            fn set_log_filter(d: String) -> TestResult {
+                validate("set_log_filter", &SetLogFilter::new(d))
+            }
+        }
+    }
+}
@@ -80,11 +80,12 @@ impl<'a> CreateOutlet<'a> {
 }
 
 /// Response body when interacting with a portal endpoint
-#[derive(Clone, Debug, Decode, Encode)]
+#[derive(Clone, Debug, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct InletStatus<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<9302588>,
     #[b(1)] pub bind_addr: Cow<'a, str>,
     #[b(2)] pub worker_addr: Cow<'a, str>,
@@ -123,11 +124,12 @@ impl<'a> InletStatus<'a> {
 }
 
 /// Response body when interacting with a portal endpoint
-#[derive(Clone, Debug, Decode, Encode)]
+#[derive(Clone, Debug, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct OutletStatus<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<4012569>,
     #[b(1)] pub tcp_addr: Cow<'a, str>,
     #[b(2)] pub worker_addr: Cow<'a, str>,
@@ -166,11 +168,12 @@ impl<'a> OutletStatus<'a> {
 }
 
 /// Response body when returning a list of Inlets
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct InletList<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<8401504>,
     #[b(1)] pub list: Vec<InletStatus<'a>>
 }
@@ -186,11 +189,12 @@ impl<'a> InletList<'a> {
 }
 
 /// Response body when returning a list of Outlets
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct OutletList<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<8708916>,
     #[b(1)] pub list: Vec<OutletStatus<'a>>
 }
@@ -204,3 +208,33 @@ impl<'a> OutletList<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::portal::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn create_inlet(b: String, o: String, a: Option<String>, c: bool) -> TestResult {
+                validate("create_inlet", &CreateInlet::new(b, o, a.map(|a| a.into()), c))
+            }
+
+            fn create_outlet(t: String, w: String, a: Option<String>, c: bool) -> TestResult {
+                validate("create_outlet", &CreateOutlet::new(t, w, a.map(|a| a.into()), c))
+            }
+
+            fn inlet_list(l: Vec<(String, String, String, Option<String>)>) -> TestResult {
+                let l = l.into_iter().map(|(b, w, a, p)| InletStatus::new(b, w, a, p.map(|p| p.into()))).collect();
+                validate("inlet_list", &InletList::new(l))
+            }
+
+            fn outlet_list(l: Vec<(String, String, String, Option<String>)>) -> TestResult {
+                let l = l.into_iter().map(|(t, w, a, p)| OutletStatus::new(t, w, a, p.map(|p| p.into()))).collect();
+                validate("outlet_list", &OutletList::new(l))
+            }
+        }
+    }
+}
@@ -55,11 +55,12 @@ impl<'a> CreateSecureChannelRequest<'a> {
 }
 
 /// Response body when instructing a node to create a Secure Channel
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct CreateSecureChannelResponse<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<6056513>,
     #[b(1)] pub addr: CowStr<'a>,
 }
@@ -109,6 +110,26 @@ impl<'a> CreateSecureChannelListenerRequest<'a> {
         }
     }
 }
+/// Request body when instructing a node to delete a Secure Channel Listener
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct DeleteSecureChannelListenerRequest<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<7372150>,
+    #[b(1)] pub addr: CowStr<'a>,
+}
+
+impl<'a> DeleteSecureChannelListenerRequest<'a> {
+    pub fn new(addr: &Address) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            addr: addr.to_string().into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Decode, Encode)]
 #[rustfmt::skip]
 #[cbor(map)]
@@ -193,3 +214,74 @@ impl<'a> ShowSecureChannelResponse<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use ockam_core::LOCAL;
+
+        use crate::nodes::models::secure_channel::*;
+        use crate::schema::validate;
+
+        fn address(a: String) -> Address {
+            Address::new(LOCAL, a)
+        }
+
+        fn identifiers(ids: Option<Vec<String>>) -> Option<Vec<IdentityIdentifier>> {
+            ids.map(|ids| {
+                ids.iter()
+                    .map(|i| IdentityIdentifier::from_key_id(i))
+                    .collect()
+            })
+        }
+
+        quickcheck! {
+            fn create_secure_channel_request(i: Option<Vec<String>>, m: u8, t: Option<u32>) -> TestResult {
+                let addr = "/dnsaddr/localhost/tcp/4000/service/api".parse().unwrap();
+                let mode = match m % 3 {
+                    0 => CredentialExchangeMode::None,
+                    1 => CredentialExchangeMode::Oneway,
+                    _ => CredentialExchangeMode::Mutual,
+                };
+                let mut req = CreateSecureChannelRequest::new(&addr, identifiers(i), mode);
+                req.timeout = t.map(|t| Duration::from_millis(t.into()));
+                validate("create_secure_channel_request", &req)
+            }
+
+            fn create_secure_channel_response(a: String) -> TestResult {
+                validate("create_secure_channel_response", &CreateSecureChannelResponse::new(&address(a)))
+            }
+
+            fn create_secure_channel_listener_request(a: String, i: Option<Vec<String>>) -> TestResult {
+                let req = CreateSecureChannelListenerRequest::new(&address(a), identifiers(i));
+                validate("create_secure_channel_listener_request", &req)
+            }
+
+            fn delete_secure_channel_listener_request(a: String) -> TestResult {
+                validate("delete_secure_channel_listener_request", &DeleteSecureChannelListenerRequest::new(&address(a)))
+            }
+
+            fn delete_secure_channel_request(a: String) -> TestResult {
+                validate("delete_secure_channel_request", &DeleteSecureChannelRequest::new(&address(a)))
+            }
+
+            fn delete_secure_channel_response(a: Option<String>) -> TestResult {
+                validate("delete_secure_channel_response", &DeleteSecureChannelResponse::new(a.map(address)))
+            }
+
+            fn show_secure_channel_request(a: String) -> TestResult {
+                validate("show_secure_channel_request", &ShowSecureChannelRequest::new(&address(a)))
+            }
+
+            fn show_secure_channel_response(c: Option<String>, r: Option<String>, i: Option<Vec<String>>) -> TestResult {
+                let mut res = ShowSecureChannelResponse::new(None);
+                res.channel = c.map(|c| c.into());
+                res.route = r.map(|r| r.into());
+                res.authorized_identifiers = i.map(|i| i.into_iter().map(|i| i.into()).collect());
+                validate("show_secure_channel_response", &res)
+            }
+        }
+    }
+}
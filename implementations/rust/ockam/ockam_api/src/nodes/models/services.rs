@@ -2,6 +2,9 @@ use std::path::Path;
 
 use minicbor::{bytes::ByteSlice, Decode, Encode};
 use ockam_core::compat::borrow::Cow;
+use ockam_core::CowStr;
+use ockam_identity::IdentityIdentifier;
+use serde::Serialize;
 
 #[cfg(feature = "tag")]
 use ockam_core::TypeTag;
@@ -141,6 +144,39 @@ impl<'a> StartAuthenticatorRequest<'a> {
     }
 }
 
+/// Request body when instructing a node to start a controller
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct StartControllerRequest<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<6057394>,
+    #[b(1)] path: Option<&'a Path>,
+    #[n(2)] admins: Vec<IdentityIdentifier>,
+}
+
+impl<'a> StartControllerRequest<'a> {
+    pub fn new(path: Option<&'a Path>, admins: Vec<IdentityIdentifier>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            path,
+            admins,
+        }
+    }
+
+    /// Directory to store the controller state in, by default the
+    /// `controller` directory of the node
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+
+    /// Identities allowed to use the controller without enrolling
+    pub fn admins(&self) -> &[IdentityIdentifier] {
+        &self.admins
+    }
+}
+
 #[derive(Debug, Clone, Decode, Encode)]
 #[rustfmt::skip]
 #[cbor(map)]
@@ -192,3 +228,128 @@ impl<'a> StartCredentialsService<'a> {
         self.oneway
     }
 }
+
+/// Request body when instructing a node to stop a service
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct DeleteServiceRequest<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<2360197>,
+    #[b(1)] addr: CowStr<'a>,
+}
+
+impl<'a> DeleteServiceRequest<'a> {
+    pub fn new(addr: impl Into<CowStr<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            addr: addr.into(),
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.addr
+    }
+}
+
+/// Response body describing a running service
+#[derive(Debug, Clone, Decode, Encode, Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct ServiceStatus<'a> {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<8542064>,
+    #[b(1)] pub addr: CowStr<'a>,
+    #[b(2)] pub service_type: CowStr<'a>,
+}
+
+impl<'a> ServiceStatus<'a> {
+    pub fn new(addr: impl Into<CowStr<'a>>, service_type: impl Into<CowStr<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            addr: addr.into(),
+            service_type: service_type.into(),
+        }
+    }
+}
+
+/// Response body when returning a list of services
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct ServiceList<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<9587601>,
+    #[b(1)] pub list: Vec<ServiceStatus<'a>>
+}
+
+impl<'a> ServiceList<'a> {
+    pub fn new(list: Vec<ServiceStatus<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            list,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::services::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn start_vault_service_request(a: String) -> TestResult {
+                validate("start_vault_service_request", &StartVaultServiceRequest::new(a))
+            }
+
+            fn start_identity_service_request(a: String) -> TestResult {
+                validate("start_identity_service_request", &StartIdentityServiceRequest::new(a))
+            }
+
+            fn start_authenticated_service_request(a: String) -> TestResult {
+                validate("start_authenticated_service_request", &StartAuthenticatedServiceRequest::new(a))
+            }
+
+            fn start_uppercase_service_request(a: String) -> TestResult {
+                validate("start_uppercase_service_request", &StartUppercaseServiceRequest::new(a))
+            }
+
+            fn start_echoer_service_request(a: String) -> TestResult {
+                validate("start_echoer_service_request", &StartEchoerServiceRequest::new(a))
+            }
+
+            fn start_authenticator_request(a: String, p: String, b: Vec<u8>) -> TestResult {
+                validate("start_authenticator_request", &StartAuthenticatorRequest::new(&a, Path::new(&p), &b))
+            }
+
+            fn start_controller_request(p: Option<String>, i: Vec<String>) -> TestResult {
+                let i = i.iter().map(|i| IdentityIdentifier::from_key_id(i)).collect();
+                validate("start_controller_request", &StartControllerRequest::new(p.as_deref().map(Path::new), i))
+            }
+
+            fn start_verifier_service(a: String) -> TestResult {
+                validate("start_verifier_service", &StartVerifierService::new(&a))
+            }
+
+            fn start_credentials_service(a: String, o: bool) -> TestResult {
+                validate("start_credentials_service", &StartCredentialsService::new(&a, o))
+            }
+
+            fn delete_service_request(a: String) -> TestResult {
+                validate("delete_service_request", &DeleteServiceRequest::new(a))
+            }
+
+            fn service_list(l: Vec<(String, String)>) -> TestResult {
+                let l = l.into_iter().map(|(a, t)| ServiceStatus::new(a, t)).collect();
+                validate("service_list", &ServiceList::new(l))
+            }
+        }
+    }
+}
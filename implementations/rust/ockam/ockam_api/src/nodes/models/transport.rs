@@ -1,5 +1,6 @@
 use minicbor::{Decode, Encode};
 use ockam_core::compat::borrow::Cow;
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
 #[cfg(feature = "tag")]
@@ -63,9 +64,10 @@ impl<'a> DeleteTransport<'a> {
 /// Encode which type of transport is being requested
 // TODO: we have a TransportType in ockam_core.  Do we really want to
 // mirror this kind of type here?
-#[derive(Copy, Clone, Debug, Decode, Encode)]
+#[derive(Copy, Clone, Debug, Decode, Encode, PartialEq, Eq, Serialize, Deserialize)]
 #[rustfmt::skip]
 #[cbor(index_only)]
+#[serde(rename_all = "snake_case")]
 pub enum TransportType {
     /// Ockam TCP transport
     #[n(0)] Tcp,
@@ -73,6 +75,20 @@ pub enum TransportType {
     #[n(1)] Ble,
     /// Websocket transport
     #[n(2)] WebSocket,
+    /// Ockam UDP transport
+    #[n(3)] Udp,
+}
+
+impl TransportType {
+    /// The node manager path segment for this transport type
+    pub fn path_segment(&self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Ble => "ble",
+            Self::WebSocket => "ws",
+            Self::Udp => "udp",
+        }
+    }
 }
 
 impl Display for TransportType {
@@ -81,13 +97,15 @@ impl Display for TransportType {
             Self::Tcp => "TCP",
             Self::Ble => "BLE",
             Self::WebSocket => "Websocket",
+            Self::Udp => "UDP",
         })
     }
 }
 
 /// Encode which type of transport is being requested
-#[derive(Copy, Clone, Debug, Decode, Encode, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Decode, Encode, PartialEq, Eq, Serialize, Deserialize)]
 #[rustfmt::skip]
+#[serde(rename_all = "snake_case")]
 pub enum TransportMode {
     /// Listen on a set address
     #[n(0)] Listen,
@@ -107,11 +125,12 @@ impl Display for TransportMode {
 ///////////////////-!  RESPONSE BODIES
 
 /// Respons body when interacting with a transport
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct TransportStatus<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<1581592>,
     /// The type of transport to create
     #[n(2)] pub tt: TransportType,
@@ -145,11 +164,12 @@ impl<'a> TransportStatus<'a> {
 }
 
 /// Response body when interacting with a transport
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Decode, Encode, Serialize)]
 #[rustfmt::skip]
 #[cbor(map)]
 pub struct TransportList<'a> {
     #[cfg(feature = "tag")]
+    #[serde(skip)]
     #[n(0)] tag: TypeTag<5212817>,
     #[n(1)] pub list: Vec<TransportStatus<'a>>
 }
@@ -163,3 +183,45 @@ impl<'a> TransportList<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::transport::*;
+        use crate::schema::validate;
+
+        fn transport_type(t: u8) -> TransportType {
+            match t % 4 {
+                0 => TransportType::Tcp,
+                1 => TransportType::Ble,
+                2 => TransportType::WebSocket,
+                _ => TransportType::Udp,
+            }
+        }
+
+        fn transport_mode(listen: bool) -> TransportMode {
+            if listen {
+                TransportMode::Listen
+            } else {
+                TransportMode::Connect
+            }
+        }
+
+        quickcheck! {
+            fn create_transport(t: u8, m: bool, a: String) -> TestResult {
+                validate("create_transport", &CreateTransport::new(transport_type(t), transport_mode(m), a))
+            }
+
+            fn delete_transport(t: String, f: bool) -> TestResult {
+                validate("delete_transport", &DeleteTransport::new(t, f))
+            }
+
+            fn transport_list(l: Vec<(u8, bool, String, String)>) -> TestResult {
+                let l = l.into_iter().map(|(t, m, p, i)| TransportStatus::new(transport_type(t), transport_mode(m), p, i)).collect();
+                validate("transport_list", &TransportList::new(l))
+            }
+        }
+    }
+}
@@ -23,3 +23,19 @@ impl<'a> CreateVaultRequest<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::models::vault::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn create_vault_request(p: Option<String>) -> TestResult {
+                validate("create_vault_request", &CreateVaultRequest::new(p))
+            }
+        }
+    }
+}
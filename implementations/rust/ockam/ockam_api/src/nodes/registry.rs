@@ -1,5 +1,9 @@
+use crate::nodes::config::PersistedResource;
 use crate::nodes::service::Alias;
+use crate::session::Key;
+use ockam::remote::RemoteForwarderInfo;
 use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::sync::{Arc, Mutex};
 use ockam_core::{Address, Route};
 use ockam_identity::IdentityIdentifier;
 
@@ -98,6 +102,25 @@ pub(crate) struct CredentialsServiceInfo {}
 #[derive(Default)]
 pub(crate) struct AuthenticatorServiceInfo {}
 
+#[derive(Default)]
+pub(crate) struct ForwardingServiceInfo {}
+
+#[derive(Default)]
+pub(crate) struct ControllerServiceInfo {}
+
+pub(crate) struct ForwarderEntry {
+    /// Current forwarder, replaced when the session recovers it
+    pub(crate) info: RemoteForwarderInfo,
+    /// Resource to remove from the node config when deleted
+    pub(crate) resource: PersistedResource,
+    /// Session monitoring the forwarder, if it is recovered
+    pub(crate) session: Option<Key>,
+}
+
+/// Forwarders keyed by the remote address they were first created
+/// with, shared with the session recovery closures
+pub(crate) type ForwarderRegistry = Arc<Mutex<BTreeMap<String, ForwarderEntry>>>;
+
 pub(crate) struct InletInfo {
     pub(crate) bind_addr: String,
     pub(crate) worker_addr: Address,
@@ -147,6 +170,10 @@ pub(crate) struct Registry {
     pub(crate) credentials_services: BTreeMap<Address, CredentialsServiceInfo>,
     #[cfg(feature = "direct-authenticator")]
     pub(crate) authenticator_service: BTreeMap<Address, AuthenticatorServiceInfo>,
+    pub(crate) forwarding_services: BTreeMap<Address, ForwardingServiceInfo>,
+    /// Keyed by the primary address of the controller worker
+    pub(crate) controller_services: BTreeMap<Address, ControllerServiceInfo>,
+    pub(crate) forwarders: ForwarderRegistry,
 
     // FIXME: wow this is a terrible way to store data
     pub(crate) inlets: BTreeMap<Alias, InletInfo>,
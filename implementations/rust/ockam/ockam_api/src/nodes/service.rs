@@ -3,10 +3,11 @@
 use std::collections::BTreeMap;
 use std::error::Error as _;
 use std::path::PathBuf;
+use std::time::Instant;
 
 use minicbor::Decoder;
 
-use ockam::{Address, Context, ForwardingService, Result, Routed, TcpTransport, Worker};
+use ockam::{Address, Context, Result, Route, Routed, TcpTransport, Worker};
 use ockam_core::api::{Error, Method, Request, Response, Status};
 use ockam_core::compat::{
     boxed::Box,
@@ -14,30 +15,40 @@ use ockam_core::compat::{
     sync::{Arc, Mutex},
 };
 use ockam_core::errcode::{Kind, Origin};
-use ockam_core::AsyncTryClone;
+use ockam_core::{AccessControl, AsyncTryClone};
 use ockam_identity::{Identity, IdentityIdentifier, PublicIdentity};
 use ockam_multiaddr::MultiAddr;
 use ockam_node::tokio;
 use ockam_node::tokio::task::JoinHandle;
+#[cfg(feature = "ockam_transport_udp")]
+use ockam_transport_udp::UdpTransport;
+#[cfg(feature = "ockam_transport_websocket")]
+use ockam_transport_websocket::WebSocketTransport;
 use ockam_vault::storage::FileStorage;
 use ockam_vault::Vault;
 
 use super::registry::Registry;
+use super::NodeLogs;
 use crate::config::lookup::ProjectLookup;
 use crate::config::{cli::AuthoritiesConfig, Config};
+use crate::controller::ProjectLauncher;
 use crate::error::ApiError;
 use crate::lmdb::LmdbStorage;
-use crate::nodes::config::NodeManConfig;
-use crate::nodes::models::base::NodeStatus;
+use crate::nodes::config::{NodeManConfig, PersistedResource, ServiceKind};
+use crate::nodes::models::base::{NodeMetrics, NodeStatus};
 use crate::nodes::models::transport::{TransportMode, TransportType};
 use crate::session::{Medic, Sessions};
 use crate::DefaultAddress;
 
 pub mod message;
 
+mod controller;
 mod credentials;
 mod forwarder;
+mod health;
 mod identity;
+mod logs;
+mod persistence;
 mod portals;
 mod secure_channel;
 mod services;
@@ -86,6 +97,7 @@ impl AsRef<[AuthorityInfo]> for Authorities {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct AuthorityInfo {
     identity: PublicIdentity,
     addr: MultiAddr,
@@ -99,6 +111,12 @@ pub struct NodeManager {
     api_transport_id: Alias,
     transports: BTreeMap<Alias, (TransportType, TransportMode, String)>,
     tcp_transport: TcpTransport,
+    /// Created on first use, see `NodeManager::ws_transport`
+    #[cfg(feature = "ockam_transport_websocket")]
+    ws_transport: Option<WebSocketTransport>,
+    /// Created on first use, see `NodeManager::udp_transport`
+    #[cfg(feature = "ockam_transport_udp")]
+    udp_transport: Option<UdpTransport>,
     pub(crate) controller_identity_id: IdentityIdentifier,
     skip_defaults: bool,
     enable_credential_checks: bool,
@@ -111,6 +129,16 @@ pub struct NodeManager {
     pub(crate) registry: Registry,
     sessions: Arc<Mutex<Sessions>>,
     medic: JoinHandle<Result<(), ockam_core::Error>>,
+    /// Started with the first credential, see `NodeManager::get_credential_impl`
+    credential_refresher: Option<JoinHandle<()>>,
+    credential_refresh: Arc<Mutex<credentials::RefreshStatus>>,
+    started_at: Instant,
+    recent_errors: health::RecentErrors,
+    logs: NodeLogs,
+    /// Needed to start a controller, see `NodeManager::set_project_launcher`
+    project_launcher: Option<Arc<dyn ProjectLauncher>>,
+    /// Checked for every request, see `NodeManager::set_api_access_control`
+    api_access_control: Option<Arc<dyn AccessControl>>,
 }
 
 pub struct IdentityOverride {
@@ -119,6 +147,14 @@ pub struct IdentityOverride {
 }
 
 impl NodeManager {
+    /// Only answer the requests allowed by `access_control`
+    ///
+    /// Unlike the access control of a worker, it isn't inherited by
+    /// the services started through the node manager.
+    pub fn set_api_access_control(&mut self, access_control: impl AccessControl) {
+        self.api_access_control = Some(Arc::new(access_control))
+    }
+
     pub(crate) fn identity(&self) -> Result<&Identity<Vault>> {
         self.identity
             .as_ref()
@@ -244,6 +280,10 @@ impl NodeManager {
             api_transport_id,
             transports,
             tcp_transport,
+            #[cfg(feature = "ockam_transport_websocket")]
+            ws_transport: None,
+            #[cfg(feature = "ockam_transport_udp")]
+            udp_transport: None,
             controller_identity_id: Self::load_controller_identity_id()?,
             skip_defaults,
             enable_credential_checks,
@@ -259,6 +299,13 @@ impl NodeManager {
                 tokio::spawn(medic.start(ctx))
             },
             sessions,
+            credential_refresher: None,
+            credential_refresh: Default::default(),
+            started_at: Instant::now(),
+            recent_errors: Default::default(),
+            logs: Default::default(),
+            project_launcher: None,
+            api_access_control: None,
         };
 
         if !skip_defaults {
@@ -311,7 +358,7 @@ impl NodeManager {
         self.start_uppercase_service_impl(ctx, DefaultAddress::UPPERCASE_SERVICE.into())
             .await?;
 
-        ForwardingService::create(ctx).await?;
+        self.start_forwarding_service_impl(ctx).await?;
 
         self.create_secure_channel_listener_impl(
             DefaultAddress::SECURE_CHANNEL_LISTENER.into(),
@@ -325,6 +372,30 @@ impl NodeManager {
                 .await?;
         }
 
+        // Restarted nodes skip the defaults, so they are restored
+        // along with the other resources of the node.  The
+        // credentials service is left out, as restarted nodes have no
+        // authorities.
+        for (kind, addr) in [
+            (ServiceKind::Vault, DefaultAddress::VAULT_SERVICE),
+            (ServiceKind::Identity, DefaultAddress::IDENTITY_SERVICE),
+            (
+                ServiceKind::Authenticated,
+                DefaultAddress::AUTHENTICATED_SERVICE,
+            ),
+            (ServiceKind::Uppercase, DefaultAddress::UPPERCASE_SERVICE),
+            (ServiceKind::Forwarding, DefaultAddress::FORWARDING_SERVICE),
+        ] {
+            self.persist(PersistedResource::Service {
+                kind,
+                addr: addr.into(),
+            });
+        }
+        self.persist(PersistedResource::SecureChannelListener {
+            addr: DefaultAddress::SECURE_CHANNEL_LISTENER.into(),
+            authorized_identifiers: None,
+        });
+
         Ok(())
     }
 }
@@ -332,12 +403,15 @@ impl NodeManager {
 impl NodeManager {
     //////// Request matching and response handling ////////
 
+    /// Returns `None` for the requests answered with a stream of
+    /// responses, which their handlers send
     async fn handle_request(
         &mut self,
         ctx: &mut Context,
         req: &Request<'_>,
         dec: &mut Decoder<'_>,
-    ) -> Result<Vec<u8>> {
+        return_route: Route,
+    ) -> Result<Option<Vec<u8>>> {
         debug! {
             target: TARGET,
             id     = %req.id(),
@@ -367,27 +441,42 @@ impl NodeManager {
                     self.transports.len() as u32,
                 ))
                 .to_vec()?,
+            (Get, ["node", "health"]) => self.get_node_health(ctx, req).await?.to_vec()?,
+            (Get, ["node", "logs"]) => self.get_logs(req, dec)?.to_vec()?,
+            (Get, ["node", "logs", "stream"]) => {
+                self.stream_logs(ctx, req, dec, return_route).await?;
+                return Ok(None);
+            }
+            (Put, ["node", "logs", "filter"]) => self.set_log_filter(req, dec)?,
+            (Get, ["node", "metrics"]) => Response::ok(req.id())
+                .body(NodeMetrics::new(ctx.metrics().encode()))
+                .to_vec()?,
 
-            // ==*== Tcp Connection ==*==
-            // TODO: Get all tcp connections
-            (Get, ["node", "tcp", "connection"]) => self
-                .get_tcp_con_or_list(req, TransportMode::Connect)
+            // ==*== Transport connections ==*==
+            (Get, ["node", tt @ ("tcp" | "ws" | "udp"), "connection"]) => self
+                .get_transport_list(req, tt, TransportMode::Connect)
+                .to_vec()?,
+            (Post, ["node", tt @ ("tcp" | "ws" | "udp"), "connection"]) => self
+                .add_transport(ctx, req, dec, tt, TransportMode::Connect)
+                .await?
+                .to_vec()?,
+            (Delete, ["node", tt @ ("tcp" | "ws" | "udp"), "connection"]) => self
+                .delete_transport(req, dec, tt, TransportMode::Connect)
+                .await?
                 .to_vec()?,
-            (Post, ["node", "tcp", "connection"]) => {
-                self.add_transport(req, dec).await?.to_vec()?
-            }
-            (Delete, ["node", "tcp", "connection"]) => {
-                self.delete_transport(req, dec).await?.to_vec()?
-            }
 
-            // ==*== Tcp Listeners ==*==
-            (Get, ["node", "tcp", "listener"]) => self
-                .get_tcp_con_or_list(req, TransportMode::Listen)
+            // ==*== Transport listeners ==*==
+            (Get, ["node", tt @ ("tcp" | "ws" | "udp"), "listener"]) => self
+                .get_transport_list(req, tt, TransportMode::Listen)
+                .to_vec()?,
+            (Post, ["node", tt @ ("tcp" | "ws" | "udp"), "listener"]) => self
+                .add_transport(ctx, req, dec, tt, TransportMode::Listen)
+                .await?
+                .to_vec()?,
+            (Delete, ["node", tt @ ("tcp" | "ws" | "udp"), "listener"]) => self
+                .delete_transport(req, dec, tt, TransportMode::Listen)
+                .await?
                 .to_vec()?,
-            (Post, ["node", "tcp", "listener"]) => self.add_transport(req, dec).await?.to_vec()?,
-            (Delete, ["node", "tcp", "listener"]) => {
-                self.delete_transport(req, dec).await?.to_vec()?
-            }
 
             // ==*== Vault ==*==
             (Post, ["node", "vault"]) => self.create_vault(req, dec).await?.to_vec()?,
@@ -428,8 +517,14 @@ impl NodeManager {
                 .create_secure_channel_listener(req, dec)
                 .await?
                 .to_vec()?,
+            (Delete, ["node", "secure_channel_listener"]) => self
+                .delete_secure_channel_listener(ctx, req, dec)
+                .await?
+                .to_vec()?,
 
             // ==*== Services ==*==
+            (Get, ["node", "services"]) => self.list_services(req).to_vec()?,
+            (Delete, ["node", "services"]) => self.delete_service(ctx, req, dec).await?.to_vec()?,
             (Post, ["node", "services", "vault"]) => {
                 self.start_vault_service(ctx, req, dec).await?.to_vec()?
             }
@@ -458,9 +553,17 @@ impl NodeManager {
                 .start_credentials_service(ctx, req, dec)
                 .await?
                 .to_vec()?,
+            (Post, ["node", "services", "controller"]) => self
+                .start_controller_service(ctx, req, dec)
+                .await?
+                .to_vec()?,
 
             // ==*== Forwarder commands ==*==
+            (Get, ["node", "forwarder"]) => self.get_forwarders(req).to_vec()?,
             (Post, ["node", "forwarder"]) => self.create_forwarder(ctx, req.id(), dec).await?,
+            (Delete, ["node", "forwarder"]) => {
+                self.delete_forwarder(ctx, req, dec).await?.to_vec()?
+            }
 
             // ==*== Inlets & Outlets ==*==
             (Get, ["node", "inlet"]) => self.get_inlets(req).to_vec()?,
@@ -495,6 +598,12 @@ impl NodeManager {
                 self.delete_project(ctx, dec, space_id, project_id).await?
             }
 
+            // ==*== Invitations ==*==
+            (Post, ["v0", "invitations"]) => self.create_invitation(ctx, dec).await?,
+            (Get, ["v0", "invitations"]) => self.list_invitations(ctx, dec).await?,
+            (Put, ["v0", "invitations", id]) => self.accept_invitation(ctx, dec, id).await?,
+            (Delete, ["v0", "invitations", id]) => self.reject_invitation(ctx, dec, id).await?,
+
             // ==*== Enroll ==*==
             (Post, ["v0", "enroll", "auth0"]) => self.enroll_auth0(ctx, dec).await?,
             (Get, ["v0", "enroll", "token"]) => self.generate_enrollment_token(ctx, dec).await?,
@@ -525,7 +634,7 @@ impl NodeManager {
                     .to_vec()?
             }
         };
-        Ok(r)
+        Ok(Some(r))
     }
 }
 
@@ -535,6 +644,8 @@ impl Worker for NodeManager {
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        self.restore(ctx).await;
+
         if !self.skip_defaults {
             self.initialize_defaults(ctx).await?;
         }
@@ -544,6 +655,9 @@ impl Worker for NodeManager {
 
     async fn shutdown(&mut self, _: &mut Self::Context) -> Result<()> {
         self.medic.abort();
+        if let Some(r) = &self.credential_refresher {
+            r.abort();
+        }
         Ok(())
     }
 
@@ -557,8 +671,20 @@ impl Worker for NodeManager {
             }
         };
 
-        let r = match self.handle_request(ctx, &req, &mut dec).await {
-            Ok(r) => r,
+        if let Some(ac) = &self.api_access_control {
+            if !ac.is_authorized(msg.local_message()).await? {
+                warn!(method = ?req.method(), path = %req.path(), "Rejected request");
+                let r = ockam_core::api::forbidden(&req, "request not allowed").to_vec()?;
+                return ctx.send(msg.return_route(), r).await;
+            }
+        }
+
+        let r = match self
+            .handle_request(ctx, &req, &mut dec, msg.return_route())
+            .await
+        {
+            Ok(Some(r)) => r,
+            Ok(None) => return Ok(()),
             Err(err) => {
                 error! {
                     target: TARGET,
@@ -569,6 +695,7 @@ impl Worker for NodeManager {
                     cause  = ?err.source(),
                     "failed to handle request"
                 }
+                self.recent_errors.push(req.path(), err.to_string());
                 let err =
                     Error::new(req.path()).with_message(format!("failed to handle request: {err}"));
                 Response::builder(req.id(), Status::InternalServerError)
@@ -597,13 +724,49 @@ pub(crate) mod tests {
     impl NodeManager {
         pub(crate) async fn test_create(ctx: &Context) -> Result<Route> {
             let node_dir = tempfile::tempdir().unwrap();
-            let node_manager = "manager";
             let transport = TcpTransport::create(ctx).await?;
+            Self::test_create_in(ctx, node_dir.into_path(), "manager", transport).await
+        }
+
+        /// Start a node manager at `node_manager`, using or re-using
+        /// the node directory `node_dir`
+        pub(crate) async fn test_create_in(
+            ctx: &Context,
+            node_dir: PathBuf,
+            node_manager: &str,
+            transport: TcpTransport,
+        ) -> Result<Route> {
+            let node_man = Self::test_new(ctx, node_dir, transport).await?;
+
+            // Initialize node_man worker and return its route
+            ctx.start_worker(node_manager, node_man).await?;
+            Ok(route![node_manager])
+        }
+
+        /// Start a node manager which trusts `controller` as the
+        /// identity of the Orchestrator controller
+        pub(crate) async fn test_create_with_controller(
+            ctx: &Context,
+            controller: IdentityIdentifier,
+        ) -> Result<Route> {
+            let node_dir = tempfile::tempdir().unwrap();
+            let transport = TcpTransport::create(ctx).await?;
+            let mut node_man = Self::test_new(ctx, node_dir.into_path(), transport).await?;
+            node_man.controller_identity_id = controller;
+            ctx.start_worker("manager", node_man).await?;
+            Ok(route!["manager"])
+        }
+
+        async fn test_new(
+            ctx: &Context,
+            node_dir: PathBuf,
+            transport: TcpTransport,
+        ) -> Result<NodeManager> {
             let node_address = transport.listen("127.0.0.1:0").await?;
             let mut node_man = NodeManager::create(
                 ctx,
                 "node".to_string(),
-                node_dir.into_path(),
+                node_dir,
                 None,
                 true,
                 false,
@@ -620,12 +783,9 @@ pub(crate) mod tests {
             .await?;
 
             // Initialize identity
-            node_man.create_vault_impl(None, false).await?;
-            node_man.create_identity_impl(ctx, false).await?;
-
-            // Initialize node_man worker and return its route
-            ctx.start_worker(node_manager, node_man).await?;
-            Ok(route![node_manager])
+            node_man.create_vault_impl(None, true).await?;
+            node_man.create_identity_impl(ctx, true).await?;
+            Ok(node_man)
         }
     }
 }
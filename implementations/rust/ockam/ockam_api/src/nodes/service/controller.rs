@@ -0,0 +1,65 @@
+use minicbor::Decoder;
+use ockam::{Context, Result};
+use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_core::compat::sync::Arc;
+use ockam_identity::IdentityIdentifier;
+use std::path::Path;
+
+use crate::controller::{ProjectLauncher, Server};
+use crate::error::ApiError;
+use crate::nodes::config::{PersistedResource, ServiceKind};
+use crate::nodes::models::services::StartControllerRequest;
+use crate::nodes::NodeManager;
+
+impl NodeManager {
+    /// Start project nodes with `launcher` when a controller started
+    /// on this node creates projects
+    pub fn set_project_launcher(&mut self, launcher: Arc<dyn ProjectLauncher>) {
+        self.project_launcher = Some(launcher)
+    }
+
+    pub(super) async fn start_controller_service(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder> {
+        let body: StartControllerRequest = dec.decode()?;
+        let path = match body.path() {
+            Some(p) => p.to_path_buf(),
+            None => self.node_dir.join("controller"),
+        };
+        self.start_controller_service_impl(ctx, &path, body.admins())
+            .await?;
+        self.persist(PersistedResource::Service {
+            kind: ServiceKind::Controller {
+                path,
+                admins: body.admins().to_vec(),
+            },
+            addr: Server::addresses()[0].to_string(),
+        });
+        Ok(Response::ok(req.id()))
+    }
+
+    pub(super) async fn start_controller_service_impl(
+        &mut self,
+        ctx: &Context,
+        path: &Path,
+        admins: &[IdentityIdentifier],
+    ) -> Result<()> {
+        if !self.registry.controller_services.is_empty() {
+            return Err(ApiError::generic("controller already started"));
+        }
+        let launcher = self
+            .project_launcher
+            .clone()
+            .ok_or_else(|| ApiError::generic("this node can not launch project nodes"))?;
+        let addrs = Server::addresses();
+        let server = Server::new(path, admins, launcher)?;
+        ctx.start_worker(addrs.clone(), server).await?;
+        self.registry
+            .controller_services
+            .insert(addrs[0].clone(), Default::default());
+        Ok(())
+    }
+}
@@ -1,16 +1,175 @@
 use crate::authenticator::direct::Client;
 use crate::error::ApiError;
+use crate::lmdb::LmdbStorage;
 use crate::multiaddr_to_route;
 use crate::nodes::models::credentials::{GetCredentialRequest, PresentCredentialRequest};
-use crate::nodes::service::map_multiaddr_err;
+use crate::nodes::service::{map_multiaddr_err, AuthorityInfo};
 use crate::nodes::NodeManager;
 use crate::DefaultAddress;
 use minicbor::Decoder;
 use ockam::Result;
 use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_core::compat::sync::{Arc, Mutex};
 use ockam_core::{route, AsyncTryClone};
+use ockam_identity::credential::{CredentialData, Timestamp, Unverified};
+use ockam_identity::{Identity, PublicIdentity, TrustMultiIdentifiersPolicy};
 use ockam_multiaddr::MultiAddr;
+use ockam_node::tokio;
+use ockam_vault::Vault;
 use std::str::FromStr;
+use std::time::Duration;
+use tracing as log;
+
+/// Delay before the first retry of a failed credential refresh
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Upper bound of the delay between two credential refresh attempts
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Timeout of the secure channel to the project authority
+const AUTHORITY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// State of the credential refresher, reported by the health endpoint
+#[derive(Debug, Default)]
+pub(crate) struct RefreshStatus {
+    /// Unix timestamp of the next refresh attempt
+    pub(crate) next_attempt_at: Option<u64>,
+    /// Number of consecutive failed attempts
+    pub(crate) failures: u32,
+    /// Error of the last failed attempt
+    pub(crate) last_error: Option<String>,
+}
+
+/// Renews the credential of a node before it expires.
+///
+/// The credential is re-requested from the project authority once 80% of
+/// its validity has elapsed. Failed attempts are retried with an
+/// exponential backoff until one succeeds.
+pub(crate) struct CredentialRefresher {
+    identity: Identity<Vault>,
+    authority: AuthorityInfo,
+    authorities: Vec<PublicIdentity>,
+    storage: LmdbStorage,
+    status: Arc<Mutex<RefreshStatus>>,
+    /// Delay before the first retry, and least time between two refreshes
+    retry_delay: Duration,
+}
+
+impl CredentialRefresher {
+    /// Refresh the credential of `identity` until it has none.
+    pub(crate) async fn run(self) {
+        let mut failures = 0;
+        // Credentials which are already due when issued must not make the
+        // refresher ask the authority again right away.
+        let mut not_before = 0;
+        loop {
+            let refresh_at = match self.identity.credential().await {
+                Some(c) => match CredentialData::<Unverified>::try_from(&c) {
+                    Ok(d) => refresh_at(
+                        d.unverified_created_at().into(),
+                        d.unverified_expires_at().into(),
+                    )
+                    .max(not_before),
+                    Err(e) => {
+                        log::error!(err = %e, "invalid credential, stopping refresher");
+                        return;
+                    }
+                },
+                None => {
+                    log::debug!("no credential to refresh");
+                    return;
+                }
+            };
+            let now = Timestamp::now().map(u64::from).unwrap_or_default();
+
+            if now < refresh_at {
+                // The credential has been replaced since the last attempt.
+                failures = 0;
+                self.update(|s| {
+                    s.next_attempt_at = Some(refresh_at);
+                    s.failures = 0;
+                    s.last_error = None;
+                });
+                tokio::time::sleep(Duration::from_secs(refresh_at - now)).await;
+                continue;
+            }
+
+            log::debug!("refreshing credential");
+            match self.refresh().await {
+                Ok(()) => {
+                    log::info!("credential refreshed");
+                    failures = 0;
+                    let now = Timestamp::now().map(u64::from).unwrap_or(now);
+                    not_before = now + self.retry_delay.as_secs()
+                }
+                Err(e) => {
+                    failures += 1;
+                    let delay = retry_delay(self.retry_delay, failures);
+                    log::warn!(err = %e, %failures, "failed to refresh credential");
+                    self.update(|s| {
+                        s.next_attempt_at = Some(now + delay.as_secs());
+                        s.failures = failures;
+                        s.last_error = Some(e.to_string());
+                    });
+                    tokio::time::sleep(delay).await
+                }
+            }
+        }
+    }
+
+    /// Get a new credential over a fresh secure channel to the authority.
+    async fn refresh(&self) -> Result<()> {
+        let route = multiaddr_to_route(&self.authority.addr)
+            .ok_or_else(|| ApiError::generic("invalid authority route"))?;
+        let allowed = vec![self.authority.identity.identifier().clone()];
+        let sc = self
+            .identity
+            .create_secure_channel_extended(
+                route,
+                TrustMultiIdentifiersPolicy::new(allowed),
+                &self.storage,
+                AUTHORITY_TIMEOUT,
+            )
+            .await?;
+
+        let result = async {
+            let r = route![sc.clone(), DefaultAddress::AUTHENTICATOR];
+            let mut client = Client::new(r, self.identity.ctx()).await?;
+            let credential = client.credential().await?;
+            self.identity
+                .verify_self_credential(&credential, self.authorities.iter())
+                .await?;
+            self.identity
+                .set_credential(Some(credential.to_owned()))
+                .await;
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = self.identity.stop_secure_channel(&sc).await {
+            log::debug!(err = %e, "failed to stop secure channel to authority")
+        }
+        result
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RefreshStatus)) {
+        f(&mut self.status.lock().unwrap())
+    }
+}
+
+/// Unix timestamp at which a credential is refreshed, once 80% of its
+/// validity has elapsed.
+fn refresh_at(created: u64, expires: u64) -> u64 {
+    let validity = expires.saturating_sub(created);
+    created.saturating_add(validity / 5 * 4)
+}
+
+/// Delay before the next attempt after `failures` consecutive failures,
+/// starting from `base`.
+fn retry_delay(base: Duration, failures: u32) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+    base.saturating_mul(factor).min(MAX_RETRY_DELAY)
+}
 
 impl NodeManager {
     pub(super) async fn get_credential_impl(&mut self, overwrite: bool) -> Result<()> {
@@ -63,6 +222,33 @@ impl NodeManager {
 
         identity.set_credential(Some(credential.to_owned())).await;
 
+        self.start_credential_refresher().await?;
+
+        Ok(())
+    }
+
+    /// Start renewing the credential of the node, unless already done.
+    async fn start_credential_refresher(&mut self) -> Result<()> {
+        if let Some(r) = &self.credential_refresher {
+            if !r.is_finished() {
+                return Ok(());
+            }
+        }
+        let authorities = self.authorities()?;
+        let authority = authorities
+            .as_ref()
+            .first()
+            .ok_or_else(|| ApiError::generic("No known Authority"))?
+            .clone();
+        let refresher = CredentialRefresher {
+            identity: self.identity()?.async_try_clone().await?,
+            authority,
+            authorities: authorities.public_identities(),
+            storage: self.authenticated_storage.clone(),
+            status: self.credential_refresh.clone(),
+            retry_delay: RETRY_DELAY,
+        };
+        self.credential_refresher = Some(tokio::spawn(refresher.run()));
         Ok(())
     }
 
@@ -110,3 +296,144 @@ impl NodeManager {
         Ok(response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::route_to_multiaddr;
+    use ockam_core::compat::collections::VecDeque;
+    use ockam_core::{Routed, Worker};
+    use ockam_identity::authenticated_storage::mem::InMemoryStorage;
+    use ockam_identity::credential::Credential;
+    use ockam_identity::{IdentityIdentifier, TrustEveryonePolicy};
+    use ockam_node::Context;
+    use std::time::Instant;
+
+    #[test]
+    fn refresh_before_expiry() {
+        assert_eq!(refresh_at(1_000, 2_000), 1_800);
+        // A credential without validity is refreshed right away
+        assert_eq!(refresh_at(2_000, 1_000), 2_000);
+    }
+
+    #[test]
+    fn retries_back_off() {
+        assert_eq!(retry_delay(RETRY_DELAY, 1), RETRY_DELAY);
+        assert_eq!(retry_delay(RETRY_DELAY, 2), RETRY_DELAY * 2);
+        assert_eq!(retry_delay(RETRY_DELAY, 3), RETRY_DELAY * 4);
+        assert_eq!(retry_delay(RETRY_DELAY, 100), MAX_RETRY_DELAY);
+    }
+
+    #[ockam_macros::test]
+    async fn refresh_replaces_and_retries(ctx: &mut Context) -> Result<()> {
+        let authority = Identity::create(ctx, &Vault::create()).await?;
+        authority
+            .create_secure_channel_listener(
+                "authority",
+                TrustEveryonePolicy,
+                &InMemoryStorage::new(),
+            )
+            .await?;
+
+        // The first credential is due right away
+        let identity = Identity::create(ctx, &Vault::create()).await?;
+        let first = authority
+            .issue_credential(
+                Credential::builder(identity.identifier().clone())
+                    .valid_for(Duration::from_secs(1)),
+            )
+            .await?;
+        identity.set_credential(Some(first.to_owned())).await;
+
+        // A refresh yielding a credential which is due as well, a failure,
+        // then a long-lived credential
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mock = MockAuthority {
+            identity: authority.async_try_clone().await?,
+            subject: identity.identifier().clone(),
+            replies: VecDeque::from([
+                Some(Duration::from_secs(1)),
+                None,
+                Some(Duration::from_secs(3600)),
+            ]),
+            requests: requests.clone(),
+        };
+        ctx.start_worker(DefaultAddress::AUTHENTICATOR, mock)
+            .await?;
+
+        let dir = tempfile::tempdir().unwrap();
+        let status = Arc::new(Mutex::new(RefreshStatus::default()));
+        let refresher = CredentialRefresher {
+            identity: identity.async_try_clone().await?,
+            authority: AuthorityInfo {
+                identity: authority.to_public().await?,
+                addr: route_to_multiaddr(&route!["authority"]).unwrap(),
+            },
+            authorities: vec![authority.to_public().await?],
+            storage: LmdbStorage::new(dir.path().join("lmdb")).await?,
+            status: status.clone(),
+            retry_delay: Duration::from_secs(1),
+        };
+        let task = tokio::spawn(refresher.run());
+
+        // Wait for the long-lived credential, noting the failure on the way
+        let mut failed = false;
+        loop {
+            {
+                let s = status.lock().unwrap();
+                failed |= s.failures == 1 && s.last_error.is_some();
+                let now = Timestamp::now().map(u64::from).unwrap();
+                if s.failures == 0 && s.next_attempt_at.unwrap_or_default() > now + 60 {
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await
+        }
+        task.abort();
+        assert!(failed);
+
+        // Neither the due credential nor the failure caused an immediate
+        // new request
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1] - requests[0] >= Duration::from_millis(900));
+        assert!(requests[2] - requests[1] >= Duration::from_millis(900));
+
+        let credential = identity.credential().await.unwrap();
+        let data = CredentialData::<Unverified>::try_from(&credential)?;
+        let created: u64 = data.unverified_created_at().into();
+        let expires: u64 = data.unverified_expires_at().into();
+        assert_eq!(expires - created, 3600);
+
+        ctx.stop().await
+    }
+
+    /// Authority which answers credential requests with the given replies
+    /// in turn, a validity to issue a credential or `None` to fail
+    struct MockAuthority {
+        identity: Identity<Vault>,
+        subject: IdentityIdentifier,
+        replies: VecDeque<Option<Duration>>,
+        requests: Arc<Mutex<Vec<Instant>>>,
+    }
+
+    #[ockam_core::worker]
+    impl Worker for MockAuthority {
+        type Message = Vec<u8>;
+        type Context = Context;
+
+        async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Vec<u8>>) -> Result<()> {
+            self.requests.lock().unwrap().push(Instant::now());
+            let req: Request = Decoder::new(msg.as_body()).decode()?;
+            let r = match self.replies.pop_front().flatten() {
+                Some(validity) => {
+                    let builder = Credential::builder(self.subject.clone()).valid_for(validity);
+                    let credential = self.identity.issue_credential(builder).await?;
+                    Response::ok(req.id()).body(credential).to_vec()?
+                }
+                None => Response::internal_error(req.id()).to_vec()?,
+            };
+            ctx.send(msg.return_route(), r).await
+        }
+    }
+}
@@ -4,9 +4,9 @@ use std::time::Duration;
 
 use minicbor::Decoder;
 
-use ockam::remote::RemoteForwarder;
+use ockam::remote::{RemoteForwarder, RemoteForwarderInfo};
 use ockam::{Address, Result};
-use ockam_core::api::{Error, Id, Request, Response, Status};
+use ockam_core::api::{Error, Id, Request, Response, ResponseBuilder, Status};
 use ockam_core::AsyncTryClone;
 use ockam_identity::IdentityIdentifier;
 use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Project, Secure, Tcp};
@@ -16,13 +16,15 @@ use ockam_node::Context;
 
 use crate::config::lookup::ProjectLookup;
 use crate::error::ApiError;
-use crate::nodes::models::forwarder::{CreateForwarder, ForwarderInfo};
+use crate::nodes::config::PersistedResource;
+use crate::nodes::models::forwarder::{CreateForwarder, DeleteForwarder, ForwarderInfo};
 use crate::nodes::models::secure_channel::{
     CreateSecureChannelRequest, CreateSecureChannelResponse, CredentialExchangeMode,
     DeleteSecureChannelRequest,
 };
+use crate::nodes::registry::{ForwarderEntry, ForwarderRegistry};
 use crate::nodes::NodeManager;
-use crate::session::Session;
+use crate::session::{Key, Session};
 use crate::{multiaddr_to_addr, multiaddr_to_route, try_address_to_multiaddr};
 
 const MAX_RECOVERY_TIME: Duration = Duration::from_secs(10);
@@ -40,7 +42,91 @@ impl NodeManager {
 
         debug!(addr = %req.address(), alias = ?req.alias(), "Handling CreateForwarder request");
 
-        let addr = self.connect(&req).await?;
+        match self.create_forwarder_impl(ctx, &req).await? {
+            Ok(info) => {
+                self.persist(forwarder_resource(&req));
+                let b = ForwarderInfo::from(info);
+                debug!(
+                    forwarding_route = %b.forwarding_route(),
+                    remote_address = %b.remote_address(),
+                    "CreateForwarder request processed, sending back response"
+                );
+                Ok(Response::ok(rid).body(b).to_vec()?)
+            }
+            Err(err) => {
+                error!(?err, "Failed to create forwarder");
+                Ok(Response::builder(rid, Status::InternalServerError)
+                    .body(err.to_string())
+                    .to_vec()?)
+            }
+        }
+    }
+
+    pub(super) fn get_forwarders(
+        &self,
+        req: &Request<'_>,
+    ) -> ResponseBuilder<Vec<ForwarderInfo<'_>>> {
+        let forwarders = self.registry.forwarders.lock().unwrap();
+        Response::ok(req.id()).body(
+            forwarders
+                .values()
+                .map(|entry| ForwarderInfo::from(&entry.info))
+                .collect(),
+        )
+    }
+
+    pub(super) async fn delete_forwarder(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder> {
+        let body: DeleteForwarder = dec.decode()?;
+        debug!(remote_address = %body.remote_address(), "Handling DeleteForwarder request");
+
+        let entry = {
+            let mut forwarders = self.registry.forwarders.lock().unwrap();
+            let key = forwarders
+                .iter()
+                .find(|(_, e)| e.info.remote_address() == body.remote_address())
+                .map(|(k, _)| k.clone());
+            match key {
+                Some(k) => forwarders.remove(&k),
+                None => None,
+            }
+        };
+        let entry = match entry {
+            Some(e) => e,
+            None => return Ok(Response::not_found(req.id())),
+        };
+
+        // Stop monitoring the forwarder first, so that it is not
+        // re-created once its worker is gone
+        if let Some(k) = &entry.session {
+            self.sessions.lock().unwrap().remove(k);
+        }
+        self.unpersist(&entry.resource);
+
+        match ctx.stop_worker(entry.info.worker_address().clone()).await {
+            Ok(()) => Ok(Response::ok(req.id())),
+            Err(err) => {
+                error!(?err, "Failed to stop forwarder");
+                Ok(Response::internal_error(req.id()))
+            }
+        }
+    }
+
+    /// Create a forwarder, with automatic recovery unless it is at a
+    /// rust node
+    ///
+    /// The outer result fails if the forwarder address can not be
+    /// reached, the inner one if the forwarder could not be created.
+    pub(super) async fn create_forwarder_impl(
+        &mut self,
+        ctx: &mut Context,
+        req: &CreateForwarder<'_>,
+    ) -> Result<Result<RemoteForwarderInfo>> {
+        let addr = self.connect(req).await?;
         let route = multiaddr_to_route(&addr)
             .ok_or_else(|| ApiError::message("invalid address: {addr}"))?;
 
@@ -56,7 +142,7 @@ impl NodeManager {
             } else {
                 RemoteForwarder::create(ctx, route).await
             };
-            if f.is_ok() {
+            if let Ok(info) = &f {
                 let c = Arc::new(ctx.async_try_clone().await?);
                 let mut s = Session::new(addr);
                 if let Some(id) = req.authorized() {
@@ -65,6 +151,7 @@ impl NodeManager {
                     s.put(IDENTITY, id)
                 }
                 let this = ctx.address();
+                let id = info.remote_address().to_string();
                 enable_recovery(
                     &mut s,
                     this,
@@ -72,29 +159,37 @@ impl NodeManager {
                     req.address().clone(),
                     req.alias().map(|a| a.to_string()),
                     self.projects.clone(),
+                    self.registry.forwarders.clone(),
+                    id,
                 );
-                self.sessions.lock().unwrap().add(s);
+                let k = self.sessions.lock().unwrap().add(s);
+                self.register_forwarder(req, info, Some(k));
             }
-            f
+            return Ok(f);
         };
 
-        match forwarder {
-            Ok(info) => {
-                let b = ForwarderInfo::from(info);
-                debug!(
-                    forwarding_route = %b.forwarding_route(),
-                    remote_address = %b.remote_address(),
-                    "CreateForwarder request processed, sending back response"
-                );
-                Ok(Response::ok(rid).body(b).to_vec()?)
-            }
-            Err(err) => {
-                error!(?err, "Failed to create forwarder");
-                Ok(Response::builder(rid, Status::InternalServerError)
-                    .body(err.to_string())
-                    .to_vec()?)
-            }
+        if let Ok(info) = &forwarder {
+            self.register_forwarder(req, info, None);
         }
+        Ok(forwarder)
+    }
+
+    fn register_forwarder(
+        &self,
+        req: &CreateForwarder<'_>,
+        info: &RemoteForwarderInfo,
+        session: Option<Key>,
+    ) {
+        let entry = ForwarderEntry {
+            info: info.clone(),
+            resource: forwarder_resource(req),
+            session,
+        };
+        self.registry
+            .forwarders
+            .lock()
+            .unwrap()
+            .insert(info.remote_address().to_string(), entry);
     }
 
     /// Resolve project ID (if any) and create secure channel if necessary.
@@ -135,6 +230,15 @@ impl NodeManager {
     }
 }
 
+fn forwarder_resource(req: &CreateForwarder<'_>) -> PersistedResource {
+    PersistedResource::Forwarder {
+        address: req.address().clone(),
+        alias: req.alias().map(|a| a.to_string()),
+        at_rust_node: req.at_rust_node(),
+        authorized: req.authorized(),
+    }
+}
+
 fn resolve_project(
     set: &BTreeMap<String, ProjectLookup>,
     name: &str,
@@ -147,6 +251,9 @@ fn resolve_project(
 }
 
 /// Configure the session for automatic recovery.
+///
+/// The registry entry `id` is updated with every re-created forwarder.
+#[allow(clippy::too_many_arguments)]
 fn enable_recovery(
     session: &mut Session,
     manager: Address,
@@ -154,6 +261,8 @@ fn enable_recovery(
     addr: MultiAddr,
     alias: Option<String>,
     projects: Arc<BTreeMap<String, ProjectLookup>>,
+    forwarders: ForwarderRegistry,
+    id: String,
 ) {
     let auth = session.get::<IdentityIdentifier>(IDENTITY).cloned();
     session.set_replacement(move |prev| {
@@ -163,6 +272,8 @@ fn enable_recovery(
         let auth = auth.clone();
         let manager = manager.clone();
         let projects = projects.clone();
+        let forwarders = forwarders.clone();
+        let id = id.clone();
         Box::pin(async move {
             debug!(%prev, %addr, "creating new remote forwarder");
             let f = async {
@@ -191,10 +302,13 @@ fn enable_recovery(
                 };
                 let r = multiaddr_to_route(&a)
                     .ok_or_else(|| ApiError::message(format!("invalid multiaddr: {a}")))?;
-                if let Some(alias) = &alias {
-                    RemoteForwarder::create_static(&ctx, r, alias).await?;
+                let info = if let Some(alias) = &alias {
+                    RemoteForwarder::create_static(&ctx, r, alias).await?
                 } else {
-                    RemoteForwarder::create(&ctx, r).await?;
+                    RemoteForwarder::create(&ctx, r).await?
+                };
+                if let Some(entry) = forwarders.lock().unwrap().get_mut(&id) {
+                    entry.info = info
                 }
                 Ok(a)
             };
@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::SystemTime;
+
+use ockam::{Context, Result};
+use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_identity::credential::{CredentialData, Unverified};
+
+use crate::nodes::models::base::{
+    NodeHealth, RecentError, SecureChannelHealth, SessionHealth, TransportHealth,
+};
+use crate::nodes::NodeManager;
+use crate::session;
+
+/// Number of errors kept for the health endpoint
+const MAX_RECENT_ERRORS: usize = 16;
+
+/// The most recent errors of a node manager
+#[derive(Default)]
+pub(crate) struct RecentErrors(VecDeque<RecentError<'static>>);
+
+impl RecentErrors {
+    pub(crate) fn push(&mut self, path: &str, message: String) {
+        if self.0.len() == MAX_RECENT_ERRORS {
+            self.0.pop_front();
+        }
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.0
+            .push_back(RecentError::new(time, path.to_string(), message))
+    }
+}
+
+impl NodeManager {
+    pub(super) async fn get_node_health(
+        &self,
+        ctx: &Context,
+        req: &Request<'_>,
+    ) -> Result<ResponseBuilder<NodeHealth<'_>>> {
+        let workers = ctx.list_workers().await?;
+        let mut health = NodeHealth::new(
+            self.node_name.as_str(),
+            env!("CARGO_PKG_VERSION"),
+            self.started_at.elapsed().as_secs(),
+            std::process::id() as i32,
+            workers.len() as u32,
+        );
+
+        let mut transports = BTreeMap::new();
+        for (tt, tm, _) in self.transports.values() {
+            *transports
+                .entry((tt.to_string(), tm.to_string()))
+                .or_insert(0) += 1;
+        }
+        health.transports = transports
+            .into_iter()
+            .map(|((tt, tm), count)| TransportHealth::new(tt, tm, count))
+            .collect();
+
+        health.secure_channels = self
+            .registry
+            .secure_channels
+            .list()
+            .iter()
+            .map(|c| {
+                let state = if workers.contains(c.addr()) {
+                    "open"
+                } else {
+                    "closed"
+                };
+                SecureChannelHealth::new(c.addr().to_string(), c.route().to_string(), state)
+            })
+            .collect();
+
+        health.sessions = self
+            .sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(k, s)| {
+                let up = s.status() == session::Status::Up;
+                SessionHealth::new(k.to_string(), s.address().to_string(), up)
+            })
+            .collect();
+        if health.sessions.iter().any(|s| !s.up) {
+            health.status = "Degraded".into();
+            health.ready = false;
+        }
+
+        if let Ok(identity) = self.identity() {
+            if let Some(c) = identity.credential().await {
+                health.credential_expires_at = CredentialData::<Unverified>::try_from(&c)
+                    .ok()
+                    .map(|d| d.unverified_expires_at().into());
+            }
+        }
+
+        health.recent_errors = self.recent_errors.0.iter().cloned().collect();
+
+        {
+            let refresh = self.credential_refresh.lock().unwrap();
+            health.credential_refresh_at = refresh.next_attempt_at;
+            health.credential_refresh_failures = refresh.failures;
+            health.credential_refresh_error = refresh.last_error.clone().map(Cow::Owned);
+            if refresh.failures > 0 {
+                health.status = "Degraded".into();
+            }
+        }
+
+        Ok(Response::ok(req.id()).body(health))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use minicbor::Decoder;
+    use ockam_core::api::Status;
+
+    use super::*;
+
+    #[ockam_macros::test]
+    async fn health_reports_recent_errors(ctx: &mut Context) -> Result<()> {
+        let manager = NodeManager::test_create(ctx).await?;
+
+        // A request without its body fails to be handled
+        let request = Request::post("/node/forwarder").to_vec()?;
+        let response: Vec<u8> = ctx.send_and_receive(manager.clone(), request).await?;
+        let header: Response = Decoder::new(&response).decode()?;
+        assert_eq!(header.status(), Some(Status::InternalServerError));
+
+        let request = Request::get("/node/health").to_vec()?;
+        let response: Vec<u8> = ctx.send_and_receive(manager, request).await?;
+        let mut dec = Decoder::new(&response);
+        let header: Response = dec.decode()?;
+        assert_eq!(header.status(), Some(Status::Ok));
+        let health: NodeHealth = dec.decode()?;
+        assert_eq!(health.node_name, "node");
+        assert!(health.ready);
+        assert_eq!(health.transports.len(), 1);
+        assert_eq!(health.transports[0].count, 1);
+        assert_eq!(health.recent_errors.len(), 1);
+        assert_eq!(health.recent_errors[0].path, "/node/forwarder");
+
+        ctx.stop().await
+    }
+}
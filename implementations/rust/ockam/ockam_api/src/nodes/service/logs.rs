@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use minicbor::Decoder;
+use ockam::{Context, Result, Route};
+use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_node::api::respond_stream;
+use ockam_node::tokio;
+
+use crate::nodes::models::logs::{GetLogs, LogRecords, SetLogFilter};
+use crate::nodes::{NodeLogs, NodeManager};
+
+/// Shortest interval between two batches of streamed log records, so
+/// that the records written while streaming do not flood the client
+const STREAM_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest interval without streamed log records, after which an
+/// empty batch tells the client that the node is still there
+const STREAM_KEEPALIVE: Duration = Duration::from_secs(10);
+
+impl NodeManager {
+    /// Serve the log records of this node with [`NodeLogs`], instead
+    /// of none
+    pub fn set_logs(&mut self, logs: NodeLogs) {
+        self.logs = logs
+    }
+
+    pub(super) fn get_logs(
+        &self,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder<LogRecords<'_>>> {
+        let body: GetLogs = dec.decode()?;
+        let (next, lines) = self.logs.since(body.since);
+        let lines = lines.into_iter().map(Into::into).collect();
+        Ok(Response::ok(req.id()).body(LogRecords::new(next, lines)))
+    }
+
+    /// Stream the log records of this node as they are written,
+    /// until the client cancels the stream
+    pub(super) async fn stream_logs(
+        &self,
+        ctx: &Context,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+        route: Route,
+    ) -> Result<()> {
+        let body: GetLogs = dec.decode()?;
+        let mut stream = respond_stream(ctx, route, req).await?;
+        stream.set_timeout(STREAM_KEEPALIVE);
+        let logs = self.logs.clone();
+        let mut since = body.since;
+        tokio::spawn(async move {
+            let mut changes = logs.watch();
+            let mut idle = false;
+            loop {
+                let (next, lines) = logs.since(since);
+                since = next;
+                if !lines.is_empty() || idle {
+                    let empty = lines.is_empty();
+                    let lines = lines.into_iter().map(Into::into).collect();
+                    if stream.send(LogRecords::new(next, lines)).await.is_err() {
+                        // The client is gone
+                        return;
+                    }
+                    if !empty {
+                        tokio::time::sleep(STREAM_INTERVAL).await;
+                    }
+                }
+                idle = tokio::select! {
+                    changed = changes.changed() => if changed.is_err() { break } else { false },
+                    cancelled = stream.cancelled() => if cancelled.is_ok() { break } else { true },
+                };
+            }
+            let _ = stream.end().await;
+        });
+        Ok(())
+    }
+
+    pub(super) fn set_log_filter(
+        &self,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<Vec<u8>> {
+        let body: SetLogFilter = dec.decode()?;
+        info!(directives = %body.directives, "Handling request to set the log filter");
+        match self.logs.set_filter(&body.directives) {
+            Ok(()) => Ok(Response::ok(req.id()).to_vec()?),
+            Err(e) => Ok(ockam_core::api::bad_request(req, &e).to_vec()?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ockam_node::api::request_stream;
+
+    use super::*;
+
+    #[ockam_macros::test]
+    async fn log_stream_ends_when_cancelled(ctx: &mut Context) -> Result<()> {
+        let manager = NodeManager::test_create(ctx).await?;
+
+        let req = Request::get("/node/logs/stream").body(GetLogs::new(0));
+        let mut stream = request_stream(ctx, "stream logs", None, manager, req).await?;
+        stream.cancel().await?;
+        while stream.next::<LogRecords>().await?.is_some() {}
+
+        ctx.stop().await
+    }
+}
@@ -78,3 +78,20 @@ mod node {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod schema {
+        use quickcheck::{quickcheck, TestResult};
+
+        use crate::nodes::service::message::*;
+        use crate::schema::validate;
+
+        quickcheck! {
+            fn send_message(m: Vec<u8>) -> TestResult {
+                let route = "/service/uppercase".parse().unwrap();
+                validate("send_message", &SendMessage::new(&route, m))
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::str::FromStr;
+
+use ockam::{Address, Context, Result};
+use ockam_multiaddr::MultiAddr;
+
+use super::{invalid_multiaddr_error, map_multiaddr_err};
+use crate::multiaddr_to_route;
+use crate::nodes::config::{PersistedResource, ServiceKind};
+use crate::nodes::models::forwarder::CreateForwarder;
+use crate::nodes::NodeManager;
+
+impl NodeManager {
+    /// Record a resource in the node config, so that it is re-created
+    /// when the node restarts
+    pub(super) fn persist(&self, resource: PersistedResource) {
+        {
+            let mut config = self.config.writelock_inner();
+            if config.resources.contains(&resource) {
+                return;
+            }
+            config.resources.push(resource);
+        }
+        if let Err(e) = self.config.persist_config_updates() {
+            warn!("Failed to persist node resources: {}", e);
+        }
+    }
+
+    /// Remove a resource from the node config
+    pub(super) fn unpersist(&self, resource: &PersistedResource) {
+        self.unpersist_matching(|r| r == resource)
+    }
+
+    /// Remove every resource matching `f` from the node config
+    pub(super) fn unpersist_matching(&self, f: impl Fn(&PersistedResource) -> bool) {
+        self.config.writelock_inner().resources.retain(|r| !f(r));
+        if let Err(e) = self.config.persist_config_updates() {
+            warn!("Failed to persist node resources: {}", e);
+        }
+    }
+
+    /// Re-create the resources recorded by a previous run of this node
+    ///
+    /// Resources which can not be re-created are logged and kept, as
+    /// the failure may be temporary.
+    pub(super) async fn restore(&mut self, ctx: &mut Context) {
+        let resources = self.config.readlock_inner().resources.clone();
+        if !resources.is_empty() {
+            info!("Restoring {} node resources", resources.len());
+        }
+        for resource in resources {
+            if let Err(e) = self.restore_resource(ctx, &resource).await {
+                warn!(?resource, "Failed to restore node resource: {}", e);
+            }
+        }
+    }
+
+    async fn restore_resource(
+        &mut self,
+        ctx: &mut Context,
+        resource: &PersistedResource,
+    ) -> Result<()> {
+        debug!(?resource, "Restoring node resource");
+        match resource {
+            PersistedResource::Transport { tt, tm, addr } => {
+                self.add_transport_impl(ctx, *tt, *tm, addr.clone()).await?;
+            }
+            PersistedResource::SecureChannelListener {
+                addr,
+                authorized_identifiers,
+            } => {
+                self.create_secure_channel_listener_impl(
+                    Address::from(addr.as_str()),
+                    authorized_identifiers.clone(),
+                )
+                .await?;
+            }
+            PersistedResource::Service { kind, addr } => {
+                self.restore_service(ctx, kind, Address::from(addr.as_str()))
+                    .await?;
+            }
+            PersistedResource::Inlet {
+                alias,
+                bind_addr,
+                outlet_route,
+                check_credential,
+            } => {
+                let outlet_route = MultiAddr::from_str(outlet_route).map_err(map_multiaddr_err)?;
+                let outlet_route =
+                    multiaddr_to_route(&outlet_route).ok_or_else(invalid_multiaddr_error)?;
+                self.create_inlet_impl(
+                    alias.clone(),
+                    bind_addr.clone(),
+                    outlet_route,
+                    *check_credential,
+                )
+                .await?;
+            }
+            PersistedResource::Outlet {
+                alias,
+                tcp_addr,
+                worker_addr,
+                check_credential,
+            } => {
+                self.create_outlet_impl(
+                    alias.clone(),
+                    tcp_addr.clone(),
+                    Address::from(worker_addr.as_str()),
+                    *check_credential,
+                )
+                .await?;
+            }
+            PersistedResource::Forwarder {
+                address,
+                alias,
+                at_rust_node,
+                authorized,
+            } => {
+                let req = CreateForwarder::at_node(
+                    address.clone(),
+                    alias.clone(),
+                    *at_rust_node,
+                    authorized.clone(),
+                );
+                self.create_forwarder_impl(ctx, &req).await??;
+            }
+        }
+        Ok(())
+    }
+
+    async fn restore_service(
+        &mut self,
+        ctx: &Context,
+        kind: &ServiceKind,
+        addr: Address,
+    ) -> Result<()> {
+        match kind {
+            ServiceKind::Vault => self.start_vault_service_impl(ctx, addr).await,
+            ServiceKind::Identity => self.start_identity_service_impl(ctx, addr).await,
+            ServiceKind::Authenticated => self.start_authenticated_service_impl(ctx, addr).await,
+            ServiceKind::Uppercase => self.start_uppercase_service_impl(ctx, addr).await,
+            ServiceKind::Echoer => self.start_echoer_service_impl(ctx, addr).await,
+            ServiceKind::Verifier => self.start_verifier_service_impl(ctx, addr).await,
+            ServiceKind::Forwarding => self.start_forwarding_service_impl(ctx).await,
+            ServiceKind::Credentials { oneway } => {
+                self.start_credentials_service_impl(addr, *oneway).await
+            }
+            #[cfg(feature = "direct-authenticator")]
+            ServiceKind::Authenticator { path, project } => {
+                self.start_direct_authenticator_service_impl(ctx, addr, path, project)
+                    .await
+            }
+            #[cfg(not(feature = "direct-authenticator"))]
+            ServiceKind::Authenticator { .. } => Err(crate::error::ApiError::generic(
+                "direct authenticator not available",
+            )),
+            ServiceKind::Controller { path, admins } => {
+                self.start_controller_service_impl(ctx, path, admins).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use minicbor::Decoder;
+    use ockam::TcpTransport;
+    use ockam_core::api::{Request, Response, Status};
+    use ockam_core::AsyncTryClone;
+
+    use super::*;
+    use crate::nodes::models::services::StartUppercaseServiceRequest;
+
+    #[ockam_macros::test]
+    async fn resources_are_restored_on_restart(ctx: &mut Context) -> Result<()> {
+        let node_dir = tempfile::tempdir().unwrap();
+        let transport = TcpTransport::create(ctx).await?;
+        let manager = NodeManager::test_create_in(
+            ctx,
+            node_dir.path().into(),
+            "manager",
+            transport.async_try_clone().await?,
+        )
+        .await?;
+
+        // Start a service through the API, so that it is persisted
+        let request = Request::post("/node/services/uppercase")
+            .body(StartUppercaseServiceRequest::new("restored_uppercase"))
+            .to_vec()?;
+        let response: Vec<u8> = ctx.send_and_receive(manager, request).await?;
+        let header: Response = Decoder::new(&response).decode()?;
+        assert_eq!(header.status(), Some(Status::Ok));
+
+        // Stop the node manager along with everything it started, and
+        // wait for their addresses to be released
+        let started: Vec<Address> = vec![
+            "manager".into(),
+            "restored_uppercase".into(),
+            crate::DefaultAddress::ECHO_SERVICE.into(),
+        ];
+        for addr in &started {
+            ctx.stop_worker(addr.clone()).await?;
+        }
+        while ctx
+            .list_workers()
+            .await?
+            .iter()
+            .any(|addr| started.contains(addr))
+        {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+
+        // A node manager re-using the node directory starts it again,
+        // before it handles any request
+        let manager = NodeManager::test_create_in(
+            ctx,
+            node_dir.path().into(),
+            "restarted_manager",
+            transport,
+        )
+        .await?;
+        let response: Vec<u8> = ctx
+            .send_and_receive(manager, Request::get("/node").to_vec()?)
+            .await?;
+        let header: Response = Decoder::new(&response).decode()?;
+        assert_eq!(header.status(), Some(Status::Ok));
+
+        let reply: String = ctx
+            .send_and_receive("restored_uppercase", "hello".to_string())
+            .await?;
+        assert_eq!(reply, "HELLO");
+
+        ctx.stop().await
+    }
+}
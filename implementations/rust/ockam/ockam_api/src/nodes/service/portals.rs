@@ -1,14 +1,15 @@
 use crate::authenticator::direct::{PROJECT_ID, ROLE};
 use crate::multiaddr_to_route;
+use crate::nodes::config::PersistedResource;
 use crate::nodes::models::portal::{
     CreateInlet, CreateOutlet, InletList, InletStatus, OutletList, OutletStatus,
 };
 use crate::nodes::registry::{InletInfo, OutletInfo};
-use crate::nodes::service::{map_multiaddr_err, random_alias};
+use crate::nodes::service::{map_multiaddr_err, random_alias, Alias};
 use crate::nodes::NodeManager;
 use minicbor::Decoder;
 use ockam::tcp::{InletOptions, OutletOptions};
-use ockam::{Address, Result};
+use ockam::{Address, Result, Route};
 use ockam_core::api::{Request, Response, ResponseBuilder};
 use ockam_core::{AccessControl, AllowAll};
 use ockam_identity::credential::access_control::CredentialAccessControl;
@@ -53,6 +54,28 @@ impl NodeManager {
         ))
     }
 
+    /// Create an inlet and register it, returning the address of its
+    /// worker
+    pub(super) async fn create_inlet_impl(
+        &mut self,
+        alias: Alias,
+        bind_addr: String,
+        outlet_route: Route,
+        check_credential: bool,
+    ) -> Result<Address> {
+        let access_control = self.access_control(check_credential)?;
+        let options = InletOptions::new(bind_addr.clone(), outlet_route, access_control);
+
+        let (worker_addr, _) = self.tcp_transport.create_inlet_extended(options).await?;
+
+        // TODO: Use better way to store inlets?
+        self.registry
+            .inlets
+            .insert(alias, InletInfo::new(&bind_addr, Some(&worker_addr)));
+
+        Ok(worker_addr)
+    }
+
     pub(super) async fn create_inlet<'a>(
         &mut self,
         req: &Request<'_>,
@@ -71,8 +94,8 @@ impl NodeManager {
 
         info!("Handling request to create inlet portal");
 
-        let outlet_route = MultiAddr::from_str(&outlet_route).map_err(map_multiaddr_err)?;
-        let outlet_route = match multiaddr_to_route(&outlet_route) {
+        let outlet_multiaddr = MultiAddr::from_str(&outlet_route).map_err(map_multiaddr_err)?;
+        let outlet_route = match multiaddr_to_route(&outlet_multiaddr) {
             Some(route) => route,
             None => {
                 return Ok(Response::bad_request(req.id())
@@ -80,18 +103,23 @@ impl NodeManager {
             }
         };
 
-        let access_control = self.access_control(check_credential)?;
-        let options = InletOptions::new(bind_addr.clone(), outlet_route, access_control);
-
-        let res = self.tcp_transport.create_inlet_extended(options).await;
+        let res = self
+            .create_inlet_impl(
+                alias.clone(),
+                bind_addr.clone(),
+                outlet_route,
+                check_credential,
+            )
+            .await;
 
         Ok(match res {
-            Ok((worker_addr, _)) => {
-                // TODO: Use better way to store inlets?
-                self.registry.inlets.insert(
-                    alias.clone(),
-                    InletInfo::new(&bind_addr, Some(&worker_addr)),
-                );
+            Ok(worker_addr) => {
+                self.persist(PersistedResource::Inlet {
+                    alias: alias.clone(),
+                    bind_addr: bind_addr.clone(),
+                    outlet_route: outlet_multiaddr.to_string(),
+                    check_credential,
+                });
 
                 Response::ok(req.id()).body(InletStatus::new(
                     bind_addr,
@@ -132,6 +160,27 @@ impl NodeManager {
         }
     }
 
+    /// Create an outlet and register it
+    pub(super) async fn create_outlet_impl(
+        &mut self,
+        alias: Alias,
+        tcp_addr: String,
+        worker_addr: Address,
+        check_credential: bool,
+    ) -> Result<()> {
+        let access_control = self.access_control(check_credential)?;
+        let options = OutletOptions::new(worker_addr.clone(), tcp_addr.clone(), access_control);
+
+        self.tcp_transport.create_outlet_extended(options).await?;
+
+        // TODO: Use better way to store outlets?
+        self.registry
+            .outlets
+            .insert(alias, OutletInfo::new(&tcp_addr, Some(&worker_addr)));
+
+        Ok(())
+    }
+
     pub(super) async fn create_outlet<'a>(
         &mut self,
         req: &Request<'_>,
@@ -151,18 +200,23 @@ impl NodeManager {
         info!("Handling request to create outlet portal");
         let worker_addr = Address::from(worker_addr.as_ref());
 
-        let access_control = self.access_control(check_credential)?;
-        let options = OutletOptions::new(worker_addr.clone(), tcp_addr.clone(), access_control);
-
-        let res = self.tcp_transport.create_outlet_extended(options).await;
+        let res = self
+            .create_outlet_impl(
+                alias.clone(),
+                tcp_addr.clone(),
+                worker_addr.clone(),
+                check_credential,
+            )
+            .await;
 
         Ok(match res {
-            Ok(_) => {
-                // TODO: Use better way to store outlets?
-                self.registry.outlets.insert(
-                    alias.clone(),
-                    OutletInfo::new(&tcp_addr, Some(&worker_addr)),
-                );
+            Ok(()) => {
+                self.persist(PersistedResource::Outlet {
+                    alias: alias.clone(),
+                    tcp_addr: tcp_addr.clone(),
+                    worker_addr: worker_addr.to_string(),
+                    check_credential,
+                });
 
                 Response::ok(req.id()).body(OutletStatus::new(
                     tcp_addr,
@@ -2,16 +2,17 @@ use std::time::Duration;
 
 use super::map_multiaddr_err;
 use crate::error::ApiError;
+use crate::nodes::config::PersistedResource;
 use crate::nodes::models::secure_channel::{
     CreateSecureChannelListenerRequest, CreateSecureChannelRequest, CreateSecureChannelResponse,
-    CredentialExchangeMode, DeleteSecureChannelRequest, DeleteSecureChannelResponse,
-    ShowSecureChannelRequest, ShowSecureChannelResponse,
+    CredentialExchangeMode, DeleteSecureChannelListenerRequest, DeleteSecureChannelRequest,
+    DeleteSecureChannelResponse, ShowSecureChannelRequest, ShowSecureChannelResponse,
 };
 use crate::nodes::NodeManager;
 use crate::DefaultAddress;
 use minicbor::Decoder;
 use ockam::identity::TrustEveryonePolicy;
-use ockam::{Address, Result, Route};
+use ockam::{Address, Context, Result, Route};
 use ockam_core::api::{Request, Response, ResponseBuilder};
 use ockam_core::{route, AsyncTryClone};
 use ockam_identity::{Identity, IdentityIdentifier, TrustMultiIdentifiersPolicy};
@@ -310,8 +311,12 @@ impl NodeManager {
             return Ok(Response::bad_request(req.id()));
         }
 
-        self.create_secure_channel_listener_impl(addr, authorized_identifiers)
+        self.create_secure_channel_listener_impl(addr.clone(), authorized_identifiers.clone())
             .await?;
+        self.persist(PersistedResource::SecureChannelListener {
+            addr: addr.to_string(),
+            authorized_identifiers,
+        });
 
         let response = Response::ok(req.id());
 
@@ -330,4 +335,38 @@ impl NodeManager {
                 .collect(),
         )
     }
+
+    pub(super) async fn delete_secure_channel_listener(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder<()>> {
+        let body: DeleteSecureChannelListenerRequest = dec.decode()?;
+        let addr = Address::from(body.addr.as_ref());
+        info!(
+            "Handling request to delete secure channel listener: {}",
+            addr
+        );
+
+        if self
+            .registry
+            .secure_channel_listeners
+            .remove(&addr)
+            .is_none()
+        {
+            return Ok(Response::not_found(req.id()));
+        }
+        self.unpersist_matching(|r| {
+            matches!(r, PersistedResource::SecureChannelListener { addr: a, .. } if Address::from(a.as_str()) == addr)
+        });
+
+        match ctx.stop_worker(addr).await {
+            Ok(()) => Ok(Response::ok(req.id())),
+            Err(err) => {
+                error!(?err, "Failed to stop secure channel listener");
+                Ok(Response::internal_error(req.id()))
+            }
+        }
+    }
 }
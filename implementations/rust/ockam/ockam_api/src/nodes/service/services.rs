@@ -2,18 +2,22 @@ use crate::auth::Server;
 use crate::echoer::Echoer;
 use crate::error::ApiError;
 use crate::identity::IdentityService;
+use crate::nodes::config::{PersistedResource, ServiceKind};
 use crate::nodes::models::services::{
-    StartAuthenticatedServiceRequest, StartAuthenticatorRequest, StartCredentialsService,
-    StartEchoerServiceRequest, StartIdentityServiceRequest, StartUppercaseServiceRequest,
-    StartVaultServiceRequest, StartVerifierService,
+    DeleteServiceRequest, ServiceList, ServiceStatus, StartAuthenticatedServiceRequest,
+    StartAuthenticatorRequest, StartCredentialsService, StartEchoerServiceRequest,
+    StartIdentityServiceRequest, StartUppercaseServiceRequest, StartVaultServiceRequest,
+    StartVerifierService,
 };
 use crate::nodes::registry::{CredentialsServiceInfo, VerifierServiceInfo};
 use crate::nodes::NodeManager;
 use crate::uppercase::Uppercase;
 use crate::vault::VaultService;
+use crate::DefaultAddress;
 use minicbor::Decoder;
-use ockam::{Address, AsyncTryClone, Context, Result};
+use ockam::{Address, AsyncTryClone, Context, ForwardingService, Result};
 use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_core::compat::collections::BTreeMap;
 
 impl NodeManager {
     pub(super) async fn start_vault_service_impl(
@@ -45,10 +49,16 @@ impl NodeManager {
     ) -> Result<ResponseBuilder> {
         let req_body: StartVaultServiceRequest = dec.decode()?;
 
-        let addr = req_body.addr.to_string().into();
+        let addr: Address = req_body.addr.to_string().into();
 
-        let response = match self.start_vault_service_impl(ctx, addr).await {
-            Ok(_) => Response::ok(req.id()),
+        let response = match self.start_vault_service_impl(ctx, addr.clone()).await {
+            Ok(_) => {
+                self.persist(PersistedResource::Service {
+                    kind: ServiceKind::Vault,
+                    addr: addr.to_string(),
+                });
+                Response::ok(req.id())
+            }
             Err(_err) => Response::bad_request(req.id()),
         };
 
@@ -82,25 +92,27 @@ impl NodeManager {
     ) -> Result<ResponseBuilder> {
         let req_body: StartIdentityServiceRequest = dec.decode()?;
 
-        let addr = req_body.addr.to_string().into();
+        let addr: Address = req_body.addr.to_string().into();
 
-        let response = match self.start_identity_service_impl(ctx, addr).await {
-            Ok(_) => Response::ok(req.id()),
+        let response = match self.start_identity_service_impl(ctx, addr.clone()).await {
+            Ok(_) => {
+                self.persist(PersistedResource::Service {
+                    kind: ServiceKind::Identity,
+                    addr: addr.to_string(),
+                });
+                Response::ok(req.id())
+            }
             Err(_err) => Response::bad_request(req.id()),
         };
 
         Ok(response)
     }
 
-    pub(super) async fn start_verifier_service<'a>(
+    pub(super) async fn start_verifier_service_impl(
         &mut self,
         ctx: &Context,
-        req: &'a Request<'_>,
-        dec: &mut Decoder<'_>,
-    ) -> Result<ResponseBuilder> {
-        let body: StartVerifierService = dec.decode()?;
-        let addr: Address = body.address().into();
-
+        addr: Address,
+    ) -> Result<()> {
         if self.registry.verifier_services.contains_key(&addr) {
             return Err(ApiError::generic("verifier exists at this address"));
         }
@@ -118,6 +130,24 @@ impl NodeManager {
             .verifier_services
             .insert(addr, VerifierServiceInfo::default());
 
+        Ok(())
+    }
+
+    pub(super) async fn start_verifier_service<'a>(
+        &mut self,
+        ctx: &Context,
+        req: &'a Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder> {
+        let body: StartVerifierService = dec.decode()?;
+        let addr: Address = body.address().into();
+
+        self.start_verifier_service_impl(ctx, addr.clone()).await?;
+        self.persist(PersistedResource::Service {
+            kind: ServiceKind::Verifier,
+            addr: addr.to_string(),
+        });
+
         Ok(Response::ok(req.id()))
     }
 
@@ -162,7 +192,12 @@ impl NodeManager {
         let addr: Address = body.address().into();
         let oneway = body.oneway();
 
-        self.start_credentials_service_impl(addr, oneway).await?;
+        self.start_credentials_service_impl(addr.clone(), oneway)
+            .await?;
+        self.persist(PersistedResource::Service {
+            kind: ServiceKind::Credentials { oneway },
+            addr: addr.to_string(),
+        });
 
         Ok(Response::ok(req.id()))
     }
@@ -197,10 +232,19 @@ impl NodeManager {
     ) -> Result<ResponseBuilder> {
         let req_body: StartAuthenticatedServiceRequest = dec.decode()?;
 
-        let addr = req_body.addr.to_string().into();
+        let addr: Address = req_body.addr.to_string().into();
 
-        let response = match self.start_authenticated_service_impl(ctx, addr).await {
-            Ok(_) => Response::ok(req.id()),
+        let response = match self
+            .start_authenticated_service_impl(ctx, addr.clone())
+            .await
+        {
+            Ok(_) => {
+                self.persist(PersistedResource::Service {
+                    kind: ServiceKind::Authenticated,
+                    addr: addr.to_string(),
+                });
+                Response::ok(req.id())
+            }
             Err(_err) => Response::bad_request(req.id()),
         };
 
@@ -235,10 +279,16 @@ impl NodeManager {
     ) -> Result<ResponseBuilder> {
         let req_body: StartUppercaseServiceRequest = dec.decode()?;
 
-        let addr = req_body.addr.to_string().into();
+        let addr: Address = req_body.addr.to_string().into();
 
-        let response = match self.start_uppercase_service_impl(ctx, addr).await {
-            Ok(_) => Response::ok(req.id()),
+        let response = match self.start_uppercase_service_impl(ctx, addr.clone()).await {
+            Ok(_) => {
+                self.persist(PersistedResource::Service {
+                    kind: ServiceKind::Uppercase,
+                    addr: addr.to_string(),
+                });
+                Response::ok(req.id())
+            }
             Err(_err) => Response::bad_request(req.id()),
         };
 
@@ -271,10 +321,16 @@ impl NodeManager {
     ) -> Result<ResponseBuilder> {
         let req_body: StartEchoerServiceRequest = dec.decode()?;
 
-        let addr = req_body.addr.to_string().into();
+        let addr: Address = req_body.addr.to_string().into();
 
-        let response = match self.start_echoer_service_impl(ctx, addr).await {
-            Ok(_) => Response::ok(req.id()),
+        let response = match self.start_echoer_service_impl(ctx, addr.clone()).await {
+            Ok(_) => {
+                self.persist(PersistedResource::Service {
+                    kind: ServiceKind::Echoer,
+                    addr: addr.to_string(),
+                });
+                Response::ok(req.id())
+            }
             Err(_err) => Response::bad_request(req.id()),
         };
 
@@ -295,8 +351,20 @@ impl NodeManager {
             let body: StartAuthenticatorRequest = dec.decode()?;
             let addr: Address = body.address().into();
 
-            self.start_direct_authenticator_service_impl(ctx, addr, body.path(), body.project())
-                .await?;
+            self.start_direct_authenticator_service_impl(
+                ctx,
+                addr.clone(),
+                body.path(),
+                body.project(),
+            )
+            .await?;
+            self.persist(PersistedResource::Service {
+                kind: ServiceKind::Authenticator {
+                    path: body.path().to_path_buf(),
+                    project: body.project().to_vec(),
+                },
+                addr: addr.to_string(),
+            });
         }
 
         Ok(Response::ok(req.id()))
@@ -323,4 +391,137 @@ impl NodeManager {
             .insert(addr, AuthenticatorServiceInfo::default());
         Ok(())
     }
+
+    pub(super) async fn start_forwarding_service_impl(&mut self, ctx: &Context) -> Result<()> {
+        let addr = Address::from(DefaultAddress::FORWARDING_SERVICE);
+        if self.registry.forwarding_services.contains_key(&addr) {
+            return Err(ApiError::generic("forwarding service already started"));
+        }
+        ForwardingService::create(ctx).await?;
+        self.registry
+            .forwarding_services
+            .insert(addr, Default::default());
+        Ok(())
+    }
+
+    pub(super) fn list_services(&self, req: &Request<'_>) -> ResponseBuilder<ServiceList<'_>> {
+        let r = &self.registry;
+        let mut list = Vec::new();
+        list.extend(services(&r.vault_services, "vault"));
+        list.extend(services(&r.identity_services, "identity"));
+        list.extend(services(&r.authenticated_services, "authenticated"));
+        list.extend(services(&r.uppercase_services, "uppercase"));
+        list.extend(services(&r.echoer_services, "echo"));
+        list.extend(services(&r.verifier_services, "verifier"));
+        list.extend(services(&r.credentials_services, "credentials"));
+        #[cfg(feature = "direct-authenticator")]
+        list.extend(services(&r.authenticator_service, "authenticator"));
+        list.extend(services(&r.forwarding_services, "forwarding"));
+        list.extend(services(&r.controller_services, "controller"));
+        Response::ok(req.id()).body(ServiceList::new(list))
+    }
+
+    pub(super) async fn delete_service(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder> {
+        let body: DeleteServiceRequest = dec.decode()?;
+        let addr: Address = body.address().into();
+        info!("Handling request to delete service: {}", addr);
+
+        let r = &mut self.registry;
+        let removed = r.vault_services.remove(&addr).is_some()
+            || r.identity_services.remove(&addr).is_some()
+            || r.authenticated_services.remove(&addr).is_some()
+            || r.uppercase_services.remove(&addr).is_some()
+            || r.echoer_services.remove(&addr).is_some()
+            || r.verifier_services.remove(&addr).is_some()
+            || r.credentials_services.remove(&addr).is_some()
+            || r.forwarding_services.remove(&addr).is_some()
+            || r.controller_services.remove(&addr).is_some();
+        #[cfg(feature = "direct-authenticator")]
+        let removed = removed || r.authenticator_service.remove(&addr).is_some();
+        if !removed {
+            return Ok(Response::not_found(req.id()));
+        }
+
+        self.unpersist_matching(|r| {
+            matches!(r, PersistedResource::Service { addr: a, .. } if Address::from(a.as_str()) == addr)
+        });
+
+        match ctx.stop_worker(addr).await {
+            Ok(()) => Ok(Response::ok(req.id())),
+            Err(err) => {
+                error!(?err, "Failed to stop service");
+                Ok(Response::internal_error(req.id()))
+            }
+        }
+    }
+}
+
+fn services<'a, V>(
+    map: &'a BTreeMap<Address, V>,
+    service_type: &'static str,
+) -> impl Iterator<Item = ServiceStatus<'static>> + 'a {
+    map.keys()
+        .map(move |addr| ServiceStatus::new(addr.address().to_string(), service_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use minicbor::Decoder;
+    use ockam_core::api::{Request, Response, Status};
+
+    use super::*;
+
+    #[ockam_macros::test]
+    async fn services_can_be_listed_and_deleted(ctx: &mut Context) -> Result<()> {
+        let manager = NodeManager::test_create(ctx).await?;
+
+        let request = Request::post("/node/services/uppercase")
+            .body(StartUppercaseServiceRequest::new("deleted_uppercase"))
+            .to_vec()?;
+        let response: Vec<u8> = ctx.send_and_receive(manager.clone(), request).await?;
+        let header: Response = Decoder::new(&response).decode()?;
+        assert_eq!(header.status(), Some(Status::Ok));
+
+        let request = Request::get("/node/services").to_vec()?;
+        let response: Vec<u8> = ctx.send_and_receive(manager.clone(), request).await?;
+        let mut dec = Decoder::new(&response);
+        let header: Response = dec.decode()?;
+        assert_eq!(header.status(), Some(Status::Ok));
+        let services: ServiceList = dec.decode()?;
+        assert!(services
+            .list
+            .iter()
+            .any(|s| s.addr == "deleted_uppercase" && s.service_type == "uppercase"));
+
+        let delete = Request::delete("/node/services")
+            .body(DeleteServiceRequest::new("deleted_uppercase"))
+            .to_vec()?;
+        let response: Vec<u8> = ctx
+            .send_and_receive(manager.clone(), delete.clone())
+            .await?;
+        let header: Response = Decoder::new(&response).decode()?;
+        assert_eq!(header.status(), Some(Status::Ok));
+        // Workers stop asynchronously
+        while ctx
+            .list_workers()
+            .await?
+            .contains(&"deleted_uppercase".into())
+        {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+
+        // The service is gone, so deleting it again fails
+        let response: Vec<u8> = ctx.send_and_receive(manager, delete).await?;
+        let header: Response = Decoder::new(&response).decode()?;
+        assert_eq!(header.status(), Some(Status::NotFound));
+
+        ctx.stop().await
+    }
 }
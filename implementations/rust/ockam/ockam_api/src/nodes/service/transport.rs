@@ -1,60 +1,154 @@
+use crate::nodes::config::PersistedResource;
 use crate::nodes::models::transport::{
-    CreateTransport, DeleteTransport, TransportList, TransportMode, TransportStatus,
+    CreateTransport, DeleteTransport, TransportList, TransportMode, TransportStatus, TransportType,
 };
 use crate::nodes::service::{random_alias, Alias};
 use crate::nodes::NodeManager;
 use minicbor::Decoder;
-use ockam::Result;
+use ockam::{Context, Result};
 use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_core::errcode::{Kind, Origin};
+#[cfg(feature = "ockam_transport_udp")]
+use ockam_transport_udp::UdpTransport;
+#[cfg(feature = "ockam_transport_websocket")]
+use ockam_transport_websocket::WebSocketTransport;
 
 impl NodeManager {
-    pub(super) fn get_tcp_con_or_list(
+    pub(super) fn get_transport_list(
         &self,
         req: &Request<'_>,
+        path_segment: &str,
         mode: TransportMode,
     ) -> ResponseBuilder<TransportList<'_>> {
         Response::ok(req.id()).body(TransportList::new(
             self.transports
                 .iter()
-                .filter(|(_, (_, tm, _))| *tm == mode)
+                .filter(|(_, (tt, tm, _))| tt.path_segment() == path_segment && *tm == mode)
                 .map(|(tid, (tt, tm, addr))| TransportStatus::new(*tt, *tm, addr, tid))
                 .collect(),
         ))
     }
 
+    /// The WebSocket transport of the node, created on first use
+    #[cfg(feature = "ockam_transport_websocket")]
+    async fn ws_transport(&mut self, ctx: &Context) -> Result<&WebSocketTransport> {
+        let transport = match self.ws_transport.take() {
+            Some(transport) => transport,
+            None => WebSocketTransport::create(ctx).await?,
+        };
+        Ok(self.ws_transport.insert(transport))
+    }
+
+    /// The UDP transport of the node, created on first use
+    #[cfg(feature = "ockam_transport_udp")]
+    async fn udp_transport(&mut self, ctx: &Context) -> Result<&UdpTransport> {
+        let transport = match self.udp_transport.take() {
+            Some(transport) => transport,
+            None => UdpTransport::create(ctx).await?,
+        };
+        Ok(self.udp_transport.insert(transport))
+    }
+
+    /// Create a transport and register it, returning its ID and its
+    /// address
+    #[cfg_attr(
+        not(any(feature = "ockam_transport_udp", feature = "ockam_transport_websocket")),
+        allow(unused_variables)
+    )]
+    pub(super) async fn add_transport_impl(
+        &mut self,
+        ctx: &Context,
+        tt: TransportType,
+        tm: TransportMode,
+        addr: String,
+    ) -> Result<(Alias, String)> {
+        use {TransportMode::*, TransportType::*};
+
+        let addr = match (tt, tm) {
+            (Tcp, Listen) => self
+                .tcp_transport
+                .listen(&addr)
+                .await
+                .map(|socket| socket.to_string())?,
+            (Tcp, Connect) => self.tcp_transport.connect(&addr).await.map(|_| addr)?,
+            #[cfg(feature = "ockam_transport_websocket")]
+            (WebSocket, Listen) => self
+                .ws_transport(ctx)
+                .await?
+                .listen(&addr)
+                .await
+                .map(|socket| socket.to_string())?,
+            #[cfg(feature = "ockam_transport_websocket")]
+            (WebSocket, Connect) => self
+                .ws_transport(ctx)
+                .await?
+                .connect(&addr)
+                .await
+                .map(|_| addr)?,
+            #[cfg(feature = "ockam_transport_udp")]
+            (Udp, Listen) => self
+                .udp_transport(ctx)
+                .await?
+                .listen(&addr)
+                .await
+                .map(|socket| socket.to_string())?,
+            #[cfg(feature = "ockam_transport_udp")]
+            (Udp, Connect) => self
+                .udp_transport(ctx)
+                .await?
+                .connect(&addr)
+                .await
+                .map(|_| addr)?,
+            _ => {
+                return Err(ockam_core::Error::new(
+                    Origin::Transport,
+                    Kind::Unsupported,
+                    format!("{} transports can not be created by this node", tt),
+                ))
+            }
+        };
+
+        let tid = random_alias();
+        self.transports.insert(tid.clone(), (tt, tm, addr.clone()));
+        Ok((tid, addr))
+    }
+
     pub(super) async fn add_transport<'a>(
         &mut self,
+        ctx: &Context,
         req: &Request<'_>,
         dec: &mut Decoder<'_>,
+        path_segment: &str,
+        mode: TransportMode,
     ) -> Result<ResponseBuilder<TransportStatus<'a>>> {
         let CreateTransport { tt, tm, addr, .. } = dec.decode()?;
 
-        use {super::TransportType::*, TransportMode::*};
-
         info!(
             "Handling request to create a new transport: {}, {}, {}",
             tt, tm, addr
         );
         let addr = addr.to_string();
 
-        let res = match (tt, tm) {
-            (Tcp, Listen) => self
-                .tcp_transport
-                .listen(&addr)
-                .await
-                .map(|socket| socket.to_string()),
-            (Tcp, Connect) => self
-                .tcp_transport
-                .connect(&addr)
-                .await
-                .map(|ockam_addr| ockam_addr.to_string()),
-            _ => unimplemented!(),
-        };
+        if tt.path_segment() != path_segment || tm != mode {
+            warn!(
+                "Request to create a {} {} transport was sent to the {} {} endpoint",
+                tt, tm, path_segment, mode
+            );
+            return Ok(Response::bad_request(req.id()).body(TransportStatus::new(
+                tt,
+                tm,
+                "transport type or mode does not match the request path".to_string(),
+                "<none>".to_string(),
+            )));
+        }
 
-        let response = match res {
-            Ok(_) => {
-                let tid = random_alias();
-                self.transports.insert(tid.clone(), (tt, tm, addr.clone()));
+        let response = match self.add_transport_impl(ctx, tt, tm, addr).await {
+            Ok((tid, addr)) => {
+                self.persist(PersistedResource::Transport {
+                    tt,
+                    tm,
+                    addr: addr.clone(),
+                });
                 Response::ok(req.id()).body(TransportStatus::new(tt, tm, addr, tid))
             }
             Err(msg) => Response::bad_request(req.id()).body(TransportStatus::new(
@@ -72,6 +166,8 @@ impl NodeManager {
         &mut self,
         req: &Request<'_>,
         dec: &mut Decoder<'_>,
+        path_segment: &str,
+        mode: TransportMode,
     ) -> Result<ResponseBuilder<()>> {
         let body: DeleteTransport = dec.decode()?;
         info!("Handling request to delete transport: {}", body.tid);
@@ -83,17 +179,61 @@ impl NodeManager {
             return Ok(Response::bad_request(req.id()));
         }
 
-        match self.transports.get(&tid) {
-            Some(t) if t.1 == TransportMode::Listen => {
-                warn!("It is not currently supported to destroy LISTEN transports");
-                Ok(Response::bad_request(req.id()))
+        use {TransportMode::*, TransportType::*};
+
+        let (tt, tm, addr) = match self.transports.get(&tid) {
+            Some((tt, tm, _)) if tt.path_segment() != path_segment || *tm != mode => {
+                warn!(
+                    "Request to delete {} {} transport {} was sent to the {} {} endpoint",
+                    tt, tm, tid, path_segment, mode
+                );
+                return Ok(Response::bad_request(req.id()));
             }
-            Some(t) => {
-                self.tcp_transport.disconnect(&t.2).await?;
-                self.transports.remove(&tid);
-                Ok(Response::ok(req.id()))
+            Some(t) => t.clone(),
+            None => return Ok(Response::not_found(req.id())),
+        };
+
+        let res = match (tt, tm) {
+            (Tcp, Connect) => self.tcp_transport.disconnect(&addr).await,
+            #[cfg(feature = "ockam_transport_websocket")]
+            (WebSocket, Connect) => match &self.ws_transport {
+                Some(ws) => ws.disconnect(&addr).await,
+                None => Ok(()),
+            },
+            #[cfg(feature = "ockam_transport_websocket")]
+            (WebSocket, Listen) => match &self.ws_transport {
+                Some(ws) => ws.stop_listener(&addr).await,
+                None => Ok(()),
+            },
+            #[cfg(feature = "ockam_transport_udp")]
+            (Udp, Connect) => match &self.udp_transport {
+                Some(udp) => udp.disconnect(&addr).await,
+                None => Ok(()),
+            },
+            #[cfg(feature = "ockam_transport_udp")]
+            (Udp, Listen) => match &self.udp_transport {
+                Some(udp) => udp.stop_listener(&addr).await,
+                None => Ok(()),
+            },
+            _ => {
+                warn!(
+                    "It is not currently supported to destroy {} {} transports",
+                    tt, tm
+                );
+                return Ok(Response::bad_request(req.id()));
+            }
+        };
+
+        // The transport is forgotten even if closing it failed, as it
+        // may have been closed by its peer in the meantime
+        self.transports.remove(&tid);
+        self.unpersist(&PersistedResource::Transport { tt, tm, addr });
+        match res {
+            Ok(()) => Ok(Response::ok(req.id())),
+            Err(e) => {
+                warn!("Failed to close {} {} transport {}: {}", tt, tm, tid, e);
+                Ok(Response::internal_error(req.id()))
             }
-            None => Ok(Response::bad_request(req.id())),
         }
     }
 }
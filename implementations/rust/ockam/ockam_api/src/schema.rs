@@ -0,0 +1,29 @@
+//! Checks of API types against the CDDL schema
+
+use cddl_cat::context::BasicContext;
+use cddl_cat::flatten::flatten_from_str;
+use cddl_cat::validate_cbor;
+use minicbor::Encode;
+use ockam_core::api::SCHEMA;
+use quickcheck::TestResult;
+
+thread_local! {
+    /// The parsed schema, parsing it for every value is slow
+    static RULES: BasicContext = BasicContext::new(flatten_from_str(SCHEMA).unwrap());
+}
+
+/// Validate the CBOR encoding of `value` against the schema `rule`.
+pub(crate) fn validate<T: Encode<()>>(rule: &str, value: &T) -> TestResult {
+    let cbor = minicbor::to_vec(value).unwrap();
+    let cbor: serde_cbor::Value = serde_cbor::from_slice(&cbor).unwrap();
+    RULES.with(|rules| {
+        let result = match rules.rules.get(rule) {
+            Some(def) => validate_cbor(def, &cbor, rules),
+            None => return TestResult::error(format!("no schema rule {rule}")),
+        };
+        if let Err(e) = result {
+            return TestResult::error(format!("{rule}: {e}"));
+        }
+        TestResult::passed()
+    })
+}
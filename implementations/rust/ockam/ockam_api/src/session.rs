@@ -11,10 +11,10 @@ use ockam_node::tokio::sync::mpsc;
 use ockam_node::tokio::task::JoinSet;
 use ockam_node::tokio::time::{timeout, Duration};
 use ockam_node::Context;
-use sessions::{Key, Ping, Status};
+use sessions::Ping;
 use tracing as log;
 
-pub use sessions::{Session, Sessions};
+pub use sessions::{Key, Session, Sessions, Status};
 
 const MAX_FAILURES: usize = 3;
 const DELAY: Duration = Duration::from_secs(3);
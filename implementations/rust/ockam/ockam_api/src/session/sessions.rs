@@ -62,6 +62,17 @@ impl Sessions {
         k
     }
 
+    pub fn remove(&mut self, k: &Key) -> Option<Session> {
+        let s = self.map.remove(k)?;
+        log::debug! {
+            target: "ockam_api::session",
+            key = %k,
+            addr = %s.address(),
+            "session removed"
+        }
+        Some(s)
+    }
+
     #[allow(unused)]
     pub fn session(&self, k: &Key) -> Option<&Session> {
         self.map.get(k)
@@ -71,7 +82,6 @@ impl Sessions {
         self.map.get_mut(k)
     }
 
-    #[allow(unused)]
     pub fn iter(&self) -> impl Iterator<Item = (&Key, &Session)> + '_ {
         self.map.iter()
     }
@@ -0,0 +1,135 @@
+use minicbor::{Decode, Encode};
+use ockam_core::api::{is_ok, Request};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result, Routed, Worker};
+use ockam_node::api::request;
+use ockam_node::Context;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cbor(map)]
+pub struct Value {
+    #[n(1)]
+    value: u64,
+}
+
+#[ockam_macros::api]
+pub trait Counters {
+    #[put("/counters/{name}")]
+    async fn set(&mut self, name: &str, value: Value) -> Result<()>;
+
+    #[get("/counters/{name}")]
+    async fn get(&mut self, name: &str) -> Result<Option<Value>>;
+
+    #[post("/counters/{name}/add/{n}")]
+    async fn add(&mut self, name: &str, n: u64) -> Result<Value>;
+
+    #[delete("/counters")]
+    async fn clear(&mut self) -> Result<()>;
+}
+
+#[derive(Default)]
+struct Server {
+    counters: BTreeMap<String, u64>,
+}
+
+#[ockam_core::async_trait]
+impl Counters for Server {
+    async fn set(&mut self, name: &str, value: Value) -> Result<()> {
+        self.counters.insert(name.to_string(), value.value);
+        Ok(())
+    }
+
+    async fn get(&mut self, name: &str) -> Result<Option<Value>> {
+        Ok(self.counters.get(name).map(|v| Value { value: *v }))
+    }
+
+    async fn add(&mut self, name: &str, n: u64) -> Result<Value> {
+        match self.counters.get_mut(name) {
+            Some(v) => {
+                *v += n;
+                Ok(Value { value: *v })
+            }
+            None => Err(Error::new(
+                Origin::Application,
+                Kind::NotFound,
+                "no such counter",
+            )),
+        }
+    }
+
+    async fn clear(&mut self) -> Result<()> {
+        Err(Error::new(
+            Origin::Application,
+            Kind::Misuse,
+            "counters can not be cleared",
+        ))
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for Server {
+    type Context = Context;
+    type Message = Vec<u8>;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Vec<u8>>) -> Result<()> {
+        let r = self.handle_request(msg.as_body()).await?;
+        ctx.send(msg.return_route(), r).await
+    }
+}
+
+#[ockam_macros::test]
+async fn typed_client(ctx: &mut Context) -> Result<()> {
+    ctx.start_worker("counters", Server::default()).await?;
+    let mut client = CountersClient::new("counters".into(), ctx).await?;
+
+    assert_eq!(None, client.get("a").await?);
+    client.set("a", Value { value: 40 }).await?;
+    assert_eq!(Some(Value { value: 40 }), client.get("a").await?);
+    assert_eq!(Value { value: 42 }, client.add("a", 2).await?);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn errors_keep_their_kind(ctx: &mut Context) -> Result<()> {
+    ctx.start_worker("counters", Server::default()).await?;
+    let mut client = CountersClient::new("counters".into(), ctx).await?;
+
+    let e = client.add("b", 1).await.unwrap_err();
+    assert_eq!(Kind::NotFound, e.code().kind);
+    let e = client.clear().await.unwrap_err();
+    assert_eq!(Kind::Misuse, e.code().kind);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn invalid_requests(ctx: &mut Context) -> Result<()> {
+    ctx.start_worker("counters", Server::default()).await?;
+
+    // The path parameter is not a number
+    let req = Request::post("/counters/a/add/two");
+    let res = request(ctx, "add", None, "counters", req).await?;
+    assert_eq!(Kind::Invalid, is_ok("add", &res).unwrap_err().code().kind);
+
+    // The request body is missing
+    let req = Request::put("/counters/a");
+    let res = request(ctx, "set", None, "counters", req).await?;
+    assert_eq!(Kind::Invalid, is_ok("set", &res).unwrap_err().code().kind);
+
+    // The path is known but not for this method
+    let req = Request::get("/counters");
+    let res = request(ctx, "clear", None, "counters", req).await?;
+    assert_eq!(
+        Kind::Unsupported,
+        is_ok("clear", &res).unwrap_err().code().kind
+    );
+
+    // The path is unknown
+    let req = Request::get("/counters/a/b");
+    let res = request(ctx, "get", None, "counters", req).await?;
+    assert_eq!(Kind::Invalid, is_ok("get", &res).unwrap_err().code().kind);
+
+    ctx.stop().await
+}
@@ -1,7 +1,9 @@
 use cddl_cat::validate_cbor_bytes;
 use ockam_core::api::SCHEMA;
-use ockam_core::api::{Error, Id, Method, Request, Response, Status};
+use ockam_core::api::{Error, Id, Method, Request, Response, Status, Stream};
 use quickcheck::{quickcheck, Arbitrary, Gen, TestResult};
+use std::fs;
+use std::path::Path;
 
 const METHODS: &[Method] = &[
     Method::Get,
@@ -11,6 +13,8 @@ const METHODS: &[Method] = &[
     Method::Patch,
 ];
 
+const STREAM: &[Stream] = &[Stream::Begin, Stream::Item, Stream::End];
+
 const STATUS: &[Status] = &[
     Status::Ok,
     Status::BadRequest,
@@ -41,11 +45,15 @@ impl Arbitrary for Req {
 
 impl Arbitrary for Res {
     fn arbitrary(g: &mut Gen) -> Self {
-        Res(Response::new(
-            Id::fresh(),
-            *g.choose(STATUS).unwrap(),
-            bool::arbitrary(g),
-        ))
+        let mut r = Response::builder(Id::fresh(), *g.choose(STATUS).unwrap());
+        if bool::arbitrary(g) {
+            r = r.stream(*g.choose(STREAM).unwrap())
+        }
+        if bool::arbitrary(g) {
+            Res(r.body(()).into_parts().0)
+        } else {
+            Res(r.into_parts().0)
+        }
     }
 }
 
@@ -100,3 +108,28 @@ quickcheck! {
         TestResult::passed()
     }
 }
+
+/// Every node manager and cloud API type must have a schema rule with
+/// its type tag.
+#[test]
+fn api_types_have_schema_rules() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut missing = Vec::new();
+    for dir in ["nodes/models", "nodes/service", "cloud"] {
+        for entry in fs::read_dir(src.join(dir)).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension() != Some("rs".as_ref()) {
+                continue;
+            }
+            let code = fs::read_to_string(&path).unwrap();
+            for (i, _) in code.match_indices("TypeTag<") {
+                let rest = &code[i + 8..];
+                let tag = &rest[..rest.find('>').unwrap()];
+                if !SCHEMA.contains(&format!("?0: {tag}")) {
+                    missing.push(format!("{} ({})", tag, path.display()))
+                }
+            }
+        }
+    }
+    assert!(missing.is_empty(), "no schema rule for: {missing:?}")
+}
@@ -1,4 +1,6 @@
+use crate::error::exit_with;
 use crate::{util::exitcode, CommandGlobalOpts};
+use anyhow::anyhow;
 use clap::Args;
 
 #[derive(Clone, Debug, Args)]
@@ -15,11 +17,14 @@ impl GetCommand {
                 println!("Node: {}\nAddress: {}", self.alias, addr);
             }
             None => {
-                eprintln!(
-                    "Alias {} not known.  Add it first with `ockam alias set`!",
-                    self.alias
+                exit_with(
+                    &options,
+                    exitcode::DATAERR,
+                    anyhow!(
+                        "Alias {} not known.  Add it first with `ockam alias set`!",
+                        self.alias
+                    ),
                 );
-                std::process::exit(exitcode::DATAERR);
             }
         }
     }
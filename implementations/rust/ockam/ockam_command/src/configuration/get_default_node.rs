@@ -1,4 +1,6 @@
+use crate::error::exit_with;
 use crate::{util::exitcode, CommandGlobalOpts};
+use anyhow::anyhow;
 use clap::Args;
 
 #[derive(Clone, Debug, Args)]
@@ -11,8 +13,11 @@ impl GetDefaultNodeCommand {
                 println!("Current Default Node: {}", name)
             }
             None => {
-                eprintln!("Default Node is not set");
-                std::process::exit(exitcode::UNAVAILABLE);
+                exit_with(
+                    &options,
+                    exitcode::UNAVAILABLE,
+                    anyhow!("Default Node is not set"),
+                );
             }
         }
     }
@@ -1,6 +1,10 @@
+use crate::error::exit_with;
+use crate::util::exitcode;
+use crate::util::output::print_structured;
 use crate::CommandGlobalOpts;
 use clap::Args;
 use ockam_api::config::lookup::LookupValue;
+use serde_json::json;
 
 #[derive(Clone, Debug, Args)]
 pub struct ListCommand {}
@@ -9,14 +13,25 @@ impl ListCommand {
     pub fn run(self, options: CommandGlobalOpts) {
         let lookup = options.config.lookup();
 
+        let mut nodes = vec![];
         for (alias, value) in &lookup.map {
             // Currently we only have this one type of lookup but we
             // need to be ready for more values.  Remove this "allow"
             // in the future
             #[allow(irrefutable_let_patterns)]
             if let LookupValue::Address(addr) = value {
-                println!("Node:    {}\nAddress: {}\n", alias, addr);
+                nodes.push(json!({ "node": alias, "address": addr.to_string() }));
             }
         }
+
+        match print_structured(&options, &nodes) {
+            Ok(true) => {}
+            Ok(false) => {
+                for node in &nodes {
+                    println!("Node:    {}\nAddress: {}\n", node["node"], node["address"]);
+                }
+            }
+            Err(e) => exit_with(&options, exitcode::IOERR, e),
+        }
     }
 }
@@ -1,4 +1,6 @@
+use crate::error::exit_with;
 use crate::{util::exitcode, CommandGlobalOpts};
+use anyhow::anyhow;
 use clap::Args;
 use ockam_api::config::lookup::InternetAddress;
 
@@ -15,18 +17,14 @@ impl SetCommand {
         let target_addr = match InternetAddress::new(&self.target) {
             Some(addr) => addr,
             None => {
-                eprintln!(
-                    "Invalid alias address!  Please provide an address in the following schema: <address>:<port>. \
-                     IPv6, IPv4, and DNS addresses are supported!"
-                );
-                std::process::exit(exitcode::USAGE);
+                exit_with(&options, exitcode::USAGE, anyhow!("Invalid alias address!  Please provide an address in the following schema: <address>:<port>. \
+                     IPv6, IPv4, and DNS addresses are supported!"));
             }
         };
 
         options.config.set_node_alias(self.name, target_addr);
         if let Err(e) = options.config.persist_config_updates() {
-            eprintln!("{}", e);
-            std::process::exit(exitcode::IOERR);
+            exit_with(&options, exitcode::IOERR, anyhow!("{}", e));
         }
     }
 }
@@ -1,7 +1,9 @@
+use crate::error::exit_with;
 use crate::{
     util::{exitcode, get_final_element},
     CommandGlobalOpts,
 };
+use anyhow::anyhow;
 use clap::Args;
 
 #[derive(Clone, Debug, Args)]
@@ -12,16 +14,22 @@ pub struct SetDefaultNodeCommand {
 
 impl SetDefaultNodeCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let name = get_final_element(&self.name);
+        let name = get_final_element(&options, &self.name);
         if options.config.get_node(name).is_ok() {
             options.config.set_default_node(&name.to_string());
             if let Err(e) = options.config.persist_config_updates() {
-                eprintln!("failed to update configuration: {}", e);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &options,
+                    exitcode::IOERR,
+                    anyhow!("failed to update configuration: {}", e),
+                );
             }
         } else {
-            eprintln!("Node ({}) is not registered yet", self.name);
-            std::process::exit(exitcode::CANTCREAT);
+            exit_with(
+                &options,
+                exitcode::CANTCREAT,
+                anyhow!("Node ({}) is not registered yet", self.name),
+            );
         }
     }
 }
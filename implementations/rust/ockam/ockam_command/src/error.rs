@@ -1,7 +1,12 @@
 use std::fmt::{Debug, Display, Formatter};
 
+use ockam_core::api::Status;
+use ockam_core::errcode::Kind;
+use serde::Serialize;
+
+use crate::util::output::Output;
 use crate::util::ConfigError;
-use crate::{exitcode, ExitCode};
+use crate::{exitcode, CommandGlobalOpts, ExitCode, OutputFormat};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -19,6 +24,101 @@ impl Error {
     pub fn code(&self) -> ExitCode {
         self.code
     }
+
+    /// The kind of the underlying ockam error, or one derived from the
+    /// exit code if there is none
+    pub fn kind(&self) -> Kind {
+        for e in self.inner.chain() {
+            if let Some(e) = e.downcast_ref::<ockam_core::Error>() {
+                return e.code().kind;
+            }
+            if let Some(e) = e.downcast_ref::<ApiError>() {
+                return e.kind();
+            }
+        }
+        match self.code {
+            exitcode::USAGE | exitcode::DATAERR | exitcode::CONFIG => Kind::Invalid,
+            exitcode::NOINPUT | exitcode::NOHOST => Kind::NotFound,
+            exitcode::IOERR | exitcode::OSFILE | exitcode::CANTCREAT => Kind::Io,
+            exitcode::TEMPFAIL => Kind::Timeout,
+            exitcode::PROTOCOL => Kind::Protocol,
+            exitcode::SOFTWARE => Kind::Internal,
+            _ => Kind::Unknown,
+        }
+    }
+
+    /// Print the error in the given output format
+    ///
+    /// Plain errors go to stderr, while JSON and YAML errors are
+    /// printed to stdout so that it always holds a single document.
+    pub fn print(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Plain => eprintln!("{:?}", self),
+            format => match format.render(&ErrorOutput::from(self)) {
+                Ok(s) => println!("{}", s),
+                Err(_) => eprintln!("{:?}", self),
+            },
+        }
+    }
+}
+
+/// Report an error in the output format of this invocation and exit
+/// with the given code
+pub fn exit_with(opts: &CommandGlobalOpts, code: ExitCode, err: anyhow::Error) -> ! {
+    Error::new(code, err).print(opts.global_args.output_format);
+    std::process::exit(code)
+}
+
+/// Error response of a node to an API request
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: Option<Status>,
+    pub message: String,
+}
+
+impl ApiError {
+    fn kind(&self) -> Kind {
+        self.status.map(Kind::from).unwrap_or(Kind::Protocol)
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Structured form of an [`Error`], used for JSON and YAML output
+#[derive(Serialize)]
+struct ErrorOutput {
+    error: ErrorBody,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    kind: Kind,
+    exit_code: ExitCode,
+    message: String,
+}
+
+impl From<&Error> for ErrorOutput {
+    fn from(e: &Error) -> Self {
+        ErrorOutput {
+            error: ErrorBody {
+                kind: e.kind(),
+                exit_code: e.code,
+                message: format!("{:#}", e.inner),
+            },
+        }
+    }
+}
+
+impl Output for ErrorOutput {
+    fn output(&self) -> anyhow::Result<String> {
+        Ok(self.error.message.clone())
+    }
 }
 
 impl Debug for Error {
@@ -68,3 +168,42 @@ impl From<ockam_multiaddr::Error> for Error {
         Error::new(exitcode::SOFTWARE, e.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_core::errcode::Origin;
+
+    #[test]
+    fn kind_of_ockam_error() {
+        let e = ockam_core::Error::new(Origin::Node, Kind::NotFound, "no such worker");
+        let err = Error::from(anyhow::Error::from(e).context("failed to send message"));
+        assert_eq!(err.kind(), Kind::NotFound);
+    }
+
+    #[test]
+    fn kind_of_api_error() {
+        let e = ApiError {
+            status: Some(Status::Conflict),
+            message: "alias already exists".to_string(),
+        };
+        let err = Error::new(exitcode::CANTCREAT, e.into());
+        assert_eq!(err.kind(), Kind::Conflict);
+    }
+
+    #[test]
+    fn kind_from_exit_code() {
+        let err = Error::new(exitcode::CONFIG, anyhow::anyhow!("invalid config"));
+        assert_eq!(err.kind(), Kind::Invalid);
+    }
+
+    #[test]
+    fn json_error_output() {
+        let err = Error::new(exitcode::IOERR, anyhow::anyhow!("connection refused"));
+        let json = OutputFormat::Json.render(&ErrorOutput::from(&err)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["error"]["kind"], "Io");
+        assert_eq!(value["error"]["exit_code"], exitcode::IOERR);
+        assert_eq!(value["error"]["message"], "connection refused");
+    }
+}
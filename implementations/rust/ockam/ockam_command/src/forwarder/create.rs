@@ -48,7 +48,7 @@ impl CreateCommand {
 
 async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, CreateCommand)) -> Result<()> {
     let tcp = TcpTransport::create(&ctx).await?;
-    let api_node = get_final_element(&cmd.to);
+    let api_node = get_final_element(&opts, &cmd.to);
     let at_rust_node = is_local_node(&cmd.at).context("Argument --at is not valid")?;
 
     let lookup = opts.config.lookup();
@@ -0,0 +1,44 @@
+use clap::Args;
+use serde_json::json;
+
+use ockam::Context;
+
+use crate::forwarder::HELP_DETAIL;
+use crate::node::NodeOpts;
+use crate::util::api;
+use crate::util::output::print_structured;
+use crate::util::{node_rpc, Rpc};
+use crate::{help, CommandGlobalOpts, Result};
+
+/// Delete Forwarders
+#[derive(Clone, Debug, Args)]
+#[command(
+    arg_required_else_help = true,
+    help_template = help::template(HELP_DETAIL)
+)]
+pub struct DeleteCommand {
+    /// Remote address of the forwarder, as shown by `ockam forwarder list`
+    remote_address: String,
+
+    /// Node which created the forwarder
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+impl DeleteCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, DeleteCommand)) -> Result<()> {
+    let mut rpc = Rpc::background(&ctx, &opts, &cmd.node_opts.api_node)?;
+    rpc.request(api::delete_forwarder(&cmd.remote_address))
+        .await?;
+    rpc.is_ok()?;
+
+    if !print_structured(&opts, &json!({ "remote_address": cmd.remote_address }))? {
+        println!("Deleted forwarder `{}`", cmd.remote_address);
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use clap::Args;
+
+use ockam::Context;
+use ockam_api::nodes::models::forwarder::ForwarderInfo;
+
+use crate::forwarder::HELP_DETAIL;
+use crate::node::NodeOpts;
+use crate::util::api;
+use crate::util::output::print_output;
+use crate::util::{node_rpc, Rpc};
+use crate::{help, CommandGlobalOpts, Result};
+
+/// List Forwarders
+#[derive(Clone, Debug, Args)]
+#[command(help_template = help::template(HELP_DETAIL))]
+pub struct ListCommand {
+    /// Node which created the forwarders
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+impl ListCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, ListCommand)) -> Result<()> {
+    let mut rpc = Rpc::background(&ctx, &opts, &cmd.node_opts.api_node)?;
+    rpc.request(api::list_forwarders()).await?;
+    let forwarders = rpc.parse_response::<Vec<ForwarderInfo>>()?;
+    print_output(&opts, &forwarders)?;
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use clap::{Args, Subcommand};
 
 pub(crate) use create::CreateCommand;
+pub(crate) use delete::DeleteCommand;
+pub(crate) use list::ListCommand;
 
 use crate::{help, CommandGlobalOpts};
 
 mod create;
+mod delete;
+mod list;
 
 const HELP_DETAIL: &str = "\
 About:
@@ -62,12 +66,16 @@ pub struct ForwarderCommand {
 #[derive(Clone, Debug, Subcommand)]
 pub enum ForwarderSubCommand {
     Create(CreateCommand),
+    List(ListCommand),
+    Delete(DeleteCommand),
 }
 
 impl ForwarderCommand {
     pub fn run(self, opts: CommandGlobalOpts) {
         match self.subcommand {
             ForwarderSubCommand::Create(c) => c.run(opts),
+            ForwarderSubCommand::List(c) => c.run(opts),
+            ForwarderSubCommand::Delete(c) => c.run(opts),
         }
     }
 }
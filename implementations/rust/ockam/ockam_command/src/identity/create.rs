@@ -1,7 +1,10 @@
+use crate::error::exit_with;
 use crate::help;
 use crate::node::NodeOpts;
-use crate::util::{api, connect_to, exitcode};
+use crate::util::output::print_structured;
+use crate::util::{api, connect_to, exitcode, node_port};
 use crate::CommandGlobalOpts;
+use anyhow::anyhow;
 use clap::Args;
 use ockam::Context;
 use ockam_api::nodes::NODEMANAGER_ADDR;
@@ -17,10 +20,9 @@ pub struct CreateCommand {
 
 impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) -> anyhow::Result<()> {
-        let cfg = options.config;
-        let port = cfg.get_node_port(&self.node_opts.api_node);
+        let port = node_port(&options, &self.node_opts.api_node);
 
-        connect_to(port, self, create_identity);
+        connect_to(port, (options, self), create_identity);
 
         Ok(())
     }
@@ -28,7 +30,7 @@ impl CreateCommand {
 
 pub async fn create_identity(
     ctx: Context,
-    _cmd: CreateCommand,
+    (opts, _cmd): (CommandGlobalOpts, CreateCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     let resp: Vec<u8> = ctx
@@ -42,11 +44,16 @@ pub async fn create_identity(
 
     match response.status() {
         Some(Status::Ok) => {
-            println!("Identity {} created!", result.identity_id)
+            if !print_structured(&opts, &result)? {
+                println!("Identity {} created!", result.identity_id)
+            }
         }
         _ => {
-            eprintln!("An error occurred while creating Identity",);
-            std::process::exit(exitcode::CANTCREAT);
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!("An error occurred while creating Identity",),
+            );
         }
     }
 
@@ -1,10 +1,14 @@
-use crate::util::{connect_to, exitcode, get_final_element};
+use crate::error::exit_with;
+use crate::util::output::print_structured;
+use crate::util::{connect_to, exitcode, get_final_element, node_port};
 use crate::CommandGlobalOpts;
 use crate::{node::NodeOpts, util::api};
+use anyhow::anyhow;
 use clap::Args;
 use ockam::{Context, Route};
 use ockam_api::nodes::NODEMANAGER_ADDR;
 use ockam_core::api::Status;
+use serde_json::json;
 
 #[derive(Clone, Debug, Args)]
 pub struct ShowCommand {
@@ -16,11 +20,10 @@ pub struct ShowCommand {
 
 impl ShowCommand {
     pub fn run(self, options: CommandGlobalOpts) -> anyhow::Result<()> {
-        let cfg = options.config;
-        let node = get_final_element(&self.node_opts.api_node);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, &self.node_opts.api_node);
+        let port = node_port(&options, node);
 
-        connect_to(port, self, show_identity);
+        connect_to(port, (options, self), show_identity);
 
         Ok(())
     }
@@ -28,7 +31,7 @@ impl ShowCommand {
 
 pub async fn show_identity(
     ctx: Context,
-    cmd: ShowCommand,
+    (opts, cmd): (CommandGlobalOpts, ShowCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     if cmd.full {
@@ -43,11 +46,17 @@ pub async fn show_identity(
 
         match response.status() {
             Some(Status::Ok) => {
-                println!("{}", hex::encode(result.identity.0.as_ref()))
+                let identity = hex::encode(result.identity.0.as_ref());
+                if !print_structured(&opts, &json!({ "identity": identity }))? {
+                    println!("{}", identity)
+                }
             }
             _ => {
-                eprintln!("An error occurred while exporting Identity",);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &opts,
+                    exitcode::IOERR,
+                    anyhow!("An error occurred while exporting Identity",),
+                );
             }
         }
 
@@ -64,11 +73,16 @@ pub async fn show_identity(
 
         match response.status() {
             Some(Status::Ok) => {
-                println!("{}", result.identity_id)
+                if !print_structured(&opts, &result)? {
+                    println!("{}", result.identity_id)
+                }
             }
             _ => {
-                eprintln!("An error occurred while getting Identity",);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &opts,
+                    exitcode::IOERR,
+                    anyhow!("An error occurred while getting Identity",),
+                );
             }
         }
 
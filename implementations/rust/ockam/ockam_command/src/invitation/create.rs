@@ -0,0 +1,81 @@
+use anyhow::Context as _;
+use clap::Args;
+
+use ockam::Context;
+use ockam_api::cloud::invitation::Invitation;
+
+use crate::node::util::{delete_embedded_node, start_embedded_node};
+use crate::project::config as project_config;
+use crate::space::config as space_config;
+use crate::util::api::{self, CloudOpts};
+use crate::util::{node_rpc, RpcBuilder};
+use crate::CommandGlobalOpts;
+
+#[derive(Clone, Debug, Args)]
+pub struct CreateCommand {
+    /// Email address of the person to invite.
+    #[arg(display_order = 1001)]
+    pub invitee: String,
+
+    /// Name of the space to invite to.
+    #[arg(display_order = 1002, long)]
+    pub space: String,
+
+    /// Name of a project of the space, to invite to the project only.
+    #[arg(display_order = 1003, long)]
+    pub project: Option<String>,
+
+    #[command(flatten)]
+    pub cloud_opts: CloudOpts,
+}
+
+impl CreateCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(
+    mut ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
+) -> crate::Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(
+    ctx: &mut Context,
+    opts: CommandGlobalOpts,
+    cmd: CreateCommand,
+) -> crate::Result<()> {
+    let controller_route = &cmd.cloud_opts.route();
+    let node_name = start_embedded_node(ctx, &opts.config).await?;
+
+    // Lookup space and project
+    let space_id =
+        space_config::get_space(ctx, &opts, &cmd.space, &node_name, controller_route).await?;
+    let project_id = match &cmd.project {
+        Some(name) => Some(match project_config::get_project(&opts.config, name) {
+            Some(id) => id,
+            None => {
+                project_config::refresh_projects(ctx, &opts, &node_name, controller_route, None)
+                    .await?;
+                project_config::get_project(&opts.config, name)
+                    .context(format!("Project '{}' does not exist", name))?
+            }
+        }),
+        None => None,
+    };
+
+    // Send request
+    let mut rpc = RpcBuilder::new(ctx, &opts, &node_name).build();
+    rpc.request(api::invitation::create(
+        &cmd.invitee,
+        &space_id,
+        project_id.as_deref(),
+        controller_route,
+    ))
+    .await?;
+    rpc.parse_and_print_response::<Invitation>()?;
+    delete_embedded_node(&opts.config, rpc.node_name()).await;
+    Ok(())
+}
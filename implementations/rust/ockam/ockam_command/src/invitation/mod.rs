@@ -0,0 +1,51 @@
+use clap::{Args, Subcommand};
+
+pub use accept::AcceptCommand;
+pub use create::CreateCommand;
+pub use list::ListCommand;
+pub use reject::RejectCommand;
+
+use crate::CommandGlobalOpts;
+
+mod accept;
+mod create;
+mod list;
+mod reject;
+
+/// Manage invitations to spaces and projects in Ockam Orchestrator
+#[derive(Clone, Debug, Args)]
+#[command(arg_required_else_help = true, subcommand_required = true)]
+pub struct InvitationCommand {
+    #[command(subcommand)]
+    subcommand: InvitationSubcommand,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum InvitationSubcommand {
+    /// Invite someone to a space or project
+    #[command(display_order = 800)]
+    Create(CreateCommand),
+
+    /// List invitations
+    #[command(display_order = 800)]
+    List(ListCommand),
+
+    /// Accept an invitation
+    #[command(display_order = 800)]
+    Accept(AcceptCommand),
+
+    /// Reject an invitation
+    #[command(display_order = 800)]
+    Reject(RejectCommand),
+}
+
+impl InvitationCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        match self.subcommand {
+            InvitationSubcommand::Create(c) => c.run(options),
+            InvitationSubcommand::List(c) => c.run(options),
+            InvitationSubcommand::Accept(c) => c.run(options),
+            InvitationSubcommand::Reject(c) => c.run(options),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use clap::Args;
+
+use ockam::Context;
+
+use crate::node::util::delete_embedded_node;
+use crate::util::api::{self, CloudOpts};
+use crate::util::{node_rpc, Rpc};
+use crate::CommandGlobalOpts;
+
+#[derive(Clone, Debug, Args)]
+pub struct RejectCommand {
+    /// Id of the invitation.
+    #[arg(display_order = 1001)]
+    pub id: String,
+
+    #[command(flatten)]
+    pub cloud_opts: CloudOpts,
+}
+
+impl RejectCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(
+    mut ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, RejectCommand),
+) -> crate::Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(
+    ctx: &mut Context,
+    opts: CommandGlobalOpts,
+    cmd: RejectCommand,
+) -> crate::Result<()> {
+    let mut rpc = Rpc::embedded(ctx, &opts).await?;
+    rpc.request(api::invitation::reject(&cmd.id, &cmd.cloud_opts.route()))
+        .await?;
+    rpc.is_ok()?;
+    delete_embedded_node(&opts.config, rpc.node_name()).await;
+    Ok(())
+}
@@ -11,6 +11,7 @@ mod error;
 mod forwarder;
 mod help;
 mod identity;
+mod invitation;
 mod message;
 mod node;
 mod project;
@@ -21,6 +22,7 @@ mod space;
 mod subscription;
 mod tcp;
 mod terminal;
+mod transport;
 mod upgrade;
 mod util;
 mod vault;
@@ -35,8 +37,11 @@ use enroll::EnrollCommand;
 use error::Result;
 use forwarder::ForwarderCommand;
 use identity::IdentityCommand;
+use invitation::InvitationCommand;
 use message::MessageCommand;
 use node::NodeCommand;
+use ockam_api::nodes::models::transport::TransportType;
+use ockam_api::nodes::NodeLogs;
 use project::ProjectCommand;
 use rand::prelude::random;
 use reset::ResetCommand;
@@ -48,6 +53,7 @@ use tcp::{
     connection::TcpConnectionCommand, inlet::TcpInletCommand, listener::TcpListenerCommand,
     outlet::TcpOutletCommand,
 };
+use transport::{connection::ConnectionCommand, listener::ListenerCommand};
 use util::{exitcode, exitcode::ExitCode, setup_logging, OckamConfig};
 use vault::VaultCommand;
 use version::Version;
@@ -192,13 +198,7 @@ pub struct GlobalArgs {
     no_color: bool,
 
     /// Output format
-    #[arg(
-        hide = help::hide(),
-        global = true,
-        long = "output",
-        value_enum,
-        default_value = "plain"
-    )]
+    #[arg(global = true, long = "output", value_enum, default_value = "plain")]
     output_format: OutputFormat,
 
     // if test_argument_parser is true, command arguments are checked
@@ -210,10 +210,11 @@ pub struct GlobalArgs {
     export: ExportCommandArgs,
 }
 
-#[derive(Debug, Clone, ValueEnum, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
 pub enum OutputFormat {
     Plain,
     Json,
+    Yaml,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -236,13 +237,16 @@ pub struct ExportCommandArgs {
 pub struct CommandGlobalOpts {
     pub global_args: GlobalArgs,
     pub config: OckamConfig,
+    /// Log lines of this invocation, served by foreground nodes
+    pub logs: NodeLogs,
 }
 
 impl CommandGlobalOpts {
-    fn new(global_args: GlobalArgs, config: OckamConfig) -> Self {
+    fn new(global_args: GlobalArgs, config: OckamConfig, logs: NodeLogs) -> Self {
         Self {
             global_args,
             config,
+            logs,
         }
     }
 }
@@ -255,6 +259,8 @@ pub enum OckamSubcommand {
     Space(SpaceCommand),
     #[command(display_order = 802)]
     Project(ProjectCommand),
+    #[command(display_order = 802)]
+    Invitation(InvitationCommand),
     #[command(display_order = 803)]
     Reset(ResetCommand),
 
@@ -270,13 +276,25 @@ pub enum OckamSubcommand {
     TcpOutlet(TcpOutletCommand),
     #[command(display_order = 816)]
     TcpInlet(TcpInletCommand),
+    /// Manage WebSocket Listeners
     #[command(display_order = 817)]
-    SecureChannelListener(SecureChannelListenerCommand),
+    WsListener(ListenerCommand),
+    /// Manage WebSocket Connections
     #[command(display_order = 818)]
-    SecureChannel(SecureChannelCommand),
+    WsConnection(ConnectionCommand),
+    /// Manage UDP Listeners
     #[command(display_order = 819)]
-    Forwarder(ForwarderCommand),
+    UdpListener(ListenerCommand),
+    /// Manage UDP Connections
     #[command(display_order = 820)]
+    UdpConnection(ConnectionCommand),
+    #[command(display_order = 821)]
+    SecureChannelListener(SecureChannelListenerCommand),
+    #[command(display_order = 822)]
+    SecureChannel(SecureChannelCommand),
+    #[command(display_order = 823)]
+    Forwarder(ForwarderCommand),
+    #[command(display_order = 824)]
     Message(MessageCommand),
 
     #[command(display_order = 900)]
@@ -303,11 +321,14 @@ pub fn run() {
 
     let config = OckamConfig::load();
 
-    if !command.global_args.quiet {
-        setup_logging(command.global_args.verbose, command.global_args.no_color);
+    let logs = if !command.global_args.quiet {
+        let logs = setup_logging(command.global_args.verbose, command.global_args.no_color);
         tracing::debug!("{}", Version::short());
         tracing::debug!("Parsed {:?}", &command);
-    }
+        logs
+    } else {
+        NodeLogs::new()
+    };
 
     if let Some(path) = command.global_args.export.export_path {
         let name = command.global_args.export.export_as.clone();
@@ -318,7 +339,7 @@ pub fn run() {
         return;
     }
 
-    let options = CommandGlobalOpts::new(command.global_args, config);
+    let options = CommandGlobalOpts::new(command.global_args, config, logs);
 
     // If test_argument_parser is true, command arguments are checked
     // but the command is not executed. This is useful to test arguments
@@ -338,11 +359,16 @@ pub fn run() {
         OckamSubcommand::Message(c) => c.run(options),
         OckamSubcommand::Node(c) => c.run(options),
         OckamSubcommand::Project(c) => c.run(options),
+        OckamSubcommand::Invitation(c) => c.run(options),
         OckamSubcommand::Space(c) => c.run(options),
         OckamSubcommand::TcpConnection(c) => c.run(options),
         OckamSubcommand::TcpInlet(c) => c.run(options),
         OckamSubcommand::TcpListener(c) => c.run(options),
         OckamSubcommand::TcpOutlet(c) => c.run(options),
+        OckamSubcommand::WsConnection(c) => c.run(options, TransportType::WebSocket),
+        OckamSubcommand::WsListener(c) => c.run(options, TransportType::WebSocket),
+        OckamSubcommand::UdpConnection(c) => c.run(options, TransportType::Udp),
+        OckamSubcommand::UdpListener(c) => c.run(options, TransportType::Udp),
         OckamSubcommand::Vault(c) => c.run(options),
         OckamSubcommand::Identity(c) => c.run(options),
         OckamSubcommand::SecureChannel(c) => c.run(options),
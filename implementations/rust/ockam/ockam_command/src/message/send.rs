@@ -50,7 +50,7 @@ async fn rpc(mut ctx: Context, (opts, cmd): (CommandGlobalOpts, SendCommand)) ->
 
         // Setup environment depending on whether we are sending the message from an embedded node or a background node
         let (api_node, tcp) = if let Some(node) = &cmd.from {
-            let api_node = get_final_element(node).to_string();
+            let api_node = get_final_element(opts, node).to_string();
             let tcp = TcpTransport::create(ctx).await?;
             (api_node, Some(tcp))
         } else {
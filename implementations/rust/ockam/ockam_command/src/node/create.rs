@@ -1,11 +1,14 @@
+use crate::error::exit_with;
 use clap::Args;
 use rand::prelude::random;
 
-use anyhow::{Context as _, Result};
+use anyhow::{anyhow, Context as _, Result};
 use std::{
     net::{IpAddr, SocketAddr},
     path::{Path, PathBuf},
     str::FromStr,
+    sync::Arc,
+    time::Duration,
 };
 
 use crate::node::util::{
@@ -13,24 +16,38 @@ use crate::node::util::{
 };
 use crate::project::ProjectInfo;
 use crate::secure_channel::listener::create as secure_channel_listener;
-use crate::service::config::Config;
+use crate::service::config::{self, Config, ServiceConfigs};
+use crate::service::controller::ProjectNodeLauncher;
 use crate::service::start::{self, StartCommand, StartSubCommand};
-use crate::util::{bind_to_port_check, exitcode};
+use crate::util::{api, bind_to_port_check, exitcode, get_final_element};
 use crate::{
     help,
     node::show::print_query_status,
     node::HELP_DETAIL,
     project,
-    util::{connect_to, embedded_node, find_available_port, startup, OckamConfig},
+    util::{connect_to, embedded_node, find_available_port, startup, NodeConfig},
     CommandGlobalOpts,
 };
-use ockam::{Address, AsyncTryClone, NodeBuilder, TCP};
+use minicbor::Decoder;
+#[cfg(unix)]
+use ockam::access_control::{AllowedTransport, AnyAccessControl, IdentityAnyIdAccessControl};
+use ockam::{metrics, Address, AsyncTryClone, NodeBuilder, Route, TCP};
 use ockam::{Context, TcpTransport};
+#[cfg(unix)]
+use ockam::{UdsTransport, UDS};
+use ockam_api::config::lookup::ConfigLookup;
 use ockam_api::{
+    clean_multiaddr, is_local_node,
+    nodes::models::forwarder::CreateForwarder,
+    nodes::models::portal::{CreateInlet, CreateOutlet},
+    nodes::models::secure_channel::CredentialExchangeMode,
     nodes::models::transport::{TransportMode, TransportType},
     nodes::{NodeManager, NODEMANAGER_ADDR},
 };
+use ockam_core::api::{Error, Request, Response, Status};
 use ockam_core::LOCAL;
+use ockam_multiaddr::proto::{Node, Project};
+use ockam_multiaddr::{MultiAddr, Protocol};
 
 /// Create Nodes
 #[derive(Clone, Debug, Args)]
@@ -54,6 +71,20 @@ pub struct CreateCommand {
     )]
     pub tcp_listener_address: String,
 
+    /// Serve the node metrics over HTTP at `/metrics` on this address
+    #[arg(display_order = 900, long, id = "METRICS_ADDRESS")]
+    pub metrics_address: Option<SocketAddr>,
+
+    /// Don't serve the node manager API on a Unix domain socket in the
+    /// node directory, commands then reach the node over TCP
+    #[arg(display_order = 900, long)]
+    pub no_api_socket: bool,
+
+    /// Also serve the node manager API over the TCP listener when it is
+    /// served on a Unix domain socket
+    #[arg(display_order = 900, long, conflicts_with = "no_api_socket")]
+    pub tcp_api: bool,
+
     /// Skip creation of default Vault and Identity
     #[arg(long, short, hide = true)]
     pub skip_defaults: bool,
@@ -70,13 +101,14 @@ pub struct CreateCommand {
     #[arg(display_order = 900, long, hide = true)]
     pub child_process: bool,
 
-    /// JSON config to setup a foreground node
+    /// JSON or YAML file declaring the services and resources of the
+    /// node
     ///
-    /// This argument is currently ignored on background nodes.  Node
-    /// configuration is run asynchronously and may take several
-    /// seconds to complete.
-    #[arg(long, hide = true)]
-    pub launch_config: Option<PathBuf>,
+    /// The default services are only started if the file declares
+    /// them.  Node configuration is run asynchronously and may take
+    /// several seconds to complete.
+    #[arg(display_order = 900, long, alias = "launch-config", id = "CONFIG_PATH")]
+    pub config: Option<PathBuf>,
 
     #[arg(long, hide = true)]
     pub no_watchdog: bool,
@@ -84,8 +116,9 @@ pub struct CreateCommand {
     #[arg(long, hide = true)]
     pub project: Option<PathBuf>,
 
+    /// Run the commands exported to this file once the node is created
     #[arg(long, hide = true)]
-    pub config: Option<PathBuf>,
+    pub run: Option<PathBuf>,
 }
 
 impl Default for CreateCommand {
@@ -94,14 +127,17 @@ impl Default for CreateCommand {
             node_name: hex::encode(&random::<[u8; 4]>()),
             foreground: false,
             tcp_listener_address: "127.0.0.1:0".to_string(),
+            metrics_address: None,
+            no_api_socket: false,
+            tcp_api: false,
             skip_defaults: false,
             enable_credential_checks: false,
             no_shared_identity: false,
             child_process: false,
-            launch_config: None,
+            config: None,
             no_watchdog: false,
             project: None,
-            config: None,
+            run: None,
         }
     }
 }
@@ -110,6 +146,13 @@ impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) {
         let verbose = options.global_args.verbose;
         let cfg = &options.config;
+
+        // Report configuration errors before creating anything
+        if let Some(path) = &self.config {
+            if let Err(e) = Config::read(path) {
+                exit_with(&options, exitcode::CONFIG, anyhow!("{:#}", e));
+            }
+        }
         if self.foreground {
             let cmd = self.overwrite_addr().unwrap();
             let addr = SocketAddr::from_str(&cmd.tcp_listener_address).unwrap();
@@ -119,28 +162,45 @@ impl CreateCommand {
             // calls to it don't fail
             if cfg.get_node_dir(&cmd.node_name).is_err() {
                 println!("Creating node directory...");
-                if let Err(e) = cfg.create_node(&cmd.node_name, addr, verbose) {
-                    eprintln!(
-                        "failed to update node configuration for '{}': {}",
-                        cmd.node_name, e
+                if let Err(e) = cfg.create_node(
+                    &cmd.node_name,
+                    addr,
+                    verbose,
+                    cmd.metrics_address,
+                    !cmd.no_api_socket,
+                    cmd.tcp_api,
+                ) {
+                    exit_with(
+                        &options,
+                        exitcode::CANTCREAT,
+                        anyhow!(
+                            "failed to update node configuration for '{}': {}",
+                            cmd.node_name,
+                            e
+                        ),
                     );
-                    std::process::exit(exitcode::CANTCREAT);
                 }
 
                 // Save the config update
                 if let Err(e) = cfg.persist_config_updates() {
-                    eprintln!("failed to update configuration: {}", e);
-                    std::process::exit(exitcode::IOERR);
+                    exit_with(
+                        &options,
+                        exitcode::IOERR,
+                        anyhow!("failed to update configuration: {}", e),
+                    );
                 }
             }
 
-            if let Err(e) = run_background_node(cmd, addr, cfg.clone()) {
+            if let Err(e) = run_background_node(cmd, addr, options.clone()) {
                 eprintln!("Ockam node failed: {:?}", e);
             }
         } else {
             if self.child_process {
-                eprintln!("Cannot create a background node from background node");
-                std::process::exit(exitcode::CONFIG);
+                exit_with(
+                    &options,
+                    exitcode::CONFIG,
+                    anyhow!("Cannot create a background node from background node"),
+                );
             }
 
             let cmd = self.overwrite_addr().unwrap();
@@ -151,13 +211,14 @@ impl CreateCommand {
                 (options.clone(), cmd.clone(), addr),
             )
             .unwrap();
+            wait_for_api_socket(&options, &cmd.node_name);
             connect_to(
                 addr.port(),
-                (cfg.clone(), cmd.node_name, true),
+                (options.clone(), (cmd.node_name, true)),
                 print_query_status,
             );
-            if let Some(config) = self.config {
-                crate::node::util::run::CommandsRunner::run(&config)
+            if let Some(commands) = self.run {
+                crate::node::util::run::CommandsRunner::run(&commands)
                     .context("Failed to run commands from config")
                     .unwrap();
             }
@@ -173,31 +234,51 @@ impl CreateCommand {
 
         // Check if the port is used by some other services or process
         if !bind_to_port_check(&addr) {
-            eprintln!("Another process is listening on the provided port!");
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Another process is listening on the provided port!"),
+            );
         }
 
         // FIXME: not really clear why this is causing issues
         if cfg.port_is_used(addr.port()) {
-            eprintln!("Another node is listening on the provided port!");
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Another node is listening on the provided port!"),
+            );
         }
 
         // First we create a new node in the configuration so that
         // we can ask it for the correct log path, as well as
         // making sure the watchdog can do its job later on.
-        if let Err(e) = cfg.create_node(&cmd.node_name, addr, verbose) {
-            eprintln!(
-                "failed to update node configuration for '{}': {}",
-                cmd.node_name, e
+        if let Err(e) = cfg.create_node(
+            &cmd.node_name,
+            addr,
+            verbose,
+            cmd.metrics_address,
+            !cmd.no_api_socket,
+            cmd.tcp_api,
+        ) {
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!(
+                    "failed to update node configuration for '{}': {}",
+                    cmd.node_name,
+                    e
+                ),
             );
-            std::process::exit(exitcode::CANTCREAT);
         }
 
         // Save the config update
         if let Err(e) = cfg.persist_config_updates() {
-            eprintln!("failed to update configuration: {}", e);
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("failed to update configuration: {}", e),
+            );
         }
 
         create_default_identity_if_needed(&ctx, cfg).await?;
@@ -212,8 +293,11 @@ impl CreateCommand {
             cmd.enable_credential_checks,
             &cmd.node_name,
             &cmd.tcp_listener_address,
+            cmd.metrics_address,
             cmd.project.as_deref(),
-        );
+            cmd.config.as_deref(),
+        )
+        .unwrap_or_else(|e| exit_with(&opts, exitcode::IOERR, e));
 
         // Unless this CLI was called from another watchdog we
         // start the watchdog here
@@ -237,17 +321,16 @@ impl CreateCommand {
     }
 }
 
-fn run_background_node(c: CreateCommand, addr: SocketAddr, cfg: OckamConfig) -> Result<()> {
+fn run_background_node(c: CreateCommand, addr: SocketAddr, opts: CommandGlobalOpts) -> Result<()> {
     let (mut ctx, mut executor) = NodeBuilder::without_access_control().no_logging().build();
 
     executor
         .execute(async move {
-            let v = run_background_node_impl(&mut ctx, c, addr, cfg).await;
+            let v = run_background_node_impl(&mut ctx, &opts, c, addr).await;
 
             match v {
                 Err(e) => {
-                    eprintln!("Background node error {:?}", e);
-                    std::process::exit(1);
+                    exit_with(&opts, 1, anyhow!("Background node error {:?}", e));
                 }
                 Ok(v) => v,
             }
@@ -257,19 +340,21 @@ fn run_background_node(c: CreateCommand, addr: SocketAddr, cfg: OckamConfig) ->
 
 async fn run_background_node_impl(
     ctx: &mut Context,
+    opts: &CommandGlobalOpts,
     c: CreateCommand,
     addr: SocketAddr,
-    cfg: OckamConfig,
 ) -> Result<()> {
+    let cfg = &opts.config;
+
     // This node was initially created as a foreground node
     if !c.child_process {
-        create_default_identity_if_needed(ctx, &cfg).await?;
+        create_default_identity_if_needed(ctx, cfg).await?;
     }
 
     let identity_override = if c.skip_defaults || c.no_shared_identity {
         None
     } else {
-        Some(get_identity_override(ctx, &cfg).await?)
+        Some(get_identity_override(ctx, cfg).await?)
     };
 
     let project_id = match &c.project {
@@ -277,8 +362,8 @@ async fn run_background_node_impl(
             let s = tokio::fs::read_to_string(path).await?;
             let p: ProjectInfo = serde_json::from_str(&s)?;
             let project_id = p.id.as_bytes().to_vec();
-            project::config::set_project(&cfg, &(&p).into()).await?;
-            add_project_authority(p, &c.node_name, &cfg).await?;
+            project::config::set_project(cfg, &(&p).into()).await?;
+            add_project_authority(p, &c.node_name, cfg).await?;
             Some(project_id)
         }
         None => None,
@@ -290,12 +375,12 @@ async fn run_background_node_impl(
 
     let node_dir = cfg.get_node_dir(&c.node_name)?;
     let projects = cfg.inner().lookup().projects().collect();
-    let node_man = NodeManager::create(
+    let mut node_man = NodeManager::create(
         ctx,
         c.node_name.clone(),
         node_dir,
         identity_override,
-        c.skip_defaults || c.launch_config.is_some(),
+        c.skip_defaults || c.config.is_some(),
         c.enable_credential_checks,
         Some(&cfg.authorities(&c.node_name)?.snapshot()),
         project_id,
@@ -304,38 +389,118 @@ async fn run_background_node_impl(
         tcp.async_try_clone().await?,
     )
     .await?;
+    node_man.set_logs(opts.logs.clone());
+    let node_cfg = cfg.get_node(&c.node_name)?;
+    node_man.set_project_launcher(Arc::new(ProjectNodeLauncher::new(
+        tcp.async_try_clone().await?,
+        node_cfg.verbose,
+    )));
 
-    ctx.start_worker(NODEMANAGER_ADDR, node_man).await?;
+    start_node_manager(ctx, node_man, &node_cfg).await?;
+
+    if let Some(metrics_address) = c.metrics_address {
+        metrics::serve_metrics(ctx, metrics_address).await?;
+    }
 
-    if let Some(path) = c.launch_config {
+    if let Some(path) = c.config {
         let node_opts = super::NodeOpts {
             api_node: c.node_name,
         };
-        start_services(ctx, &tcp, &path, addr, node_opts).await?
+        start_services(ctx, opts, &tcp, &path, addr, node_opts, &cfg.lookup()).await?
     }
 
     Ok(())
 }
 
+/// Start the node manager, serving its API on the node's Unix domain
+/// socket if enabled
+///
+/// Once the API is served on the socket, plain TCP connections only
+/// reach it through a secure channel, unless the node was created with
+/// `--tcp-api`.  Failing to bind the socket is not fatal, the API is
+/// then served over the TCP listener instead.
+#[cfg(unix)]
+async fn start_node_manager(
+    ctx: &Context,
+    mut node_man: NodeManager,
+    node_cfg: &NodeConfig,
+) -> Result<()> {
+    let serving_socket = match &node_cfg.api_socket {
+        Some(path) => match UdsTransport::create(ctx).await?.listen(path).await {
+            Ok(_) => true,
+            Err(e) => {
+                tracing::warn!(%e, path = %path.display(), "Failed to serve the node API on a Unix domain socket");
+                false
+            }
+        },
+        None => false,
+    };
+    if serving_socket && !node_cfg.tcp_api {
+        node_man.set_api_access_control(AnyAccessControl::new(
+            AllowedTransport::single(UDS),
+            IdentityAnyIdAccessControl,
+        ));
+    }
+    ctx.start_worker(NODEMANAGER_ADDR, node_man).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn start_node_manager(
+    ctx: &Context,
+    node_man: NodeManager,
+    _node_cfg: &NodeConfig,
+) -> Result<()> {
+    ctx.start_worker(NODEMANAGER_ADDR, node_man).await?;
+    Ok(())
+}
+
+/// Wait for a node which was just spawned to serve its API socket, so
+/// that the first request doesn't fall back to its TCP listener
+fn wait_for_api_socket(opts: &CommandGlobalOpts, node_name: &str) {
+    let path = match opts.config.get_node(node_name) {
+        Ok(NodeConfig {
+            api_socket: Some(path),
+            ..
+        }) => path,
+        _ => return,
+    };
+    for _ in 0..10 {
+        if path.exists() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+}
+
 async fn start_services(
     ctx: &Context,
+    opts: &CommandGlobalOpts,
     tcp: &TcpTransport,
     cfg: &Path,
     addr: SocketAddr,
     node_opts: super::NodeOpts,
+    lookup: &ConfigLookup,
 ) -> Result<()> {
-    let config = {
-        let c = Config::read(cfg)?;
-        if let Some(sc) = c.startup_services {
-            sc
-        } else {
-            return Ok(());
-        }
-    };
+    let config = Config::read(cfg)?;
 
     let addr = Address::from((TCP, addr.to_string()));
     tcp.connect(addr.address()).await?;
 
+    if let Some(services) = config.startup_services.clone() {
+        start_startup_services(ctx, opts, services, &addr, node_opts).await?;
+    }
+
+    create_resources(ctx, opts, &config, addr.into(), lookup).await
+}
+
+async fn start_startup_services(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    config: ServiceConfigs,
+    addr: &Address,
+    node_opts: super::NodeOpts,
+) -> Result<()> {
     if let Some(cfg) = config.vault {
         if !cfg.disabled {
             let cmd = StartCommand {
@@ -362,7 +527,7 @@ async fn start_services(
             let ids = cfg.authorized_identifiers;
             let rte = addr.clone().into();
             println!("starting secure-channel listener ...");
-            secure_channel_listener::create_listener(ctx, adr, ids, rte).await?;
+            secure_channel_listener::create_listener(ctx, opts, adr, ids, rte).await?;
         }
     }
     if let Some(cfg) = config.verifier {
@@ -386,9 +551,154 @@ async fn start_services(
                 },
             };
             println!("starting authenticator service ...");
-            start::start_authenticator_service(ctx, cmd, addr.into()).await?
+            start::start_authenticator_service(ctx, cmd, addr.clone().into()).await?
         }
     }
+    if let Some(cfg) = config.controller {
+        if !cfg.disabled {
+            println!("starting controller ...");
+            start::start_controller(ctx, cfg.path.as_deref(), cfg.admins, addr.clone().into())
+                .await?
+        }
+    }
+
+    Ok(())
+}
+
+/// Create the resources declared in a node config, through the API of
+/// the node
+async fn create_resources(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    config: &Config,
+    mut base_route: Route,
+    lookup: &ConfigLookup,
+) -> Result<()> {
+    let route: Route = base_route.modify().append(NODEMANAGER_ADDR).into();
+
+    for (i, c) in config.tcp_listeners.iter().enumerate() {
+        let req = api::create_transport(TransportType::Tcp, TransportMode::Listen, &c.address)?;
+        println!("creating tcp listener {} ...", c.address);
+        request(ctx, &route, req)
+            .await
+            .with_context(|| anyhow!("tcp_listeners[{i}]"))?;
+    }
+    for (i, c) in config.tcp_connections.iter().enumerate() {
+        let req = api::create_transport(TransportType::Tcp, TransportMode::Connect, &c.address)?;
+        println!("creating tcp connection to {} ...", c.address);
+        request(ctx, &route, req)
+            .await
+            .with_context(|| anyhow!("tcp_connections[{i}]"))?;
+    }
+    for (i, c) in config.secure_channels.iter().enumerate() {
+        let f = async {
+            let to = resolve(&c.to, lookup)?;
+            let req = api::create_secure_channel(
+                &to,
+                c.authorized_identifiers.clone(),
+                CredentialExchangeMode::Mutual,
+            )
+            .to_vec()?;
+            println!("creating secure channel to {} ...", c.to);
+            request(ctx, &route, req).await
+        };
+        f.await.with_context(|| anyhow!("secure_channels[{i}]"))?;
+    }
+    for (i, c) in config.outlets.iter().enumerate() {
+        let req = Request::post("/node/outlet")
+            .body(CreateOutlet::new(
+                &c.to,
+                get_final_element(opts, &c.from),
+                c.alias.as_deref().map(Into::into),
+                c.check_credential,
+            ))
+            .to_vec()?;
+        println!("creating outlet {} ...", c.from);
+        request(ctx, &route, req)
+            .await
+            .with_context(|| anyhow!("outlets[{i}]"))?;
+    }
+    for (i, c) in config.inlets.iter().enumerate() {
+        let f = async {
+            let to = resolve(&c.to, lookup)?;
+            let req = Request::post("/node/inlet")
+                .body(CreateInlet::new(
+                    &c.from,
+                    to.to_string(),
+                    c.alias.as_deref().map(Into::into),
+                    c.check_credential,
+                ))
+                .to_vec()?;
+            println!("creating inlet {} ...", c.from);
+            request(ctx, &route, req).await
+        };
+        f.await.with_context(|| anyhow!("inlets[{i}]"))?;
+    }
+    for (i, c) in config.forwarders.iter().enumerate() {
+        let f = async {
+            let at = config::multiaddr(&c.at)?;
+            let at_rust_node = is_local_node(&at)?;
+            let alias = c.alias.as_ref().map(|alias| {
+                if at_rust_node {
+                    format!("forward_to_{}", alias)
+                } else {
+                    alias.clone()
+                }
+            });
+            let ma = resolve(&c.at, lookup)?;
+            let body = if Some(Project::CODE) == at.first().map(|p| p.code()) {
+                if c.authorized.is_some() {
+                    return Err(anyhow!("authorized can not be used with project addresses"));
+                }
+                CreateForwarder::at_project(ma, alias)
+            } else {
+                CreateForwarder::at_node(ma, alias, at_rust_node, c.authorized.clone())
+            };
+            let req = Request::post("/node/forwarder").body(body).to_vec()?;
+            println!("creating forwarder at {} ...", c.at);
+            request(ctx, &route, req).await
+        };
+        f.await.with_context(|| anyhow!("forwarders[{i}]"))?;
+    }
 
     Ok(())
 }
+
+/// Parse a route, substituting the addresses of `/node/...` entries
+fn resolve(route: &str, lookup: &ConfigLookup) -> Result<MultiAddr> {
+    let ma = config::multiaddr(route)?;
+    for p in ma.iter() {
+        if p.code() == Node::CODE {
+            let node = p
+                .cast::<Node>()
+                .ok_or_else(|| anyhow!("invalid node address protocol"))?;
+            if lookup.get_node(&node).is_none() {
+                return Err(anyhow!("unknown node '{}'", &*node));
+            }
+        }
+    }
+    clean_multiaddr(&ma, lookup)
+        .map(|(ma, _)| ma)
+        .ok_or_else(|| anyhow!("invalid route '{route}'"))
+}
+
+/// Send a request to the node manager, failing unless it succeeds
+async fn request(ctx: &Context, route: &Route, req: Vec<u8>) -> Result<()> {
+    let res: Vec<u8> = ctx.send_and_receive(route.clone(), req).await?;
+    let mut dec = Decoder::new(&res);
+    let hdr: Response = dec.decode()?;
+    if hdr.status() == Some(Status::Ok) {
+        return Ok(());
+    }
+    let msg = if hdr.has_body() {
+        dec.decode::<Error>()
+            .ok()
+            .and_then(|e| e.message().map(|m| m.to_string()))
+    } else {
+        None
+    };
+    Err(anyhow!(
+        "request failed: {}",
+        msg.unwrap_or_else(|| format!("{:?}", hdr.status()))
+    ))
+}
@@ -0,0 +1,103 @@
+use crate::error::exit_with;
+use crate::{
+    help,
+    node::HELP_DETAIL,
+    util::{exitcode, startup, systemd},
+    CommandGlobalOpts,
+};
+use anyhow::anyhow;
+use clap::Args;
+use std::time::Duration;
+
+/// Run a node as a systemd service
+///
+/// The node is restarted by systemd when it fails and when the
+/// machine reboots.  User units only run while the user is logged in,
+/// unless lingering is enabled with `loginctl enable-linger`.
+#[derive(Clone, Debug, Args)]
+#[command(arg_required_else_help = true, help_template = help::template(HELP_DETAIL))]
+pub struct InstallServiceCommand {
+    /// Name of the node.
+    node_name: String,
+
+    /// Install a system unit instead of a user unit
+    #[arg(long)]
+    system: bool,
+}
+
+impl InstallServiceCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        let cfg = &options.config;
+        let node = match cfg.get_node(&self.node_name) {
+            Ok(node) => node,
+            Err(_) => exit_with(
+                &options,
+                exitcode::IOERR,
+                anyhow!("Node {} does not exist!", &self.node_name),
+            ),
+        };
+        if let Some(service) = &node.service {
+            exit_with(
+                &options,
+                exitcode::CANTCREAT,
+                anyhow!(
+                    "Node {} is already run by {}",
+                    &self.node_name,
+                    service.unit
+                ),
+            );
+        }
+
+        let service = match systemd::write_unit(cfg, &node, self.system) {
+            Ok(service) => service,
+            Err(e) => exit_with(&options, exitcode::OSERR, anyhow!("{e:?}")),
+        };
+
+        // The process started by the CLI must release the node's
+        // ports before systemd starts the node again
+        if let Some(pid) = node.pid {
+            if startup::stop(pid, false).is_ok() {
+                wait_for_port(node.port);
+            }
+        }
+
+        // Record the unit before starting it, so that the node is
+        // managed through systemd even if it fails to start
+        if let Err(e) = cfg.set_node_service(&self.node_name, Some(service.clone())) {
+            exit_with(
+                &options,
+                exitcode::IOERR,
+                anyhow!("Failed to update node {}: {}", &self.node_name, e),
+            );
+        }
+        if let Err(e) = cfg.set_node_pid(&self.node_name, None) {
+            exit_with(
+                &options,
+                exitcode::IOERR,
+                anyhow!("Failed to update pid for node {}: {}", &self.node_name, e),
+            );
+        }
+        if let Err(e) = cfg.persist_config_updates() {
+            exit_with(
+                &options,
+                exitcode::IOERR,
+                anyhow!("Failed to update configuration: {}", e),
+            );
+        }
+
+        if let Err(e) = systemd::enable(&service) {
+            exit_with(&options, exitcode::OSERR, anyhow!("{e:?}"));
+        }
+        println!("Node '{}' is now run by {}", &self.node_name, &service.unit);
+    }
+}
+
+/// Wait up to a few seconds for a stopped node to release its port
+fn wait_for_port(port: u16) {
+    for _ in 0..50 {
+        if std::net::TcpListener::bind(("127.0.0.1", port)).is_ok() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
@@ -1,6 +1,12 @@
+use crate::error::exit_with;
+use crate::node::show::{query_node_info, NodeInfo};
+use crate::util::output::print_output;
 use crate::util::{connect_to, exitcode, verify_pids};
-use crate::{help, node::show::print_query_status, node::HELP_DETAIL, CommandGlobalOpts};
+use crate::{help, node::HELP_DETAIL, CommandGlobalOpts};
+use anyhow::anyhow;
 use clap::Args;
+use ockam::{Context, Route};
+use std::sync::{Arc, Mutex};
 
 /// List Nodes
 #[derive(Clone, Debug, Args)]
@@ -27,14 +33,34 @@ impl ListCommand {
             // and has been restarted by something that is not this CLI.
             inner.nodes.iter().map(|(name, _)| name.clone()).collect()
         };
-        verify_pids(cfg, node_names);
+        verify_pids(&options, node_names);
 
+        let nodes = Arc::new(Mutex::new(Vec::new()));
         cfg.inner().nodes.iter().for_each(|(node_name, node_cfg)| {
             connect_to(
                 node_cfg.port,
-                (cfg.clone(), node_name.clone(), false),
-                print_query_status,
+                (options.clone(), (node_name.clone(), nodes.clone())),
+                collect_node_info,
             )
         });
+
+        let nodes = nodes.lock().unwrap();
+        if let Err(e) = print_output(&options, &*nodes) {
+            exit_with(&options, exitcode::IOERR, anyhow!("{e:?}"));
+        }
     }
 }
+
+/// Node states collected from the nodes' APIs
+type NodeInfos = Arc<Mutex<Vec<NodeInfo>>>;
+
+async fn collect_node_info(
+    mut ctx: Context,
+    (opts, (node_name, nodes)): (CommandGlobalOpts, (String, NodeInfos)),
+    base_route: Route,
+) -> anyhow::Result<()> {
+    let args = (opts.config.clone(), node_name, false);
+    let info = query_node_info(&mut ctx, args, base_route).await?;
+    nodes.lock().unwrap().push(info);
+    Ok(())
+}
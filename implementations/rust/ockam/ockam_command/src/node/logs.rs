@@ -0,0 +1,80 @@
+use anyhow::Context as _;
+use clap::Args;
+
+use ockam::{Context, TcpTransport};
+use ockam_api::nodes::models::logs::{GetLogs, LogRecords, SetLogFilter};
+use ockam_core::api::Request;
+
+use crate::util::{node_rpc, Rpc, RpcBuilder};
+use crate::{help, node::HELP_DETAIL, CommandGlobalOpts};
+
+/// Show the logs of a node, or change its log filter
+#[derive(Clone, Debug, Args)]
+#[command(help_template = help::template(HELP_DETAIL))]
+pub struct LogsCommand {
+    /// Name of the node.
+    #[arg(default_value = "default")]
+    node_name: String,
+
+    /// Keep printing new log records as the node produces them
+    #[arg(short, long)]
+    follow: bool,
+
+    /// Replace the log filter of the node with `RUST_LOG`-style
+    /// directives, e.g. `ockam_node=debug,info`, instead of showing
+    /// its logs
+    #[arg(long, value_name = "DIRECTIVES", conflicts_with = "follow")]
+    filter: Option<String>,
+}
+
+impl LogsCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, LogsCommand)) -> crate::Result<()> {
+    let tcp = TcpTransport::create(&ctx).await?;
+    let mut rpc = RpcBuilder::new(&ctx, &opts, &cmd.node_name)
+        .tcp(&tcp)?
+        .build();
+
+    if let Some(directives) = &cmd.filter {
+        rpc.request(Request::put("/node/logs/filter").body(SetLogFilter::new(directives.as_str())))
+            .await?;
+        rpc.is_ok()?;
+        println!(
+            "Log filter of node `{}` set to `{}`",
+            cmd.node_name, directives
+        );
+        return Ok(());
+    }
+
+    if cmd.follow {
+        return follow(&mut rpc).await;
+    }
+
+    rpc.request(Request::get("/node/logs").body(GetLogs::new(0)))
+        .await?;
+    rpc.is_ok()?;
+    let (_, mut dec) = rpc.check_response()?;
+    let records: LogRecords = dec.decode().context("Failed to decode log records")?;
+    for line in &records.lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Print the log records of the node as it writes them, until the node
+/// stops or the command is interrupted
+async fn follow(rpc: &mut Rpc<'_>) -> crate::Result<()> {
+    let mut stream = rpc
+        .request_stream(Request::get("/node/logs/stream").body(GetLogs::new(0)))
+        .await?;
+    while let Some(records) = stream.next::<LogRecords>().await? {
+        for line in &records.lines {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use clap::Args;
+
+use ockam::Context;
+use ockam_api::nodes::models::base::NodeMetrics;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::util::output::Output;
+use crate::util::{get_final_element, node_rpc, Rpc};
+use crate::{help, node::HELP_DETAIL, CommandGlobalOpts};
+
+/// Show the metrics of a node
+#[derive(Clone, Debug, Args)]
+#[command(help_template = help::template(HELP_DETAIL))]
+pub struct MetricsCommand {
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+impl MetricsCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, MetricsCommand)) -> crate::Result<()> {
+    let node = get_final_element(&opts, &cmd.node_opts.api_node);
+    let mut rpc = Rpc::background(&ctx, &opts, node)?;
+    rpc.request(Request::get("/node/metrics")).await?;
+    rpc.parse_and_print_response::<NodeMetrics>()?;
+    Ok(())
+}
+
+impl Output for NodeMetrics<'_> {
+    fn output(&self) -> anyhow::Result<String> {
+        Ok(self.text.trim_end().to_string())
+    }
+}
@@ -1,6 +1,9 @@
 mod create;
 mod delete;
+mod install_service;
 mod list;
+mod logs;
+mod metrics;
 mod show;
 mod start;
 mod stop;
@@ -8,7 +11,10 @@ pub mod util;
 
 pub(crate) use create::CreateCommand;
 use delete::DeleteCommand;
+use install_service::InstallServiceCommand;
 use list::ListCommand;
+use logs::LogsCommand;
+use metrics::MetricsCommand;
 use show::ShowCommand;
 use start::StartCommand;
 use stop::StopCommand;
@@ -102,6 +108,19 @@ Examples:
     # List all created nodes
     $ ockam node list
 
+    # Create a node serving its metrics over HTTP at /metrics
+    $ ockam node create n1 --metrics-address 127.0.0.1:9090
+
+    # Show the metrics of a node
+    $ ockam node metrics --node n1
+
+    # Follow the logs of a node, and make them more detailed
+    $ ockam node logs n1 --follow
+    $ ockam node logs n1 --filter ockam_node=debug,info
+
+    # Run a node as a systemd service, which survives reboots
+    $ ockam node install-service n1
+
     # Delete the node
     $ ockam node delete n1
 
@@ -132,8 +151,14 @@ pub enum NodeSubcommand {
     #[command(display_order = 800)]
     Delete(DeleteCommand),
     #[command(display_order = 800)]
+    InstallService(InstallServiceCommand),
+    #[command(display_order = 800)]
     List(ListCommand),
     #[command(display_order = 800)]
+    Logs(LogsCommand),
+    #[command(display_order = 800)]
+    Metrics(MetricsCommand),
+    #[command(display_order = 800)]
     Show(ShowCommand),
     #[command(display_order = 800)]
     Start(StartCommand),
@@ -146,7 +171,10 @@ impl NodeCommand {
         match self.subcommand {
             NodeSubcommand::Create(c) => c.run(options),
             NodeSubcommand::Delete(c) => c.run(options),
+            NodeSubcommand::InstallService(c) => c.run(options),
             NodeSubcommand::List(c) => c.run(options),
+            NodeSubcommand::Logs(c) => c.run(options),
+            NodeSubcommand::Metrics(c) => c.run(options),
             NodeSubcommand::Show(c) => c.run(options),
             NodeSubcommand::Start(c) => c.run(options),
             NodeSubcommand::Stop(c) => c.run(options),
@@ -1,12 +1,19 @@
+use crate::error::exit_with;
+use crate::util::output::{print_output, Output};
 use crate::util::{api, connect_to, exitcode, OckamConfig};
 use crate::{help, node::HELP_DETAIL, CommandGlobalOpts};
+use anyhow::anyhow;
 use anyhow::Context;
 use clap::Args;
 use colorful::Colorful;
+use core::fmt::Write;
+use minicbor::Decoder;
 use ockam::Route;
 use ockam_api::config::cli::NodeConfig;
-use ockam_api::nodes::{models::base::NodeStatus, NODEMANAGER_ADDR};
-use ockam_core::api::Status;
+use ockam_api::nodes::models::base::{NodeHealth, NodeStatus};
+use ockam_api::nodes::NODEMANAGER_ADDR;
+use ockam_core::api::{Response, Status};
+use serde::Serialize;
 use std::time::Duration;
 
 /// Show Nodes
@@ -16,6 +23,11 @@ pub struct ShowCommand {
     /// Name of the node.
     #[arg(default_value = "default")]
     node_name: String,
+
+    /// Also check the health of the node, exiting with a non-zero
+    /// status if it is down or not ready
+    #[arg(long, visible_alias = "check")]
+    probe: bool,
 }
 
 impl ShowCommand {
@@ -24,36 +36,75 @@ impl ShowCommand {
         let port = match cfg.inner().nodes.get(&self.node_name) {
             Some(cfg) => cfg.port,
             None => {
-                eprintln!("No such node available.  Run `ockam node list` to list available nodes");
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &options,
+                    exitcode::IOERR,
+                    anyhow!(
+                        "No such node available.  Run `ockam node list` to list available nodes"
+                    ),
+                );
             }
         };
-        connect_to(
-            port,
-            (cfg.clone(), self.node_name, false),
-            print_query_status,
-        );
+        if self.probe {
+            connect_to(port, (options, self.node_name), print_node_health);
+        } else {
+            connect_to(port, (options, (self.node_name, false)), print_query_status);
+        }
+    }
+}
+
+/// State of a node, as shown by `node show` and `node list`
+#[derive(Debug, Serialize)]
+pub struct NodeInfo {
+    pub name: String,
+    pub status: String,
+    pub pid: Option<i32>,
+    pub tcp_listener_address: String,
+    pub secure_channel_listener_address: String,
+    pub identity: Option<String>,
+    /// The systemd unit running the node, if it is service-managed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<NodeHealth<'static>>,
+}
+
+impl NodeInfo {
+    fn new(node_cfg: &NodeConfig, status: &str, identity: Option<String>) -> Self {
+        Self {
+            name: node_cfg.name.clone(),
+            status: status.to_string(),
+            pid: node_cfg.pid,
+            tcp_listener_address: format!("/ip4/127.0.0.1/tcp/{}", node_cfg.port),
+            secure_channel_listener_address: "/service/api".to_string(),
+            identity,
+            service: node_cfg.service.as_ref().map(|s| s.unit.clone()),
+            health: None,
+        }
     }
 }
 
-// TODO: This function should be replaced with a better system of
-// printing the node state in the future but for now we can just tell
-// clippy to stop complainaing about it.
-#[allow(clippy::too_many_arguments)]
-fn print_node_info(node_cfg: &NodeConfig, node_name: &str, status: &str, default_id: &str) {
-    println!(
-        r#"
+impl Output for NodeInfo {
+    fn output(&self) -> anyhow::Result<String> {
+        let default_id = self.identity.as_deref().unwrap_or("N/A");
+        let status = match self.status.as_str() {
+            "UP" => self.status.as_str().light_green(),
+            "DOWN" => self.status.as_str().light_red(),
+            s => s.white(),
+        };
+        Ok(format!(
+            r#"
 Node:
   Name: {}
-  Status: {}
+  Status: {}{}
   Services:
     Service:
       Type: TCP Listener
-      Address: /ip4/127.0.0.1/tcp/{}
+      Address: {}
     Service:
       Type: Secure Channel Listener
-      Address: /service/api
-      Route: /ip4/127.0.0.1/tcp/{}/service/api
+      Address: {}
+      Route: {}{}
       Identity: {}
       Authorized Identities:
         - {}
@@ -63,47 +114,147 @@ Node:
     Service:
       Type: Echo
       Address: /service/echo
-  Secure Channel Listener Address: /service/api
-"#,
-        node_name,
-        match status {
-            "UP" => status.light_green(),
-            "DOWN" => status.light_red(),
-            _ => status.white(),
-        },
-        node_cfg.port,
-        node_cfg.port,
-        default_id,
-        default_id,
-    );
+  Secure Channel Listener Address: {}
+{}"#,
+            self.name,
+            status,
+            match &self.service {
+                Some(unit) => format!("\n  Systemd Unit: {}", unit),
+                None => String::new(),
+            },
+            self.tcp_listener_address,
+            self.secure_channel_listener_address,
+            self.tcp_listener_address,
+            self.secure_channel_listener_address,
+            default_id,
+            default_id,
+            self.secure_channel_listener_address,
+            match &self.health {
+                Some(h) => h.output()?,
+                None => String::new(),
+            },
+        ))
+    }
+}
+
+impl Output for NodeHealth<'_> {
+    fn output(&self) -> anyhow::Result<String> {
+        let mut w = String::new();
+        writeln!(w, "  Health:")?;
+        writeln!(w, "    Status: {}", self.status)?;
+        writeln!(w, "    Ready: {}", self.ready)?;
+        writeln!(w, "    Version: {}", self.version)?;
+        writeln!(w, "    Uptime: {}s", self.uptime_secs)?;
+        writeln!(w, "    Workers: {}", self.workers)?;
+        writeln!(w, "    Transports:")?;
+        for t in &self.transports {
+            writeln!(w, "      - {} {}: {}", t.transport_type, t.mode, t.count)?;
+        }
+        writeln!(w, "    Secure Channels:")?;
+        for c in &self.secure_channels {
+            writeln!(w, "      - {} to {}: {}", c.address, c.route, c.state)?;
+        }
+        writeln!(w, "    Sessions:")?;
+        for s in &self.sessions {
+            let state = if s.up { "up" } else { "down" };
+            writeln!(w, "      - {}: {}", s.address, state)?;
+        }
+        match self.credential_expires_at {
+            Some(t) => writeln!(w, "    Credential Expires At: {}", t)?,
+            None => writeln!(w, "    Credential Expires At: N/A")?,
+        }
+        if let Some(t) = self.credential_refresh_at {
+            writeln!(w, "    Credential Refresh At: {}", t)?;
+        }
+        if let Some(e) = &self.credential_refresh_error {
+            writeln!(
+                w,
+                "    Credential Refresh Failed ({} times): {}",
+                self.credential_refresh_failures, e
+            )?;
+        }
+        writeln!(w, "    Recent Errors:")?;
+        for e in &self.recent_errors {
+            writeln!(w, "      - [{}] {}: {}", e.time, e.path, e.message)?;
+        }
+        Ok(w)
+    }
+}
+
+impl Output for Vec<NodeInfo> {
+    fn output(&self) -> anyhow::Result<String> {
+        let mut w = String::new();
+        for info in self {
+            w.push_str(&info.output()?);
+        }
+        Ok(w)
+    }
 }
 
 pub async fn print_query_status(
     mut ctx: ockam::Context,
-    (cfg, node_name, wait_until_ready): (OckamConfig, String, bool),
+    (opts, (node_name, wait_until_ready)): (CommandGlobalOpts, (String, bool)),
+    base_route: Route,
+) -> anyhow::Result<()> {
+    let args = (opts.config.clone(), node_name, wait_until_ready);
+    let info = query_node_info(&mut ctx, args, base_route).await?;
+    print_output(&opts, &info)
+}
+
+/// Print the status and health of a node, exiting with
+/// `exitcode::UNAVAILABLE` unless it is up and ready
+async fn print_node_health(
+    mut ctx: ockam::Context,
+    (opts, node_name): (CommandGlobalOpts, String),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
+    let args = (opts.config.clone(), node_name, false);
+    let mut info = query_node_info(&mut ctx, args, base_route.clone()).await?;
+    if info.status == "UP" {
+        let route: Route = base_route.modify().append(NODEMANAGER_ADDR).into();
+        ctx.send(route, api::node_health()?).await?;
+        let resp = ctx
+            .receive_duration_timeout::<Vec<u8>>(Duration::from_millis(250))
+            .await
+            .context("Failed to process request for node health")?;
+        let mut dec = Decoder::new(&resp);
+        let header: Response = dec.decode()?;
+        if header.status() == Some(Status::Ok) {
+            info.health = Some(dec.decode()?);
+        }
+    }
+    print_output(&opts, &info)?;
+    match &info.health {
+        Some(h) if h.ready => Ok(()),
+        _ => std::process::exit(exitcode::UNAVAILABLE),
+    }
+}
+
+/// Query the status of a node, waiting for it to come up if asked to
+pub async fn query_node_info(
+    ctx: &mut ockam::Context,
+    (cfg, node_name, wait_until_ready): (OckamConfig, String, bool),
+    mut base_route: Route,
+) -> anyhow::Result<NodeInfo> {
     let route = base_route.modify().append(NODEMANAGER_ADDR).into();
     let node_cfg = cfg.get_node(&node_name)?;
 
     // Wait until node is up.
-    if query_status(&mut ctx, &route).await.is_err() {
+    if query_status(ctx, &route).await.is_err() {
         if wait_until_ready {
             let mut attempts = 10;
             while attempts > 0 {
                 tokio::time::sleep(Duration::from_millis(250)).await;
-                if query_status(&mut ctx, &route).await.is_ok() {
+                if query_status(ctx, &route).await.is_ok() {
                     break;
                 }
                 attempts -= 1;
             }
             if attempts <= 0 {
-                print_node_info(&node_cfg, &node_name, "DOWN", "N/A");
-                return Ok(());
+                return Ok(NodeInfo::new(&node_cfg, "DOWN", None));
             }
         } else {
-            print_node_info(&node_cfg, &node_name, "DOWN", "N/A");
-            return Ok(());
+            return Ok(NodeInfo::new(&node_cfg, "DOWN", None));
         }
     }
 
@@ -122,8 +273,7 @@ pub async fn print_query_status(
         _ => String::from("NOT FOUND"),
     };
 
-    print_node_info(&node_cfg, &node_name, "UP", &default_id);
-    Ok(())
+    Ok(NodeInfo::new(&node_cfg, "UP", Some(default_id)))
 }
 
 async fn query_status(ctx: &mut ockam::Context, route: &Route) -> anyhow::Result<()> {
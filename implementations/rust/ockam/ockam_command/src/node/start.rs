@@ -1,9 +1,11 @@
+use crate::error::exit_with;
 use crate::{
     help,
     node::HELP_DETAIL,
-    util::{exitcode, startup::spawn_node},
+    util::{exitcode, startup::spawn_node, systemd},
     CommandGlobalOpts,
 };
+use anyhow::anyhow;
 use clap::Args;
 use nix::unistd::Pid;
 use rand::prelude::random;
@@ -24,6 +26,25 @@ impl StartCommand {
             .get_node(&self.node_name)
             .expect("failed to load node config");
 
+        // Service-managed nodes are started by systemd
+        if let Some(service) = &cfg_node.service {
+            if systemd::is_active(service) {
+                exit_with(
+                    &opts,
+                    exitcode::IOERR,
+                    anyhow!(
+                        "Node '{}' is already running as {}",
+                        self.node_name,
+                        service.unit
+                    ),
+                );
+            }
+            if let Err(e) = systemd::start(service) {
+                exit_with(&opts, exitcode::OSERR, anyhow!("{e:?}"));
+            }
+            return;
+        }
+
         // First we check whether a PID was registered and if it is still alive.
         if let Some(pid) = cfg_node.pid {
             // Note: On CI machines where <defunct> processes can occur,
@@ -31,11 +52,15 @@ impl StartCommand {
             let res = nix::sys::signal::kill(Pid::from_raw(pid), None);
 
             if res.is_ok() {
-                eprintln!(
-                    "Node '{}' already appears to be running as PID {}",
-                    self.node_name, pid
+                exit_with(
+                    &opts,
+                    exitcode::IOERR,
+                    anyhow!(
+                        "Node '{}' already appears to be running as PID {}",
+                        self.node_name,
+                        pid
+                    ),
                 );
-                std::process::exit(exitcode::IOERR);
             }
         }
 
@@ -49,7 +74,10 @@ impl StartCommand {
             false,                      // Default value. TODO: implement persistence of this option
             &cfg_node.name,             // The selected node name
             &cfg_node.addr.to_string(), // The selected node api address
+            cfg_node.metrics_address,   // Previously user-chosen metrics endpoint
             None,                       // No project information available
-        );
+            None,                       // Resources are restored from the node state
+        )
+        .unwrap_or_else(|e| exit_with(&opts, exitcode::IOERR, e));
     }
 }
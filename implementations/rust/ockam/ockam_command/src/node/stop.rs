@@ -1,9 +1,11 @@
+use crate::error::exit_with;
 use crate::{
     help,
     node::HELP_DETAIL,
-    util::{exitcode, startup},
+    util::{exitcode, startup, systemd},
     CommandGlobalOpts,
 };
+use anyhow::anyhow;
 use clap::Args;
 use rand::prelude::random;
 
@@ -21,35 +23,62 @@ pub struct StopCommand {
 
 impl StopCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let cfg = options.config;
+        let cfg = &options.config;
+
+        // Service-managed nodes are stopped by systemd, which would
+        // otherwise restart them
+        if let Some(service) = cfg.get_node(&self.node_name).ok().and_then(|n| n.service) {
+            if let Err(e) = systemd::stop(&service, self.force) {
+                exit_with(&options, exitcode::OSERR, anyhow!("{e:?}"));
+            }
+            clear_pid(&options, &self.node_name);
+            return;
+        }
+
         match cfg.get_node_pid(&self.node_name) {
             Ok(Some(pid)) => {
                 if let Err(e) = startup::stop(pid, self.force) {
-                    eprintln!("{e:?}");
-                    std::process::exit(exitcode::OSERR);
+                    exit_with(&options, exitcode::OSERR, anyhow!("{e:?}"));
                 } else {
-                    // Clear pid in config, so StartCommand does not have to rely on
-                    // `kill 0 pid` to detect if a node is running.
-                    if let Err(e) = cfg.set_node_pid(&self.node_name, None) {
-                        eprintln!("Failed to update pid for node {}: {}", &self.node_name, e);
-                        std::process::exit(exitcode::IOERR);
-                    }
-
-                    // Save the config update
-                    if let Err(e) = cfg.persist_config_updates() {
-                        eprintln!("Failed to update configuration: {}", e);
-                        std::process::exit(exitcode::IOERR);
-                    }
+                    clear_pid(&options, &self.node_name);
                 }
             }
             Ok(_) => {
-                eprintln!("Node {} is not running!", &self.node_name);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &options,
+                    exitcode::IOERR,
+                    anyhow!("Node {} is not running!", &self.node_name),
+                );
             }
             Err(_) => {
-                eprintln!("Node {} does not exist!", &self.node_name);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &options,
+                    exitcode::IOERR,
+                    anyhow!("Node {} does not exist!", &self.node_name),
+                );
             }
         };
     }
 }
+
+/// Clear the pid of a stopped node in the config, so StartCommand does
+/// not have to rely on `kill 0 pid` to detect if a node is running.
+fn clear_pid(opts: &CommandGlobalOpts, node_name: &str) {
+    let cfg = &opts.config;
+    if let Err(e) = cfg.set_node_pid(node_name, None) {
+        exit_with(
+            opts,
+            exitcode::IOERR,
+            anyhow!("Failed to update pid for node {}: {}", node_name, e),
+        );
+    }
+
+    // Save the config update
+    if let Err(e) = cfg.persist_config_updates() {
+        exit_with(
+            opts,
+            exitcode::IOERR,
+            anyhow!("Failed to update configuration: {}", e),
+        );
+    }
+}
@@ -12,12 +12,13 @@ use ockam_multiaddr::MultiAddr;
 use ockam_vault::storage::FileStorage;
 use ockam_vault::Vault;
 use sysinfo::{get_current_pid, ProcessExt, System, SystemExt};
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::node::CreateCommand;
 use crate::project::ProjectInfo;
+use crate::util::{startup, systemd};
+use crate::CommandGlobalOpts;
 use crate::{project, OckamConfig};
-use crate::{util::startup, CommandGlobalOpts};
 
 pub async fn start_embedded_node(ctx: &Context, cfg: &OckamConfig) -> Result<String> {
     let cmd = CreateCommand::default();
@@ -58,7 +59,7 @@ pub async fn start_embedded_node(ctx: &Context, cfg: &OckamConfig) -> Result<Str
         cmd.node_name.clone(),
         node_dir,
         identity_override,
-        cmd.skip_defaults || cmd.launch_config.is_some(),
+        cmd.skip_defaults || cmd.config.is_some(),
         cmd.enable_credential_checks,
         Some(&cfg.authorities(&cmd.node_name)?.snapshot()),
         project_id,
@@ -167,7 +168,7 @@ pub fn delete_all_nodes(opts: CommandGlobalOpts, force: bool) -> anyhow::Result<
         inner.nodes.iter().map(|(name, _)| name.clone()).collect()
     };
     for node_name in nn.iter() {
-        delete_node(&opts, node_name, force)
+        delete_node(&opts.config, node_name, force)
     }
 
     // Try to delete dangling embedded nodes directories
@@ -207,44 +208,51 @@ pub fn delete_all_nodes(opts: CommandGlobalOpts, force: bool) -> anyhow::Result<
     Ok(())
 }
 
-pub fn delete_node(opts: &CommandGlobalOpts, node_name: &str, sigkill: bool) {
+pub fn delete_node(cfg: &OckamConfig, node_name: &str, sigkill: bool) {
     trace!(%node_name, "Deleting node");
 
+    // Remove the unit of a service-managed node, so that systemd
+    // doesn't restart it
+    if let Some(service) = cfg.get_node(node_name).ok().and_then(|n| n.service) {
+        if let Err(e) = systemd::uninstall(&service) {
+            warn!(%node_name, "Failed to remove the systemd unit: {e:?}");
+        }
+    }
+
     // We ignore the result of killing the node process as it could be not
     // found (after a restart or if the user manually deleted it, for example).
-    let _ = delete_node_pid(opts, node_name, sigkill);
+    let _ = delete_node_pid(cfg, node_name, sigkill);
 
-    delete_node_config(opts, node_name);
+    delete_node_config(cfg, node_name);
 }
 
-fn delete_node_pid(opts: &CommandGlobalOpts, node_name: &str, sigkill: bool) -> anyhow::Result<()> {
+fn delete_node_pid(cfg: &OckamConfig, node_name: &str, sigkill: bool) -> anyhow::Result<()> {
     trace!(%node_name, "Deleting node pid");
     // Stop the process PID if it has one assigned in the config file
-    if let Some(pid) = opts.config.get_node_pid(node_name)? {
+    if let Some(pid) = cfg.get_node_pid(node_name)? {
         startup::stop(pid, sigkill)?;
         // Give some room for the process to stop
         std::thread::sleep(std::time::Duration::from_millis(100));
         // If it fails to bind, the port is still in use, so we try again to stop the process
-        let addr = format!("127.0.0.1:{}", opts.config.get_node_port(node_name));
-        if std::net::TcpListener::bind(&addr).is_err() {
-            startup::stop(pid, sigkill)?;
+        if let Some(port) = cfg.get_node_port(node_name) {
+            let addr = format!("127.0.0.1:{}", port);
+            if std::net::TcpListener::bind(&addr).is_err() {
+                startup::stop(pid, sigkill)?;
+            }
         }
     }
     Ok(())
 }
 
-fn delete_node_config(opts: &CommandGlobalOpts, node_name: &str) {
+fn delete_node_config(cfg: &OckamConfig, node_name: &str) {
     trace!(%node_name, "Deleting node config");
 
     // Try removing the node's directory.
     // If the directory is not found, we ignore the result and continue.
-    let _ = opts
-        .config
-        .get_node_dir_raw(node_name)
-        .map(std::fs::remove_dir_all);
+    let _ = cfg.get_node_dir_raw(node_name).map(std::fs::remove_dir_all);
 
     // Try removing the node's info from the config file.
-    opts.config.remove_node(node_name);
+    cfg.remove_node(node_name);
 }
 
 pub mod run {
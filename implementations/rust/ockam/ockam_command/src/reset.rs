@@ -1,5 +1,7 @@
+use crate::error::exit_with;
 use crate::node::util::delete_all_nodes;
 use crate::CommandGlobalOpts;
+use anyhow::anyhow;
 use clap::Args;
 use std::io::{self, BufReader, Read, Write};
 
@@ -17,9 +19,8 @@ impl ResetCommand {
 
 fn run_impl(opts: CommandGlobalOpts, cmd: ResetCommand) -> crate::Result<()> {
     if cmd.yes || get_user_confirmation() {
-        if let Err(e) = delete_all_nodes(opts, true) {
-            eprintln!("{}", e);
-            std::process::exit(crate::util::exitcode::IOERR);
+        if let Err(e) = delete_all_nodes(opts.clone(), true) {
+            exit_with(&opts, crate::util::exitcode::IOERR, anyhow!("{}", e));
         }
     }
     Ok(())
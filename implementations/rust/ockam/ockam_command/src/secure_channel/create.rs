@@ -10,11 +10,12 @@ use clap::Args;
 use colorful::Colorful;
 use serde_json::json;
 
+use crate::error::exit_with;
 use crate::secure_channel::HELP_DETAIL;
 use crate::util::api::CloudOpts;
+use crate::util::output::print_structured;
 use crate::util::RpcBuilder;
 use ockam::{identity::IdentityIdentifier, route, Context, TcpTransport};
-use ockam_api::config::lookup::ConfigLookup;
 use ockam_api::nodes::models::secure_channel::CredentialExchangeMode;
 use ockam_api::{
     clean_multiaddr, nodes::models::secure_channel::CreateSecureChannelResponse, route_to_multiaddr,
@@ -75,8 +76,8 @@ impl CreateCommand {
     }
 
     // Read the `from` argument and return node name
-    fn parse_from_node(&self, _config: &ConfigLookup) -> String {
-        get_final_element(&self.from).to_string()
+    fn parse_from_node(&self, opts: &CommandGlobalOpts) -> String {
+        get_final_element(opts, &self.from).to_string()
     }
 
     fn print_output(
@@ -89,18 +90,16 @@ impl CreateCommand {
         let route = &route![response.addr.to_string()];
         match route_to_multiaddr(route) {
             Some(multiaddr) => {
-                // if stdout is not interactive/tty write the secure channel address to it
-                // in case some other program is trying to read it as piped input
-                if !atty::is(Stream::Stdout) {
+                // if output format is json or yaml write it to stdout, otherwise
+                // if stdout is not interactive/tty write the secure channel address
+                // to it in case some other program is trying to read it as piped input
+                let structured =
+                    print_structured(options, &json!({ "address": multiaddr.to_string() }))
+                        .unwrap_or_else(|e| exit_with(options, exitcode::IOERR, e));
+                if !structured && !atty::is(Stream::Stdout) {
                     println!("{}", multiaddr)
                 }
 
-                // if output format is json, write json to stdout.
-                if options.global_args.output_format == OutputFormat::Json {
-                    let json = json!([{ "address": multiaddr.to_string() }]);
-                    println!("{}", json);
-                }
-
                 // if stderr is interactive/tty and we haven't been asked to be quiet
                 // and output format is plain then write a plain info to stderr.
                 if atty::is(Stream::Stderr)
@@ -154,8 +153,7 @@ impl CreateCommand {
 async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, CreateCommand)) -> Result<()> {
     let tcp = TcpTransport::create(&ctx).await?;
 
-    let config = &opts.config.lookup();
-    let from = &cmd.parse_from_node(config);
+    let from = &cmd.parse_from_node(&opts);
     let to = &cmd
         .parse_to_route(&ctx, &opts, &cmd.cloud_opts.route(), from, &tcp)
         .await?;
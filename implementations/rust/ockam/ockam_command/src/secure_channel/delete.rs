@@ -1,4 +1,6 @@
+use crate::error::exit_with;
 use crate::secure_channel::HELP_DETAIL;
+use crate::util::output::print_structured;
 use crate::{
     help,
     util::{api, exitcode, get_final_element, node_rpc, Rpc},
@@ -6,6 +8,7 @@ use crate::{
 };
 use std::str::FromStr;
 
+use anyhow::anyhow;
 use atty::Stream;
 use colorful::Colorful;
 use serde_json::json;
@@ -34,8 +37,8 @@ impl DeleteCommand {
     }
 
     // Read the `at` argument and return node name
-    fn parse_at_node(&self) -> String {
-        get_final_element(&self.at).to_string()
+    fn parse_at_node(&self, opts: &CommandGlobalOpts) -> String {
+        get_final_element(opts, &self.at).to_string()
     }
 
     fn print_output(
@@ -50,18 +53,16 @@ impl DeleteCommand {
                 let route = &route![address.to_string()];
                 match route_to_multiaddr(route) {
                     Some(multiaddr) => {
-                        // if stdout is not interactive/tty write the secure channel address to it
-                        // in case some other program is trying to read it as piped input
-                        if !atty::is(Stream::Stdout) {
+                        // if output format is json or yaml write it to stdout, otherwise
+                        // if stdout is not interactive/tty write the secure channel address
+                        // to it in case some other program is trying to read it as piped input
+                        let structured =
+                            print_structured(options, &json!({ "address": multiaddr.to_string() }))
+                                .unwrap_or_else(|e| exit_with(options, exitcode::IOERR, e));
+                        if !structured && !atty::is(Stream::Stdout) {
                             println!("{}", multiaddr)
                         }
 
-                        // if output format is json, write json to stdout.
-                        if options.global_args.output_format == OutputFormat::Json {
-                            let json = json!([{ "address": multiaddr.to_string() }]);
-                            println!("{}", json);
-                        }
-
                         // if stderr is interactive/tty and we haven't been asked to be quiet
                         // and output format is plain then write a plain info to stderr.
                         if atty::is(Stream::Stderr)
@@ -107,17 +108,15 @@ impl DeleteCommand {
             None => {
                 // if stderr is interactive/tty and we haven't been asked to be quiet
                 // and output format is plain then write a plain info to stderr.
-                if atty::is(Stream::Stderr)
-                    && !options.global_args.quiet
-                    && options.global_args.output_format == OutputFormat::Plain
-                {
-                    eprintln!(
+                exit_with(
+                    options,
+                    exitcode::NOINPUT,
+                    anyhow!(
                         "Could not find secure channel with address {} at node {}",
-                        address, &self.at
-                    );
-                }
-
-                println!("channel with address {} not found", address)
+                        address,
+                        &self.at
+                    ),
+                )
             }
         }
     }
@@ -141,7 +140,7 @@ fn parse_address(input: &str) -> core::result::Result<Address, AddressParseError
 }
 
 async fn rpc(ctx: Context, (options, command): (CommandGlobalOpts, DeleteCommand)) -> Result<()> {
-    let at = &command.parse_at_node();
+    let at = &command.parse_at_node(&options);
     let address = &command.address;
 
     let mut rpc = Rpc::background(&ctx, &options, at)?;
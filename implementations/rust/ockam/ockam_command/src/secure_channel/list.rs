@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use atty::Stream;
 use clap::Args;
 use colorful::Colorful;
@@ -9,7 +10,9 @@ use ockam_core::{route, Address};
 
 use serde_json::json;
 
+use crate::error::exit_with;
 use crate::secure_channel::HELP_DETAIL;
+use crate::util::output::print_structured;
 use crate::util::RpcBuilder;
 use crate::{
     exitcode, help,
@@ -43,6 +46,7 @@ impl ListCommand {
             println!("\nSecure Channels")
         }
 
+        let mut channels = Vec::new();
         for (channel_address, show_response) in zipped {
             let from = &self.at;
 
@@ -78,15 +82,12 @@ impl ListCommand {
 
             // if stdout is not interactive/tty write the secure channel address to it
             // in case some other program is trying to read it as piped input
-            if !atty::is(Stream::Stdout) {
+            if options.global_args.output_format == OutputFormat::Plain && !atty::is(Stream::Stdout)
+            {
                 println!("{}", at)
             }
 
-            // if output format is json, write json to stdout.
-            if options.global_args.output_format == OutputFormat::Json {
-                let json = json!([{ "address": at }]);
-                println!("{}", json);
-            }
+            channels.push(json!({ "from": format!("/node/{}", from), "to": to, "address": at }));
 
             // if stderr is interactive/tty and we haven't been asked to be quiet
             // and output format is plain then write a plain info to stderr.
@@ -111,6 +112,9 @@ impl ListCommand {
                 }
             }
         }
+        // if output format is json or yaml, write all channels to stdout
+        print_structured(options, &channels).map_err(|e| e.to_string())?;
+
         Ok(())
     }
 }
@@ -153,10 +157,10 @@ async fn rpc(
     let responses = results?;
 
     if let Err(e) = command.print_output(&options, channel_identifiers, responses) {
-        if atty::is(Stream::Stderr)
-            && !options.global_args.quiet
-            && options.global_args.output_format == OutputFormat::Plain
-        {
+        if options.global_args.output_format != OutputFormat::Plain {
+            exit_with(&options, exitcode::PROTOCOL, anyhow!(e));
+        }
+        if has_plain_stderr(&options) {
             eprintln!("{}", e);
         }
         std::process::exit(exitcode::PROTOCOL)
@@ -1,8 +1,12 @@
+use crate::error::exit_with;
 use crate::secure_channel::HELP_DETAIL;
-use crate::util::{api, connect_to, exitcode, get_final_element};
+use crate::util::output::print_structured;
+use crate::util::{api, connect_to, exitcode, get_final_element, node_port};
 use crate::{help, CommandGlobalOpts};
 
+use anyhow::anyhow;
 use clap::Args;
+use serde_json::json;
 
 use ockam::identity::IdentityIdentifier;
 
@@ -34,12 +38,11 @@ pub struct SecureChannelListenerNodeOpts {
 
 impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let cfg = options.config;
-        let node = get_final_element(&self.node_opts.at);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, &self.node_opts.at);
+        let port = node_port(&options, node);
 
-        connect_to(port, self, |ctx, cmd, rte| async {
-            create_listener(&ctx, cmd.address, cmd.authorized_identifier, rte).await?;
+        connect_to(port, (options, self), |ctx, (opts, cmd), rte| async move {
+            create_listener(&ctx, &opts, cmd.address, cmd.authorized_identifier, rte).await?;
             drop(ctx);
             Ok(())
         });
@@ -48,6 +51,7 @@ impl CreateCommand {
 
 pub async fn create_listener(
     ctx: &ockam::Context,
+    opts: &CommandGlobalOpts,
     addr: Address,
     authorized_identifiers: Option<Vec<IdentityIdentifier>>,
     mut base_route: Route,
@@ -63,12 +67,16 @@ pub async fn create_listener(
 
     match response.status() {
         Some(Status::Ok) => {
-            println!("/service/{}", addr.address());
+            let address = format!("/service/{}", addr.address());
+            if !print_structured(opts, &json!({ "address": address }))? {
+                println!("{}", address);
+            }
             Ok(())
         }
-        _ => {
-            eprintln!("An error occurred while creating secure channel listener",);
-            std::process::exit(exitcode::CANTCREAT)
-        }
+        _ => exit_with(
+            opts,
+            exitcode::CANTCREAT,
+            anyhow!("An error occurred while creating secure channel listener"),
+        ),
     }
 }
@@ -0,0 +1,58 @@
+use crate::util::output::print_structured;
+use clap::Args;
+use serde_json::json;
+
+use ockam::Context;
+use ockam_core::Address;
+
+use crate::node::NodeOpts;
+use crate::secure_channel::HELP_DETAIL;
+use crate::util::api;
+use crate::util::{node_rpc, Rpc};
+use crate::{help, CommandGlobalOpts};
+
+/// Delete Secure Channel Listeners
+#[derive(Args, Clone, Debug)]
+#[command(arg_required_else_help = true, help_template = help::template(HELP_DETAIL))]
+pub struct DeleteCommand {
+    /// Node of which the secure channel listener shall be deleted
+    #[command(flatten)]
+    node_opts: NodeOpts,
+
+    /// Address of the listener
+    address: Address,
+}
+
+impl DeleteCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(
+    mut ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, DeleteCommand),
+) -> crate::Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(
+    ctx: &mut Context,
+    opts: CommandGlobalOpts,
+    cmd: DeleteCommand,
+) -> crate::Result<()> {
+    let mut rpc = Rpc::background(ctx, &opts, &cmd.node_opts.api_node)?;
+    rpc.request(api::delete_secure_channel_listener(&cmd.address))
+        .await?;
+    rpc.is_ok()?;
+
+    let address = cmd.address.to_string();
+    if !print_structured(&opts, &json!({ "address": address }))? {
+        println!(
+            "Deleted secure channel listener `{}` on node `{}`",
+            address, &cmd.node_opts.api_node
+        );
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::util::output::print_structured;
 use clap::Args;
 
 use ockam::Context;
@@ -36,12 +37,14 @@ async fn run_impl(
     rpc.request(api::list_secure_channel_listener()).await?;
     let res = rpc.parse_response::<Vec<String>>()?;
 
-    println!(
-        "Secure channel listeners for node `{}`:",
-        &cmd.node_opts.api_node
-    );
-    for addr in res {
-        println!("  {}", addr);
+    if !print_structured(&opts, &res)? {
+        println!(
+            "Secure channel listeners for node `{}`:",
+            &cmd.node_opts.api_node
+        );
+        for addr in res {
+            println!("  {}", addr);
+        }
     }
 
     Ok(())
@@ -1,7 +1,9 @@
 pub mod create;
+pub mod delete;
 pub mod list;
 
 pub(crate) use create::CreateCommand;
+pub(crate) use delete::DeleteCommand;
 pub(crate) use list::ListCommand;
 
 use crate::secure_channel::HELP_DETAIL;
@@ -26,6 +28,8 @@ pub enum SecureChannelListenerSubcommand {
     Create(CreateCommand),
     #[command(display_order = 800)]
     List(ListCommand),
+    #[command(display_order = 800)]
+    Delete(DeleteCommand),
 }
 
 impl SecureChannelListenerCommand {
@@ -33,6 +37,7 @@ impl SecureChannelListenerCommand {
         match self.subcommand {
             SecureChannelListenerSubcommand::Create(c) => c.run(options),
             SecureChannelListenerSubcommand::List(c) => c.run(options),
+            SecureChannelListenerSubcommand::Delete(c) => c.run(options),
         }
     }
 }
@@ -29,13 +29,13 @@ impl ShowCommand {
     }
 
     // Read the `at` argument and return node name
-    fn parse_at_node(&self) -> String {
-        get_final_element(&self.at).to_string()
+    fn parse_at_node(&self, opts: &CommandGlobalOpts) -> String {
+        get_final_element(opts, &self.at).to_string()
     }
 }
 
 async fn rpc(ctx: Context, (options, command): (CommandGlobalOpts, ShowCommand)) -> Result<()> {
-    let at = &command.parse_at_node();
+    let at = &command.parse_at_node(&options);
     let address = &command.address;
 
     let mut rpc = Rpc::background(&ctx, &options, at)?;
@@ -1,7 +1,9 @@
 use anyhow::{anyhow, Context, Result};
 use ockam::identity::IdentityIdentifier;
 use ockam_api::DefaultAddress;
+use ockam_multiaddr::MultiAddr;
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +58,17 @@ pub struct AuthenticatorConfig {
     pub(crate) disabled: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControllerConfig {
+    #[serde(default)]
+    pub(crate) path: Option<PathBuf>,
+
+    pub(crate) admins: Vec<IdentityIdentifier>,
+
+    #[serde(default)]
+    pub(crate) disabled: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceConfigs {
     pub(crate) vault: Option<VaultConfig>,
@@ -63,18 +76,168 @@ pub struct ServiceConfigs {
     pub(crate) secure_channel_listener: Option<SecureChannelListenerConfig>,
     pub(crate) verifier: Option<VerifierConfig>,
     pub(crate) authenticator: Option<AuthenticatorConfig>,
+    #[serde(default)]
+    pub(crate) controller: Option<ControllerConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TcpListenerConfig {
+    /// Socket address to listen on
+    pub(crate) address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TcpConnectionConfig {
+    /// `host:port` of the peer
+    pub(crate) address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecureChannelConfig {
+    /// Route to a secure channel listener
+    pub(crate) to: String,
+
+    #[serde(default)]
+    pub(crate) authorized_identifiers: Option<Vec<IdentityIdentifier>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InletConfig {
+    /// Socket address to accept TCP connections on
+    pub(crate) from: String,
+
+    /// Route to a TCP outlet
+    pub(crate) to: String,
+
+    #[serde(default)]
+    pub(crate) alias: Option<String>,
+
+    #[serde(default)]
+    pub(crate) check_credential: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutletConfig {
+    /// Address of the outlet worker
+    pub(crate) from: String,
+
+    /// Socket address of the TCP target
+    pub(crate) to: String,
+
+    #[serde(default)]
+    pub(crate) alias: Option<String>,
+
+    #[serde(default)]
+    pub(crate) check_credential: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForwarderConfig {
+    /// Route to the node at which to create the forwarder
+    pub(crate) at: String,
+
+    #[serde(default)]
+    pub(crate) alias: Option<String>,
+
+    #[serde(default)]
+    pub(crate) authorized: Option<IdentityIdentifier>,
 }
 
+/// Declarative configuration of a node
+///
+/// Resources are created in the order of the fields below, and in
+/// the order they are listed within each field.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     pub(crate) startup_services: Option<ServiceConfigs>,
+
+    #[serde(default)]
+    pub(crate) tcp_listeners: Vec<TcpListenerConfig>,
+
+    #[serde(default)]
+    pub(crate) tcp_connections: Vec<TcpConnectionConfig>,
+
+    #[serde(default)]
+    pub(crate) secure_channels: Vec<SecureChannelConfig>,
+
+    #[serde(default)]
+    pub(crate) outlets: Vec<OutletConfig>,
+
+    #[serde(default)]
+    pub(crate) inlets: Vec<InletConfig>,
+
+    #[serde(default)]
+    pub(crate) forwarders: Vec<ForwarderConfig>,
 }
 
 impl Config {
+    /// Read a JSON config, or a YAML one if the file has a `.yaml` or
+    /// `.yml` extension
     pub(crate) fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let s = std::fs::read_to_string(path.as_ref())
-            .with_context(|| anyhow!("failed to read {:?}", path.as_ref()))?;
-        serde_json::from_str(&s).with_context(|| anyhow!("invalid config {:?}", path.as_ref()))
+        let path = path.as_ref();
+        let s =
+            std::fs::read_to_string(path).with_context(|| anyhow!("failed to read {:?}", path))?;
+        let config: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => {
+                serde_yaml::from_str(&s).with_context(|| anyhow!("invalid config {:?}", path))?
+            }
+            _ => serde_json::from_str(&s).with_context(|| anyhow!("invalid config {:?}", path))?,
+        };
+        config
+            .validate()
+            .with_context(|| anyhow!("invalid config {:?}", path))?;
+        Ok(config)
+    }
+
+    /// Check the addresses of all entries, naming the offending entry
+    /// in the error
+    fn validate(&self) -> Result<()> {
+        for (i, c) in self.tcp_listeners.iter().enumerate() {
+            socket_addr(&c.address).with_context(|| anyhow!("tcp_listeners[{i}].address"))?;
+        }
+        for (i, c) in self.tcp_connections.iter().enumerate() {
+            host_port(&c.address).with_context(|| anyhow!("tcp_connections[{i}].address"))?;
+        }
+        for (i, c) in self.secure_channels.iter().enumerate() {
+            multiaddr(&c.to).with_context(|| anyhow!("secure_channels[{i}].to"))?;
+        }
+        for (i, c) in self.outlets.iter().enumerate() {
+            if c.from.is_empty() {
+                return Err(anyhow!("outlets[{i}].from: missing worker address"));
+            }
+            socket_addr(&c.to).with_context(|| anyhow!("outlets[{i}].to"))?;
+        }
+        for (i, c) in self.inlets.iter().enumerate() {
+            socket_addr(&c.from).with_context(|| anyhow!("inlets[{i}].from"))?;
+            multiaddr(&c.to).with_context(|| anyhow!("inlets[{i}].to"))?;
+        }
+        for (i, c) in self.forwarders.iter().enumerate() {
+            multiaddr(&c.at).with_context(|| anyhow!("forwarders[{i}].at"))?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn socket_addr(s: &str) -> Result<SocketAddr> {
+    s.parse()
+        .map_err(|_| anyhow!("invalid socket address '{s}'"))
+}
+
+pub(crate) fn multiaddr(s: &str) -> Result<MultiAddr> {
+    s.parse().map_err(|_| anyhow!("invalid route '{s}'"))
+}
+
+fn host_port(s: &str) -> Result<()> {
+    match s.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => Ok(()),
+        _ => Err(anyhow!("invalid address '{s}', expected 'host:port'")),
     }
 }
 
@@ -97,3 +260,58 @@ fn verifier_default_addr() -> String {
 fn authenticator_default_addr() -> String {
     DefaultAddress::AUTHENTICATOR.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn read_yaml_config() {
+        let contents = r#"
+tcp_listeners:
+  - address: 127.0.0.1:4000
+outlets:
+  - from: outlet
+    to: 127.0.0.1:5000
+inlets:
+  - from: 127.0.0.1:6000
+    to: /node/n1/service/outlet
+    alias: db
+"#;
+        let dir = tempdir().expect("Failed to create temp dir");
+        let file_path = dir.path().join("node.yaml");
+        std::fs::write(&file_path, contents).expect("Failed to write contents to file");
+        let config = Config::read(&file_path).expect("Failed to read config");
+        assert!(config.startup_services.is_none());
+        assert_eq!(config.tcp_listeners.len(), 1);
+        assert_eq!(config.outlets[0].from, "outlet");
+        assert_eq!(config.inlets[0].alias.as_deref(), Some("db"));
+        assert!(!config.inlets[0].check_credential);
+    }
+
+    #[test]
+    fn invalid_entries_are_named() {
+        let contents = r#"{
+            "inlets": [
+                { "from": "127.0.0.1:6000", "to": "/node/n1/service/outlet" },
+                { "from": "localhost", "to": "/node/n1/service/outlet" }
+            ]
+        }"#;
+        let dir = tempdir().expect("Failed to create temp dir");
+        let file_path = dir.path().join("node.json");
+        std::fs::write(&file_path, contents).expect("Failed to write contents to file");
+        let err = Config::read(&file_path).unwrap_err();
+        assert!(format!("{err:#}").contains("inlets[1].from"));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let file_path = dir.path().join("node.json");
+        std::fs::write(&file_path, r#"{ "policies": [] }"#)
+            .expect("Failed to write contents to file");
+        assert!(Config::read(&file_path).is_err());
+    }
+}
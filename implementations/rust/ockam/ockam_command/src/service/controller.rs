@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as _, Result};
+use minicbor::Decoder;
+use ockam::{route, Address, Context, TcpTransport, TCP};
+use ockam_api::controller::{ProjectLauncher, ProjectNode};
+use ockam_api::error::ApiError;
+use ockam_api::nodes::models::services::StartAuthenticatorRequest;
+use ockam_api::nodes::NODEMANAGER_ADDR;
+use ockam_api::DefaultAddress;
+use ockam_core::api::{Request, Response, Status};
+use ockam_core::async_trait;
+use tracing::debug;
+
+use crate::node::util::delete_node;
+use crate::util::{api, find_available_port, startup, OckamConfig};
+
+/// Launches the nodes of the projects created on a controller as
+/// background nodes, named `project-<id>`
+pub(crate) struct ProjectNodeLauncher {
+    tcp: TcpTransport,
+    verbose: u8,
+    /// Processes of the launched nodes, in case a command which
+    /// loaded the configuration earlier overwrites their entries
+    pids: Mutex<BTreeMap<String, i32>>,
+}
+
+impl ProjectNodeLauncher {
+    pub(crate) fn new(tcp: TcpTransport, verbose: u8) -> Self {
+        Self {
+            tcp,
+            verbose,
+            pids: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    async fn launch_impl(
+        &self,
+        ctx: &Context,
+        project_id: &str,
+        enrollers: &Path,
+    ) -> Result<ProjectNode> {
+        let name = node_name(project_id);
+        let port = find_available_port()?;
+        let addr = SocketAddr::from(([127, 0, 0, 1], port));
+
+        // Reload the configuration, other commands may have changed
+        // it since this node started
+        let cfg = OckamConfig::load();
+        // The node manager of the project node is reached over TCP
+        cfg.create_node(&name, addr, self.verbose, None, true, true)?;
+        cfg.persist_config_updates()?;
+        startup::spawn_node(
+            &cfg,
+            self.verbose,
+            false,
+            true,
+            false,
+            &name,
+            &addr.to_string(),
+            None,
+            None,
+            None,
+        )?;
+        if let Some(pid) = cfg.get_node_pid(&name)? {
+            self.pids.lock().unwrap().insert(name.clone(), pid);
+        }
+
+        let ctx = ctx.new_detached(Address::random_local()).await?;
+        let peer = addr.to_string();
+        let tcp = self.wait_for_node(&ctx, &peer).await?;
+        let node = route![(TCP, peer.clone()), NODEMANAGER_ADDR];
+        let result = async {
+            let req = Request::post("/node/services/authenticator")
+                .body(StartAuthenticatorRequest::new(
+                    DefaultAddress::AUTHENTICATOR,
+                    enrollers,
+                    project_id.as_bytes(),
+                ))
+                .to_vec()?;
+            let res: Vec<u8> = ctx.send_and_receive(node.clone(), req).await?;
+            check_response(&res, "failed to start the project authenticator")?;
+
+            let res: Vec<u8> = ctx
+                .send_and_receive(node.clone(), api::short_identity()?)
+                .await?;
+            check_response(&res, "failed to get the project node identity")?;
+            let (_, body) = api::parse_short_identity_response(&res)?;
+            let identity = body.identity_id.as_ref().try_into()?;
+
+            let res: Vec<u8> = ctx
+                .send_and_receive(node.clone(), api::long_identity()?)
+                .await?;
+            check_response(&res, "failed to export the project node identity")?;
+            let (_, body) = api::parse_long_identity_response(&res)?;
+
+            Ok(ProjectNode {
+                access_route: format!("/dnsaddr/127.0.0.1/tcp/{port}/service/api"),
+                identity,
+                authority_identity: body.identity.to_vec().into(),
+            })
+        }
+        .await;
+        let _ = self.tcp.disconnect(&tcp).await;
+        result
+    }
+
+    /// Connect to a node which has just been spawned, returning once
+    /// its node manager answers
+    async fn wait_for_node(&self, ctx: &Context, peer: &str) -> Result<String> {
+        for _ in 0..20 {
+            ctx.sleep(Duration::from_millis(500)).await;
+            if self.tcp.connect(peer).await.is_err() {
+                continue;
+            }
+            let node = route![(TCP, peer), NODEMANAGER_ADDR];
+            match ctx
+                .send_and_receive::<_, _, Vec<u8>>(node, api::query_status()?)
+                .await
+            {
+                Ok(_) => return Ok(peer.to_string()),
+                Err(e) => debug!(%peer, %e, "project node not ready"),
+            }
+        }
+        Err(anyhow!("the project node at {peer} did not start"))
+    }
+}
+
+#[async_trait]
+impl ProjectLauncher for ProjectNodeLauncher {
+    async fn launch(
+        &self,
+        ctx: &Context,
+        project_id: &str,
+        enrollers: &Path,
+    ) -> ockam_core::Result<ProjectNode> {
+        self.launch_impl(ctx, project_id, enrollers)
+            .await
+            .map_err(|e| ApiError::generic(&format!("{e:#}")))
+    }
+
+    async fn stop(&self, _ctx: &Context, project_id: &str) -> ockam_core::Result<()> {
+        let name = node_name(project_id);
+        let pid = self.pids.lock().unwrap().remove(&name);
+        let cfg = OckamConfig::load();
+        if let (Some(pid), Err(_)) = (pid, cfg.get_node(&name)) {
+            let _ = startup::stop(pid, false);
+        }
+        delete_node(&cfg, &name, false);
+        cfg.persist_config_updates()
+            .context("failed to update configuration")
+            .map_err(|e| ApiError::generic(&format!("{e:#}")))
+    }
+}
+
+fn node_name(project_id: &str) -> String {
+    format!("project-{project_id}")
+}
+
+fn check_response(res: &[u8], msg: &str) -> Result<()> {
+    let mut dec = Decoder::new(res);
+    let hdr: Response = dec.decode()?;
+    match hdr.status() {
+        Some(Status::Ok) => Ok(()),
+        _ => Err(anyhow!("{msg}")),
+    }
+}
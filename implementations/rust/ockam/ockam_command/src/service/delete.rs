@@ -0,0 +1,37 @@
+use clap::Args;
+use serde_json::json;
+
+use ockam::Context;
+
+use crate::node::NodeOpts;
+use crate::util::api;
+use crate::util::output::print_structured;
+use crate::util::{node_rpc, Rpc};
+use crate::{CommandGlobalOpts, Result};
+
+/// Stop a service of a node
+#[derive(Clone, Debug, Args)]
+pub struct DeleteCommand {
+    #[command(flatten)]
+    pub node_opts: NodeOpts,
+
+    /// Address of the service
+    pub addr: String,
+}
+
+impl DeleteCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, DeleteCommand)) -> Result<()> {
+    let mut rpc = Rpc::background(&ctx, &opts, &cmd.node_opts.api_node)?;
+    rpc.request(api::delete_service(&cmd.addr)).await?;
+    rpc.is_ok()?;
+
+    if !print_structured(&opts, &json!({ "address": cmd.addr }))? {
+        println!("Deleted service `{}`", cmd.addr);
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use clap::Args;
+
+use ockam::Context;
+use ockam_api::nodes::models::services::ServiceList;
+
+use crate::node::NodeOpts;
+use crate::util::api;
+use crate::util::output::print_output;
+use crate::util::{node_rpc, Rpc};
+use crate::{CommandGlobalOpts, Result};
+
+/// List the services of a node
+#[derive(Clone, Debug, Args)]
+pub struct ListCommand {
+    #[command(flatten)]
+    pub node_opts: NodeOpts,
+}
+
+impl ListCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, ListCommand)) -> Result<()> {
+    let mut rpc = Rpc::background(&ctx, &opts, &cmd.node_opts.api_node)?;
+    rpc.request(api::list_services()).await?;
+    let services = rpc.parse_response::<ServiceList>()?;
+    print_output(&opts, &services.list)?;
+    Ok(())
+}
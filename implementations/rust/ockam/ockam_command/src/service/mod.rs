@@ -1,6 +1,11 @@
 pub(crate) mod config;
+pub(crate) mod controller;
+pub(crate) mod delete;
+pub(crate) mod list;
 pub(crate) mod start;
 
+pub(crate) use delete::DeleteCommand;
+pub(crate) use list::ListCommand;
 pub(crate) use start::StartCommand;
 
 use crate::help;
@@ -18,13 +23,18 @@ pub struct ServiceCommand {
 pub enum ServiceSubcommand {
     #[command(display_order = 900)]
     Start(StartCommand),
+    #[command(display_order = 900)]
+    List(ListCommand),
+    #[command(display_order = 900)]
+    Delete(DeleteCommand),
 }
 
 impl ServiceCommand {
     pub fn run(self, options: CommandGlobalOpts) {
         match self.subcommand {
-            ServiceSubcommand::Start(c) => c.run(options),
+            ServiceSubcommand::Start(c) => c.run(options).unwrap(),
+            ServiceSubcommand::List(c) => c.run(options),
+            ServiceSubcommand::Delete(c) => c.run(options),
         }
-        .unwrap()
     }
 }
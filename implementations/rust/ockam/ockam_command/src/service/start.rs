@@ -1,19 +1,22 @@
 use crate::node::NodeOpts;
-use crate::util::{api, connect_to, exitcode};
+use crate::util::{api, connect_to, exitcode, node_port, OckamConfig};
 use crate::CommandGlobalOpts;
 use anyhow::{anyhow, Context as _, Result};
 use clap::{Args, Subcommand};
 use minicbor::Decoder;
+use ockam::identity::{IdentityIdentifier, PublicIdentity};
 use ockam::Context;
 use ockam_api::error::ApiError;
 use ockam_api::nodes::models::services::{
-    StartAuthenticatorRequest, StartCredentialsService, StartVerifierService,
+    StartAuthenticatorRequest, StartControllerRequest, StartCredentialsService,
+    StartVerifierService,
 };
 use ockam_api::nodes::NODEMANAGER_ADDR;
 use ockam_api::DefaultAddress;
 use ockam_core::api::{Error, Request, Response, Status};
 use ockam_core::Route;
-use std::path::PathBuf;
+use ockam_vault::Vault;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
 #[derive(Clone, Debug, Args)]
@@ -60,6 +63,19 @@ pub enum StartSubCommand {
         #[arg(long)]
         project: String,
     },
+    /// Serve the spaces and projects API, launching a node for every
+    /// project created
+    Controller {
+        /// Directory to store the state of the controller in, by
+        /// default in the node directory
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Identity allowed to create spaces and projects, the
+        /// default identity if none is given
+        #[arg(long)]
+        admin: Vec<IdentityIdentifier>,
+    },
 }
 
 fn vault_default_addr() -> String {
@@ -88,46 +104,63 @@ fn authenticator_default_addr() -> String {
 
 impl StartCommand {
     pub fn run(self, options: CommandGlobalOpts) -> Result<()> {
-        let cfg = options.config;
-        let port = cfg.get_node_port(&self.node_opts.api_node);
+        let port = node_port(&options, &self.node_opts.api_node);
 
         match self.create_subcommand {
-            StartSubCommand::Vault { .. } => connect_to(port, self, |ctx, cmd, rte| async {
-                start_vault_service(&ctx, cmd, rte).await?;
-                drop(ctx);
-                Ok(())
-            }),
-            StartSubCommand::Identity { .. } => connect_to(port, self, |ctx, cmd, rte| async {
-                start_identity_service(&ctx, cmd, rte).await?;
-                drop(ctx);
-                Ok(())
-            }),
+            StartSubCommand::Vault { .. } => {
+                connect_to(port, (options, self), |ctx, (_, cmd), rte| async {
+                    start_vault_service(&ctx, cmd, rte).await?;
+                    drop(ctx);
+                    Ok(())
+                })
+            }
+            StartSubCommand::Identity { .. } => {
+                connect_to(port, (options, self), |ctx, (_, cmd), rte| async {
+                    start_identity_service(&ctx, cmd, rte).await?;
+                    drop(ctx);
+                    Ok(())
+                })
+            }
             StartSubCommand::Authenticated { .. } => {
-                connect_to(port, self, |mut ctx, cmd, rte| async {
+                connect_to(port, (options, self), |mut ctx, (_, cmd), rte| async {
                     start_authenticated_service(&mut ctx, cmd, rte).await?;
                     drop(ctx);
                     Ok(())
                 })
             }
-            StartSubCommand::Verifier { .. } => connect_to(port, self, |ctx, cmd, rte| async {
-                start_verifier_service(&ctx, cmd, rte).await?;
-                drop(ctx);
-                Ok(())
-            }),
+            StartSubCommand::Verifier { .. } => {
+                connect_to(port, (options, self), |ctx, (_, cmd), rte| async {
+                    start_verifier_service(&ctx, cmd, rte).await?;
+                    drop(ctx);
+                    Ok(())
+                })
+            }
             StartSubCommand::Credentials { .. } => {
-                connect_to(port, self, |mut ctx, cmd, rte| async {
+                connect_to(port, (options, self), |mut ctx, (_, cmd), rte| async {
                     start_credentials_service(&mut ctx, cmd, rte).await?;
                     drop(ctx);
                     Ok(())
                 })
             }
             StartSubCommand::Authenticator { .. } => {
-                connect_to(port, self, |ctx, cmd, rte| async {
+                connect_to(port, (options, self), |ctx, (_, cmd), rte| async {
                     start_authenticator_service(&ctx, cmd, rte).await?;
                     drop(ctx);
                     Ok(())
                 })
             }
+            StartSubCommand::Controller { path, admin } => connect_to(
+                port,
+                (options, (path, admin)),
+                |ctx, (opts, (path, mut admins)), rte| async move {
+                    if admins.is_empty() {
+                        admins.push(default_identifier(&opts.config).await?);
+                    }
+                    start_controller(&ctx, path.as_deref(), admins, rte).await?;
+                    drop(ctx);
+                    Ok(())
+                },
+            ),
         }
 
         Ok(())
@@ -399,3 +432,48 @@ pub async fn start_authenticator_service(
 
     Err(anyhow!("Failed to start authenticator service"))
 }
+
+pub async fn start_controller(
+    ctx: &Context,
+    path: Option<&Path>,
+    admins: Vec<IdentityIdentifier>,
+    mut route: Route,
+) -> Result<()> {
+    let req = Request::post("/node/services/controller")
+        .body(StartControllerRequest::new(path, admins))
+        .to_vec()?;
+
+    let res: Vec<u8> = ctx
+        .send_and_receive(route.modify().append(NODEMANAGER_ADDR), req)
+        .await?;
+
+    let mut dec = Decoder::new(&res);
+    let hdr: Response = dec.decode()?;
+
+    if let Some(Status::Ok) = hdr.status() {
+        println!(
+            "Controller started, its identity is the identity of the node.\n\
+             Point commands at it with OCKAM_CONTROLLER_ADDR and OCKAM_CONTROLLER_IDENTITY_ID."
+        );
+        return Ok(());
+    }
+
+    if hdr.has_body() {
+        if let Ok(err) = dec.decode::<Error>() {
+            if let Some(msg) = err.message() {
+                return Err(anyhow!("Failed to start controller: {}", msg));
+            }
+        }
+    }
+
+    Err(anyhow!("Failed to start controller"))
+}
+
+/// The identifier of the default identity of the CLI
+async fn default_identifier(cfg: &OckamConfig) -> Result<IdentityIdentifier> {
+    let identity = cfg
+        .get_default_identity()
+        .context("Default identity was not found, use --admin")?;
+    let identity = PublicIdentity::import(&identity, &Vault::default()).await?;
+    Ok(identity.identifier().clone())
+}
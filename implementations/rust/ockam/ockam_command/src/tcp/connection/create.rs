@@ -1,7 +1,9 @@
+use crate::error::exit_with;
 use crate::{
-    util::{api, connect_to, exitcode, get_final_element},
-    CommandGlobalOpts, OutputFormat,
+    util::{api, connect_to, exitcode, get_final_element, node_port, output::print_structured},
+    CommandGlobalOpts,
 };
+use anyhow::anyhow;
 use clap::Args;
 use colorful::Colorful;
 use ockam::{Context, Route, TCP};
@@ -38,17 +40,16 @@ pub struct CreateCommand {
 
 impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let cfg = &options.config;
-        let node = get_final_element(&self.node_opts.from);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, &self.node_opts.from);
+        let port = node_port(&options, node);
 
-        connect_to(port, (self.clone(), options.clone()), create_connection);
+        connect_to(port, (options, self.clone()), create_connection);
     }
 }
 
 pub async fn create_connection(
     ctx: Context,
-    (cmd, opts): (CreateCommand, CommandGlobalOpts),
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     let resp: Vec<u8> = match ctx
@@ -60,8 +61,11 @@ pub async fn create_connection(
     {
         Ok(sr_msg) => sr_msg,
         Err(e) => {
-            eprintln!("Wasn't able to send or receive `Message`: {}", e);
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
         }
     };
 
@@ -77,43 +81,47 @@ pub async fn create_connection(
             let multiaddr = match route_to_multiaddr(&r) {
                 Some(addr) => addr,
                 None => {
-                    eprintln!("Couldn't convert given address into `MultiAddr`");
-                    std::process::exit(exitcode::SOFTWARE);
+                    exit_with(
+                        &opts,
+                        exitcode::SOFTWARE,
+                        anyhow!("Couldn't convert given address into `MultiAddr`"),
+                    );
                 }
             };
 
             let from = cmd.node_opts.from;
             let to = cmd.address.parse::<SocketAddrV4>().unwrap();
 
-            // if output format is json, write json to stdout.
-            match opts.global_args.output_format {
-                OutputFormat::Plain => {
-                    if opts.global_args.no_color {
-                        eprintln!("\n  Created TCP Connection:");
-                        eprintln!("  • From: /node/{}", from);
-                        eprintln!("  •   To: {} (/ip4/{}/tcp/{})", to, to.ip(), to.port());
-                    } else {
-                        eprintln!("\n  Created TCP Connection:");
-                        eprintln!("{}", format!("  • From: /node/{}", from).light_magenta());
-                        eprintln!(
-                            "{}",
-                            format!("  •   To: {} (/ip4/{}/tcp/{})", to, to.ip(), to.port())
-                                .light_magenta()
-                        );
-                    }
-                }
-                OutputFormat::Json => {
-                    let json = json!([{"route": multiaddr.to_string() }]);
-                    eprintln!("{}", json);
+            let output = json!({
+                "from": format!("/node/{}", from),
+                "to": to.to_string(),
+                "route": multiaddr.to_string(),
+            });
+            if !print_structured(&opts, &output)? {
+                if opts.global_args.no_color {
+                    eprintln!("\n  Created TCP Connection:");
+                    eprintln!("  • From: /node/{}", from);
+                    eprintln!("  •   To: {} (/ip4/{}/tcp/{})", to, to.ip(), to.port());
+                } else {
+                    eprintln!("\n  Created TCP Connection:");
+                    eprintln!("{}", format!("  • From: /node/{}", from).light_magenta());
+                    eprintln!(
+                        "{}",
+                        format!("  •   To: {} (/ip4/{}/tcp/{})", to, to.ip(), to.port())
+                            .light_magenta()
+                    );
                 }
             }
         }
         _ => {
-            eprintln!(
-                "An error occurred while creating the tcp connection: {}",
-                payload
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!(
+                    "An error occurred while creating the tcp connection: {}",
+                    payload
+                ),
             );
-            std::process::exit(exitcode::CANTCREAT);
         }
     }
     Ok(())
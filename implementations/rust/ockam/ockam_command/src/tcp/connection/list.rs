@@ -1,13 +1,12 @@
+use crate::error::exit_with;
 use crate::node::NodeOpts;
-use crate::util::{api, connect_to, exitcode, get_final_element};
+use crate::util::output::print_output;
+use crate::util::{api, connect_to, exitcode, get_final_element, node_port};
 use crate::CommandGlobalOpts;
+use anyhow::anyhow;
 use clap::Args;
-use cli_table::{print_stdout, Cell, Style, Table};
 use ockam::{Context, Route};
-use ockam_api::nodes::{
-    models::transport::{TransportList, TransportStatus},
-    NODEMANAGER_ADDR,
-};
+use ockam_api::nodes::NODEMANAGER_ADDR;
 
 #[derive(Args, Clone, Debug)]
 pub struct ListCommand {
@@ -17,15 +16,18 @@ pub struct ListCommand {
 
 impl ListCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let cfg = &options.config;
-        let node = get_final_element(&self.node_opts.api_node);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, &self.node_opts.api_node);
+        let port = node_port(&options, node);
 
-        connect_to(port, (), list_connections);
+        connect_to(port, (options, ()), list_connections);
     }
 }
 
-pub async fn list_connections(ctx: Context, _: (), mut base_route: Route) -> anyhow::Result<()> {
+pub async fn list_connections(
+    ctx: Context,
+    (opts, _): (CommandGlobalOpts, ()),
+    mut base_route: Route,
+) -> anyhow::Result<()> {
     let resp: Vec<u8> = match ctx
         .send_and_receive(
             base_route.modify().append(NODEMANAGER_ADDR),
@@ -35,42 +37,16 @@ pub async fn list_connections(ctx: Context, _: (), mut base_route: Route) -> any
     {
         Ok(sr_msg) => sr_msg,
         Err(e) => {
-            eprintln!("Wasn't able to send or receive `Message`: {}", e);
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
         }
     };
 
-    let TransportList { list, .. } = api::parse_tcp_list(&resp)?;
-
-    let table = list
-        .iter()
-        .fold(
-            vec![],
-            |mut acc,
-             TransportStatus {
-                 tt,
-                 tm,
-                 payload,
-                 tid,
-                 ..
-             }| {
-                let row = vec![tid.cell(), tt.cell(), tm.cell(), payload.cell()];
-                acc.push(row);
-                acc
-            },
-        )
-        .table()
-        .title(vec![
-            "Transport ID".cell().bold(true),
-            "Transport Type".cell().bold(true),
-            "Mode".cell().bold(true),
-            "Address bind".cell().bold(true),
-        ]);
-
-    if let Err(e) = print_stdout(table) {
-        eprintln!("failed to print node status: {}", e);
-        std::process::exit(exitcode::IOERR);
-    }
+    let list = api::parse_transport_list(&resp)?.list;
+    print_output(&opts, &list)?;
 
     Ok(())
 }
@@ -1,5 +1,8 @@
-use crate::util::{bind_to_port_check, connect_to, exitcode, get_final_element};
+use crate::error::exit_with;
+use crate::util::output::print_structured;
+use crate::util::{bind_to_port_check, connect_to, exitcode, get_final_element, node_port};
 use crate::{help, CommandGlobalOpts};
+use anyhow::anyhow;
 use clap::Args;
 use minicbor::Decoder;
 use ockam::{Context, Route};
@@ -60,30 +63,36 @@ impl CreateCommand {
             to: match clean_multiaddr(&self.to, &cfg.lookup()) {
                 Some((addr, _meta)) => addr,
                 None => {
-                    eprintln!("failed to normalize MultiAddr route");
-                    std::process::exit(exitcode::USAGE);
+                    exit_with(
+                        &options,
+                        exitcode::USAGE,
+                        anyhow!("failed to normalize MultiAddr route"),
+                    );
                 }
             },
             ..self
         };
 
-        let node = get_final_element(&command.at);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, &command.at);
+        let port = node_port(&options, node);
 
         // Check if the port is used by some other services or process
         if !bind_to_port_check(&command.from) {
-            eprintln!("Another process is listening on the provided port!");
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &options,
+                exitcode::IOERR,
+                anyhow!("Another process is listening on the provided port!"),
+            );
         }
 
-        connect_to(port, command, create_inlet);
+        connect_to(port, (options, command), create_inlet);
         Ok(())
     }
 }
 
 pub async fn create_inlet(
     ctx: Context,
-    cmd: CreateCommand,
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     let route = base_route.modify().append(NODEMANAGER_ADDR);
@@ -95,17 +104,27 @@ pub async fn create_inlet(
     )?;
     let response: Vec<u8> = ctx.send_and_receive(route, message).await?;
 
-    let (response, InletStatus { bind_addr, .. }) = parse_inlet_status(&response)?;
+    let (response, status) = parse_inlet_status(&response)?;
 
     match response.status() {
         Some(Status::Ok) => {
-            println!("{}", bind_addr)
+            if !print_structured(&opts, &status)? {
+                println!("{}", status.bind_addr)
+            }
         }
 
-        _ => {
-            eprintln!("An unknown error occurred while creating an inlet...");
-            std::process::exit(exitcode::UNAVAILABLE)
-        }
+        _ => exit_with(
+            &opts,
+            exitcode::UNAVAILABLE,
+            crate::error::ApiError {
+                status: response.status(),
+                message: format!(
+                    "An error occurred while creating an inlet: {}",
+                    status.payload.as_deref().unwrap_or("unknown error")
+                ),
+            }
+            .into(),
+        ),
     }
 
     Ok(())
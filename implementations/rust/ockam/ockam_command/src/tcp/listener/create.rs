@@ -1,8 +1,11 @@
+use crate::error::exit_with;
+use crate::util::output::print_structured;
 use crate::util::{bind_to_port_check, get_final_element};
 use crate::{
-    util::{api, connect_to, exitcode},
+    util::{api, connect_to, exitcode, node_port},
     CommandGlobalOpts,
 };
+use anyhow::anyhow;
 use clap::Args;
 use ockam::{Context, Route, TCP};
 use ockam_api::{
@@ -10,6 +13,7 @@ use ockam_api::{
     route_to_multiaddr,
 };
 use ockam_core::api::Status;
+use serde_json::json;
 use std::str::FromStr;
 
 #[derive(Args, Clone, Debug)]
@@ -30,31 +34,32 @@ pub struct TCPListenerNodeOpts {
 
 impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let cfg = &options.config;
-        let node = get_final_element(&self.node_opts.at);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, &self.node_opts.at);
+        let port = node_port(&options, node);
 
         let input_addr = match std::net::SocketAddr::from_str(&self.address) {
             Ok(value) => value,
             _ => {
-                eprintln!("Invalid Input Address");
-                std::process::exit(exitcode::IOERR);
+                exit_with(&options, exitcode::IOERR, anyhow!("Invalid Input Address"));
             }
         };
 
         // Check if the port is used by some other services or process
         if !bind_to_port_check(&input_addr) {
-            eprintln!("Another process is listening on the provided port!");
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &options,
+                exitcode::IOERR,
+                anyhow!("Another process is listening on the provided port!"),
+            );
         }
 
-        connect_to(port, self.clone(), create_listener);
+        connect_to(port, (options, self.clone()), create_listener);
     }
 }
 
 pub async fn create_listener(
     ctx: Context,
-    cmd: CreateCommand,
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     let resp: Vec<u8> = match ctx
@@ -66,8 +71,11 @@ pub async fn create_listener(
     {
         Ok(sr_msg) => sr_msg,
         Err(e) => {
-            eprintln!("Wasn't able to send or receive `Message`: {}", e);
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
         }
     };
 
@@ -83,22 +91,30 @@ pub async fn create_listener(
             let multiaddr = match route_to_multiaddr(&r) {
                 Some(addr) => addr,
                 None => {
-                    eprintln!("Couldn't convert given address into `MultiAddr`");
-                    std::process::exit(exitcode::SOFTWARE);
+                    exit_with(
+                        &opts,
+                        exitcode::SOFTWARE,
+                        anyhow!("Couldn't convert given address into `MultiAddr`"),
+                    );
                 }
             };
 
-            println!(
-                "Tcp listener created! You can send messages to it via this route:\n`{}`",
-                multiaddr
-            )
+            if !print_structured(&opts, &json!({ "route": multiaddr.to_string() }))? {
+                println!(
+                    "Tcp listener created! You can send messages to it via this route:\n`{}`",
+                    multiaddr
+                )
+            }
         }
         _ => {
-            eprintln!(
-                "An error occurred while creating the tcp listener: {}",
-                payload
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!(
+                    "An error occurred while creating the tcp listener: {}",
+                    payload
+                ),
             );
-            std::process::exit(exitcode::CANTCREAT);
         }
     }
     Ok(())
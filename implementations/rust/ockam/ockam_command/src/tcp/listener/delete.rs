@@ -1,12 +1,16 @@
+use crate::error::exit_with;
+use anyhow::anyhow;
 use clap::Args;
 use ockam::{Context, Route};
 use ockam_api::nodes::NODEMANAGER_ADDR;
 use ockam_core::api::{Response, Status};
+use serde_json::json;
 
 use crate::util::get_final_element;
+use crate::util::output::print_structured;
 use crate::{
     node::NodeOpts,
-    util::{api, connect_to, exitcode},
+    util::{api, connect_to, exitcode, node_port},
     CommandGlobalOpts,
 };
 
@@ -25,16 +29,15 @@ pub struct DeleteCommand {
 
 impl DeleteCommand {
     pub fn run(self, options: CommandGlobalOpts) {
-        let cfg = &options.config;
-        let node = get_final_element(&self.node_opts.api_node);
-        let port = cfg.get_node_port(node);
-        connect_to(port, self, delete_listener);
+        let node = get_final_element(&options, &self.node_opts.api_node);
+        let port = node_port(&options, node);
+        connect_to(port, (options, self), delete_listener);
     }
 }
 
 pub async fn delete_listener(
     ctx: Context,
-    cmd: DeleteCommand,
+    (opts, cmd): (CommandGlobalOpts, DeleteCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     let resp: Vec<u8> = match ctx
@@ -46,21 +49,33 @@ pub async fn delete_listener(
     {
         Ok(sr_msg) => sr_msg,
         Err(e) => {
-            eprintln!("Wasn't able to send or receive `Message`: {}", e);
-            std::process::exit(exitcode::IOERR);
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
         }
     };
     let r: Response = api::parse_response(&resp)?;
 
     match r.status() {
-        Some(Status::Ok) => println!("Tcp listener `{}` successfully delete", cmd.id),
-        _ => {
-            eprintln!("Failed to delete tcp listener");
-            if !cmd.force {
-                eprintln!("You may have to provide --force to delete the API transport");
-                std::process::exit(exitcode::UNAVAILABLE);
+        Some(Status::Ok) => {
+            if !print_structured(&opts, &json!({ "id": cmd.id }))? {
+                println!("Tcp listener `{}` successfully delete", cmd.id);
             }
+            Ok(())
+        }
+        _ => {
+            let hint = if cmd.force {
+                ""
+            } else {
+                ", you may have to provide --force to delete the API transport"
+            };
+            exit_with(
+                &opts,
+                exitcode::UNAVAILABLE,
+                anyhow!("Failed to delete tcp listener `{}`{hint}", cmd.id),
+            )
         }
     }
-    Ok(())
 }
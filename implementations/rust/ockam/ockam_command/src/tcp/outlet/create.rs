@@ -1,4 +1,6 @@
-use crate::util::{connect_to, exitcode, get_final_element};
+use crate::error::exit_with;
+use crate::util::output::print_structured;
+use crate::util::{connect_to, exitcode, get_final_element, node_port};
 use crate::{help, CommandGlobalOpts};
 use clap::Args;
 use minicbor::Decoder;
@@ -58,41 +60,52 @@ pub struct CreateCommand {
 
 impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) -> anyhow::Result<()> {
-        let cfg = &options.config;
         let at = &self.at.clone();
-        let node = get_final_element(at);
-        let port = cfg.get_node_port(node);
+        let node = get_final_element(&options, at);
+        let port = node_port(&options, node);
 
         let command = CreateCommand {
-            from: String::from(get_final_element(&self.from)),
+            from: String::from(get_final_element(&options, &self.from)),
             ..self
         };
 
-        connect_to(port, command, create_outlet);
+        connect_to(port, (options, command), create_outlet);
         Ok(())
     }
 }
 
 pub async fn create_outlet(
     ctx: Context,
-    cmd: CreateCommand,
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     let route = base_route.modify().append(NODEMANAGER_ADDR);
     let message = make_api_request(cmd)?;
     let response: Vec<u8> = ctx.send_and_receive(route, message).await?;
 
-    let (response, OutletStatus { worker_addr, .. }) = parse_outlet_status(&response)?;
-    let addr = route_to_multiaddr(&route![worker_addr.to_string()])
-        .ok_or_else(|| ApiError::generic("Invalid Outlet Address"))?;
+    let (response, status) = parse_outlet_status(&response)?;
 
     match response.status() {
         Some(Status::Ok) => {
-            println!("{}", addr);
+            if !print_structured(&opts, &status)? {
+                let addr = route_to_multiaddr(&route![status.worker_addr.to_string()])
+                    .ok_or_else(|| ApiError::generic("Invalid Outlet Address"))?;
+                println!("{}", addr);
+            }
         }
         _ => {
-            eprintln!("An unknown error occurred while creating an outlet...");
-            std::process::exit(exitcode::UNAVAILABLE);
+            exit_with(
+                &opts,
+                exitcode::UNAVAILABLE,
+                crate::error::ApiError {
+                    status: response.status(),
+                    message: format!(
+                        "An error occurred while creating an outlet: {}",
+                        status.payload.as_deref().unwrap_or("unknown error")
+                    ),
+                }
+                .into(),
+            );
         }
     }
 
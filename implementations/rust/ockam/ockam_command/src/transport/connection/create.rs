@@ -0,0 +1,95 @@
+use crate::error::exit_with;
+use crate::util::output::print_structured;
+use crate::{
+    util::{api, connect_to, exitcode, get_final_element, node_port},
+    CommandGlobalOpts,
+};
+use anyhow::anyhow;
+use clap::Args;
+use ockam::{Context, Route};
+use ockam_api::nodes::{
+    models::transport::{TransportMode, TransportStatus, TransportType},
+    NODEMANAGER_ADDR,
+};
+use ockam_core::api::Status;
+
+#[derive(Clone, Debug, Args)]
+pub struct ConnectionNodeOpts {
+    /// Node that will initiate the connection
+    #[arg(
+        global = true,
+        short,
+        long,
+        value_name = "NODE",
+        default_value = "default"
+    )]
+    pub from: String,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct CreateCommand {
+    #[command(flatten)]
+    node_opts: ConnectionNodeOpts,
+
+    /// The address to connect to (required)
+    #[arg(id = "to", short, long, value_name = "ADDRESS")]
+    pub address: String,
+}
+
+impl CreateCommand {
+    pub fn run(self, options: CommandGlobalOpts, tt: TransportType) {
+        let node = get_final_element(&options, &self.node_opts.from);
+        let port = node_port(&options, node);
+
+        connect_to(port, (options, (self, tt)), create_connection);
+    }
+}
+
+pub async fn create_connection(
+    ctx: Context,
+    (opts, (cmd, tt)): (CommandGlobalOpts, (CreateCommand, TransportType)),
+    mut base_route: Route,
+) -> anyhow::Result<()> {
+    let resp: Vec<u8> = match ctx
+        .send_and_receive(
+            base_route.modify().append(NODEMANAGER_ADDR),
+            api::create_transport(tt, TransportMode::Connect, &cmd.address)?,
+        )
+        .await
+    {
+        Ok(sr_msg) => sr_msg,
+        Err(e) => {
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
+        }
+    };
+
+    let (response, status) = api::parse_transport_status(&resp)?;
+    let TransportStatus { payload, tid, .. } = &status;
+
+    match response.status() {
+        Some(Status::Ok) => {
+            if !print_structured(&opts, &status)? {
+                println!(
+                    "{} connection `{}` created from /node/{} to {}",
+                    tt, tid, cmd.node_opts.from, payload
+                )
+            }
+        }
+        _ => {
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!(
+                    "An error occurred while creating the {} connection: {}",
+                    tt,
+                    payload
+                ),
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+mod create;
+mod delete;
+mod list;
+
+pub(crate) use create::CreateCommand;
+pub(crate) use delete::DeleteCommand;
+pub(crate) use list::ListCommand;
+
+use crate::CommandGlobalOpts;
+use clap::{Args, Subcommand};
+use ockam_api::nodes::models::transport::TransportType;
+
+/// Manage transport connections
+#[derive(Args, Clone, Debug)]
+pub struct ConnectionCommand {
+    #[command(subcommand)]
+    subcommand: ConnectionSubCommand,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ConnectionSubCommand {
+    /// Create a connection from the selected node
+    Create(CreateCommand),
+
+    /// Delete a connection on the selected node
+    Delete(DeleteCommand),
+
+    /// List connections registered on the selected node
+    List(ListCommand),
+}
+
+impl ConnectionCommand {
+    pub fn run(self, options: CommandGlobalOpts, tt: TransportType) {
+        match self.subcommand {
+            ConnectionSubCommand::Create(c) => c.run(options, tt),
+            ConnectionSubCommand::Delete(c) => c.run(options, tt),
+            ConnectionSubCommand::List(c) => c.run(options, tt),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::error::exit_with;
+use crate::util::output::print_structured;
+use crate::{
+    util::{api, connect_to, exitcode, get_final_element, node_port},
+    CommandGlobalOpts,
+};
+use anyhow::anyhow;
+use clap::Args;
+use ockam::{Context, Route};
+use ockam_api::nodes::{
+    models::transport::{TransportMode, TransportStatus, TransportType},
+    NODEMANAGER_ADDR,
+};
+use ockam_core::api::Status;
+use std::str::FromStr;
+
+#[derive(Args, Clone, Debug)]
+pub struct CreateCommand {
+    #[command(flatten)]
+    node_opts: ListenerNodeOpts,
+
+    /// Address for this listener (eg. 127.0.0.1:7000)
+    pub address: String,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ListenerNodeOpts {
+    /// Node at which to create the listener
+    #[arg(global = true, long, value_name = "NODE", default_value = "default")]
+    pub at: String,
+}
+
+impl CreateCommand {
+    pub fn run(self, options: CommandGlobalOpts, tt: TransportType) {
+        let node = get_final_element(&options, &self.node_opts.at);
+        let port = node_port(&options, node);
+
+        if std::net::SocketAddr::from_str(&self.address).is_err() {
+            exit_with(&options, exitcode::IOERR, anyhow!("Invalid Input Address"));
+        }
+
+        connect_to(port, (options, (self, tt)), create_listener);
+    }
+}
+
+pub async fn create_listener(
+    ctx: Context,
+    (opts, (cmd, tt)): (CommandGlobalOpts, (CreateCommand, TransportType)),
+    mut base_route: Route,
+) -> anyhow::Result<()> {
+    let resp: Vec<u8> = match ctx
+        .send_and_receive(
+            base_route.modify().append(NODEMANAGER_ADDR),
+            api::create_transport(tt, TransportMode::Listen, &cmd.address)?,
+        )
+        .await
+    {
+        Ok(sr_msg) => sr_msg,
+        Err(e) => {
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
+        }
+    };
+
+    let (response, status) = api::parse_transport_status(&resp)?;
+    let TransportStatus { payload, tid, .. } = &status;
+
+    match response.status() {
+        Some(Status::Ok) => {
+            if !print_structured(&opts, &status)? {
+                println!(
+                    "{} listener `{}` created on /node/{} at {}",
+                    tt, tid, cmd.node_opts.at, payload
+                )
+            }
+        }
+        _ => {
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!(
+                    "An error occurred while creating the {} listener: {}",
+                    tt,
+                    payload
+                ),
+            );
+        }
+    }
+    Ok(())
+}
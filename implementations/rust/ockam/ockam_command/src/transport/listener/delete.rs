@@ -0,0 +1,67 @@
+use crate::error::exit_with;
+use anyhow::anyhow;
+use clap::Args;
+use ockam::{Context, Route};
+use ockam_api::nodes::models::transport::{TransportMode, TransportType};
+use ockam_api::nodes::NODEMANAGER_ADDR;
+use ockam_core::api::{Response, Status};
+
+use crate::util::get_final_element;
+use crate::{
+    node::NodeOpts,
+    util::{api, connect_to, exitcode, node_port},
+    CommandGlobalOpts,
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct DeleteCommand {
+    #[command(flatten)]
+    node_opts: NodeOpts,
+
+    /// Listener ID
+    pub id: String,
+}
+
+impl DeleteCommand {
+    pub fn run(self, options: CommandGlobalOpts, tt: TransportType) {
+        let node = get_final_element(&options, &self.node_opts.api_node);
+        let port = node_port(&options, node);
+        connect_to(port, (options, (self, tt)), delete_listener);
+    }
+}
+
+pub async fn delete_listener(
+    ctx: Context,
+    (opts, (cmd, tt)): (CommandGlobalOpts, (DeleteCommand, TransportType)),
+    mut base_route: Route,
+) -> anyhow::Result<()> {
+    let resp: Vec<u8> = match ctx
+        .send_and_receive(
+            base_route.modify().append(NODEMANAGER_ADDR),
+            api::delete_transport(tt, TransportMode::Listen, &cmd.id, false)?,
+        )
+        .await
+    {
+        Ok(sr_msg) => sr_msg,
+        Err(e) => {
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
+        }
+    };
+    let r: Response = api::parse_response(&resp)?;
+
+    match r.status() {
+        Some(Status::Ok) => println!("{} listener `{}` successfully deleted", tt, cmd.id),
+        _ => {
+            exit_with(
+                &opts,
+                exitcode::UNAVAILABLE,
+                anyhow!("Failed to delete {} listener `{}`", tt, cmd.id),
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::error::exit_with;
+use crate::node::NodeOpts;
+use crate::util::output::print_output;
+use crate::util::{api, connect_to, exitcode, get_final_element, node_port};
+use crate::CommandGlobalOpts;
+use anyhow::anyhow;
+use clap::Args;
+use ockam::{Context, Route};
+use ockam_api::nodes::models::transport::{TransportMode, TransportType};
+use ockam_api::nodes::NODEMANAGER_ADDR;
+
+#[derive(Args, Clone, Debug)]
+pub struct ListCommand {
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+impl ListCommand {
+    pub fn run(self, options: CommandGlobalOpts, tt: TransportType) {
+        let node = get_final_element(&options, &self.node_opts.api_node);
+        let port = node_port(&options, node);
+
+        connect_to(port, (options, tt), list_listeners);
+    }
+}
+
+pub async fn list_listeners(
+    ctx: Context,
+    (opts, tt): (CommandGlobalOpts, TransportType),
+    mut base_route: Route,
+) -> anyhow::Result<()> {
+    let resp: Vec<u8> = match ctx
+        .send_and_receive(
+            base_route.modify().append(NODEMANAGER_ADDR),
+            api::list_transports(tt, TransportMode::Listen)?,
+        )
+        .await
+    {
+        Ok(sr_msg) => sr_msg,
+        Err(e) => {
+            exit_with(
+                &opts,
+                exitcode::IOERR,
+                anyhow!("Wasn't able to send or receive `Message`: {}", e),
+            );
+        }
+    };
+
+    let list = api::parse_transport_list(&resp)?.list;
+    print_output(&opts, &list)?;
+
+    Ok(())
+}
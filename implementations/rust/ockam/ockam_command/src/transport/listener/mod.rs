@@ -0,0 +1,40 @@
+mod create;
+mod delete;
+mod list;
+
+pub(crate) use create::CreateCommand;
+pub(crate) use delete::DeleteCommand;
+pub(crate) use list::ListCommand;
+
+use crate::CommandGlobalOpts;
+use clap::{Args, Subcommand};
+use ockam_api::nodes::models::transport::TransportType;
+
+/// Manage transport listeners
+#[derive(Args, Clone, Debug)]
+pub struct ListenerCommand {
+    #[command(subcommand)]
+    subcommand: ListenerSubCommand,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ListenerSubCommand {
+    /// Create a listener on the selected node
+    Create(CreateCommand),
+
+    /// Delete a listener on the selected node
+    Delete(DeleteCommand),
+
+    /// List listeners registered on the selected node
+    List(ListCommand),
+}
+
+impl ListenerCommand {
+    pub fn run(self, options: CommandGlobalOpts, tt: TransportType) {
+        match self.subcommand {
+            ListenerSubCommand::Create(c) => c.run(options, tt),
+            ListenerSubCommand::Delete(c) => c.run(options, tt),
+            ListenerSubCommand::List(c) => c.run(options, tt),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+//! Connection and listener commands shared by the WebSocket and UDP transports
+
+pub(crate) mod connection;
+pub(crate) mod listener;
@@ -29,6 +29,13 @@ pub(crate) fn query_status() -> Result<Vec<u8>> {
     Ok(buf)
 }
 
+/// Construct a request to query the health of a node
+pub(crate) fn node_health() -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    Request::get("/node/health").encode(&mut buf)?;
+    Ok(buf)
+}
+
 /// Construct a request to query node tcp connections
 pub(crate) fn list_tcp_connections() -> Result<Vec<u8>> {
     let mut buf = vec![];
@@ -101,6 +108,55 @@ pub(crate) fn delete_tcp_listener(cmd: &crate::tcp::listener::DeleteCommand) ->
     Ok(buf)
 }
 
+/// The node manager path for transports of a given type and mode
+fn transport_path(
+    tt: models::transport::TransportType,
+    tm: models::transport::TransportMode,
+) -> String {
+    let kind = match tm {
+        models::transport::TransportMode::Connect => "connection",
+        models::transport::TransportMode::Listen => "listener",
+    };
+    format!("/node/{}/{}", tt.path_segment(), kind)
+}
+
+/// Construct a request to query node transports of a given type and mode
+pub(crate) fn list_transports(
+    tt: models::transport::TransportType,
+    tm: models::transport::TransportMode,
+) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    Request::get(transport_path(tt, tm)).encode(&mut buf)?;
+    Ok(buf)
+}
+
+/// Construct a request to create a node transport
+pub(crate) fn create_transport(
+    tt: models::transport::TransportType,
+    tm: models::transport::TransportMode,
+    addr: &str,
+) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    Request::post(transport_path(tt, tm))
+        .body(models::transport::CreateTransport::new(tt, tm, addr))
+        .encode(&mut buf)?;
+    Ok(buf)
+}
+
+/// Construct a request to delete a node transport
+pub(crate) fn delete_transport(
+    tt: models::transport::TransportType,
+    tm: models::transport::TransportMode,
+    id: &str,
+    force: bool,
+) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    Request::delete(transport_path(tt, tm))
+        .body(models::transport::DeleteTransport::new(id, force))
+        .encode(&mut buf)?;
+    Ok(buf)
+}
+
 /// Construct a request to create a Vault
 pub(crate) fn create_vault(path: Option<String>) -> Result<Vec<u8>> {
     let mut buf = vec![];
@@ -186,6 +242,40 @@ pub(crate) fn list_secure_channel_listener() -> RequestBuilder<'static, ()> {
     Request::get("/node/secure_channel_listener")
 }
 
+/// Construct a request to delete a Secure Channel Listener
+pub(crate) fn delete_secure_channel_listener(
+    addr: &Address,
+) -> RequestBuilder<'static, models::secure_channel::DeleteSecureChannelListenerRequest<'static>> {
+    let payload = models::secure_channel::DeleteSecureChannelListenerRequest::new(addr);
+    Request::delete("/node/secure_channel_listener").body(payload)
+}
+
+/// Construct a request to list the services of a node
+pub(crate) fn list_services() -> RequestBuilder<'static, ()> {
+    Request::get("/node/services")
+}
+
+/// Construct a request to stop a service
+pub(crate) fn delete_service(
+    addr: &str,
+) -> RequestBuilder<'_, models::services::DeleteServiceRequest<'_>> {
+    let payload = models::services::DeleteServiceRequest::new(addr);
+    Request::delete("/node/services").body(payload)
+}
+
+/// Construct a request to list the forwarders created by a node
+pub(crate) fn list_forwarders() -> RequestBuilder<'static, ()> {
+    Request::get("/node/forwarder")
+}
+
+/// Construct a request to delete a forwarder
+pub(crate) fn delete_forwarder(
+    remote_address: &str,
+) -> RequestBuilder<'_, models::forwarder::DeleteForwarder<'_>> {
+    let payload = models::forwarder::DeleteForwarder::new(remote_address);
+    Request::delete("/node/forwarder").body(payload)
+}
+
 /// Construct a request to start a Vault Service
 pub(crate) fn start_vault_service(addr: &str) -> Result<Vec<u8>> {
     let payload = models::services::StartVaultServiceRequest::new(addr);
@@ -288,6 +378,42 @@ pub(crate) mod space {
     }
 }
 
+/// Helpers to create invitations API requests
+pub(crate) mod invitation {
+    use ockam_api::cloud::invitation::*;
+
+    use super::*;
+
+    pub(crate) fn create<'a>(
+        invitee: &'a str,
+        space_id: &'a str,
+        project_id: Option<&'a str>,
+        cloud_route: &'a MultiAddr,
+    ) -> RequestBuilder<'a, CloudRequestWrapper<'a, CreateInvitation<'a>>> {
+        let b = CreateInvitation::new(invitee, space_id, project_id);
+        Request::post("v0/invitations").body(CloudRequestWrapper::new(b, cloud_route))
+    }
+
+    pub(crate) fn list(cloud_route: &MultiAddr) -> RequestBuilder<BareCloudRequestWrapper> {
+        Request::get("v0/invitations").body(CloudRequestWrapper::bare(cloud_route))
+    }
+
+    pub(crate) fn accept<'a>(
+        id: &str,
+        cloud_route: &'a MultiAddr,
+    ) -> RequestBuilder<'a, BareCloudRequestWrapper<'a>> {
+        Request::put(format!("v0/invitations/{}", id)).body(CloudRequestWrapper::bare(cloud_route))
+    }
+
+    pub(crate) fn reject<'a>(
+        id: &str,
+        cloud_route: &'a MultiAddr,
+    ) -> RequestBuilder<'a, BareCloudRequestWrapper<'a>> {
+        Request::delete(format!("v0/invitations/{}", id))
+            .body(CloudRequestWrapper::bare(cloud_route))
+    }
+}
+
 /// Helpers to create projects API requests
 pub(crate) mod project {
     use ockam_api::cloud::project::*;
@@ -371,8 +497,8 @@ pub(crate) fn parse_status(resp: &[u8]) -> Result<models::base::NodeStatus> {
     Ok(dec.decode::<models::base::NodeStatus>()?)
 }
 
-/// Parse the returned status response
-pub(crate) fn parse_tcp_list(resp: &[u8]) -> Result<models::transport::TransportList> {
+/// Parse the returned list of transports
+pub(crate) fn parse_transport_list(resp: &[u8]) -> Result<models::transport::TransportList> {
     let mut dec = Decoder::new(resp);
     let _ = dec.decode::<Response>()?;
     Ok(dec.decode::<models::transport::TransportList>()?)
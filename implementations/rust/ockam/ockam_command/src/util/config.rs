@@ -7,12 +7,10 @@ use slug::slugify;
 use tracing::{error, trace};
 
 use ockam::identity::IdentityIdentifier;
-pub use ockam_api::config::cli::NodeConfig;
+pub use ockam_api::config::cli::{NodeConfig, NodeService};
 use ockam_api::config::lookup::ProjectLookup;
 use ockam_api::config::{cli, lookup::ConfigLookup, lookup::InternetAddress, Config};
 
-use crate::util::exitcode;
-
 /// A simple wrapper around the main configuration structure to add
 /// local config utility/ query functions
 #[derive(Clone)]
@@ -115,16 +113,9 @@ impl OckamConfig {
     }
 
     /// Get the API port used by a node
-    pub fn get_node_port(&self, name: &str) -> u16 {
+    pub fn get_node_port(&self, name: &str) -> Option<u16> {
         let inner = self.inner.readlock_inner();
-        inner
-            .nodes
-            .get(name)
-            .unwrap_or_else(|| {
-                eprintln!("No such node available. Run `ockam node list` to list available nodes");
-                std::process::exit(exitcode::IOERR);
-            })
-            .port
+        inner.nodes.get(name).map(|n| n.port)
     }
 
     /// In the future this will actually refer to the watchdog pid or
@@ -146,6 +137,12 @@ impl OckamConfig {
         inner.nodes.iter().any(|(_, n)| n.port == port)
     }
 
+    /// Get the configuration of the node using this API port
+    pub fn get_node_by_port(&self, port: u16) -> Option<NodeConfig> {
+        let inner = self.inner.readlock_inner();
+        inner.nodes.values().find(|n| n.port == port).cloned()
+    }
+
     /// Get only a single node configuration
     pub fn get_node(&self, node: &str) -> Result<NodeConfig> {
         let inner = self.inner.readlock_inner();
@@ -202,7 +199,15 @@ impl OckamConfig {
     }
 
     /// Add a new node to the configuration for future lookup
-    pub fn create_node(&self, name: &str, bind: SocketAddr, verbose: u8) -> Result<()> {
+    pub fn create_node(
+        &self,
+        name: &str,
+        bind: SocketAddr,
+        verbose: u8,
+        metrics_address: Option<SocketAddr>,
+        api_socket: bool,
+        tcp_api: bool,
+    ) -> Result<()> {
         let mut inner = self.inner.writelock_inner();
 
         if inner.nodes.contains_key(name) {
@@ -219,6 +224,13 @@ impl OckamConfig {
 
         create_dir_all(&state_dir).context("failed to create new node state directory")?;
 
+        // Unix domain sockets are not available on every platform
+        let api_socket = if api_socket && cfg!(unix) {
+            Some(state_dir.join("api.sock"))
+        } else {
+            None
+        };
+
         // Add this node to the config lookup table
         inner.lookup.set_node(name, bind.into());
 
@@ -237,6 +249,10 @@ impl OckamConfig {
                 verbose,
                 state_dir: Some(state_dir),
                 pid: None,
+                metrics_address,
+                api_socket,
+                tcp_api,
+                service: None,
             },
         );
         Ok(())
@@ -269,6 +285,17 @@ impl OckamConfig {
         Ok(())
     }
 
+    pub fn set_node_service(&self, name: &str, service: Option<NodeService>) -> Result<()> {
+        let mut inner = self.inner.writelock_inner();
+
+        if !inner.nodes.contains_key(name) {
+            return Err(ConfigError::NotFound(name.to_string()).into());
+        }
+
+        inner.nodes.get_mut(name).unwrap().service = service;
+        Ok(())
+    }
+
     pub fn set_node_alias(&self, alias: String, addr: InternetAddress) {
         let mut inner = self.inner.writelock_inner();
         inner.lookup.set_node(&alias, addr);
@@ -1,3 +1,4 @@
+use crate::error::exit_with;
 use core::time::Duration;
 use std::{
     env,
@@ -10,15 +11,19 @@ use crossbeam_channel::{bounded, Sender};
 use minicbor::{data::Type, Decode, Decoder, Encode};
 use tracing::{debug, error, trace};
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::{filter::LevelFilter, fmt, EnvFilter};
+use tracing_subscriber::{filter::LevelFilter, fmt, reload, EnvFilter};
 
 pub use addon::AddonCommand;
 pub use config::*;
+#[cfg(unix)]
+use ockam::UdsTransport;
 use ockam::{route, Address, Context, NodeBuilder, Route, TcpTransport, TCP};
-use ockam_api::nodes::NODEMANAGER_ADDR;
+use ockam_api::nodes::{NodeLogs, NODEMANAGER_ADDR};
 use ockam_core::api::{RequestBuilder, Response, Status};
 use ockam_multiaddr::MultiAddr;
+use ockam_node::api::{request_stream, ResponseStream};
 
+use crate::error::ApiError;
 use crate::node::util::start_embedded_node;
 use crate::util::output::Output;
 use crate::{CommandGlobalOpts, OutputFormat};
@@ -26,6 +31,7 @@ use crate::{CommandGlobalOpts, OutputFormat};
 pub mod api;
 pub mod exitcode;
 pub mod startup;
+pub mod systemd;
 
 mod addon;
 mod config;
@@ -88,6 +94,7 @@ impl<'a> RpcBuilder<'a> {
             node_name: self.node_name,
             to: self.to,
             mode: self.mode,
+            api_conn: None,
         }
     }
 }
@@ -99,6 +106,8 @@ pub struct Rpc<'a> {
     node_name: String,
     to: Route,
     mode: RpcMode<'a>,
+    /// Connection to the node API socket, shared by all requests
+    api_conn: Option<Address>,
 }
 
 impl<'a> Rpc<'a> {
@@ -112,6 +121,7 @@ impl<'a> Rpc<'a> {
             node_name,
             to: NODEMANAGER_ADDR.into(),
             mode: RpcMode::Embedded,
+            api_conn: None,
         })
     }
 
@@ -129,6 +139,7 @@ impl<'a> Rpc<'a> {
             node_name: node_name.to_string(),
             to: NODEMANAGER_ADDR.into(),
             mode: RpcMode::Background { cfg, tcp: None },
+            api_conn: None,
         })
     }
 
@@ -170,10 +181,37 @@ impl<'a> Rpc<'a> {
         Ok(())
     }
 
+    /// Send a request answered with a stream of responses.
+    pub async fn request_stream<T>(&mut self, req: RequestBuilder<'_, T>) -> Result<ResponseStream>
+    where
+        T: Encode<()>,
+    {
+        let route = self.route_impl(self.ctx).await?;
+        let label = req.header().path().to_string();
+        let stream = request_stream(self.ctx, &label, None, route, req)
+            .await
+            .context("Failed to start response stream from node")?;
+        Ok(stream)
+    }
+
     async fn route_impl(&mut self, ctx: &Context) -> Result<Route> {
         let route = match self.mode {
             RpcMode::Embedded => self.to.clone(),
             RpcMode::Background { ref cfg, ref tcp } => {
+                if self.api_conn.is_none() {
+                    self.api_conn = match connect_api_socket(ctx, cfg).await {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            debug!(%e, "Falling back to the TCP listener");
+                            None
+                        }
+                    };
+                }
+                if let Some(conn) = &self.api_conn {
+                    let route = self.to.modify().prepend(conn.clone()).into();
+                    debug!(%route, "Sending request");
+                    return Ok(route);
+                }
                 let addr = Address::from((TCP, format!("localhost:{}", cfg.port)));
                 let addr_str = addr.address();
                 match tcp {
@@ -231,8 +269,9 @@ impl<'a> Rpc<'a> {
         if hdr.status() == Some(Status::Ok) {
             Ok(dec)
         } else {
-            let msg = self.parse_err_msg(hdr, dec);
-            Err(anyhow!(msg))
+            let status = hdr.status();
+            let message = self.parse_err_msg(hdr, dec);
+            Err(ApiError { status, message }.into())
         }
     }
 
@@ -281,12 +320,12 @@ impl<'a> Rpc<'a> {
     where
         T: Output + serde::Serialize,
     {
-        let o = match self.opts.global_args.output_format {
-            OutputFormat::Plain => b.output().context("Failed to serialize response body")?,
-            OutputFormat::Json => {
-                serde_json::to_string_pretty(&b).context("Failed to serialize response body")?
-            }
-        };
+        let o = self
+            .opts
+            .global_args
+            .output_format
+            .render(&b)
+            .context("Failed to serialize response body")?;
         println!("{}", o);
         Ok(b)
     }
@@ -304,6 +343,30 @@ pub async fn stop_node(mut ctx: Context) -> Result<()> {
     Ok(())
 }
 
+/// Connect to the node manager API socket of a background node
+///
+/// Returns `None` if the node doesn't serve its API on a Unix domain
+/// socket, in which case its TCP listener is used instead.
+#[cfg(unix)]
+async fn connect_api_socket(ctx: &Context, cfg: &NodeConfig) -> Result<Option<Address>> {
+    match &cfg.api_socket {
+        Some(path) if path.exists() => {
+            let uds = UdsTransport::create(ctx).await?;
+            let conn = uds
+                .connect(path)
+                .await
+                .with_context(|| format!("Failed to connect to {}", path.display()))?;
+            Ok(Some(conn))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(not(unix))]
+async fn connect_api_socket(_ctx: &Context, _cfg: &NodeConfig) -> Result<Option<Address>> {
+    Ok(None)
+}
+
 /// Connect to a remote node (on localhost for now)
 ///
 /// This function requires the "remote" port, some command payload,
@@ -311,32 +374,51 @@ pub async fn stop_node(mut ctx: Context) -> Result<()> {
 /// while also configuring a TcpTransport and connecting to another
 /// node.
 ///
-pub fn connect_to<A, F, Fut>(port: u16, a: A, lambda: F)
+pub fn connect_to<A, F, Fut>(port: u16, a: (CommandGlobalOpts, A), lambda: F)
 where
     A: Send + Sync + 'static,
-    F: FnOnce(Context, A, Route) -> Fut + Send + Sync + 'static,
+    F: FnOnce(Context, (CommandGlobalOpts, A), Route) -> Fut + Send + Sync + 'static,
     Fut: core::future::Future<Output = Result<()>> + Send + 'static,
 {
     let res = embedded_node(
-        move |ctx, a| async move {
+        move |ctx, a: (CommandGlobalOpts, A)| async move {
+            let opts = a.0.clone();
             let tcp = match TcpTransport::create(&ctx).await {
                 Ok(tcp) => tcp,
                 Err(e) => {
-                    eprintln!("Failed to create TcpTransport. {e}");
                     error!(%e);
-                    std::process::exit(exitcode::CANTCREAT);
+                    exit_with(
+                        &opts,
+                        exitcode::CANTCREAT,
+                        anyhow::Error::from(e).context("Failed to create TcpTransport"),
+                    );
+                }
+            };
+            let api_conn = match OckamConfig::load().get_node_by_port(port) {
+                Some(cfg) => connect_api_socket(&ctx, &cfg).await,
+                None => Ok(None),
+            };
+            let route = match api_conn {
+                Ok(Some(conn)) => route![conn],
+                _ => {
+                    if let Err(e) = tcp.connect(format!("localhost:{}", port)).await {
+                        error!(%e);
+                        exit_with(
+                            &opts,
+                            exitcode::IOERR,
+                            anyhow::Error::from(e).context("Failed to connect to node"),
+                        );
+                    }
+                    route![(TCP, format!("localhost:{}", port))]
                 }
             };
-            if let Err(e) = tcp.connect(format!("localhost:{}", port)).await {
-                eprintln!("Failed to connect to node. {e}");
-                error!(%e);
-                std::process::exit(exitcode::IOERR);
-            }
-            let route = route![(TCP, format!("localhost:{}", port))];
             if let Err(e) = lambda(ctx, a, route).await {
-                eprintln!("Encountered an error in command handler code. {e}");
                 error!(%e);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    &opts,
+                    exitcode::IOERR,
+                    e.context("Encountered an error in command handler code"),
+                );
             }
             Ok(())
         },
@@ -347,18 +429,20 @@ where
     }
 }
 
-pub fn node_rpc<A, F, Fut>(f: F, a: A)
+pub fn node_rpc<A, F, Fut>(f: F, a: (CommandGlobalOpts, A))
 where
     A: Send + Sync + 'static,
-    F: FnOnce(Context, A) -> Fut + Send + Sync + 'static,
+    F: FnOnce(Context, (CommandGlobalOpts, A)) -> Fut + Send + Sync + 'static,
     Fut: core::future::Future<Output = crate::Result<()>> + Send + 'static,
 {
+    let opts = a.0.clone();
     let res = embedded_node(
-        |ctx, a| async {
+        |ctx, a: (CommandGlobalOpts, A)| async {
+            let format = a.0.global_args.output_format;
             let res = f(ctx, a).await;
             if let Err(e) = res {
                 error!(%e);
-                eprintln!("{e:?}");
+                e.print(format);
                 std::process::exit(e.code());
             }
             Ok(())
@@ -366,8 +450,7 @@ where
         a,
     );
     if let Err(e) = res {
-        eprintln!("Ockam node failed: {e}");
-        std::process::exit(exitcode::SOFTWARE);
+        exit_with(&opts, exitcode::SOFTWARE, anyhow!("Ockam node failed: {e}"));
     }
 }
 
@@ -389,7 +472,7 @@ where
         match r {
             Err(e) => {
                 error!(%e);
-                eprintln!("{e:?}");
+                e.print(OutputFormat::Plain);
                 std::process::exit(e.code());
             }
             Ok(v) => v,
@@ -406,7 +489,9 @@ pub fn find_available_port() -> Result<u16> {
     Ok(address.port())
 }
 
-pub fn setup_logging(verbose: u8, no_color: bool) {
+/// Set up logging, returning the [`NodeLogs`] which records the log
+/// lines and can change the log filter
+pub fn setup_logging(verbose: u8, no_color: bool) -> NodeLogs {
     let ockam_crates = [
         "ockam",
         "ockam_node",
@@ -425,7 +510,7 @@ pub fn setup_logging(verbose: u8, no_color: bool) {
     let filter = match verbose {
         0 => match env::var("OCKAM_LOG") {
             Ok(s) if !s.is_empty() => builder.with_env_var("OCKAM_LOG").from_env_lossy(),
-            _ => return,
+            _ => return NodeLogs::new(),
         },
         1 => builder
             .with_default_directive(LevelFilter::INFO.into())
@@ -437,15 +522,48 @@ pub fn setup_logging(verbose: u8, no_color: bool) {
             .with_default_directive(LevelFilter::TRACE.into())
             .parse_lossy(ockam_crates.map(|c| format!("{c}=trace")).join(",")),
     };
+    let (filter, handle) = reload::Layer::new(filter);
+    let logs = NodeLogs::new().with_filter(move |directives| {
+        let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+        handle.reload(filter).map_err(|e| e.to_string())
+    });
     let fmt = fmt::Layer::default().with_ansi(!no_color);
+    let records = fmt::Layer::default()
+        .with_ansi(false)
+        .with_writer(LogWriter(logs.clone()));
     let result = tracing_subscriber::registry()
         .with(filter)
         .with(tracing_error::ErrorLayer::default())
         .with(fmt)
+        .with(records)
         .try_init();
     if result.is_err() {
         eprintln!("Failed to initialise tracing logging.");
     }
+    logs
+}
+
+/// Writes each formatted log event to [`NodeLogs`]
+#[derive(Clone)]
+struct LogWriter(NodeLogs);
+
+impl std::io::Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.push(String::from_utf8_lossy(buf).trim_end());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> fmt::MakeWriter<'a> for LogWriter {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
 }
 
 #[allow(unused)]
@@ -453,7 +571,7 @@ pub fn print_path(p: &Path) -> String {
     p.to_str().unwrap_or("<unprintable>").to_string()
 }
 
-pub fn get_final_element(input_path: &str) -> &str {
+pub fn get_final_element<'a>(opts: &CommandGlobalOpts, input_path: &'a str) -> &'a str {
     //  Get Node name from Node Path
     //  if Input path has "/", we split the path and return the final element
     //  if the final element is empty string, we return None
@@ -461,8 +579,11 @@ pub fn get_final_element(input_path: &str) -> &str {
         let split_path: Vec<&str> = input_path.split('/').collect();
         match split_path.last() {
             Some(last_value) if last_value.is_empty() => {
-                eprintln!("Invalid Format: {}", input_path);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    opts,
+                    exitcode::IOERR,
+                    anyhow!("Invalid Format: {}", input_path),
+                );
             }
             Some(last_value) => last_value,
             None => input_path,
@@ -472,6 +593,17 @@ pub fn get_final_element(input_path: &str) -> &str {
     };
 }
 
+/// Port of the node with the given name, exiting if there is none
+pub fn node_port(opts: &CommandGlobalOpts, name: &str) -> u16 {
+    opts.config.get_node_port(name).unwrap_or_else(|| {
+        exit_with(
+            opts,
+            exitcode::IOERR,
+            anyhow!("No such node available. Run `ockam node list` to list available nodes"),
+        )
+    })
+}
+
 pub fn comma_separated<T: AsRef<str>>(data: &[T]) -> String {
     use itertools::Itertools;
 
@@ -485,32 +617,39 @@ pub fn bind_to_port_check(address: &SocketAddr) -> bool {
     std::net::TcpListener::bind((ip, port)).is_ok()
 }
 
-pub fn verify_pids(cfg: &OckamConfig, nodes: Vec<String>) {
+pub fn verify_pids(opts: &CommandGlobalOpts, nodes: Vec<String>) {
+    let cfg = &opts.config;
     for node_name in nodes {
         let node_cfg = cfg.get_node(&node_name).unwrap();
 
         let (tx, rx) = bounded(1);
 
-        connect_to(node_cfg.port, tx, query_pid);
+        connect_to(node_cfg.port, (opts.clone(), tx), query_pid);
         let verified_pid = rx.recv().unwrap();
 
         if node_cfg.pid != verified_pid {
             if let Err(e) = cfg.set_node_pid(&node_name, verified_pid) {
-                eprintln!("Failed to update pid for node {}: {}", node_name, e);
-                std::process::exit(exitcode::IOERR);
+                exit_with(
+                    opts,
+                    exitcode::IOERR,
+                    anyhow!("Failed to update pid for node {}: {}", node_name, e),
+                );
             }
         }
     }
 
     if cfg.persist_config_updates().is_err() {
-        eprintln!("Failed to update PID information in config!");
-        std::process::exit(exitcode::IOERR);
+        exit_with(
+            opts,
+            exitcode::IOERR,
+            anyhow!("Failed to update PID information in config!"),
+        );
     }
 }
 
 pub async fn query_pid(
     mut ctx: Context,
-    tx: Sender<Option<i32>>,
+    (_opts, tx): (CommandGlobalOpts, Sender<Option<i32>>),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
     ctx.send(
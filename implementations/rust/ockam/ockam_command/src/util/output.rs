@@ -2,17 +2,72 @@ use anyhow::Context;
 use cli_table::{Cell, Style, Table};
 use core::fmt::Write;
 use ockam::identity::credential::Credential;
+use ockam_api::cloud::invitation::{Invitation, Scope, State};
 use ockam_api::cloud::project::{Enroller, Project};
 
 use crate::project::ProjectInfo;
 use crate::util::comma_separated;
 use colorful::Colorful;
 use ockam_api::cloud::space::Space;
+use ockam_api::nodes::models::forwarder::ForwarderInfo;
 use ockam_api::nodes::models::secure_channel::{
     CreateSecureChannelResponse, ShowSecureChannelResponse,
 };
+use ockam_api::nodes::models::services::ServiceStatus;
+use ockam_api::nodes::models::transport::TransportStatus;
 use ockam_api::route_to_multiaddr;
 use ockam_core::route;
+use serde::Serialize;
+
+use crate::{CommandGlobalOpts, OutputFormat};
+
+/// Print a value to stdout in the output format of this invocation
+pub fn print_output<T>(opts: &CommandGlobalOpts, value: &T) -> anyhow::Result<()>
+where
+    T: Output + Serialize + ?Sized,
+{
+    println!("{}", opts.global_args.output_format.render(value)?);
+    Ok(())
+}
+
+/// Print a value to stdout if the output format is JSON or YAML
+///
+/// Returns `false` for plain output, which is left to the caller.
+pub fn print_structured<T>(opts: &CommandGlobalOpts, value: &T) -> anyhow::Result<bool>
+where
+    T: Serialize + ?Sized,
+{
+    let s = match opts.global_args.output_format {
+        OutputFormat::Plain => return Ok(false),
+        OutputFormat::Json => serialize_json(value)?,
+        OutputFormat::Yaml => serialize_yaml(value)?,
+    };
+    println!("{}", s);
+    Ok(true)
+}
+
+fn serialize_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<String> {
+    serde_json::to_string_pretty(value).context("Failed to serialize output as JSON")
+}
+
+fn serialize_yaml<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<String> {
+    let yaml = serde_yaml::to_string(value).context("Failed to serialize output as YAML")?;
+    Ok(yaml.trim_end().to_string())
+}
+
+impl OutputFormat {
+    /// Render a value in this format
+    pub fn render<T>(&self, value: &T) -> anyhow::Result<String>
+    where
+        T: Output + Serialize + ?Sized,
+    {
+        match self {
+            OutputFormat::Plain => value.output(),
+            OutputFormat::Json => serialize_json(value),
+            OutputFormat::Yaml => serialize_yaml(value),
+        }
+    }
+}
 
 /// Trait to control how a given type will be printed as a CLI output.
 ///
@@ -42,7 +97,7 @@ pub trait Output {
     fn output(&self) -> anyhow::Result<String>;
 }
 
-impl<O: Output> Output for &O {
+impl<O: Output + ?Sized> Output for &O {
     fn output(&self) -> anyhow::Result<String> {
         (*self).output()
     }
@@ -84,6 +139,82 @@ impl Output for Vec<Space<'_>> {
     }
 }
 
+impl Output for Invitation<'_> {
+    fn output(&self) -> anyhow::Result<String> {
+        let mut w = String::new();
+        write!(w, "Invitation")?;
+        write!(w, "\n  Id: {}", self.id)?;
+        write!(w, "\n  Inviter: {}", self.inviter)?;
+        write!(w, "\n  Invitee: {}", self.invitee)?;
+        write!(w, "\n  Scope: {}", invitation_scope(&self.scope))?;
+        write!(w, "\n  State: {}", invitation_state(&self.state))?;
+        write!(w, "\n  Space Id: {}", self.space_id)?;
+        if let Some(project_id) = &self.project_id {
+            write!(w, "\n  Project Id: {}", project_id)?;
+        }
+        Ok(w)
+    }
+}
+
+impl Output for Vec<Invitation<'_>> {
+    fn output(&self) -> anyhow::Result<String> {
+        if self.is_empty() {
+            return Ok("No invitations found".to_string());
+        }
+        let mut rows = vec![];
+        for Invitation {
+            id,
+            inviter,
+            invitee,
+            scope,
+            state,
+            space_id,
+            project_id,
+            ..
+        } in self
+        {
+            rows.push([
+                id.cell(),
+                inviter.cell(),
+                invitee.cell(),
+                invitation_scope(scope).cell(),
+                invitation_state(state).cell(),
+                space_id.cell(),
+                project_id.as_deref().unwrap_or("-").cell(),
+            ]);
+        }
+        let table = rows
+            .table()
+            .title([
+                "Id".cell().bold(true),
+                "Inviter".cell().bold(true),
+                "Invitee".cell().bold(true),
+                "Scope".cell().bold(true),
+                "State".cell().bold(true),
+                "Space Id".cell().bold(true),
+                "Project Id".cell().bold(true),
+            ])
+            .display()?
+            .to_string();
+        Ok(table)
+    }
+}
+
+fn invitation_scope(scope: &Scope) -> &'static str {
+    match scope {
+        Scope::SpaceScope => "space",
+        Scope::ProjectScope => "project",
+    }
+}
+
+fn invitation_state(state: &State) -> &'static str {
+    match state {
+        State::Pending => "pending",
+        State::Accepted => "accepted",
+        State::Rejected => "rejected",
+    }
+}
+
 impl Output for Project<'_> {
     fn output(&self) -> anyhow::Result<String> {
         let mut w = String::new();
@@ -248,3 +379,77 @@ impl Output for Credential<'_> {
         Ok(self.to_string())
     }
 }
+
+impl Output for Vec<TransportStatus<'_>> {
+    fn output(&self) -> anyhow::Result<String> {
+        let mut rows = vec![];
+        for TransportStatus {
+            tt,
+            tm,
+            payload,
+            tid,
+            ..
+        } in self
+        {
+            rows.push([tid.cell(), tt.cell(), tm.cell(), payload.cell()]);
+        }
+        let table = rows
+            .table()
+            .title([
+                "Transport ID".cell().bold(true),
+                "Transport Type".cell().bold(true),
+                "Mode".cell().bold(true),
+                "Address".cell().bold(true),
+            ])
+            .display()?
+            .to_string();
+        Ok(table)
+    }
+}
+
+impl Output for Vec<ForwarderInfo<'_>> {
+    fn output(&self) -> anyhow::Result<String> {
+        if self.is_empty() {
+            return Ok("No forwarders found".to_string());
+        }
+        let mut rows = vec![];
+        for f in self {
+            rows.push([
+                f.remote_address().cell(),
+                f.worker_address().cell(),
+                f.forwarding_route().cell(),
+            ]);
+        }
+        let table = rows
+            .table()
+            .title([
+                "Remote Address".cell().bold(true),
+                "Worker Address".cell().bold(true),
+                "Route".cell().bold(true),
+            ])
+            .display()?
+            .to_string();
+        Ok(table)
+    }
+}
+
+impl Output for Vec<ServiceStatus<'_>> {
+    fn output(&self) -> anyhow::Result<String> {
+        if self.is_empty() {
+            return Ok("No services found".to_string());
+        }
+        let mut rows = vec![];
+        for ServiceStatus {
+            addr, service_type, ..
+        } in self
+        {
+            rows.push([addr.cell(), service_type.cell()]);
+        }
+        let table = rows
+            .table()
+            .title(["Address".cell().bold(true), "Type".cell().bold(true)])
+            .display()?
+            .to_string();
+        Ok(table)
+    }
+}
@@ -13,6 +13,7 @@ use std::process::Stdio;
 use std::{
     env::current_exe,
     fs::OpenOptions,
+    net::SocketAddr,
     path::{Path, PathBuf},
     process::Command,
 };
@@ -44,8 +45,10 @@ pub fn spawn_node(
     enable_credential_checks: bool,
     name: &str,
     address: &str,
+    metrics_address: Option<SocketAddr>,
     project: Option<&Path>,
-) {
+    config: Option<&Path>,
+) -> anyhow::Result<()> {
     // On systems with non-obvious path setups (or during
     // development) re-executing the current binary is a more
     // deterministic way of starting a node.
@@ -65,6 +68,50 @@ pub fn spawn_node(
         .open(elog)
         .expect("failed to open stderr log path");
 
+    let args = node_args(
+        verbose,
+        skip_defaults,
+        no_shared_identity,
+        enable_credential_checks,
+        name,
+        address,
+        metrics_address,
+        project,
+        config,
+    );
+
+    let child = Command::new(ockam_exe)
+        .args(args)
+        .stdout(main_log_file)
+        .stderr(stderr_log_file)
+        .spawn()
+        .expect("could not spawn node");
+
+    // Update the pid in the config (should we remove this?)
+    cfg.set_node_pid(name, child.id() as i32)
+        .expect("should never panic");
+
+    // Save the config update
+    cfg.persist_config_updates()
+        .context("failed to update configuration")
+}
+
+/// The arguments to run a node in foreground mode
+///
+/// Used to spawn a node process, and by the systemd units of
+/// service-managed nodes.
+#[allow(clippy::too_many_arguments)]
+pub fn node_args(
+    verbose: u8,
+    skip_defaults: bool,
+    no_shared_identity: bool,
+    enable_credential_checks: bool,
+    name: &str,
+    address: &str,
+    metrics_address: Option<SocketAddr>,
+    project: Option<&Path>,
+    config: Option<&Path>,
+) -> Vec<String> {
     let mut args = vec![
         match verbose {
             0 => "-vv".to_string(),
@@ -87,6 +134,19 @@ pub fn spawn_node(
         args.push(p.to_string())
     }
 
+    if let Some(path) = config {
+        args.push("--config".to_string());
+        let p = path
+            .to_str()
+            .unwrap_or_else(|| panic!("unsupported path {path:?}"));
+        args.push(p.to_string())
+    }
+
+    if let Some(addr) = metrics_address {
+        args.push("--metrics-address".to_string());
+        args.push(addr.to_string());
+    }
+
     if skip_defaults {
         args.push("--skip-defaults".to_string());
     }
@@ -101,20 +161,5 @@ pub fn spawn_node(
 
     args.push(name.to_owned());
 
-    let child = Command::new(ockam_exe)
-        .args(args)
-        .stdout(main_log_file)
-        .stderr(stderr_log_file)
-        .spawn()
-        .expect("could not spawn node");
-
-    // Update the pid in the config (should we remove this?)
-    cfg.set_node_pid(name, child.id() as i32)
-        .expect("should never panic");
-
-    // Save the config update
-    if let Err(e) = cfg.persist_config_updates() {
-        eprintln!("failed to update configuration: {}", e);
-        std::process::exit(exitcode::IOERR);
-    }
+    args
 }
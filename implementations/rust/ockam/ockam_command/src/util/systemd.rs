@@ -0,0 +1,267 @@
+//! Run nodes as systemd services
+//!
+//! A service-managed node is started by systemd with the same
+//! arguments `spawn_node` uses, and restarted when it fails or the
+//! machine reboots.  `node start` and `node stop` then go through
+//! `systemctl` instead of signalling the process.
+
+use crate::util::startup;
+use crate::util::{NodeConfig, NodeService, OckamConfig};
+use anyhow::{anyhow, Context, Result};
+use slug::slugify;
+use std::env::{self, current_exe};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variables locating the CLI configuration, passed on
+/// to the node
+const CONFIG_ENV: &[&str] = &["OCKAM_PROJECT_PATH", "XDG_CONFIG_HOME", "XDG_DATA_HOME"];
+
+/// Name of the unit running the given node
+pub fn unit_name(node_name: &str) -> String {
+    format!("ockam-node-{}.service", slugify(node_name))
+}
+
+/// Directory of the unit files
+fn unit_dir(system: bool) -> Result<PathBuf> {
+    if system {
+        return Ok(PathBuf::from("/etc/systemd/system"));
+    }
+    let config_dir = dirs::config_dir().context("Failed to determine the user config directory")?;
+    Ok(config_dir.join("systemd").join("user"))
+}
+
+/// Write the unit of a node and load it
+///
+/// The unit file is removed again if systemd fails to load it.
+pub fn write_unit(cfg: &OckamConfig, node: &NodeConfig, system: bool) -> Result<NodeService> {
+    let service = NodeService {
+        unit: unit_name(&node.name),
+        system,
+    };
+    let (log, stderr_log) = cfg
+        .node_log_paths(&node.name)
+        .ok_or_else(|| anyhow!("Node {} is not local", node.name))?;
+    let exe = current_exe().context("Failed to determine the path of the ockam binary")?;
+    let args = startup::node_args(
+        node.verbose,
+        true, // the node already exists
+        false,
+        false,
+        &node.name,
+        &node.addr.to_string(),
+        node.metrics_address,
+        None,
+        None,
+    );
+    let mut env: Vec<(String, String)> = CONFIG_ENV
+        .iter()
+        .filter_map(|k| env::var(k).ok().map(|v| (k.to_string(), v)))
+        .collect();
+    let user = if system {
+        // System units don't inherit the home directory the
+        // configuration is resolved from
+        if let Some(home) = dirs::home_dir() {
+            env.push(("HOME".to_string(), home.display().to_string()));
+        }
+        Some(nix::unistd::getuid().to_string())
+    } else {
+        None
+    };
+
+    let unit = render_unit(
+        &node.name,
+        &exe,
+        &args,
+        &env,
+        user.as_deref(),
+        (&log, &stderr_log),
+    );
+    let dir = unit_dir(system)?;
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(&service.unit);
+    fs::write(&path, unit).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    if let Err(e) = systemctl(&service, &["daemon-reload"]) {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    Ok(service)
+}
+
+/// Enable and start the unit of a node
+pub fn enable(service: &NodeService) -> Result<()> {
+    systemctl(service, &["enable", "--now", &service.unit])
+}
+
+/// Stop, disable and remove the unit of a node
+pub fn uninstall(service: &NodeService) -> Result<()> {
+    systemctl(service, &["disable", "--now", &service.unit])?;
+    let path = unit_dir(service.system)?.join(&service.unit);
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    systemctl(service, &["daemon-reload"])
+}
+
+/// Start the unit of a node
+pub fn start(service: &NodeService) -> Result<()> {
+    systemctl(service, &["start", &service.unit])
+}
+
+/// Stop the unit of a node
+///
+/// With `sigkill` the node is killed first, as `node stop --force`
+/// does for nodes which aren't service-managed.
+pub fn stop(service: &NodeService, sigkill: bool) -> Result<()> {
+    if sigkill {
+        systemctl(service, &["kill", "--signal=SIGKILL", &service.unit])?;
+    }
+    systemctl(service, &["stop", &service.unit])
+}
+
+/// Whether the unit of a node is running
+pub fn is_active(service: &NodeService) -> bool {
+    systemctl_cmd(service)
+        .args(["is-active", "--quiet", &service.unit])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+fn systemctl_cmd(service: &NodeService) -> Command {
+    let mut cmd = Command::new("systemctl");
+    if !service.system {
+        cmd.arg("--user");
+    }
+    cmd
+}
+
+fn systemctl(service: &NodeService, args: &[&str]) -> Result<()> {
+    let out = systemctl_cmd(service)
+        .args(args)
+        .output()
+        .context("Failed to run systemctl")?;
+    if !out.status.success() {
+        return Err(anyhow!(
+            "`systemctl {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// The unit file running a node in foreground mode
+fn render_unit(
+    node_name: &str,
+    exe: &Path,
+    args: &[String],
+    env: &[(String, String)],
+    user: Option<&str>,
+    (log, stderr_log): (&Path, &Path),
+) -> String {
+    let mut exec = quote(&exe.display().to_string());
+    for a in args {
+        exec.push(' ');
+        exec.push_str(&quote(a));
+    }
+
+    let mut unit = format!(
+        "[Unit]\n\
+         Description=Ockam node {node_name}\n\
+         After=network-online.target\n\
+         Wants=network-online.target\n\
+         \n\
+         [Service]\n\
+         Type=simple\n\
+         ExecStart={exec}\n\
+         Restart=on-failure\n\
+         RestartSec=5\n"
+    );
+    if let Some(user) = user {
+        unit.push_str(&format!("User={user}\n"));
+    }
+    for (k, v) in env {
+        unit.push_str(&format!("Environment={}\n", quote(&format!("{k}={v}"))));
+    }
+    unit.push_str(&format!(
+        "StandardOutput=append:{}\n\
+         StandardError=append:{}\n\
+         \n\
+         [Install]\n\
+         WantedBy={}\n",
+        escape(&log.display().to_string()),
+        escape(&stderr_log.display().to_string()),
+        if user.is_some() {
+            "multi-user.target"
+        } else {
+            "default.target"
+        }
+    ));
+    unit
+}
+
+/// Escape the specifier character of unit files
+fn escape(s: &str) -> String {
+    s.replace('%', "%%")
+}
+
+/// Quote a word of a unit file command line, if needed
+fn quote(s: &str) -> String {
+    let s = escape(s);
+    if !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        return s;
+    }
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_runs_node_in_foreground() {
+        let args = startup::node_args(
+            0,
+            true,
+            false,
+            false,
+            "n1",
+            "127.0.0.1:4000",
+            None,
+            None,
+            None,
+        );
+        let env = vec![(
+            "OCKAM_PROJECT_PATH".to_string(),
+            "/srv/my ockam".to_string(),
+        )];
+        let unit = render_unit(
+            "n1",
+            Path::new("/usr/bin/ockam"),
+            &args,
+            &env,
+            Some("1000"),
+            (Path::new("/n1/n1.log"), Path::new("/n1/n1.log.stderr")),
+        );
+
+        assert!(unit.contains(
+            "ExecStart=/usr/bin/ockam -vv --no-color node create --tcp-listener-address \
+             127.0.0.1:4000 --foreground --child-process --skip-defaults n1\n"
+        ));
+        assert!(unit.contains("Restart=on-failure\n"));
+        assert!(unit.contains("User=1000\n"));
+        assert!(unit.contains("Environment=\"OCKAM_PROJECT_PATH=/srv/my ockam\"\n"));
+        assert!(unit.contains("StandardOutput=append:/n1/n1.log\n"));
+        assert!(unit.contains("WantedBy=multi-user.target\n"));
+    }
+
+    #[test]
+    fn words_are_quoted_when_needed() {
+        assert_eq!(quote("plain"), "plain");
+        assert_eq!(quote("50%"), "50%%");
+        assert_eq!(quote("a b"), "\"a b\"");
+        assert_eq!(quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote(""), "\"\"");
+    }
+}
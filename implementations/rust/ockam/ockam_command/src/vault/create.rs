@@ -1,10 +1,14 @@
+use crate::error::exit_with;
 use crate::node::NodeOpts;
-use crate::util::{api, connect_to, exitcode};
+use crate::util::output::print_structured;
+use crate::util::{api, connect_to, exitcode, node_port};
 use crate::CommandGlobalOpts;
+use anyhow::anyhow;
 use clap::Args;
 use ockam::Context;
 use ockam_core::api::Status;
 use ockam_core::Route;
+use serde_json::json;
 
 /// Create vaults
 #[derive(Clone, Debug, Args)]
@@ -19,10 +23,9 @@ pub struct CreateCommand {
 
 impl CreateCommand {
     pub fn run(self, options: CommandGlobalOpts) -> anyhow::Result<()> {
-        let cfg = options.config;
-        let port = cfg.get_node_port(&self.node_opts.api_node);
+        let port = node_port(&options, &self.node_opts.api_node);
 
-        connect_to(port, self, create_vault);
+        connect_to(port, (options, self), create_vault);
 
         Ok(())
     }
@@ -30,13 +33,14 @@ impl CreateCommand {
 
 pub async fn create_vault(
     ctx: Context,
-    cmd: CreateCommand,
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
     mut base_route: Route,
 ) -> anyhow::Result<()> {
+    let path = cmd.path;
     let resp: Vec<u8> = ctx
         .send_and_receive(
             base_route.modify().append("_internal.nodemanager"),
-            api::create_vault(cmd.path)?,
+            api::create_vault(path.clone())?,
         )
         .await?;
 
@@ -44,11 +48,16 @@ pub async fn create_vault(
 
     match response.status() {
         Some(Status::Ok) => {
-            println!("Vault created!")
+            if !print_structured(&opts, &json!({ "path": path }))? {
+                println!("Vault created!")
+            }
         }
         _ => {
-            eprintln!("An error occurred while creating Vault",);
-            std::process::exit(exitcode::CANTCREAT);
+            exit_with(
+                &opts,
+                exitcode::CANTCREAT,
+                anyhow!("An error occurred while creating Vault",),
+            );
         }
     }
 
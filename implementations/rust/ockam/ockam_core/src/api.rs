@@ -2,6 +2,7 @@
 
 use crate::compat::borrow::Cow;
 use crate::compat::rand;
+use crate::compat::string::ToString;
 use crate::compat::vec::Vec;
 use crate::errcode::{Kind, Origin};
 use crate::Result;
@@ -66,7 +67,28 @@ pub struct Response {
     /// how to handle unknown codes.
     #[n(3)] status: Option<Status>,
     /// Indicator if a response body is expected after this header.
-    #[n(4)] has_body: bool
+    #[n(4)] has_body: bool,
+    /// The part of a streamed response this header belongs to.
+    ///
+    /// Absent for responses which are not streamed.
+    #[n(5)] stream: Option<Stream>
+}
+
+/// Parts of a streamed response.
+///
+/// A request answered with a stream of responses receives a `Begin`
+/// header first, then any number of `Item` headers, each followed by one
+/// body, and finally an `End` header. A stream which fails ends with an
+/// error response instead. The client cancels a stream by sending a
+/// request with the identifier of the streamed request to the return
+/// route of its responses.
+#[derive(Debug, Copy, Clone, Encode, Decode, PartialEq, Eq)]
+#[rustfmt::skip]
+#[cbor(index_only)]
+pub enum Stream {
+    #[n(0)] Begin,
+    #[n(1)] Item,
+    #[n(2)] End
 }
 
 /// Create an error response because the request path was unknown.
@@ -115,6 +137,18 @@ pub fn internal_error<'a>(r: &'a Request, msg: &'a str) -> ResponseBuilder<Error
     Response::internal_error(r.id()).body(e)
 }
 
+/// Create an error response for an error returned by a request handler.
+///
+/// The response status is derived from the error kind.
+pub fn error_response<'a>(r: &'a Request, e: &crate::Error) -> ResponseBuilder<Error<'a>> {
+    let mut err = Error::new(r.path()).with_message(e.to_string());
+    if let Some(m) = r.method() {
+        err = err.with_method(m)
+    }
+    let status = Status::from(e.code().kind);
+    Response::builder(r.id(), status).body(err)
+}
+
 /// A request/response identifier.
 #[derive(Debug, Default, Copy, Clone, Encode, Decode, PartialEq, Eq, PartialOrd, Ord)]
 #[cbor(transparent)]
@@ -177,6 +211,33 @@ impl Display for Status {
     }
 }
 
+impl From<Kind> for Status {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Invalid | Kind::Serialization => Status::BadRequest,
+            Kind::Misuse => Status::Forbidden,
+            Kind::NotFound => Status::NotFound,
+            Kind::AlreadyExists | Kind::Conflict => Status::Conflict,
+            Kind::Unsupported => Status::NotImplemented,
+            _ => Status::InternalServerError,
+        }
+    }
+}
+
+impl From<Status> for Kind {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::BadRequest => Kind::Invalid,
+            Status::Unauthorized | Status::Forbidden => Kind::Misuse,
+            Status::NotFound => Kind::NotFound,
+            Status::Conflict => Kind::Conflict,
+            Status::MethodNotAllowed | Status::NotImplemented => Kind::Unsupported,
+            Status::InternalServerError => Kind::Internal,
+            Status::Ok => Kind::Protocol,
+        }
+    }
+}
+
 impl Id {
     pub fn fresh() -> Self {
         // Ensure random Ids are not equal to 0 (the default Id):
@@ -265,6 +326,7 @@ impl Response {
             re,
             status: Some(status),
             has_body,
+            stream: None,
         }
     }
 
@@ -318,6 +380,10 @@ impl Response {
     pub fn has_body(&self) -> bool {
         self.has_body
     }
+
+    pub fn stream(&self) -> Option<Stream> {
+        self.stream
+    }
 }
 
 /// An error type used in response bodies.
@@ -477,6 +543,11 @@ impl<T> ResponseBuilder<T> {
         self
     }
 
+    pub fn stream(mut self, s: Stream) -> Self {
+        self.header.stream = Some(s);
+        self
+    }
+
     pub fn header(&self) -> &Response {
         &self.header
     }
@@ -590,6 +661,23 @@ pub fn decode_option<'a, 'b, T: Decode<'b, ()>>(
     }
 }
 
+/// Decode response and its body.
+pub fn decode_response<'a, 'b, T: Decode<'b, ()>>(
+    label: &'a str,
+    struct_name: impl Into<Option<&'a str>>,
+    buf: &'b [u8],
+) -> Result<T> {
+    let mut d = Decoder::new(buf);
+    let res = response(label, &mut d)?;
+    match res.status() {
+        Some(Status::Ok) => {
+            assert_response_match(struct_name, buf);
+            Ok(d.decode()?)
+        }
+        _ => Err(error(label, &res, &mut d)),
+    }
+}
+
 /// Decode and log response header.
 pub(crate) fn response(label: &str, dec: &mut Decoder<'_>) -> Result<Response> {
     let res: Response = dec.decode()?;
@@ -605,6 +693,8 @@ pub(crate) fn response(label: &str, dec: &mut Decoder<'_>) -> Result<Response> {
 }
 
 /// Decode, log and map response error to ockam_core error.
+///
+/// The error kind is derived from the response status.
 pub(crate) fn error(label: &str, res: &Response, dec: &mut Decoder<'_>) -> crate::Error {
     if res.has_body() {
         let err = match dec.decode::<Error>() {
@@ -620,7 +710,7 @@ pub(crate) fn error(label: &str, res: &Response, dec: &mut Decoder<'_>) -> crate
             "<- {label}"
         }
         let msg = err.message().unwrap_or(label);
-        crate::Error::new(Origin::Application, Kind::Protocol, msg)
+        crate::Error::new(Origin::Application, error_kind(res), msg)
     } else {
         warn! {
             target:  "ockam_api",
@@ -629,10 +719,14 @@ pub(crate) fn error(label: &str, res: &Response, dec: &mut Decoder<'_>) -> crate
             status = ?res.status(),
             "<- {label}"
         }
-        crate::Error::new(Origin::Application, Kind::Protocol, label)
+        crate::Error::new(Origin::Application, error_kind(res), label)
     }
 }
 
+fn error_kind(res: &Response) -> Kind {
+    res.status().map(Kind::from).unwrap_or(Kind::Protocol)
+}
+
 /// Newtype around a byte-slice that is assumed to be CBOR-encoded.
 #[derive(Debug, Copy, Clone)]
 pub struct Cbor<'a>(pub &'a [u8]);
@@ -209,7 +209,7 @@ pub mod string {
 pub mod sync {
     use core::convert::Infallible;
 
-    pub use alloc::sync::Arc;
+    pub use alloc::sync::{Arc, Weak};
 
     /// Wrap `spin::RwLock` as it does not return LockResult<Guard> like `std::sync::Mutex`.
     pub struct RwLock<T>(spin::RwLock<T>);
@@ -273,7 +273,7 @@ pub mod sync {
 /// Provides `std::sync` for `std` targets.
 #[cfg(feature = "std")]
 pub mod sync {
-    pub use std::sync::Arc;
+    pub use std::sync::{Arc, Weak};
     pub use std::sync::{Mutex, RwLock};
 }
 
@@ -66,6 +66,17 @@ impl<T> Sender<T> {
         .await
     }
 
+    /// Attempt to send a value without waiting for free capacity
+    pub fn try_send(&self, value: T) -> Result<(), error::TrySendError<T>> {
+        match self.0.queue.enqueue(value) {
+            Ok(()) => {
+                self.0.wake_receiver.wake();
+                Ok(())
+            }
+            Err(value) => Err(error::TrySendError::Full(value)),
+        }
+    }
+
     pub async fn closed(&self) {
         unimplemented!();
     }
@@ -146,6 +157,19 @@ impl<T> Receiver<T> {
     pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
         ReceiveFuture { inner: &self.0 }.poll_unpin(cx)
     }
+
+    pub fn try_recv(&mut self) -> Result<T, error::TryRecvError> {
+        match self.0.queue.dequeue() {
+            Some(value) => {
+                self.0.wake_sender.wake();
+                Ok(value)
+            }
+            None if self.0.is_sender_closed.load(Ordering::Acquire) => {
+                Err(error::TryRecvError::Disconnected)
+            }
+            None => Err(error::TryRecvError::Empty),
+        }
+    }
 }
 
 impl<T> core::fmt::Debug for Receiver<T> {
@@ -191,4 +215,29 @@ pub mod error {
             write!(fmt, "SendError -> channel closed")
         }
     }
+
+    #[derive(Debug)]
+    pub enum TrySendError<T> {
+        /// The channel is currently full
+        Full(T),
+        /// The receive half of the channel was dropped
+        Closed(T),
+    }
+
+    impl<T> fmt::Display for TrySendError<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TrySendError::Full(_) => write!(fmt, "TrySendError -> channel full"),
+                TrySendError::Closed(_) => write!(fmt, "TrySendError -> channel closed"),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum TryRecvError {
+        /// The channel is currently empty
+        Empty,
+        /// All senders were dropped
+        Disconnected,
+    }
 }
@@ -0,0 +1,214 @@
+// Created by Ockam Developers
+// Generated with cbindgen, do not edit
+
+#ifndef OCKAM_H
+#define OCKAM_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Error type relating to FFI specific failures.
+ */
+typedef struct {
+  int32_t code;
+  const char *domain;
+} ockam_error_t;
+
+/**
+ * Represents a handle id for a node
+ */
+typedef uint64_t ockam_node_t;
+
+/**
+ * Represents a handle id for an identity of a node
+ */
+typedef uint64_t ockam_identity_t;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Start a secure channel listener at `address` for an identity.
+ *
+ * Only the identity with the `trusted_identifier` can open a secure
+ * channel, or anyone if it is NULL.
+ */
+ockam_error_t ockam_secure_channel_listen(ockam_node_t node,
+                                          ockam_identity_t identity,
+                                          const char *address,
+                                          const char *trusted_identifier);
+
+/**
+ * Open a secure channel for an identity to the `listener` of the
+ * node at the TCP address `peer`, e.g. `127.0.0.1:4000`.
+ *
+ * Only a listener of the identity with the `trusted_identifier` is
+ * accepted, or any if it is NULL. The address of the secure channel
+ * is written to `output_buffer` as a NUL-terminated string, to pass
+ * to `ockam_message_send`.
+ */
+ockam_error_t ockam_secure_channel_create(ockam_node_t node,
+                                          ockam_identity_t identity,
+                                          const char *peer,
+                                          const char *listener,
+                                          const char *trusted_identifier,
+                                          char *output_buffer,
+                                          uint32_t output_buffer_size,
+                                          uint32_t *output_buffer_length);
+
+/**
+ * Send a message to the `destination` address on the other end of
+ * the secure `channel`, or on this node if `channel` is NULL.
+ *
+ * Nodes receive messages sent to their `app` address with
+ * `ockam_message_receive`.
+ */
+ockam_error_t ockam_message_send(ockam_node_t node,
+                                 const char *channel,
+                                 const char *destination,
+                                 const uint8_t *data,
+                                 uint32_t data_length);
+
+/**
+ * Wait up to `timeout_ms` milliseconds for a message sent to the
+ * node's `app` address and copy it to the `output_buffer`.
+ *
+ * If the buffer is too small, the message is kept for the next call.
+ * Otherwise it becomes the message `ockam_message_reply` answers.
+ */
+ockam_error_t ockam_message_receive(ockam_node_t node,
+                                    uint64_t timeout_ms,
+                                    uint8_t *output_buffer,
+                                    uint32_t output_buffer_size,
+                                    uint32_t *output_buffer_length);
+
+/**
+ * Write the identifier of the identity which sent the last message
+ * received, over a secure channel, to the `output_buffer` as a
+ * NUL-terminated string.
+ *
+ * Fails if the message was not received over a secure channel.
+ */
+ockam_error_t ockam_message_sender(ockam_node_t node,
+                                   char *output_buffer,
+                                   uint32_t output_buffer_size,
+                                   uint32_t *output_buffer_length);
+
+/**
+ * Send a message back to the sender of the last message received.
+ */
+ockam_error_t ockam_message_reply(ockam_node_t node, const uint8_t *data, uint32_t data_length);
+
+/**
+ * Free the resources of an error returned by any `ockam_` function.
+ *
+ * This is a no-op if no error occurred.
+ *
+ * # Safety
+ * the `domain` of the error must be NULL or allocated by this library
+ */
+void ockam_free_error(ockam_error_t *error);
+
+/**
+ * Create an identity with keys in the node's vault.
+ */
+ockam_error_t ockam_identity_create(ockam_node_t node, ockam_identity_t *identity);
+
+/**
+ * Import an identity exported with `ockam_identity_export`.
+ *
+ * The keys of the identity must be in the node's vault, i.e. the
+ * node must use the vault file of the node which created it.
+ */
+ockam_error_t ockam_identity_import(ockam_node_t node,
+                                    const uint8_t *input,
+                                    uint32_t input_length,
+                                    ockam_identity_t *identity);
+
+/**
+ * Export the change history of an identity to the `output_buffer`.
+ *
+ * The exported data is also the public identity which peers pass to
+ * `ockam_identity_verify`.
+ */
+ockam_error_t ockam_identity_export(ockam_node_t node,
+                                    ockam_identity_t identity,
+                                    uint8_t *output_buffer,
+                                    uint32_t output_buffer_size,
+                                    uint32_t *output_buffer_length);
+
+/**
+ * Write the identifier of an identity, e.g. `P6c20e814b56579306f55c64e8747e6c1b4a53d9a3f4ca83c252cc2fbfc72fa94`,
+ * to the `output_buffer` as a NUL-terminated string.
+ */
+ockam_error_t ockam_identity_identifier(ockam_node_t node,
+                                        ockam_identity_t identity,
+                                        char *output_buffer,
+                                        uint32_t output_buffer_size,
+                                        uint32_t *output_buffer_length);
+
+/**
+ * Sign `data` with the root key of an identity.
+ */
+ockam_error_t ockam_identity_sign(ockam_node_t node,
+                                  ockam_identity_t identity,
+                                  const uint8_t *data,
+                                  uint32_t data_length,
+                                  uint8_t *signature,
+                                  uint32_t signature_size,
+                                  uint32_t *signature_length);
+
+/**
+ * Verify the `signature` of `data` made by the root key of an
+ * identity, given as exported by `ockam_identity_export`.
+ */
+ockam_error_t ockam_identity_verify(ockam_node_t node,
+                                    const uint8_t *identity,
+                                    uint32_t identity_length,
+                                    const uint8_t *data,
+                                    uint32_t data_length,
+                                    const uint8_t *signature,
+                                    uint32_t signature_length,
+                                    bool *verified);
+
+/**
+ * Forget an identity. Its keys stay in the node's vault.
+ */
+ockam_error_t ockam_identity_destroy(ockam_node_t node, ockam_identity_t identity);
+
+/**
+ * Start a node running on its own thread.
+ *
+ * Secrets are kept in the vault file at `vault_path`, or in memory
+ * if it is NULL. Messages sent to the node are delivered to its
+ * `app` address, see `ockam_message_receive`.
+ */
+ockam_error_t ockam_node_create(const char *vault_path, ockam_node_t *node);
+
+/**
+ * Stop a node, its identities and secure channels.
+ */
+ockam_error_t ockam_node_stop(ockam_node_t node);
+
+/**
+ * Listen for TCP connections on `bind_address`, e.g. `127.0.0.1:4000`.
+ *
+ * The address actually bound, which differs if the port is 0, is
+ * written to `output_buffer` as a NUL-terminated string.
+ */
+ockam_error_t ockam_tcp_listen(ockam_node_t node,
+                               const char *bind_address,
+                               char *output_buffer,
+                               uint32_t output_buffer_size,
+                               uint32_t *output_buffer_length);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* OCKAM_H */
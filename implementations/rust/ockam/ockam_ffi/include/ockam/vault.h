@@ -3,6 +3,7 @@
 #ifndef RUST_VAULT_H
 #define RUST_VAULT_H
 
+#include <stdbool.h>
 #include <stddef.h>
 #include <stdint.h>
 
@@ -240,6 +241,48 @@ ockam_vault_extern_error_t ockam_vault_aead_aes_gcm_decrypt(ockam_vault_t
                                                             uint32_t             plaintext_size,
                                                             uint32_t*            plaintext_length);
 
+/**
+ * @brief   Sign data with a secret key.
+ * @param   vault[in]             Vault object to use for signing.
+ * @param   key[in]               Ockam secret key to sign with.
+ * @param   data[in]              Buffer containing the data to sign.
+ * @param   data_length[in]       Length of the data to sign.
+ * @param   signature[out]        Buffer to place the signature in.
+ * @param   signature_size[in]    Size of the signature buffer.
+ * @param   signature_length[out] Amount of data placed in the signature buffer.
+ * @return  an error, which should be freed using @ref ockam_vault_free_error.
+ */
+ockam_vault_extern_error_t ockam_vault_sign(ockam_vault_t        vault,
+                                            ockam_vault_secret_t key,
+                                            const uint8_t*       data,
+                                            uint32_t             data_length,
+                                            uint8_t*             signature,
+                                            uint32_t             signature_size,
+                                            uint32_t*            signature_length);
+
+/**
+ * @brief   Verify the signature of data with a public key.
+ * @param   vault[in]             Vault object to use for verification.
+ * @param   public_key[in]        Buffer containing the public key of the signer.
+ * @param   public_key_length[in] Length of the public key.
+ * @param   public_key_type[in]   Secret type of the public key, see @ref ockam_vault_secret_type_t.
+ * @param   data[in]              Buffer containing the signed data.
+ * @param   data_length[in]       Length of the signed data.
+ * @param   signature[in]         Buffer containing the signature.
+ * @param   signature_length[in]  Length of the signature.
+ * @param   verified[out]         Whether the signature is valid.
+ * @return  an error, which should be freed using @ref ockam_vault_free_error.
+ */
+ockam_vault_extern_error_t ockam_vault_verify(ockam_vault_t  vault,
+                                              const uint8_t* public_key,
+                                              uint32_t       public_key_length,
+                                              uint8_t        public_key_type,
+                                              const uint8_t* data,
+                                              uint32_t       data_length,
+                                              const uint8_t* signature,
+                                              uint32_t       signature_length,
+                                              bool*          verified);
+
 /**
  * @brief   Deinitialize the specified ockam vault object
  * @param   vault[in] The ockam vault object to deinitialize.
@@ -0,0 +1,263 @@
+use crate::node::{
+    read_opt_str, read_str, with_node, write_bytes, write_str, IdentityHandle, Message, NodeHandle,
+};
+use crate::vault::handle_panics;
+use crate::{check_buffer, FfiError, FfiOckamError};
+use core::time::Duration;
+use ockam::identity::{
+    IdentityIdentifier, IdentitySecureChannelLocalInfo, TrustEveryonePolicy, TrustIdentifierPolicy,
+};
+use ockam::{route, TCP};
+use ockam_core::{Address, Result};
+use std::os::raw::c_char;
+
+fn read_address(s: *const c_char) -> Result<Address> {
+    Ok(read_str(s)?
+        .parse::<Address>()
+        .map_err(|_| FfiError::InvalidParam)?)
+}
+
+fn read_trusted(s: *const c_char) -> Result<Option<IdentityIdentifier>> {
+    match read_opt_str(s)? {
+        Some(s) => Ok(Some(s.try_into()?)),
+        None => Ok(None),
+    }
+}
+
+/// Start a secure channel listener at `address` for an identity.
+///
+/// Only the identity with the `trusted_identifier` can open a secure
+/// channel, or anyone if it is NULL.
+#[no_mangle]
+pub extern "C" fn ockam_secure_channel_listen(
+    node: NodeHandle,
+    identity: IdentityHandle,
+    address: *const c_char,
+    trusted_identifier: *const c_char,
+) -> FfiOckamError {
+    handle_panics(|| {
+        let address = read_address(address)?;
+        let trusted = read_trusted(trusted_identifier)?;
+
+        with_node(node, |entry| {
+            Box::pin(async move {
+                let identity = entry.identity(identity)?;
+                match trusted {
+                    Some(id) => {
+                        identity
+                            .create_secure_channel_listener(
+                                address,
+                                TrustIdentifierPolicy::new(id),
+                                &entry.storage,
+                            )
+                            .await
+                    }
+                    None => {
+                        identity
+                            .create_secure_channel_listener(
+                                address,
+                                TrustEveryonePolicy,
+                                &entry.storage,
+                            )
+                            .await
+                    }
+                }
+            })
+        })?;
+        Ok(())
+    })
+}
+
+/// Open a secure channel for an identity to the `listener` of the
+/// node at the TCP address `peer`, e.g. `127.0.0.1:4000`.
+///
+/// Only a listener of the identity with the `trusted_identifier` is
+/// accepted, or any if it is NULL. The address of the secure channel
+/// is written to `output_buffer` as a NUL-terminated string, to pass
+/// to `ockam_message_send`.
+#[no_mangle]
+pub extern "C" fn ockam_secure_channel_create(
+    node: NodeHandle,
+    identity: IdentityHandle,
+    peer: *const c_char,
+    listener: *const c_char,
+    trusted_identifier: *const c_char,
+    output_buffer: *mut c_char,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> FfiOckamError {
+    *output_buffer_length = 0;
+    handle_panics(|| {
+        let peer = read_str(peer)?;
+        let listener = read_address(listener)?;
+        let trusted = read_trusted(trusted_identifier)?;
+
+        let channel = with_node(node, |entry| {
+            Box::pin(async move {
+                entry.tcp.connect(peer).await?;
+                let route = route![(TCP, peer), listener];
+                let identity = entry.identity(identity)?;
+                match trusted {
+                    Some(id) => {
+                        identity
+                            .create_secure_channel(
+                                route,
+                                TrustIdentifierPolicy::new(id),
+                                &entry.storage,
+                            )
+                            .await
+                    }
+                    None => {
+                        identity
+                            .create_secure_channel(route, TrustEveryonePolicy, &entry.storage)
+                            .await
+                    }
+                }
+            })
+        })?;
+        write_str(
+            &channel.to_string(),
+            output_buffer,
+            output_buffer_size,
+            output_buffer_length,
+        )?;
+        Ok(())
+    })
+}
+
+/// Send a message to the `destination` address on the other end of
+/// the secure `channel`, or on this node if `channel` is NULL.
+///
+/// Nodes receive messages sent to their `app` address with
+/// `ockam_message_receive`.
+#[no_mangle]
+pub extern "C" fn ockam_message_send(
+    node: NodeHandle,
+    channel: *const c_char,
+    destination: *const c_char,
+    data: *const u8,
+    data_length: u32,
+) -> FfiOckamError {
+    handle_panics(|| {
+        check_buffer!(data);
+        let channel = match read_opt_str(channel)? {
+            Some(_) => Some(read_address(channel)?),
+            None => None,
+        };
+        let destination = read_address(destination)?;
+        let data = unsafe { core::slice::from_raw_parts(data, data_length as usize) }.to_vec();
+
+        with_node(node, |entry| {
+            Box::pin(async move {
+                let route = match channel {
+                    Some(channel) => route![channel, destination],
+                    None => route![destination],
+                };
+                entry.ctx.send(route, data).await
+            })
+        })?;
+        Ok(())
+    })
+}
+
+/// Wait up to `timeout_ms` milliseconds for a message sent to the
+/// node's `app` address and copy it to the `output_buffer`.
+///
+/// If the buffer is too small, the message is kept for the next call.
+/// Otherwise it becomes the message `ockam_message_reply` answers.
+#[no_mangle]
+pub extern "C" fn ockam_message_receive(
+    node: NodeHandle,
+    timeout_ms: u64,
+    output_buffer: *mut u8,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> FfiOckamError {
+    *output_buffer_length = 0;
+    handle_panics(|| {
+        check_buffer!(output_buffer);
+        *output_buffer_length = with_node(node, |entry| {
+            Box::pin(async move {
+                let msg = match entry.pending.take() {
+                    Some(msg) => msg,
+                    None => {
+                        let msg = entry
+                            .ctx
+                            .receive_duration_timeout::<Vec<u8>>(Duration::from_millis(timeout_ms))
+                            .await?
+                            .take();
+                        let sender = IdentitySecureChannelLocalInfo::find_info(msg.local_message())
+                            .ok()
+                            .map(|info| info.their_identity_id().to_string());
+                        Message {
+                            return_route: msg.return_route(),
+                            sender,
+                            payload: msg.body(),
+                        }
+                    }
+                };
+                let mut length = 0;
+                if let Err(e) =
+                    write_bytes(&msg.payload, output_buffer, output_buffer_size, &mut length)
+                {
+                    entry.pending = Some(msg);
+                    return Err(e);
+                }
+                entry.last = Some(msg);
+                Ok(length)
+            })
+        })?;
+        Ok(())
+    })
+}
+
+/// Write the identifier of the identity which sent the last message
+/// received, over a secure channel, to the `output_buffer` as a
+/// NUL-terminated string.
+///
+/// Fails if the message was not received over a secure channel.
+#[no_mangle]
+pub extern "C" fn ockam_message_sender(
+    node: NodeHandle,
+    output_buffer: *mut c_char,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> FfiOckamError {
+    *output_buffer_length = 0;
+    handle_panics(|| {
+        let sender = with_node(node, |entry| {
+            Box::pin(async move {
+                let msg = entry.last.as_ref().ok_or(FfiError::NoMessage)?;
+                Ok(msg.sender.clone().ok_or(FfiError::EntryNotFound)?)
+            })
+        })?;
+        write_str(
+            &sender,
+            output_buffer,
+            output_buffer_size,
+            output_buffer_length,
+        )?;
+        Ok(())
+    })
+}
+
+/// Send a message back to the sender of the last message received.
+#[no_mangle]
+pub extern "C" fn ockam_message_reply(
+    node: NodeHandle,
+    data: *const u8,
+    data_length: u32,
+) -> FfiOckamError {
+    handle_panics(|| {
+        check_buffer!(data);
+        let data = unsafe { core::slice::from_raw_parts(data, data_length as usize) }.to_vec();
+
+        with_node(node, |entry| {
+            Box::pin(async move {
+                let msg = entry.last.as_ref().ok_or(FfiError::NoMessage)?;
+                entry.ctx.send(msg.return_route.clone(), data).await
+            })
+        })?;
+        Ok(())
+    })
+}
@@ -67,6 +67,15 @@ pub enum FfiError {
 
     /// Caught a panic (which would be UB if we let it unwind across the FFI).
     UnexpectedPanic,
+
+    /// No such Node.
+    NodeNotFound,
+
+    /// No such Identity.
+    IdentityNotFound,
+
+    /// No message was received to reply to.
+    NoMessage,
 }
 impl ockam_core::compat::error::Error for FfiError {}
 impl From<FfiError> for Error {
@@ -98,6 +107,9 @@ impl core::fmt::Display for FfiError {
                 f,
                 "caught a panic (which would be UB if we let it unwind across the FFI)."
             ),
+            Self::NodeNotFound => write!(f, "no such Node."),
+            Self::IdentityNotFound => write!(f, "no such Identity."),
+            Self::NoMessage => write!(f, "no message was received to reply to."),
         }
     }
 }
@@ -122,8 +134,19 @@ impl From<FfiError> for FfiOckamError {
 /// frees `FfiOckamError::domain` if it's non-null
 #[no_mangle]
 pub unsafe extern "C" fn ockam_vault_free_error(context: &mut FfiOckamError) {
-    if !context.domain.is_null() {
-        let _ = CString::from_raw(context.domain as *mut _);
-        context.domain = core::ptr::null();
+    ockam_free_error(context)
+}
+
+/// Free the resources of an error returned by any `ockam_` function.
+///
+/// This is a no-op if no error occurred.
+///
+/// # Safety
+/// the `domain` of the error must be NULL or allocated by this library
+#[no_mangle]
+pub unsafe extern "C" fn ockam_free_error(error: &mut FfiOckamError) {
+    if !error.domain.is_null() {
+        let _ = CString::from_raw(error.domain as *mut _);
+        error.domain = core::ptr::null();
     }
 }
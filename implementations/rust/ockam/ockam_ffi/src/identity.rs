@@ -0,0 +1,179 @@
+use crate::node::{with_node, write_bytes, write_str, IdentityHandle, NodeHandle};
+use crate::vault::handle_panics;
+use crate::{check_buffer, FfiError, FfiOckamError};
+use ockam::identity::{Identity, PublicIdentity};
+use ockam_core::vault::Signature;
+use std::os::raw::c_char;
+
+/// Create an identity with keys in the node's vault.
+#[no_mangle]
+pub extern "C" fn ockam_identity_create(
+    node: NodeHandle,
+    identity: &mut IdentityHandle,
+) -> FfiOckamError {
+    handle_panics(|| {
+        *identity = with_node(node, |entry| {
+            Box::pin(async move {
+                let id = Identity::create(&entry.ctx, &entry.vault).await?;
+                Ok(entry.insert_identity(id))
+            })
+        })?;
+        Ok(())
+    })
+}
+
+/// Import an identity exported with `ockam_identity_export`.
+///
+/// The keys of the identity must be in the node's vault, i.e. the
+/// node must use the vault file of the node which created it.
+#[no_mangle]
+pub extern "C" fn ockam_identity_import(
+    node: NodeHandle,
+    input: *const u8,
+    input_length: u32,
+    identity: &mut IdentityHandle,
+) -> FfiOckamError {
+    handle_panics(|| {
+        check_buffer!(input, input_length);
+        let input = unsafe { core::slice::from_raw_parts(input, input_length as usize) };
+
+        *identity = with_node(node, |entry| {
+            Box::pin(async move {
+                let id = Identity::import(&entry.ctx, input, &entry.vault).await?;
+                Ok(entry.insert_identity(id))
+            })
+        })?;
+        Ok(())
+    })
+}
+
+/// Export the change history of an identity to the `output_buffer`.
+///
+/// The exported data is also the public identity which peers pass to
+/// `ockam_identity_verify`.
+#[no_mangle]
+pub extern "C" fn ockam_identity_export(
+    node: NodeHandle,
+    identity: IdentityHandle,
+    output_buffer: *mut u8,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> FfiOckamError {
+    *output_buffer_length = 0;
+    handle_panics(|| {
+        let data = with_node(node, |entry| {
+            Box::pin(async move { entry.identity(identity)?.export().await })
+        })?;
+        write_bytes(
+            &data,
+            output_buffer,
+            output_buffer_size,
+            output_buffer_length,
+        )?;
+        Ok(())
+    })
+}
+
+/// Write the identifier of an identity, e.g. `P6c20e814b56579306f55c64e8747e6c1b4a53d9a3f4ca83c252cc2fbfc72fa94`,
+/// to the `output_buffer` as a NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn ockam_identity_identifier(
+    node: NodeHandle,
+    identity: IdentityHandle,
+    output_buffer: *mut c_char,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> FfiOckamError {
+    *output_buffer_length = 0;
+    handle_panics(|| {
+        let identifier = with_node(node, |entry| {
+            Box::pin(async move { Ok(entry.identity(identity)?.identifier().to_string()) })
+        })?;
+        write_str(
+            &identifier,
+            output_buffer,
+            output_buffer_size,
+            output_buffer_length,
+        )?;
+        Ok(())
+    })
+}
+
+/// Sign `data` with the root key of an identity.
+#[no_mangle]
+pub extern "C" fn ockam_identity_sign(
+    node: NodeHandle,
+    identity: IdentityHandle,
+    data: *const u8,
+    data_length: u32,
+    signature: *mut u8,
+    signature_size: u32,
+    signature_length: &mut u32,
+) -> FfiOckamError {
+    *signature_length = 0;
+    handle_panics(|| {
+        check_buffer!(data);
+        let data = unsafe { core::slice::from_raw_parts(data, data_length as usize) };
+
+        let sig = with_node(node, |entry| {
+            Box::pin(async move { entry.identity(identity)?.create_signature(data, None).await })
+        })?;
+        write_bytes(sig.as_ref(), signature, signature_size, signature_length)?;
+        Ok(())
+    })
+}
+
+/// Verify the `signature` of `data` made by the root key of an
+/// identity, given as exported by `ockam_identity_export`.
+#[no_mangle]
+pub extern "C" fn ockam_identity_verify(
+    node: NodeHandle,
+    identity: *const u8,
+    identity_length: u32,
+    data: *const u8,
+    data_length: u32,
+    signature: *const u8,
+    signature_length: u32,
+    verified: &mut bool,
+) -> FfiOckamError {
+    *verified = false;
+    handle_panics(|| {
+        check_buffer!(identity, identity_length);
+        check_buffer!(data);
+        check_buffer!(signature, signature_length);
+
+        let identity = unsafe { core::slice::from_raw_parts(identity, identity_length as usize) };
+        let data = unsafe { core::slice::from_raw_parts(data, data_length as usize) };
+        let signature =
+            unsafe { core::slice::from_raw_parts(signature, signature_length as usize) };
+
+        *verified = with_node(node, |entry| {
+            Box::pin(async move {
+                let identity = PublicIdentity::import(identity, &entry.vault).await?;
+                let signature = Signature::new(signature.to_vec());
+                identity
+                    .verify_signature(&signature, data, None, &entry.vault)
+                    .await
+            })
+        })?;
+        Ok(())
+    })
+}
+
+/// Forget an identity. Its keys stay in the node's vault.
+#[no_mangle]
+pub extern "C" fn ockam_identity_destroy(
+    node: NodeHandle,
+    identity: IdentityHandle,
+) -> FfiOckamError {
+    handle_panics(|| {
+        with_node(node, |entry| {
+            Box::pin(async move {
+                entry.identity(identity)?;
+                entry.identities.remove(&identity);
+                Ok(())
+            })
+        })?;
+        Ok(())
+    })
+}
@@ -1,4 +1,8 @@
-//! Ockam Vault Foreign Function Interface (FFI) for library integration.
+//! Ockam Foreign Function Interface (FFI) for library integration.
+//!
+//! The vault functions are declared in `include/ockam/vault.h`. The
+//! node, identity and secure channel functions are declared in
+//! `include/ockam/ockam.h`, which is generated with `cbindgen`.
 #![warn(
     missing_docs,
     trivial_casts,
@@ -8,11 +12,17 @@
 )]
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
+mod channel;
 mod error;
+mod identity;
 mod macros;
+mod node;
 mod vault;
 mod vault_types;
 
+pub use channel::*;
 pub use error::*;
+pub use identity::*;
+pub use node::{ockam_node_create, ockam_node_stop, ockam_tcp_listen, IdentityHandle, NodeHandle};
 pub use vault::*;
 use vault_types::*;
@@ -0,0 +1,260 @@
+use crate::vault::handle_panics;
+use crate::{check_buffer, FfiError, FfiOckamError};
+use core::future::Future;
+use lazy_static::lazy_static;
+use ockam::authenticated_storage::InMemoryStorage;
+use ockam::identity::Identity;
+use ockam::vault::{storage::FileStorage, Vault};
+use ockam::{Context, NodeBuilder, TcpTransport};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::{Error, Result, Route};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use tokio::runtime::Handle;
+
+/// Represents a handle id for a node
+pub type NodeHandle = u64;
+
+/// Represents a handle id for an identity of a node
+pub type IdentityHandle = u64;
+
+/// A message received by the node, kept until it's read
+pub(crate) struct Message {
+    pub(crate) payload: Vec<u8>,
+    pub(crate) return_route: Route,
+    pub(crate) sender: Option<String>,
+}
+
+pub(crate) struct NodeEntry {
+    pub(crate) ctx: Context,
+    pub(crate) vault: Vault,
+    pub(crate) tcp: TcpTransport,
+    pub(crate) storage: InMemoryStorage,
+    pub(crate) identities: BTreeMap<IdentityHandle, Identity<Vault>>,
+    last_identity: IdentityHandle,
+    /// A message which did not fit in the buffer it was received in
+    pub(crate) pending: Option<Message>,
+    /// The last message read, to reply to
+    pub(crate) last: Option<Message>,
+    executor: Option<JoinHandle<()>>,
+}
+
+impl NodeEntry {
+    pub(crate) fn insert_identity(&mut self, identity: Identity<Vault>) -> IdentityHandle {
+        self.last_identity += 1;
+
+        self.identities.insert(self.last_identity, identity);
+
+        self.last_identity
+    }
+
+    pub(crate) fn identity(&self, handle: IdentityHandle) -> Result<&Identity<Vault>> {
+        Ok(self
+            .identities
+            .get(&handle)
+            .ok_or(FfiError::IdentityNotFound)?)
+    }
+}
+
+#[derive(Default)]
+struct NodesMapping {
+    mapping: BTreeMap<NodeHandle, Arc<Mutex<NodeEntry>>>,
+    last_index: NodeHandle,
+}
+
+lazy_static! {
+    static ref NODES: Mutex<NodesMapping> = Mutex::new(NodesMapping::default());
+}
+
+fn get_node(node: NodeHandle) -> Result<Arc<Mutex<NodeEntry>>> {
+    Ok(NODES
+        .lock()
+        .unwrap()
+        .mapping
+        .get(&node)
+        .cloned()
+        .ok_or(FfiError::NodeNotFound)?)
+}
+
+/// Run `f` with the node, blocking the calling thread on the
+/// node's runtime until the future it returns completes.
+///
+/// Calls on the same node are serialised.
+pub(crate) fn with_node<F, T>(node: NodeHandle, f: F) -> Result<T>
+where
+    F: for<'a> FnOnce(
+        &'a mut NodeEntry,
+    ) -> core::pin::Pin<Box<dyn Future<Output = Result<T>> + 'a>>,
+{
+    let node = get_node(node)?;
+    let mut entry = node.lock().unwrap();
+    let rt: Handle = entry.ctx.runtime().clone();
+    rt.block_on(f(&mut entry))
+}
+
+/// Read a NUL-terminated UTF-8 string.
+pub(crate) fn read_str<'a>(s: *const c_char) -> Result<&'a str> {
+    check_buffer!(s);
+    let s = unsafe { CStr::from_ptr(s) };
+    Ok(s.to_str().map_err(|_| FfiError::InvalidString)?)
+}
+
+/// Read a NUL-terminated UTF-8 string which may be NULL.
+pub(crate) fn read_opt_str<'a>(s: *const c_char) -> Result<Option<&'a str>> {
+    if s.is_null() {
+        Ok(None)
+    } else {
+        read_str(s).map(Some)
+    }
+}
+
+/// Copy `data` to the output buffer.
+pub(crate) fn write_bytes(
+    data: &[u8],
+    output_buffer: *mut u8,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> Result<()> {
+    check_buffer!(output_buffer);
+    if output_buffer_size < data.len() as u32 {
+        return Err(FfiError::BufferTooSmall.into());
+    }
+    *output_buffer_length = data.len() as u32;
+
+    unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), output_buffer, data.len()) };
+    Ok(())
+}
+
+/// Copy `s` to the output buffer as a NUL-terminated string, the
+/// length does not count the terminator.
+pub(crate) fn write_str(
+    s: &str,
+    output_buffer: *mut c_char,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> Result<()> {
+    check_buffer!(output_buffer);
+    if output_buffer_size <= s.len() as u32 {
+        return Err(FfiError::BufferTooSmall.into());
+    }
+    *output_buffer_length = s.len() as u32;
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(s.as_ptr(), output_buffer as *mut u8, s.len());
+        *output_buffer.add(s.len()) = 0;
+    }
+    Ok(())
+}
+
+/// Start a node running on its own thread.
+///
+/// Secrets are kept in the vault file at `vault_path`, or in memory
+/// if it is NULL. Messages sent to the node are delivered to its
+/// `app` address, see `ockam_message_receive`.
+#[no_mangle]
+pub extern "C" fn ockam_node_create(
+    vault_path: *const c_char,
+    node: &mut NodeHandle,
+) -> FfiOckamError {
+    handle_panics(|| {
+        let vault_path = read_opt_str(vault_path)?.map(PathBuf::from);
+
+        let (tx, rx) = mpsc::channel();
+        let executor = std::thread::spawn(move || {
+            let (ctx, mut executor) = NodeBuilder::without_access_control().no_logging().build();
+            if tx.send(ctx).is_ok() {
+                let _ = executor.execute(async {});
+            }
+        });
+        let ctx = rx.recv().map_err(|_| FfiError::UnexpectedPanic)?;
+
+        let rt = ctx.runtime().clone();
+        let result = rt.block_on(async {
+            let vault = match vault_path {
+                Some(path) => Vault::new(Some(Arc::new(FileStorage::create(path).await?))),
+                None => Vault::default(),
+            };
+            let tcp = TcpTransport::create(&ctx).await?;
+            Ok::<_, Error>((vault, tcp))
+        });
+        let (vault, tcp) = match result {
+            Ok(r) => r,
+            Err(e) => {
+                let mut ctx = ctx;
+                let _ = rt.block_on(ctx.stop());
+                let _ = executor.join();
+                return Err(e.into());
+            }
+        };
+
+        let entry = NodeEntry {
+            ctx,
+            vault,
+            tcp,
+            storage: InMemoryStorage::new(),
+            identities: BTreeMap::new(),
+            last_identity: 0,
+            pending: None,
+            last: None,
+            executor: Some(executor),
+        };
+
+        let mut nodes = NODES.lock().unwrap();
+        nodes.last_index += 1;
+        *node = nodes.last_index;
+        nodes.mapping.insert(*node, Arc::new(Mutex::new(entry)));
+        Ok(())
+    })
+}
+
+/// Stop a node, its identities and secure channels.
+#[no_mangle]
+pub extern "C" fn ockam_node_stop(node: NodeHandle) -> FfiOckamError {
+    handle_panics(|| {
+        let entry = NODES
+            .lock()
+            .unwrap()
+            .mapping
+            .remove(&node)
+            .ok_or(FfiError::NodeNotFound)?;
+        let mut entry = entry.lock().unwrap();
+        let rt = entry.ctx.runtime().clone();
+        rt.block_on(entry.ctx.stop())?;
+        if let Some(executor) = entry.executor.take() {
+            let _ = executor.join();
+        }
+        Ok(())
+    })
+}
+
+/// Listen for TCP connections on `bind_address`, e.g. `127.0.0.1:4000`.
+///
+/// The address actually bound, which differs if the port is 0, is
+/// written to `output_buffer` as a NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn ockam_tcp_listen(
+    node: NodeHandle,
+    bind_address: *const c_char,
+    output_buffer: *mut c_char,
+    output_buffer_size: u32,
+    output_buffer_length: &mut u32,
+) -> FfiOckamError {
+    *output_buffer_length = 0;
+    handle_panics(|| {
+        let bind_address = read_str(bind_address)?;
+        let addr = with_node(node, |entry| {
+            Box::pin(async move { entry.tcp.listen(bind_address).await })
+        })?;
+        write_str(
+            &addr.to_string(),
+            output_buffer,
+            output_buffer_size,
+            output_buffer_length,
+        )?;
+        Ok(())
+    })
+}
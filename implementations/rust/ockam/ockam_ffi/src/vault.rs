@@ -1,4 +1,4 @@
-use crate::vault_types::{FfiSecretAttributes, SecretKeyHandle};
+use crate::vault_types::{secret_type, FfiSecretAttributes, SecretKeyHandle};
 use crate::{check_buffer, FfiError, FfiOckamError};
 use crate::{FfiVaultFatPointer, FfiVaultType};
 use core::{future::Future, result::Result as StdResult, slice};
@@ -7,7 +7,8 @@ use lazy_static::lazy_static;
 use ockam_core::compat::collections::BTreeMap;
 use ockam_core::compat::sync::Arc;
 use ockam_core::vault::{
-    AsymmetricVault, Hasher, KeyId, PublicKey, SecretAttributes, SecretVault, SymmetricVault,
+    AsymmetricVault, Hasher, KeyId, PublicKey, SecretAttributes, SecretVault, Signature, Signer,
+    SymmetricVault, Verifier,
 };
 use ockam_core::{Error, Result};
 use ockam_vault::Vault;
@@ -482,6 +483,75 @@ pub extern "C" fn ockam_vault_aead_aes_gcm_decrypt(
     })
 }
 
+/// Sign `data` with the specified secret key and put the signature in `signature`.
+#[no_mangle]
+pub extern "C" fn ockam_vault_sign(
+    context: FfiVaultFatPointer,
+    secret: SecretKeyHandle,
+    data: *const u8,
+    data_length: u32,
+    signature: *mut u8,
+    signature_size: u32,
+    signature_length: &mut u32,
+) -> FfiOckamError {
+    *signature_length = 0;
+    handle_panics(|| {
+        check_buffer!(data);
+        check_buffer!(signature);
+
+        let data = unsafe { slice::from_raw_parts(data, data_length as usize) };
+
+        block_future(async move {
+            let entry = get_vault_entry(context).await?;
+            let key_id = entry.get(secret).await?;
+            let sig = entry.vault.sign(&key_id, data).await?;
+            if signature_size < sig.as_ref().len() as u32 {
+                return Err(FfiError::BufferTooSmall.into());
+            }
+            *signature_length = sig.as_ref().len() as u32;
+
+            unsafe {
+                std::ptr::copy_nonoverlapping(sig.as_ref().as_ptr(), signature, sig.as_ref().len())
+            };
+            Ok::<(), Error>(())
+        })?;
+        Ok(())
+    })
+}
+
+/// Verify the `signature` of `data` against a public key of the specified secret type.
+#[no_mangle]
+pub extern "C" fn ockam_vault_verify(
+    context: FfiVaultFatPointer,
+    public_key: *const u8,
+    public_key_length: u32,
+    public_key_type: u8,
+    data: *const u8,
+    data_length: u32,
+    signature: *const u8,
+    signature_length: u32,
+    verified: &mut bool,
+) -> FfiOckamError {
+    *verified = false;
+    handle_panics(|| {
+        check_buffer!(public_key, public_key_length);
+        check_buffer!(data);
+        check_buffer!(signature, signature_length);
+
+        let public_key = unsafe { slice::from_raw_parts(public_key, public_key_length as usize) };
+        let data = unsafe { slice::from_raw_parts(data, data_length as usize) };
+        let signature = unsafe { slice::from_raw_parts(signature, signature_length as usize) };
+        let public_key = PublicKey::new(public_key.to_vec(), secret_type(public_key_type)?);
+
+        *verified = block_future(async move {
+            let entry = get_vault_entry(context).await?;
+            let signature = Signature::new(signature.to_vec());
+            entry.vault.verify(&signature, &public_key, data).await
+        })?;
+        Ok(())
+    })
+}
+
 /// De-initialize an Ockam Vault.
 #[no_mangle]
 pub extern "C" fn ockam_vault_deinit(context: FfiVaultFatPointer) -> FfiOckamError {
@@ -504,7 +574,7 @@ pub extern "C" fn ockam_vault_deinit(context: FfiVaultFatPointer) -> FfiOckamErr
     })
 }
 
-fn handle_panics<F>(f: F) -> FfiOckamError
+pub(crate) fn handle_panics<F>(f: F) -> FfiOckamError
 where
     F: FnOnce() -> StdResult<(), FfiOckamError>,
 {
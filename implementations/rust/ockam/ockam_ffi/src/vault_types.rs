@@ -88,15 +88,7 @@ impl TryFrom<FfiSecretAttributes> for SecretAttributes {
     type Error = FfiError;
 
     fn try_from(attrs: FfiSecretAttributes) -> Result<Self, Self::Error> {
-        let stype = match attrs.stype() {
-            0 => Ok(SecretType::Buffer),
-            1 => Ok(SecretType::Aes),
-            2 => Ok(SecretType::X25519),
-            3 => Ok(SecretType::Ed25519),
-            #[cfg(feature = "bls")]
-            4 => Ok(SecretType::Bls),
-            _ => Err(FfiError::InvalidParam),
-        }?;
+        let stype = secret_type(attrs.stype())?;
 
         let persistence = match attrs.persistence() {
             0 => Ok(SecretPersistence::Ephemeral),
@@ -107,3 +99,16 @@ impl TryFrom<FfiSecretAttributes> for SecretAttributes {
         Ok(Self::new(stype, persistence, attrs.length()))
     }
 }
+
+/// Convert the C representation of a secret type
+pub fn secret_type(stype: u8) -> Result<SecretType, FfiError> {
+    match stype {
+        0 => Ok(SecretType::Buffer),
+        1 => Ok(SecretType::Aes),
+        2 => Ok(SecretType::X25519),
+        3 => Ok(SecretType::Ed25519),
+        #[cfg(feature = "bls")]
+        4 => Ok(SecretType::Bls),
+        _ => Err(FfiError::InvalidParam),
+    }
+}
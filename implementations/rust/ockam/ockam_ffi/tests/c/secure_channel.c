@@ -0,0 +1,224 @@
+// Exercise the identity and secure channel API of include/ockam/ockam.h.
+//
+// Usage: secure_channel <vault file>
+
+#include <stdio.h>
+#include <string.h>
+
+#include "ockam/ockam.h"
+#include "ockam/vault.h"
+
+#define CHECK(call)                                                                \
+  do {                                                                             \
+    ockam_error_t error = (call);                                                  \
+    if (error.code != 0) {                                                         \
+      fprintf(stderr, "%s:%d: %s failed: %d\n", __FILE__, __LINE__, #call, error.code); \
+      ockam_free_error(&error);                                                    \
+      return 1;                                                                    \
+    }                                                                              \
+  } while (0)
+
+#define ASSERT(cond)                                                               \
+  do {                                                                             \
+    if (!(cond)) {                                                                 \
+      fprintf(stderr, "%s:%d: assertion failed: %s\n", __FILE__, __LINE__, #cond); \
+      return 1;                                                                    \
+    }                                                                              \
+  } while (0)
+
+static int test_vault_sign_verify(void) {
+  ockam_vault_t vault;
+  ockam_vault_secret_t secret;
+  ockam_vault_secret_attributes_t attributes = {
+    .type = OCKAM_VAULT_SECRET_TYPE_CURVE25519_PRIVATEKEY,
+    .persistence = OCKAM_VAULT_SECRET_EPHEMERAL,
+    .length = 32,
+  };
+  uint8_t public_key[32];
+  uint32_t public_key_length;
+  uint8_t signature[64];
+  uint32_t signature_length;
+  bool verified;
+  const uint8_t data[] = "data";
+
+  ockam_vault_extern_error_t error = ockam_vault_default_init(&vault);
+  ASSERT(error.code == 0);
+  error = ockam_vault_secret_generate(vault, &secret, attributes);
+  ASSERT(error.code == 0);
+  error = ockam_vault_secret_publickey_get(vault, secret, public_key, sizeof(public_key), &public_key_length);
+  ASSERT(error.code == 0);
+  error = ockam_vault_sign(vault, secret, data, sizeof(data), signature, sizeof(signature), &signature_length);
+  ASSERT(error.code == 0);
+  ASSERT(signature_length == 64);
+
+  error = ockam_vault_verify(vault,
+                             public_key,
+                             public_key_length,
+                             attributes.type,
+                             data,
+                             sizeof(data),
+                             signature,
+                             signature_length,
+                             &verified);
+  ASSERT(error.code == 0);
+  ASSERT(verified);
+
+  signature[0] ^= 1;
+  error = ockam_vault_verify(vault,
+                             public_key,
+                             public_key_length,
+                             attributes.type,
+                             data,
+                             sizeof(data),
+                             signature,
+                             signature_length,
+                             &verified);
+  ASSERT(error.code == 0);
+  ASSERT(!verified);
+
+  error = ockam_vault_deinit(vault);
+  ASSERT(error.code == 0);
+  return 0;
+}
+
+static int test_identity_import(const char *vault_path) {
+  ockam_node_t node;
+  ockam_identity_t identity;
+  uint8_t exported[4096];
+  uint32_t exported_length;
+  char identifier[128];
+  char imported_identifier[128];
+  uint32_t length;
+
+  CHECK(ockam_node_create(vault_path, &node));
+  CHECK(ockam_identity_create(node, &identity));
+  CHECK(ockam_identity_export(node, identity, exported, sizeof(exported), &exported_length));
+  CHECK(ockam_identity_identifier(node, identity, identifier, sizeof(identifier), &length));
+  ASSERT(length == strlen(identifier));
+  CHECK(ockam_node_stop(node));
+
+  // The keys of the identity are in the vault file
+  CHECK(ockam_node_create(vault_path, &node));
+  CHECK(ockam_identity_import(node, exported, exported_length, &identity));
+  CHECK(ockam_identity_identifier(node, identity, imported_identifier, sizeof(imported_identifier), &length));
+  ASSERT(strcmp(identifier, imported_identifier) == 0);
+
+  // Buffers which are too small are rejected
+  ockam_error_t error = ockam_identity_identifier(node, identity, identifier, 4, &length);
+  ASSERT(error.code != 0);
+  ASSERT(length == 0);
+  ockam_free_error(&error);
+
+  CHECK(ockam_identity_destroy(node, identity));
+  error = ockam_identity_export(node, identity, exported, sizeof(exported), &exported_length);
+  ASSERT(error.code != 0);
+  ockam_free_error(&error);
+
+  CHECK(ockam_node_stop(node));
+  return 0;
+}
+
+static int test_secure_channel(void) {
+  ockam_node_t alice, bob;
+  ockam_identity_t alice_identity, bob_identity;
+  uint8_t alice_exported[4096];
+  uint32_t alice_exported_length;
+  char alice_identifier[128], bob_identifier[128];
+  char address[64];
+  char channel[128];
+  char sender[128];
+  uint8_t signature[128];
+  uint32_t signature_length;
+  uint8_t message[64];
+  uint32_t length;
+  bool verified;
+  const uint8_t data[] = "data";
+
+  CHECK(ockam_node_create(NULL, &alice));
+  CHECK(ockam_node_create(NULL, &bob));
+  CHECK(ockam_identity_create(alice, &alice_identity));
+  CHECK(ockam_identity_create(bob, &bob_identity));
+  CHECK(ockam_identity_identifier(alice, alice_identity, alice_identifier, sizeof(alice_identifier), &length));
+  CHECK(ockam_identity_identifier(bob, bob_identity, bob_identifier, sizeof(bob_identifier), &length));
+
+  // Bob verifies a signature of Alice
+  CHECK(ockam_identity_export(alice, alice_identity, alice_exported, sizeof(alice_exported), &alice_exported_length));
+  CHECK(ockam_identity_sign(alice, alice_identity, data, sizeof(data), signature, sizeof(signature), &signature_length));
+  CHECK(ockam_identity_verify(bob,
+                              alice_exported,
+                              alice_exported_length,
+                              data,
+                              sizeof(data),
+                              signature,
+                              signature_length,
+                              &verified));
+  ASSERT(verified);
+  CHECK(ockam_identity_verify(bob,
+                              alice_exported,
+                              alice_exported_length,
+                              (const uint8_t *) "other",
+                              5,
+                              signature,
+                              signature_length,
+                              &verified));
+  ASSERT(!verified);
+
+  // Alice accepts secure channels from Bob only
+  CHECK(ockam_tcp_listen(alice, "127.0.0.1:0", address, sizeof(address), &length));
+  CHECK(ockam_secure_channel_listen(alice, alice_identity, "listener", bob_identifier));
+  CHECK(ockam_secure_channel_create(bob,
+                                    bob_identity,
+                                    address,
+                                    "listener",
+                                    alice_identifier,
+                                    channel,
+                                    sizeof(channel),
+                                    &length));
+
+  CHECK(ockam_message_send(bob, channel, "app", (const uint8_t *) "hello", 5));
+
+  // A buffer which is too small keeps the message for the next call
+  ockam_error_t error = ockam_message_receive(alice, 5000, message, 2, &length);
+  ASSERT(error.code != 0);
+  ockam_free_error(&error);
+  CHECK(ockam_message_receive(alice, 5000, message, sizeof(message), &length));
+  ASSERT(length == 5 && memcmp(message, "hello", 5) == 0);
+  CHECK(ockam_message_sender(alice, sender, sizeof(sender), &length));
+  ASSERT(strcmp(sender, bob_identifier) == 0);
+
+  CHECK(ockam_message_reply(alice, (const uint8_t *) "world", 5));
+  CHECK(ockam_message_receive(bob, 5000, message, sizeof(message), &length));
+  ASSERT(length == 5 && memcmp(message, "world", 5) == 0);
+  CHECK(ockam_message_sender(bob, sender, sizeof(sender), &length));
+  ASSERT(strcmp(sender, alice_identifier) == 0);
+
+  // Nothing else was sent
+  error = ockam_message_receive(bob, 100, message, sizeof(message), &length);
+  ASSERT(error.code != 0);
+  ockam_free_error(&error);
+
+  CHECK(ockam_node_stop(bob));
+  CHECK(ockam_node_stop(alice));
+
+  error = ockam_node_stop(alice);
+  ASSERT(error.code != 0);
+  ockam_free_error(&error);
+  return 0;
+}
+
+int main(int argc, char **argv) {
+  if (argc != 2) {
+    fprintf(stderr, "usage: %s <vault file>\n", argv[0]);
+    return 2;
+  }
+  if (test_vault_sign_verify() != 0) {
+    return 1;
+  }
+  if (test_identity_import(argv[1]) != 0) {
+    return 1;
+  }
+  if (test_secure_channel() != 0) {
+    return 1;
+  }
+  return 0;
+}
@@ -0,0 +1,53 @@
+//! Build the C programs in `tests/c` against the static library and
+//! run them.
+#![cfg(target_os = "linux")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The static library built for the tests, next to this test in the
+/// `deps` directory
+fn static_library() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+    std::fs::read_dir(deps)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("libockam_ffi-") && name.ends_with(".a")
+        })
+        .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+        .map(|e| e.path())
+        .expect("the static library was not built")
+}
+
+fn build(name: &str) -> PathBuf {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg(crate_dir.join("tests/c").join(format!("{name}.c")))
+        .arg(static_library())
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&out)
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "failed to build {name}.c");
+    out
+}
+
+#[test]
+fn secure_channel() {
+    let program = build("secure_channel");
+    let vault = Path::new(env!("CARGO_TARGET_TMPDIR")).join("secure_channel.vault");
+    let _ = std::fs::remove_file(&vault);
+    let status = Command::new(program).arg(&vault).status().unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,31 @@
+//! Check that `include/ockam/ockam.h` matches the FFI functions.
+//!
+//! Run with `OCKAM_FFI_UPDATE_HEADER=1` to regenerate the header.
+
+use std::path::Path;
+
+#[test]
+fn header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("failed to generate the C header")
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let path = crate_dir.join("include/ockam/ockam.h");
+    if std::env::var_os("OCKAM_FFI_UPDATE_HEADER").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+        return;
+    }
+    let current = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        current == generated,
+        "{} is out of date, run the tests with OCKAM_FFI_UPDATE_HEADER=1 to update it",
+        path.display()
+    );
+}
@@ -11,6 +11,8 @@ pub use trust_policy::*;
 pub mod access_control;
 mod local_info;
 pub use local_info::*;
+mod credentials;
+pub use credentials::*;
 
 use crate::authenticated_storage::AuthenticatedStorage;
 use crate::{Identity, IdentityVault};
@@ -27,7 +29,29 @@ impl<V: IdentityVault> Identity<V> {
     ) -> Result<()> {
         let identity_clone = self.async_try_clone().await?;
         let storage_clone = storage.async_try_clone().await?;
-        let listener = IdentityChannelListener::new(trust_policy, identity_clone, storage_clone);
+        let listener = IdentityChannelListener::new(
+            trust_policy,
+            identity_clone,
+            storage_clone,
+            ChannelCredentials::none(),
+        );
+        self.ctx.start_worker(address.into(), listener).await?;
+        Ok(())
+    }
+
+    /// Create a secure channel listener exchanging `credentials` during the
+    /// handshake of its channels
+    pub async fn create_secure_channel_listener_with_credentials(
+        &self,
+        address: impl Into<Address>,
+        trust_policy: impl TrustPolicy,
+        storage: &impl AuthenticatedStorage,
+        credentials: ChannelCredentials,
+    ) -> Result<()> {
+        let identity_clone = self.async_try_clone().await?;
+        let storage_clone = storage.async_try_clone().await?;
+        let listener =
+            IdentityChannelListener::new(trust_policy, identity_clone, storage_clone, credentials);
         self.ctx.start_worker(address.into(), listener).await?;
         Ok(())
     }
@@ -48,6 +72,30 @@ impl<V: IdentityVault> Identity<V> {
             storage_clone,
             Arc::new(trust_policy),
             Duration::from_secs(120),
+            ChannelCredentials::none(),
+        )
+        .await
+    }
+
+    /// Create a secure channel exchanging `credentials` during the handshake
+    pub async fn create_secure_channel_with_credentials(
+        &self,
+        route: impl Into<Route>,
+        trust_policy: impl TrustPolicy,
+        storage: &impl AuthenticatedStorage,
+        credentials: ChannelCredentials,
+    ) -> Result<Address> {
+        let identity_clone = self.async_try_clone().await?;
+        let storage_clone = storage.async_try_clone().await?;
+
+        DecryptorWorker::create_initiator(
+            &self.ctx,
+            route.into(),
+            identity_clone,
+            storage_clone,
+            Arc::new(trust_policy),
+            Duration::from_secs(120),
+            credentials,
         )
         .await
     }
@@ -69,6 +117,7 @@ impl<V: IdentityVault> Identity<V> {
             storage_clone,
             Arc::new(trust_policy),
             timeout,
+            ChannelCredentials::none(),
         )
         .await
     }
@@ -139,6 +188,38 @@ mod test {
         ctx.stop().await
     }
 
+    #[allow(non_snake_case)]
+    #[ockam_macros::test]
+    async fn secure_channel_metrics__untrusted_initiator__should_count_failure(
+        ctx: &mut Context,
+    ) -> Result<()> {
+        let vault = Vault::create();
+        let storage = InMemoryStorage::new();
+
+        let alice = Identity::create(ctx, &vault).await?;
+        let bob = Identity::create(ctx, &vault).await?;
+
+        // Bob only trusts himself, so he rejects Alice's identity
+        // after Alice has already completed her side of the handshake
+        let alice_trust_policy = TrustIdentifierPolicy::new(bob.identifier().clone());
+        let bob_trust_policy = TrustIdentifierPolicy::new(bob.identifier().clone());
+
+        bob.create_secure_channel_listener("bob_listener", bob_trust_policy, &storage)
+            .await?;
+        alice
+            .create_secure_channel(route!["bob_listener"], alice_trust_policy, &storage)
+            .await?;
+        sleep(Duration::from_millis(100)).await;
+
+        let metrics = ctx.metrics();
+        let handshakes = metrics.counter("ockam_secure_channel_handshakes_total", "", &[]);
+        let failures = metrics.counter("ockam_secure_channel_handshake_failures_total", "", &[]);
+        assert_eq!(handshakes.get(), 1);
+        assert_eq!(failures.get(), 1);
+
+        ctx.stop().await
+    }
+
     #[ockam_macros::test]
     async fn test_tunneled_secure_channel_works(ctx: &mut Context) -> Result<()> {
         let vault = Vault::create();
@@ -0,0 +1,58 @@
+use crate::PublicIdentity;
+use ockam_core::compat::vec::Vec;
+
+/// Credentials carried by the handshake of a secure channel.
+///
+/// With credentials in the handshake, the attributes of the other side are
+/// known as soon as the channel is created, and the channel is refused if
+/// its credential does not verify.
+#[derive(Clone, Default)]
+pub struct ChannelCredentials {
+    present: bool,
+    authorities: Option<Vec<PublicIdentity>>,
+}
+
+impl ChannelCredentials {
+    /// No credentials in the handshake
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Present the credential of our identity
+    pub fn present() -> Self {
+        Self {
+            present: true,
+            authorities: None,
+        }
+    }
+
+    /// Require a credential of the other side, issued by one of the
+    /// `authorities`
+    pub fn require(authorities: Vec<PublicIdentity>) -> Self {
+        Self {
+            present: false,
+            authorities: Some(authorities),
+        }
+    }
+
+    /// Present the credential of our identity and require a credential of
+    /// the other side, issued by one of the `authorities`
+    pub fn mutual(authorities: Vec<PublicIdentity>) -> Self {
+        Self {
+            present: true,
+            authorities: Some(authorities),
+        }
+    }
+
+    pub(crate) fn is_none(&self) -> bool {
+        !self.present && self.authorities.is_none()
+    }
+
+    pub(crate) fn presents(&self) -> bool {
+        self.present
+    }
+
+    pub(crate) fn authorities(&self) -> Option<&[PublicIdentity]> {
+        self.authorities.as_deref()
+    }
+}
@@ -1,8 +1,9 @@
 use crate::authenticated_storage::AuthenticatedStorage;
+use crate::credential::Credential;
 use crate::{
-    EncryptorWorker, Identity, IdentityChannelMessage, IdentityError, IdentityIdentifier,
-    IdentitySecureChannelLocalInfo, IdentityVault, PublicIdentity, SecureChannelTrustInfo,
-    TrustPolicy,
+    ChannelCredentials, EncryptorWorker, Identity, IdentityChannelMessage, IdentityError,
+    IdentityIdentifier, IdentitySecureChannelLocalInfo, IdentityVault, PublicIdentity,
+    SecureChannelTrustInfo, TrustPolicy,
 };
 use core::future::Future;
 use core::pin::Pin;
@@ -26,7 +27,12 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 #[derive(Serialize, Deserialize, Message)]
-pub(crate) struct AuthenticationConfirmation(pub Address);
+pub(crate) enum AuthenticationConfirmation {
+    /// The channel is established, with the given encryptor address
+    Confirmed(Address),
+    /// The credential check of the handshake failed
+    Rejected,
+}
 
 trait StartSecureChannelFuture: Future<Output = Result<SecureChannelInfo>> + Send + 'static {}
 
@@ -49,6 +55,13 @@ struct InitiatorSendIdentity {
     callback_address: Address,
 }
 
+struct InitiatorWaitForConfirm {
+    local_secure_channel_address: Address,
+    remote_identity_secure_channel_address: Address,
+    their_identity_id: IdentityIdentifier,
+    callback_address: Address,
+}
+
 struct ResponderWaitForIdentity {
     auth_hash: [u8; 32],
     local_secure_channel_address: Address,
@@ -65,6 +78,7 @@ enum State {
     InitiatorStartChannel(InitiatorStartChannel),
     ResponderWaitForKex(ResponderWaitForKex),
     InitiatorSendIdentity(InitiatorSendIdentity),
+    InitiatorWaitForConfirm(InitiatorWaitForConfirm),
     ResponderWaitForIdentity(ResponderWaitForIdentity),
     Initialized(Initialized),
 }
@@ -76,6 +90,7 @@ pub(crate) struct DecryptorWorker<V: IdentityVault, S: AuthenticatedStorage> {
     identity: Identity<V>,
     storage: S,
     trust_policy: Arc<dyn TrustPolicy>,
+    credentials: ChannelCredentials,
     state: Option<State>,
 }
 
@@ -87,6 +102,7 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
         storage: S,
         trust_policy: Arc<dyn TrustPolicy>,
         timeout: Duration,
+        credentials: ChannelCredentials,
     ) -> Result<Address> {
         let child_address = Address::random_local();
         let mut child_ctx = ctx.new_detached(child_address.clone()).await?;
@@ -116,6 +132,7 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
             kex_callback_address: None,
             identity,
             trust_policy,
+            credentials,
             storage,
             state: Some(state),
         };
@@ -127,14 +144,18 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
             &self_address
         );
 
-        let encryptor_address = child_ctx
+        let confirmation = child_ctx
             .receive_timeout::<AuthenticationConfirmation>(timeout.as_secs())
             .await?
             .take()
-            .body()
-            .0;
+            .body();
 
-        Ok(encryptor_address)
+        match confirmation {
+            AuthenticationConfirmation::Confirmed(encryptor_address) => Ok(encryptor_address),
+            AuthenticationConfirmation::Rejected => {
+                Err(IdentityError::SecureChannelRejected.into())
+            }
+        }
     }
 
     pub(crate) async fn create_responder(
@@ -142,6 +163,7 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
         identity: Identity<V>,
         storage: S,
         trust_policy: Arc<dyn TrustPolicy>,
+        credentials: ChannelCredentials,
         msg: Routed<CreateResponderChannelMessage>,
     ) -> Result<()> {
         let return_route = msg.return_route();
@@ -167,6 +189,7 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
             self_address: self_address.clone(),
             identity,
             trust_policy,
+            credentials,
             storage,
             kex_callback_address: Some(kex_callback_address.clone()),
             state: Some(state),
@@ -221,9 +244,18 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
             .create_signature(&kex_msg.auth_hash(), None)
             .await?;
         let identity = self.identity.export().await?;
-        let msg = IdentityChannelMessage::Request {
-            identity,
-            signature: signature.as_ref().to_vec(),
+        let signature = signature.as_ref().to_vec();
+        let msg = if self.credentials.is_none() {
+            IdentityChannelMessage::Request {
+                identity,
+                signature,
+            }
+        } else {
+            IdentityChannelMessage::RequestWithCredential {
+                identity,
+                signature,
+                credential: self.credential_to_present().await?,
+            }
         };
         ctx.send_from_address(
             route![kex_msg.address().clone(), state.first_responder_address],
@@ -255,14 +287,11 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
         }
 
         let body = IdentityChannelMessage::decode(msg.payload())?;
+        let confirms = body.confirms();
 
         // Wait for responder to send us his Identity and Identity Proof.
         // In case of using Noise XX this is m4 message.
-        if let IdentityChannelMessage::Request {
-            identity,
-            signature,
-        } = body
-        {
+        if let Some((identity, signature, credential)) = body.into_request() {
             debug!("Received Authentication request");
 
             let their_identity = PublicIdentity::import(&identity, &self.identity.vault).await?;
@@ -303,6 +332,20 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
                 their_identity_id
             );
 
+            // Check their credential before the channel can be used
+            if let Err(e) = self.check_credential(their_identity_id, credential).await {
+                if confirms {
+                    ctx.send_from_address(
+                        return_route,
+                        IdentityChannelMessage::Reject,
+                        self.self_address.clone(),
+                    )
+                    .await?;
+                }
+                self.reject(ctx, &state.callback_address).await?;
+                return Err(e);
+            }
+
             // Prove we posses our Identity key
             let identity = self.identity.export().await?;
             let signature = self
@@ -310,10 +353,9 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
                 .create_signature(&state.channel.auth_hash(), None)
                 .await?;
 
-            let auth_msg = IdentityChannelMessage::Response {
-                identity,
-                signature: signature.as_ref().to_vec(),
-            };
+            let credential = self.credential_to_present().await?;
+            let auth_msg =
+                IdentityChannelMessage::response(identity, signature.as_ref().to_vec(), credential);
 
             let remote_identity_secure_channel_address = return_route.recipient();
 
@@ -321,38 +363,97 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
                 .await?;
             debug!("Sent Authentication response");
 
-            let encryptor_address = Address::random_local();
-
-            self.state = Some(State::Initialized(Initialized {
+            let state = InitiatorWaitForConfirm {
                 local_secure_channel_address: state.channel.address(),
+                remote_identity_secure_channel_address,
                 their_identity_id: their_identity_id.clone(),
-                encryptor_address: encryptor_address.clone(),
-            }));
+                callback_address: state.callback_address,
+            };
 
-            let encryptor = EncryptorWorker::new(
-                self.is_initiator,
-                remote_identity_secure_channel_address,
-                state.channel.address(),
-            );
+            // The responder may still reject our credential
+            if confirms {
+                self.state = Some(State::InitiatorWaitForConfirm(state));
+                return Ok(());
+            }
 
-            ctx.start_worker(encryptor_address.clone(), encryptor)
-                .await?;
+            self.initialize_initiator(ctx, state).await
+        } else {
+            Err(IdentityError::InvalidSecureChannelInternalState.into())
+        }
+    }
 
-            info!(
-                "Initialized IdentitySecureChannel Initiator at local: {}, remote: {}",
-                &encryptor_address, &self.self_address
-            );
+    async fn handle_confirm(
+        &mut self,
+        ctx: &mut <Self as Worker>::Context,
+        msg: Routed<<Self as Worker>::Message>,
+        state: InitiatorWaitForConfirm,
+    ) -> Result<()> {
+        // Ensure message came from dedicated SecureChannel
+        if msg.return_route().next()? != &state.local_secure_channel_address {
+            return Err(IdentityError::UnknownChannelMsgDestination.into());
+        }
 
-            ctx.send(
-                state.callback_address,
-                AuthenticationConfirmation(encryptor_address),
-            )
+        match IdentityChannelMessage::decode(msg.payload())? {
+            IdentityChannelMessage::Confirm => {
+                debug!("Received Authentication confirmation");
+                self.initialize_initiator(ctx, state).await
+            }
+            IdentityChannelMessage::Reject => {
+                warn!(
+                    "SecureChannel rejected by responder: {}",
+                    state.their_identity_id
+                );
+                self.reject(ctx, &state.callback_address).await?;
+                Err(IdentityError::SecureChannelRejected.into())
+            }
+            _ => Err(IdentityError::InvalidSecureChannelInternalState.into()),
+        }
+    }
+
+    /// Start the encryptor of an authenticated channel and hand it over to
+    /// the caller waiting for the channel
+    async fn initialize_initiator(
+        &mut self,
+        ctx: &mut <Self as Worker>::Context,
+        state: InitiatorWaitForConfirm,
+    ) -> Result<()> {
+        let encryptor_address = Address::random_local();
+
+        self.state = Some(State::Initialized(Initialized {
+            local_secure_channel_address: state.local_secure_channel_address.clone(),
+            their_identity_id: state.their_identity_id,
+            encryptor_address: encryptor_address.clone(),
+        }));
+
+        let encryptor = EncryptorWorker::new(
+            self.is_initiator,
+            state.remote_identity_secure_channel_address,
+            state.local_secure_channel_address,
+        );
+
+        ctx.start_worker(encryptor_address.clone(), encryptor)
             .await?;
 
-            Ok(())
-        } else {
-            Err(IdentityError::InvalidSecureChannelInternalState.into())
-        }
+        info!(
+            "Initialized IdentitySecureChannel Initiator at local: {}, remote: {}",
+            &encryptor_address, &self.self_address
+        );
+
+        ctx.send(
+            state.callback_address,
+            AuthenticationConfirmation::Confirmed(encryptor_address),
+        )
+        .await
+    }
+
+    /// Fail the creation of the channel and stop this initiator
+    async fn reject(&mut self, ctx: &Context, callback_address: &Address) -> Result<()> {
+        ctx.send(
+            callback_address.clone(),
+            AuthenticationConfirmation::Rejected,
+        )
+        .await?;
+        ctx.stop_worker(self.self_address.clone()).await
     }
 
     async fn handle_receive_identity(
@@ -370,13 +471,16 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
 
         let body = IdentityChannelMessage::decode(msg.payload())?;
 
+        // The initiator refused our credential
+        if let IdentityChannelMessage::Reject = body {
+            warn!("SecureChannel rejected by initiator");
+            ctx.stop_worker(self.self_address.clone()).await?;
+            return Err(IdentityError::SecureChannelRejected.into());
+        }
+
         // Wait for responder to send us his Identity and Identity Proof.
         // In case of using Noise XX this is m4 message.
-        if let IdentityChannelMessage::Response {
-            identity,
-            signature,
-        } = body
-        {
+        if let Some((identity, signature, credential)) = body.into_response() {
             debug!("Received Authentication response");
 
             let their_identity = PublicIdentity::import(&identity, &self.identity.vault).await?;
@@ -417,6 +521,19 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
                 their_identity_id
             );
 
+            // Check their credential before the channel can be used, and
+            // let the initiator know that it failed
+            if let Err(e) = self.check_credential(their_identity_id, credential).await {
+                ctx.send_from_address(
+                    return_route,
+                    IdentityChannelMessage::Reject,
+                    self.self_address.clone(),
+                )
+                .await?;
+                ctx.stop_worker(self.self_address.clone()).await?;
+                return Err(e);
+            }
+
             let remote_identity_secure_channel_address = return_route.recipient();
 
             let encryptor_address = Address::random_local();
@@ -441,12 +558,78 @@ impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
                 &encryptor_address, &self.self_address
             );
 
+            // The initiator waits for our answer when credentials are part
+            // of the handshake
+            if !self.credentials.is_none() {
+                ctx.send_from_address(
+                    return_route,
+                    IdentityChannelMessage::Confirm,
+                    self.self_address.clone(),
+                )
+                .await?;
+            }
+
             Ok(())
         } else {
             Err(IdentityError::InvalidSecureChannelInternalState.into())
         }
     }
 
+    /// Our encoded credential, if it is presented during the handshake
+    async fn credential_to_present(&mut self) -> Result<Option<Vec<u8>>> {
+        if !self.credentials.presents() {
+            return Ok(None);
+        }
+        match self.identity.credential().await {
+            Some(c) => Ok(Some(minicbor::to_vec(&c)?)),
+            None => Err(IdentityError::MissingCredential.into()),
+        }
+    }
+
+    /// Verify their credential if one is required, and store its attributes
+    async fn check_credential(
+        &mut self,
+        their_identity_id: &IdentityIdentifier,
+        credential: Option<Vec<u8>>,
+    ) -> Result<()> {
+        let authorities = match self.credentials.authorities() {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        let credential = credential.ok_or(IdentityError::MissingCredential)?;
+        let credential: Credential =
+            minicbor::decode(&credential).map_err(|_| IdentityError::InvalidCredentialFormat)?;
+        self.identity
+            .receive_presented_credential(
+                their_identity_id.clone(),
+                credential,
+                authorities,
+                &self.storage,
+            )
+            .await?;
+        info!(
+            "Verified credential of SecureChannel from: {}",
+            their_identity_id
+        );
+        Ok(())
+    }
+
+    /// Count a completed or failed handshake
+    fn record_handshake(ctx: &Context, completed: bool) {
+        let (name, help) = if completed {
+            (
+                "ockam_secure_channel_handshakes_total",
+                "Completed secure channel handshakes",
+            )
+        } else {
+            (
+                "ockam_secure_channel_handshake_failures_total",
+                "Failed secure channel handshakes",
+            )
+        };
+        ctx.metrics().counter(name, help, &[]).inc();
+    }
+
     // FIXME: Avoid situation where we take state but don't put it back because of an error
     fn take_state(&mut self) -> Result<State> {
         if let Some(s) = self.state.take() {
@@ -520,11 +703,14 @@ impl<V: IdentityVault, S: AuthenticatedStorage> Worker for DecryptorWorker<V, S>
     type Message = Any;
     type Context = Context;
 
-    async fn initialize(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
         if self.is_initiator {
             match self.take_state()? {
                 State::InitiatorStartChannel(s) => {
-                    let channel = s.channel_future.await?;
+                    let channel = s.channel_future.await.map_err(|e| {
+                        Self::record_handshake(ctx, false);
+                        e
+                    })?;
 
                     self.state = Some(State::InitiatorSendIdentity(InitiatorSendIdentity {
                         channel,
@@ -542,10 +728,36 @@ impl<V: IdentityVault, S: AuthenticatedStorage> Worker for DecryptorWorker<V, S>
         &mut self,
         ctx: &mut Self::Context,
         msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let state = self.take_state()?;
+        let handshake = !matches!(state, State::Initialized(_));
+
+        let res = self.handle_state(ctx, msg, state).await;
+        if handshake {
+            match res {
+                Err(_) => Self::record_handshake(ctx, false),
+                Ok(()) if matches!(self.state, Some(State::Initialized(_))) => {
+                    Self::record_handshake(ctx, true)
+                }
+                Ok(()) => {}
+            }
+        }
+
+        res
+    }
+}
+
+impl<V: IdentityVault, S: AuthenticatedStorage> DecryptorWorker<V, S> {
+    /// Handle a message according to the current state of the channel
+    async fn handle_state(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Any>,
+        state: State,
     ) -> Result<()> {
         let msg_addr = msg.msg_addr();
 
-        match self.take_state()? {
+        match state {
             State::InitiatorStartChannel(_) => {
                 return Err(IdentityError::InvalidSecureChannelInternalState.into())
             }
@@ -567,6 +779,13 @@ impl<V: IdentityVault, S: AuthenticatedStorage> Worker for DecryptorWorker<V, S>
                     return Err(IdentityError::UnknownChannelMsgDestination.into());
                 }
             }
+            State::InitiatorWaitForConfirm(s) => {
+                if msg_addr == self.self_address {
+                    self.handle_confirm(ctx, msg, s).await?;
+                } else {
+                    return Err(IdentityError::UnknownChannelMsgDestination.into());
+                }
+            }
             State::ResponderWaitForIdentity(s) => {
                 if msg_addr == self.self_address {
                     self.handle_receive_identity(ctx, msg, s).await?;
@@ -13,4 +13,83 @@ pub(crate) enum IdentityChannelMessage {
         signature: Vec<u8>,
     },
     Confirm,
+    /// `Request` with the credential of the sender, if any, sent instead of
+    /// `Request` when credentials are part of the handshake. The sender
+    /// answers the `Response` with `Confirm` or `Reject`.
+    RequestWithCredential {
+        identity: Vec<u8>,
+        signature: Vec<u8>,
+        credential: Option<Vec<u8>>,
+    },
+    /// `Response` with the credential of the sender, sent instead of
+    /// `Response` when credentials are part of the handshake
+    ResponseWithCredential {
+        identity: Vec<u8>,
+        signature: Vec<u8>,
+        credential: Vec<u8>,
+    },
+    /// The credential check of the handshake failed
+    Reject,
+}
+
+/// Identity, signature and credential of a handshake message
+pub(crate) type Handshake = (Vec<u8>, Vec<u8>, Option<Vec<u8>>);
+
+impl IdentityChannelMessage {
+    /// A `Response`, with `credential` if there is one
+    pub(crate) fn response(
+        identity: Vec<u8>,
+        signature: Vec<u8>,
+        credential: Option<Vec<u8>>,
+    ) -> Self {
+        match credential {
+            Some(credential) => Self::ResponseWithCredential {
+                identity,
+                signature,
+                credential,
+            },
+            None => Self::Response {
+                identity,
+                signature,
+            },
+        }
+    }
+
+    /// Identity, signature and credential of a `Request`
+    pub(crate) fn into_request(self) -> Option<Handshake> {
+        match self {
+            Self::Request {
+                identity,
+                signature,
+            } => Some((identity, signature, None)),
+            Self::RequestWithCredential {
+                identity,
+                signature,
+                credential,
+            } => Some((identity, signature, credential)),
+            _ => None,
+        }
+    }
+
+    /// Whether the sender of a `Request` answers the `Response` with
+    /// `Confirm` or `Reject`
+    pub(crate) fn confirms(&self) -> bool {
+        matches!(self, Self::RequestWithCredential { .. })
+    }
+
+    /// Identity, signature and credential of a `Response`
+    pub(crate) fn into_response(self) -> Option<Handshake> {
+        match self {
+            Self::Response {
+                identity,
+                signature,
+            } => Some((identity, signature, None)),
+            Self::ResponseWithCredential {
+                identity,
+                signature,
+                credential,
+            } => Some((identity, signature, Some(credential))),
+            _ => None,
+        }
+    }
 }
@@ -174,6 +174,12 @@ impl<'a> CredentialData<'a, Unverified> {
     pub fn unverfied_key_label(&self) -> &str {
         &self.issuer_key_label
     }
+    pub fn unverified_created_at(&self) -> Timestamp {
+        self.created
+    }
+    pub fn unverified_expires_at(&self) -> Timestamp {
+        self.expires
+    }
 }
 
 impl<'a, 'b: 'a> TryFrom<&'b Credential<'a>> for CredentialData<'a, Unverified> {
@@ -20,6 +20,8 @@ pub enum IdentityError {
     InvalidCredentialFormat,
     UnknownAuthority,
     CredentialVerificationFailed,
+    MissingCredential,
+    SecureChannelRejected,
 }
 
 impl ockam_core::compat::error::Error for IdentityError {}
@@ -4,11 +4,11 @@ use ockam_core::{route, Result, Routed, Worker};
 use ockam_identity::authenticated_storage::mem::InMemoryStorage;
 use ockam_identity::credential::access_control::CredentialAccessControl;
 use ockam_identity::credential::{AttributesStorageUtils, Credential};
-use ockam_identity::{Identity, TrustEveryonePolicy, TrustIdentifierPolicy};
+use ockam_identity::{ChannelCredentials, Identity, TrustEveryonePolicy, TrustIdentifierPolicy};
 use ockam_node::{Context, WorkerBuilder};
 use ockam_vault::Vault;
 use std::sync::atomic::{AtomicI8, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[ockam_macros::test]
 async fn full_flow_oneway(ctx: &mut Context) -> Result<()> {
@@ -223,3 +223,170 @@ async fn access_control(ctx: &mut Context) -> Result<()> {
 
     ctx.stop().await
 }
+
+#[ockam_macros::test]
+async fn handshake_twoway(ctx: &mut Context) -> Result<()> {
+    let vault = Vault::create();
+
+    let authority = Identity::create(ctx, &vault).await?;
+    let authorities = vec![authority.to_public().await?];
+
+    let server = Identity::create(ctx, &vault).await?;
+    let server_storage = InMemoryStorage::new();
+
+    let credential =
+        Credential::builder(server.identifier().clone()).with_attribute("is_admin", b"true");
+    let credential = authority.issue_credential(credential).await?;
+    server.set_credential(Some(credential)).await;
+
+    server
+        .create_secure_channel_listener_with_credentials(
+            "listener",
+            TrustEveryonePolicy,
+            &server_storage,
+            ChannelCredentials::mutual(authorities.clone()),
+        )
+        .await?;
+
+    let client = Identity::create(ctx, &vault).await?;
+    let client_storage = InMemoryStorage::new();
+
+    let credential =
+        Credential::builder(client.identifier().clone()).with_attribute("is_user", b"true");
+    let credential = authority.issue_credential(credential).await?;
+    client.set_credential(Some(credential)).await;
+
+    let channel = client
+        .create_secure_channel_with_credentials(
+            route!["listener"],
+            TrustEveryonePolicy,
+            &client_storage,
+            ChannelCredentials::mutual(authorities),
+        )
+        .await?;
+
+    // The attributes of the server are known once the channel is created
+    let attrs = AttributesStorageUtils::get_attributes(server.identifier(), &client_storage)
+        .await?
+        .unwrap();
+    assert_eq!(attrs.get("is_admin").unwrap().as_slice(), b"true");
+
+    // The attributes of the client are known once its first message arrives
+    ctx.send(route![channel, ctx.address()], "Hello".to_string())
+        .await?;
+    ctx.receive::<String>().await?;
+    let attrs = AttributesStorageUtils::get_attributes(client.identifier(), &server_storage)
+        .await?
+        .unwrap();
+    assert_eq!(attrs.get("is_user").unwrap().as_slice(), b"true");
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn handshake_unverified_credential(ctx: &mut Context) -> Result<()> {
+    let vault = Vault::create();
+
+    let authority = Identity::create(ctx, &vault).await?;
+    let authorities = vec![authority.to_public().await?];
+    let client = Identity::create(ctx, &vault).await?;
+    let client_storage = InMemoryStorage::new();
+
+    // The server presents no credential
+    let server = Identity::create(ctx, &vault).await?;
+    server
+        .create_secure_channel_listener("listener", TrustEveryonePolicy, &InMemoryStorage::new())
+        .await?;
+    let res = client
+        .create_secure_channel_with_credentials(
+            route!["listener"],
+            TrustEveryonePolicy,
+            &client_storage,
+            ChannelCredentials::require(authorities.clone()),
+        )
+        .await;
+    assert!(res.is_err());
+
+    // The server presents a credential of an unknown authority
+    let other_authority = Identity::create(ctx, &vault).await?;
+    let credential = Credential::builder(server.identifier().clone());
+    let credential = other_authority.issue_credential(credential).await?;
+    server.set_credential(Some(credential)).await;
+    server
+        .create_secure_channel_listener_with_credentials(
+            "presenting_listener",
+            TrustEveryonePolicy,
+            &InMemoryStorage::new(),
+            ChannelCredentials::present(),
+        )
+        .await?;
+    let res = client
+        .create_secure_channel_with_credentials(
+            route!["presenting_listener"],
+            TrustEveryonePolicy,
+            &client_storage,
+            ChannelCredentials::require(authorities),
+        )
+        .await;
+    assert!(res.is_err());
+    assert!(
+        AttributesStorageUtils::get_attributes(server.identifier(), &client_storage)
+            .await?
+            .is_none()
+    );
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn handshake_rejected_credential(ctx: &mut Context) -> Result<()> {
+    let vault = Vault::create();
+
+    let authority = Identity::create(ctx, &vault).await?;
+    let authorities = vec![authority.to_public().await?];
+
+    let server = Identity::create(ctx, &vault).await?;
+    let server_storage = InMemoryStorage::new();
+    server
+        .create_secure_channel_listener_with_credentials(
+            "listener",
+            TrustEveryonePolicy,
+            &server_storage,
+            ChannelCredentials::require(authorities),
+        )
+        .await?;
+
+    // The client presents no credential
+    let client = Identity::create(ctx, &vault).await?;
+    let client_storage = InMemoryStorage::new();
+    let start = Instant::now();
+    let res = client
+        .create_secure_channel(route!["listener"], TrustEveryonePolicy, &client_storage)
+        .await;
+    assert!(res.is_err());
+
+    // The client presents a credential of an unknown authority
+    let other_authority = Identity::create(ctx, &vault).await?;
+    let credential = Credential::builder(client.identifier().clone());
+    let credential = other_authority.issue_credential(credential).await?;
+    client.set_credential(Some(credential)).await;
+    let res = client
+        .create_secure_channel_with_credentials(
+            route!["listener"],
+            TrustEveryonePolicy,
+            &client_storage,
+            ChannelCredentials::present(),
+        )
+        .await;
+    assert!(res.is_err());
+
+    // Both failed on the rejection of the server, not on a timeout
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(
+        AttributesStorageUtils::get_attributes(client.identifier(), &server_storage)
+            .await?
+            .is_none()
+    );
+
+    ctx.stop().await
+}
@@ -0,0 +1,605 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    AttributeArgs, FnArg, GenericArgument, Ident, ItemTrait, Lit, Meta, NestedMeta, Pat,
+    PathArguments, ReturnType, TraitItem, TraitItemMethod, Type,
+};
+
+use crate::internals::{ctx::Context, symbol::*};
+
+pub(crate) fn expand(
+    input_trait: ItemTrait,
+    attrs: AttributeArgs,
+) -> Result<TokenStream, Vec<syn::Error>> {
+    let ctx = Context::new();
+    check_attrs(&ctx, &attrs);
+    let cont = Container::from_ast(&ctx, input_trait);
+    ctx.check()?;
+    Ok(output(cont))
+}
+
+fn check_attrs(ctx: &Context, attrs: &AttributeArgs) {
+    for attr in attrs {
+        match attr {
+            NestedMeta::Meta(m) => {
+                let path = m.path().into_token_stream().to_string().replace(' ', "");
+                ctx.error_spanned_by(m.path(), format!("unknown attribute `{}`", path));
+            }
+            NestedMeta::Lit(lit) => {
+                ctx.error_spanned_by(lit, "unexpected literal in attribute");
+            }
+        }
+    }
+}
+
+fn output(cont: Container) -> TokenStream {
+    let trait_ident = &cont.original_trait.ident;
+    let vis = &cont.original_trait.vis;
+    let dispatcher = dispatcher(&cont.endpoints);
+    let client_ident = format_ident!("{}Client", trait_ident);
+    let client_doc = format!("Client of the [`{}`] API.", trait_ident);
+    let client_methods = cont.endpoints.iter().map(client_method);
+
+    // The trait is emitted without the route attributes of its methods,
+    // which are not known to the compiler.
+    let mut original_trait = cont.original_trait.clone();
+    for item in original_trait.items.iter_mut() {
+        if let TraitItem::Method(m) = item {
+            m.attrs.retain(|a| route_method(&a.path).is_none());
+        }
+    }
+    let ItemTrait {
+        attrs,
+        unsafety,
+        trait_token,
+        ident,
+        generics,
+        colon_token,
+        supertraits,
+        items,
+        ..
+    } = original_trait;
+
+    quote! {
+        #(#attrs)*
+        #[ockam_core::async_trait]
+        #vis #unsafety #trait_token #ident #generics #colon_token #supertraits {
+            #(#items)*
+
+            /// Decode a request, dispatch it to the method handling its
+            /// path and method, and encode the response.
+            ///
+            /// Errors returned by the methods are turned into error
+            /// responses whose status is derived from the error kind.
+            async fn handle_request(&mut self, data: &[u8]) -> ockam_core::Result<Vec<u8>> {
+                #dispatcher
+            }
+        }
+
+        #[doc = #client_doc]
+        #vis struct #client_ident {
+            ctx: ockam_node::Context,
+            route: ockam_core::Route,
+            // The last response, which response bodies may borrow from
+            buf: Vec<u8>,
+        }
+
+        impl ::core::fmt::Debug for #client_ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(stringify!(#client_ident))
+                    .field("route", &self.route)
+                    .finish()
+            }
+        }
+
+        impl #client_ident {
+            /// Create a client sending its requests to `route`.
+            pub async fn new(route: ockam_core::Route, ctx: &ockam_node::Context) -> ockam_core::Result<Self> {
+                let ctx = ctx.new_detached(ockam_core::Address::random_local()).await?;
+                Ok(Self { ctx, route, buf: Vec::new() })
+            }
+
+            #(#client_methods)*
+        }
+    }
+}
+
+fn dispatcher(endpoints: &[Endpoint]) -> TokenStream {
+    // Paths are split in one more segment than the longest route, so
+    // that longer paths do not match any route.
+    let max_segments = endpoints
+        .iter()
+        .map(|e| e.segments.len())
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    let arms = endpoints.iter().map(dispatcher_arm);
+
+    // Known paths requested with another method
+    let mut known_paths: Vec<String> = Vec::new();
+    let mut other_method_arms = Vec::new();
+    for e in endpoints {
+        let pattern: Vec<TokenStream> = e
+            .segments
+            .iter()
+            .map(|s| match s {
+                Segment::Literal(l) => quote! { #l },
+                Segment::Param(_) => quote! { _ },
+            })
+            .collect();
+        let shape = quote! { #(#pattern),* }.to_string();
+        if !known_paths.contains(&shape) {
+            known_paths.push(shape);
+            other_method_arms.push(quote! {
+                (_, [#(#pattern),*]) => ockam_core::api::invalid_method(&req).to_vec()?,
+            });
+        }
+    }
+
+    quote! {
+        let mut dec = minicbor::Decoder::new(data);
+        let req: ockam_core::api::Request = dec.decode()?;
+        let segments = req.path_segments::<#max_segments>();
+        let res = match (req.method(), segments.as_slice()) {
+            #(#arms)*
+            #(#other_method_arms)*
+            _ => ockam_core::api::unknown_path(&req).to_vec()?,
+        };
+        Ok(res)
+    }
+}
+
+fn dispatcher_arm(e: &Endpoint) -> TokenStream {
+    let variant = &e.method.variant;
+    let ident = &e.ident;
+    let pattern = e.segments.iter().map(|s| match s {
+        Segment::Literal(l) => quote! { #l },
+        Segment::Param(p) => quote! { #p },
+    });
+
+    let params = e.params.iter().filter(|p| !p.is_str).map(|p| {
+        let name = &p.ident;
+        let ty = &p.ty;
+        let msg = format!("invalid path parameter `{}`", name);
+        quote! {
+            let #name: #ty = match #name.parse() {
+                Ok(v) => v,
+                Err(_) => return Ok(ockam_core::api::bad_request(&req, #msg).to_vec()?),
+            };
+        }
+    });
+
+    let body = e.body.as_ref().map(|b| {
+        let name = &b.ident;
+        let ty = &b.ty;
+        let schema = schema_name(&e.request_schema);
+        quote! {
+            if !req.has_body() {
+                return Ok(ockam_core::api::bad_request(&req, "missing request body").to_vec()?);
+            }
+            ockam_core::api::assert_request_match(#schema, data);
+            let #name: #ty = match dec.decode() {
+                Ok(b) => b,
+                Err(_) => return Ok(ockam_core::api::bad_request(&req, "invalid request body").to_vec()?),
+            };
+        }
+    });
+
+    let args = e.args.iter();
+    let ok = match e.response {
+        ResponseKind::Unit => quote! {
+            Ok(()) => ockam_core::api::Response::ok(req.id()).to_vec()?,
+        },
+        ResponseKind::Option => quote! {
+            Ok(Some(b)) => ockam_core::api::Response::ok(req.id()).body(b).to_vec()?,
+            Ok(None) => ockam_core::api::Response::not_found(req.id()).to_vec()?,
+        },
+        ResponseKind::Value => quote! {
+            Ok(b) => ockam_core::api::Response::ok(req.id()).body(b).to_vec()?,
+        },
+    };
+
+    quote! {
+        (Some(ockam_core::api::Method::#variant), [#(#pattern),*]) => {
+            #(#params)*
+            #body
+            match self.#ident(#(#args),*).await {
+                #ok
+                Err(e) => ockam_core::api::error_response(&req, &e).to_vec()?,
+            }
+        }
+    }
+}
+
+fn client_method(e: &Endpoint) -> TokenStream {
+    let method = &e.original_method;
+    let docs = method.attrs.iter().filter(|a| a.path.is_ident("doc"));
+    let ident = &method.sig.ident;
+    let generics = &method.sig.generics;
+    // The lifetime of the receiver is kept, response bodies may borrow
+    // from the client through it
+    let lifetime = match method.sig.inputs.first() {
+        Some(FnArg::Receiver(r)) => r.reference.as_ref().and_then(|(_, l)| l.clone()),
+        _ => None,
+    };
+    let inputs = method
+        .sig
+        .inputs
+        .iter()
+        .filter(|i| matches!(i, FnArg::Typed(_)));
+    let output = &method.sig.output;
+    let label = ident.to_string();
+    let builder = &e.method.builder;
+
+    // Path parameters are substituted in the template
+    let mut template = String::new();
+    let mut values = Vec::new();
+    for s in &e.segments {
+        template.push('/');
+        match s {
+            Segment::Literal(l) => template.push_str(l),
+            Segment::Param(p) => {
+                template.push_str("{}");
+                values.push(p);
+            }
+        }
+    }
+    let path = if values.is_empty() {
+        quote! { #template }
+    } else {
+        quote! { format!(#template, #(#values),*) }
+    };
+
+    let req = match &e.body {
+        Some(b) => {
+            let name = &b.ident;
+            quote! { ockam_core::api::Request::#builder(#path).body(#name) }
+        }
+        None => quote! { ockam_core::api::Request::#builder(#path) },
+    };
+    let request_schema = schema_name(&e.request_schema);
+    let response_schema = schema_name(&e.response_schema);
+    let decode = match e.response {
+        ResponseKind::Unit => quote! { ockam_core::api::is_ok(label, &self.buf) },
+        ResponseKind::Option => {
+            quote! { ockam_core::api::decode_option(label, #response_schema, &self.buf) }
+        }
+        ResponseKind::Value => {
+            quote! { ockam_core::api::decode_response(label, #response_schema, &self.buf) }
+        }
+    };
+
+    quote! {
+        #(#docs)*
+        pub async fn #ident #generics(& #lifetime mut self, #(#inputs),*) #output {
+            let label = #label;
+            let req = #req;
+            self.buf = ockam_node::api::request(&mut self.ctx, label, #request_schema, self.route.clone(), req).await?;
+            #decode
+        }
+    }
+}
+
+fn schema_name(name: &Option<String>) -> TokenStream {
+    match name {
+        Some(n) => quote! { #n },
+        None => quote! { None },
+    }
+}
+
+fn route_method(path: &syn::Path) -> Option<RouteMethod> {
+    let (variant, builder) = if path == GET {
+        ("Get", "get")
+    } else if path == POST {
+        ("Post", "post")
+    } else if path == PUT {
+        ("Put", "put")
+    } else if path == DELETE {
+        ("Delete", "delete")
+    } else if path == PATCH {
+        ("Patch", "patch")
+    } else {
+        return None;
+    };
+    Some(RouteMethod {
+        variant: format_ident!("{}", variant),
+        builder: format_ident!("{}", builder),
+    })
+}
+
+struct Container {
+    // API endpoints, one per trait method.
+    endpoints: Vec<Endpoint>,
+    // Original trait.
+    original_trait: ItemTrait,
+}
+
+impl Container {
+    fn from_ast(ctx: &Context, input_trait: ItemTrait) -> Self {
+        let mut endpoints = Vec::new();
+        for item in &input_trait.items {
+            match item {
+                TraitItem::Method(m) => {
+                    if let Some(e) = Endpoint::from_ast(ctx, m) {
+                        endpoints.push(e)
+                    }
+                }
+                other => ctx.error_spanned_by(other, "only methods are supported in API traits"),
+            }
+        }
+        Self {
+            endpoints,
+            original_trait: input_trait,
+        }
+    }
+}
+
+struct RouteMethod {
+    // The `ockam_core::api::Method` variant (e.g. `Get`).
+    variant: Ident,
+    // The `ockam_core::api::Request` builder function (e.g. `get`).
+    builder: Ident,
+}
+
+enum Segment {
+    Literal(String),
+    Param(Ident),
+}
+
+struct Param {
+    ident: Ident,
+    ty: Type,
+    // Whether the parameter is a `&str`, which needs no parsing.
+    is_str: bool,
+}
+
+enum ResponseKind {
+    // `Result<()>`: an empty response.
+    Unit,
+    // `Result<Option<T>>`: `None` is a not found response.
+    Option,
+    // `Result<T>`: a response with a body.
+    Value,
+}
+
+struct Endpoint {
+    ident: Ident,
+    method: RouteMethod,
+    segments: Vec<Segment>,
+    // Path parameters
+    params: Vec<Param>,
+    // The request body parameter, if any
+    body: Option<Param>,
+    // Method arguments, in declaration order
+    args: Vec<Ident>,
+    response: ResponseKind,
+    request_schema: Option<String>,
+    response_schema: Option<String>,
+    original_method: TraitItemMethod,
+}
+
+impl Endpoint {
+    fn from_ast(ctx: &Context, m: &TraitItemMethod) -> Option<Self> {
+        if m.sig.asyncness.is_none() {
+            let msg = "the `async` keyword is missing from the method declaration";
+            ctx.error_spanned_by(m.sig.fn_token, msg);
+        }
+        if m.default.is_some() {
+            ctx.error_spanned_by(
+                &m.sig.ident,
+                "API methods can not have a default implementation",
+            );
+        }
+        if !matches!(m.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+            let msg = "API methods must take `&self` or `&mut self` as first argument";
+            ctx.error_spanned_by(&m.sig, msg);
+        }
+
+        let mut routes = m
+            .attrs
+            .iter()
+            .filter_map(|a| route_method(&a.path).map(|method| (a, method)));
+        let (attr, method) = match routes.next() {
+            Some(route) => route,
+            None => {
+                let msg = "missing route attribute, e.g. `#[get(\"/path\")]`";
+                ctx.error_spanned_by(&m.sig.ident, msg);
+                return None;
+            }
+        };
+        if let Some((a, _)) = routes.next() {
+            ctx.error_spanned_by(a, "API methods must have exactly one route attribute");
+        }
+
+        let (template, request_schema, response_schema) = parse_route_attr(ctx, attr)?;
+        let segments = parse_template(ctx, &template);
+
+        let mut params = Vec::new();
+        let mut body: Option<Param> = None;
+        let mut args = Vec::new();
+        for input in m.sig.inputs.iter() {
+            let arg = match input {
+                FnArg::Typed(arg) => arg,
+                FnArg::Receiver(_) => continue,
+            };
+            let ident = match &*arg.pat {
+                Pat::Ident(p) => p.ident.clone(),
+                other => {
+                    ctx.error_spanned_by(other, "API method arguments must be identifiers");
+                    continue;
+                }
+            };
+            let param = Param {
+                ident: ident.clone(),
+                ty: (*arg.ty).clone(),
+                is_str: is_str_ref(&arg.ty),
+            };
+            args.push(ident.clone());
+            let in_path = segments
+                .iter()
+                .any(|s| matches!(s, Segment::Param(p) if *p == ident));
+            if in_path {
+                params.push(param);
+            } else if body.is_none() {
+                body = Some(param);
+            } else {
+                let msg =
+                    "only one argument can be the request body, the others must be path parameters";
+                ctx.error_spanned_by(arg, msg);
+            }
+        }
+        for s in &segments {
+            if let Segment::Param(p) = s {
+                if !params.iter().any(|a| a.ident == *p) {
+                    let msg = format!("path parameter `{}` is not an argument of the method", p);
+                    ctx.error_spanned_by(attr, msg);
+                }
+            }
+        }
+
+        let response = response_kind(ctx, &m.sig.output)?;
+
+        Some(Self {
+            ident: m.sig.ident.clone(),
+            method,
+            segments,
+            params,
+            body,
+            args,
+            response,
+            request_schema,
+            response_schema,
+            original_method: m.clone(),
+        })
+    }
+}
+
+/// Parse `#[get("/path/{param}", request = "...", response = "...")]`.
+fn parse_route_attr(
+    ctx: &Context,
+    attr: &syn::Attribute,
+) -> Option<(String, Option<String>, Option<String>)> {
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        Ok(other) => {
+            let msg = "expected a path, e.g. `#[get(\"/path/{param}\")]`";
+            ctx.error_spanned_by(other, msg);
+            return None;
+        }
+        Err(err) => {
+            ctx.syn_error(err);
+            return None;
+        }
+    };
+
+    let mut template = None;
+    let mut request_schema = None;
+    let mut response_schema = None;
+    for nested in list.nested.iter() {
+        match nested {
+            NestedMeta::Lit(Lit::Str(s)) if template.is_none() => template = Some(s.value()),
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path == REQUEST || nv.path == RESPONSE => {
+                let name = match &nv.lit {
+                    Lit::Str(s) => s.value(),
+                    other => {
+                        let msg = format!(
+                            "expected a CDDL type name: `{} = \"...\"`",
+                            nv.path.to_token_stream()
+                        );
+                        ctx.error_spanned_by(other, msg);
+                        continue;
+                    }
+                };
+                if nv.path == REQUEST {
+                    request_schema = Some(name)
+                } else {
+                    response_schema = Some(name)
+                }
+            }
+            NestedMeta::Meta(m) => {
+                let path = m.path().into_token_stream().to_string().replace(' ', "");
+                ctx.error_spanned_by(m.path(), format!("unknown attribute `{}`", path));
+            }
+            NestedMeta::Lit(lit) => ctx.error_spanned_by(lit, "unexpected literal in attribute"),
+        }
+    }
+
+    match template {
+        Some(t) => Some((t, request_schema, response_schema)),
+        None => {
+            ctx.error_spanned_by(attr, "missing path, e.g. `#[get(\"/path/{param}\")]`");
+            None
+        }
+    }
+}
+
+/// Split a path template such as `/node/{name}` in its segments.
+fn parse_template(ctx: &Context, template: &str) -> Vec<Segment> {
+    template
+        .trim_start_matches('/')
+        .split('/')
+        .map(|s| {
+            if let Some(name) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                match syn::parse_str::<Ident>(name) {
+                    Ok(ident) => Segment::Param(ident),
+                    Err(_) => {
+                        ctx.error_spanned_by(template, format!("invalid path parameter `{}`", s));
+                        Segment::Literal(s.to_string())
+                    }
+                }
+            } else {
+                Segment::Literal(s.to_string())
+            }
+        })
+        .collect()
+}
+
+fn is_str_ref(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => matches!(&*r.elem, Type::Path(p) if p.path.is_ident("str")),
+        _ => false,
+    }
+}
+
+/// Classify the `T` of a `Result<T>` return type.
+fn response_kind(ctx: &Context, output: &ReturnType) -> Option<ResponseKind> {
+    let msg = "API methods must return a `Result`";
+    let ty = match output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            ctx.error_spanned_by(output, msg);
+            return None;
+        }
+    };
+    let ok = match generic_argument(ty, "Result") {
+        Some(ok) => ok,
+        None => {
+            ctx.error_spanned_by(ty, msg);
+            return None;
+        }
+    };
+    match ok {
+        Type::Tuple(t) if t.elems.is_empty() => Some(ResponseKind::Unit),
+        ok if generic_argument(ok, "Option").is_some() => Some(ResponseKind::Option),
+        _ => Some(ResponseKind::Value),
+    }
+}
+
+/// The first generic argument of a type named `name`, e.g. `T` for `Option<T>`.
+fn generic_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let last = match ty {
+        Type::Path(p) => p.path.segments.last()?,
+        _ => return None,
+    };
+    if last.ident != name {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
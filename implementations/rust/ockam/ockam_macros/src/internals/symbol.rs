@@ -12,6 +12,15 @@ pub(crate) const NO_MAIN: Symbol = Symbol("no_main");
 pub(crate) const OCKAM_CRATE: Symbol = Symbol("crate");
 pub(crate) const TIMEOUT_MS: Symbol = Symbol("timeout");
 
+// API trait's helper attributes
+pub(crate) const DELETE: Symbol = Symbol("delete");
+pub(crate) const GET: Symbol = Symbol("get");
+pub(crate) const PATCH: Symbol = Symbol("patch");
+pub(crate) const POST: Symbol = Symbol("post");
+pub(crate) const PUT: Symbol = Symbol("put");
+pub(crate) const REQUEST: Symbol = Symbol("request");
+pub(crate) const RESPONSE: Symbol = Symbol("response");
+
 // Derive's helper attributes
 pub(crate) const ASYNC_TRY_CLONE: Symbol = Symbol("async_try_clone");
 
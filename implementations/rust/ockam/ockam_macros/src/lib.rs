@@ -12,8 +12,9 @@
 use proc_macro::TokenStream;
 
 use quote::quote;
-use syn::{parse_macro_input, AttributeArgs, DeriveInput, ItemFn};
+use syn::{parse_macro_input, AttributeArgs, DeriveInput, ItemFn, ItemTrait};
 
+mod api_attribute;
 mod async_try_clone_derive;
 mod internals;
 mod message_derive;
@@ -157,6 +158,68 @@ pub fn vault_test(attrs: TokenStream, item: TokenStream) -> TokenStream {
     vault_test_attribute::expand(input_fn, attrs).unwrap_or_else(to_compile_error)
 }
 
+/// Generates the server-side dispatcher and a typed client of an API
+/// described by a trait.
+///
+/// Every method of the trait is an API endpoint and must be async, take
+/// `&self` or `&mut self` and return a `Result`. It is annotated with
+/// its request method and path: `#[get("...")]`, `#[post("...")]`,
+/// `#[put("...")]`, `#[delete("...")]` or `#[patch("...")]`. Segments
+/// of the path written `{name}` are path parameters, bound to the method
+/// argument of the same name, which is either a `&str` or a type
+/// implementing `FromStr` and `Display`. The remaining argument, if any,
+/// is the request body.
+///
+/// The route attributes also accept the CDDL type names of the request
+/// and response bodies, which are checked against the schema in debug
+/// builds: `#[post("/members", request = "add_member")]`.
+///
+/// The macro adds a provided `handle_request` method to the trait, which
+/// decodes a request, dispatches it to the method matching its path and
+/// method, and encodes the response:
+///
+/// - `Result<()>`: an empty response.
+/// - `Result<Option<T>>`: a response with body `T`, or a not found
+///   response for `None`.
+/// - `Result<T>`: a response with body `T`.
+///
+/// Errors are turned into error responses whose status is derived from
+/// the error kind. It also generates a `<Trait>Client` type with one
+/// method per endpoint, which maps error responses back to errors of the
+/// same kind. Response bodies may borrow from the response through the
+/// lifetime of the receiver, e.g. `fn credential<'a>(&'a mut self) ->
+/// Result<Credential<'a>>`, the client keeps it until its next request.
+///
+/// The generated code uses the `ockam_core`, `ockam_node` and `minicbor`
+/// crates, which must be dependencies of the crate using the macro.
+///
+/// Example of use:
+///
+/// ```ignore
+/// #[ockam_macros::api]
+/// pub trait Greeter {
+///     /// Greet someone.
+///     #[get("/greeting/{name}")]
+///     async fn greet(&mut self, name: &str) -> Result<String>;
+/// }
+///
+/// // Server side
+/// impl Greeter for MyGreeter { ... }
+/// let response = my_greeter.handle_request(&request).await?;
+///
+/// // Client side
+/// let mut client = GreeterClient::new(route, &ctx).await?;
+/// let greeting = client.greet("alice").await?;
+/// ```
+#[proc_macro_attribute]
+pub fn api(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    let input_trait = parse_macro_input!(item as ItemTrait);
+    let attrs = parse_macro_input!(attrs as AttributeArgs);
+    api_attribute::expand(input_trait, attrs)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
 fn to_compile_errors(errors: Vec<syn::Error>) -> proc_macro2::TokenStream {
     let compile_errors = errors.iter().map(syn::Error::to_compile_error);
     quote!(#(#compile_errors)*)
@@ -1,8 +1,12 @@
-use crate::Context;
+use crate::{Context, DEFAULT_TIMEOUT};
 use core::fmt::Display;
-use minicbor::Encode;
-use ockam_core::api::{assert_request_match, RequestBuilder};
+use core::time::Duration;
+use minicbor::{Decode, Decoder, Encode};
+use ockam_core::api::{self, assert_request_match, decode_response, is_ok};
+use ockam_core::api::{Id, Request, RequestBuilder, Response, Status, Stream};
+use ockam_core::compat::string::{String, ToString};
 use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Address, LocalInfo, Result, Route};
 
 /// Encode request header and body (if any), send the package to the server and returns its response.
@@ -68,3 +72,234 @@ where
 
     Ok((body, local_info))
 }
+
+/// Send a request answered with a stream of responses.
+///
+/// Returns once the server accepted the request and started the
+/// stream, or fails with the error response of the server.
+pub async fn request_stream<T, R>(
+    ctx: &Context,
+    label: &str,
+    struct_name: impl Into<Option<&str>>,
+    route: R,
+    req: RequestBuilder<'_, T>,
+) -> Result<ResponseStream>
+where
+    T: Encode<()>,
+    R: Into<Route> + Display,
+{
+    let mut buf = Vec::new();
+    req.encode(&mut buf)?;
+    assert_request_match(struct_name, &buf);
+    trace! {
+        target:  "ockam_api",
+        id     = %req.header().id(),
+        method = ?req.header().method(),
+        path   = %req.header().path(),
+        body   = %req.header().has_body(),
+        "-> {label}"
+    };
+
+    let mut child_ctx = ctx.new_detached(Address::random_local()).await?;
+    child_ctx.send(route, buf).await?;
+    let msg = child_ctx.receive::<Vec<u8>>().await?.take();
+    let server = msg.return_route();
+    let buf = msg.body();
+    let res: Response = Decoder::new(&buf).decode()?;
+    if res.stream() != Some(Stream::Begin) || res.status() != Some(Status::Ok) {
+        is_ok(label, &buf)?;
+        return Err(not_a_stream(label));
+    }
+
+    Ok(ResponseStream {
+        ctx: child_ctx,
+        label: label.to_string(),
+        id: req.header().id(),
+        path: req.header().path().to_string(),
+        server,
+        buf: Vec::new(),
+        done: false,
+        timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+    })
+}
+
+/// Start answering `req` with a stream of responses sent to `route`,
+/// the return route of the request.
+pub async fn respond_stream(
+    ctx: &Context,
+    route: Route,
+    req: &Request<'_>,
+) -> Result<ResponseSender> {
+    let child_ctx = ctx.new_detached(Address::random_local()).await?;
+    let begin = Response::ok(req.id()).stream(Stream::Begin).to_vec()?;
+    child_ctx.send(route.clone(), begin).await?;
+    Ok(ResponseSender {
+        ctx: child_ctx,
+        route,
+        re: req.id(),
+        path: req.path().to_string(),
+        cancelled: false,
+        timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+    })
+}
+
+fn not_a_stream(label: &str) -> ockam_core::Error {
+    let msg = format!("{label}: the response is not a stream");
+    ockam_core::Error::new(Origin::Application, Kind::Protocol, msg)
+}
+
+/// The client side of a streamed response.
+///
+/// Created by [`request_stream`]. A stream which is dropped before it
+/// ended should be [cancelled](ResponseStream::cancel), otherwise the
+/// server keeps sending its items.
+pub struct ResponseStream {
+    ctx: Context,
+    label: String,
+    id: Id,
+    path: String,
+    server: Route,
+    buf: Vec<u8>,
+    done: bool,
+    timeout: Duration,
+}
+
+impl ResponseStream {
+    /// Wait at most `timeout` for each item, instead of the
+    /// [default timeout](DEFAULT_TIMEOUT).
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout
+    }
+
+    /// Wait for the next item of the stream.
+    ///
+    /// Returns `None` once the stream ended, and fails with the error
+    /// response of the server if the stream failed, or with a timeout
+    /// error if the server sent nothing for too long.
+    pub async fn next<'a, T: Decode<'a, ()>>(&'a mut self) -> Result<Option<T>> {
+        if self.done {
+            return Ok(None);
+        }
+        let stream = loop {
+            self.buf = self
+                .ctx
+                .receive_duration_timeout::<Vec<u8>>(self.timeout)
+                .await?
+                .take()
+                .body();
+            let res: Response = Decoder::new(&self.buf).decode()?;
+            if res.re() == self.id {
+                break res.stream();
+            }
+        };
+        match stream {
+            Some(Stream::Item) => decode_response(&self.label, None, &self.buf).map(Some),
+            Some(Stream::End) => {
+                self.done = true;
+                is_ok(&self.label, &self.buf).map(|()| None)
+            }
+            _ => {
+                self.done = true;
+                is_ok(&self.label, &self.buf)?;
+                Err(not_a_stream(&self.label))
+            }
+        }
+    }
+
+    /// Ask the server to end the stream.
+    ///
+    /// The items already sent by the server are still returned by
+    /// [`next`](ResponseStream::next), until the stream ends.
+    pub async fn cancel(&mut self) -> Result<()> {
+        if self.done {
+            return Ok(());
+        }
+        let req = Request::delete(self.path.as_str()).id(self.id);
+        self.ctx.send(self.server.clone(), req.to_vec()?).await
+    }
+}
+
+/// The server side of a streamed response.
+///
+/// Created by [`respond_stream`]. Sending fails once the client is no
+/// longer reachable.
+pub struct ResponseSender {
+    ctx: Context,
+    route: Route,
+    re: Id,
+    path: String,
+    cancelled: bool,
+    timeout: Duration,
+}
+
+impl ResponseSender {
+    /// Wait at most `timeout` for the client to cancel the stream,
+    /// instead of the [default timeout](DEFAULT_TIMEOUT).
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout
+    }
+
+    /// Send an item of the stream.
+    pub async fn send<T: Encode<()>>(&mut self, item: T) -> Result<()> {
+        let res = Response::ok(self.re).stream(Stream::Item).body(item);
+        self.ctx.send(self.route.clone(), res.to_vec()?).await
+    }
+
+    /// End the stream.
+    pub async fn end(self) -> Result<()> {
+        let res = Response::ok(self.re).stream(Stream::End);
+        self.ctx.send(self.route.clone(), res.to_vec()?).await
+    }
+
+    /// End the stream with an error response.
+    ///
+    /// The response status is derived from the error kind.
+    pub async fn fail(self, e: &ockam_core::Error) -> Result<()> {
+        let err = api::Error::new(self.path.as_str()).with_message(e.to_string());
+        let res = Response::builder(self.re, Status::from(e.code().kind))
+            .stream(Stream::End)
+            .body(err);
+        self.ctx.send(self.route.clone(), res.to_vec()?).await
+    }
+
+    /// Whether the client cancelled the stream, without waiting.
+    pub async fn is_cancelled(&mut self) -> bool {
+        while !self.cancelled {
+            match self
+                .ctx
+                .receive_duration_timeout::<Vec<u8>>(Duration::ZERO)
+                .await
+            {
+                Ok(msg) => self.cancelled = is_cancel_request(self.re, &msg.take().body()),
+                Err(_) => break,
+            }
+        }
+        self.cancelled
+    }
+
+    /// Wait until the client cancels the stream.
+    ///
+    /// This is meant to be raced against the production of items,
+    /// for example with `tokio::select!`. Fails with a timeout error
+    /// if the client didn't cancel the stream in time.
+    pub async fn cancelled(&mut self) -> Result<()> {
+        while !self.cancelled {
+            let msg = self
+                .ctx
+                .receive_duration_timeout::<Vec<u8>>(self.timeout)
+                .await?
+                .take()
+                .body();
+            self.cancelled = is_cancel_request(self.re, &msg);
+        }
+        Ok(())
+    }
+}
+
+/// Whether `msg` is a request cancelling the stream answering request `re`.
+fn is_cancel_request(re: Id, msg: &[u8]) -> bool {
+    match Decoder::new(msg).decode::<Request>() {
+        Ok(req) => req.id() == re,
+        Err(_) => false,
+    }
+}
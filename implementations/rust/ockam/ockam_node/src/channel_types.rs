@@ -1,31 +1,262 @@
+use crate::error::{NodeError, WorkerReason};
+use crate::metrics::MetricsRegistry;
+use crate::tokio::sync::mpsc::{
+    channel,
+    error::{SendError, TrySendError},
+    Receiver, Sender,
+};
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use futures::future::poll_fn;
+use ockam_core::compat::{
+    string::ToString,
+    sync::{Arc, Mutex, Weak},
+};
+use ockam_core::{
+    errcode::{Kind, Origin},
+    Address, Error, Result,
+};
+
+/// Default capacity of a worker mailbox
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 16;
+
+/// Default capacity of the router channel
+pub const DEFAULT_ROUTER_CAPACITY: usize = 64;
+
+/// What happens to a message sent to a full worker mailbox
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until the worker has made room in its mailbox
+    Block,
+    /// Drop the message being sent
+    DropNewest,
+    /// Drop the oldest message in the mailbox to make room
+    DropOldest,
+    /// Return an error to the sender
+    Error,
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self {
+        Self::Block
+    }
+}
+
+/// Capacity and overflow policy of a worker mailbox
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MailboxConfig {
+    /// Maximum number of messages waiting in the mailbox
+    pub capacity: usize,
+    /// What happens to messages sent to a full mailbox
+    pub policy: OverflowPolicy,
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_MAILBOX_CAPACITY,
+            policy: OverflowPolicy::default(),
+        }
+    }
+}
+
 /// Sender used to send payload messages
-pub type MessageSender<T> = crate::tokio::sync::mpsc::Sender<T>;
+pub type MessageSender<T> = Sender<T>;
 /// Receiver used to receive payload messages
-pub type MessageReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
+pub type MessageReceiver<T> = Receiver<T>;
 
 /// Create message channel
 pub fn message_channel<T>() -> (MessageSender<T>, MessageReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(16)
+    channel(DEFAULT_MAILBOX_CAPACITY)
+}
+
+/// Create a worker mailbox with the given capacity and overflow policy
+///
+/// The returned [`MailboxSender`] applies the overflow policy.
+pub(crate) fn mailbox_channel<T>(
+    config: MailboxConfig,
+    depth: Arc<AtomicUsize>,
+    metrics: MetricsRegistry,
+    address: Address,
+) -> (MailboxSender<T>, MailboxReceiver<T>) {
+    // A zero capacity mailbox could never receive anything
+    let (sender, receiver) = channel(config.capacity.max(1));
+    let receiver = Arc::new(Mutex::new(receiver));
+    let overflow = Arc::new(Overflow {
+        policy: config.policy,
+        depth,
+        metrics,
+        address,
+    });
+    (
+        MailboxSender {
+            sender,
+            receiver: Arc::downgrade(&receiver),
+            overflow,
+        },
+        MailboxReceiver { receiver },
+    )
+}
+
+/// Overflow state of a worker mailbox, shared between its senders
+struct Overflow {
+    policy: OverflowPolicy,
+    /// Mailbox depth, incremented by the router for every message
+    depth: Arc<AtomicUsize>,
+    metrics: MetricsRegistry,
+    address: Address,
+}
+
+impl Overflow {
+    /// Account for a message which is dropped before the worker
+    /// received it
+    fn reject(&self) {
+        // The router counts messages in flight when resolving the
+        // worker, so a rejected message leaves the mailbox too
+        self.depth.fetch_sub(1, Ordering::Release);
+        self.count_drop();
+    }
+
+    fn count_drop(&self) {
+        self.metrics
+            .counter(
+                "ockam_worker_mailbox_dropped_total",
+                "Messages dropped or rejected by a full worker mailbox",
+                &[("address", &self.address.to_string())],
+            )
+            .inc();
+    }
+}
+
+/// Receiving end of a worker mailbox
+///
+/// Senders with the [`OverflowPolicy::DropOldest`] policy take
+/// messages out of a full mailbox too, so the receiver is shared.
+pub(crate) struct MailboxReceiver<T> {
+    receiver: Arc<Mutex<MessageReceiver<T>>>,
+}
+
+impl<T> MailboxReceiver<T> {
+    /// Wait for the next message, `None` once all senders are gone
+    pub(crate) async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.receiver.lock().unwrap().poll_recv(cx)).await
+    }
+}
+
+/// Sender to a worker mailbox, applying its [`OverflowPolicy`]
+pub struct MailboxSender<T> {
+    sender: MessageSender<T>,
+    /// Only upgraded to drop the oldest message, so that senders do
+    /// not keep the mailbox of a stopped worker open
+    receiver: Weak<Mutex<MessageReceiver<T>>>,
+    overflow: Arc<Overflow>,
+}
+
+impl<T> Clone for MailboxSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            receiver: self.receiver.clone(),
+            overflow: self.overflow.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for MailboxSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MailboxSender")
+            .field("policy", &self.overflow.policy)
+            .finish()
+    }
+}
+
+impl<T: fmt::Debug> MailboxSender<T> {
+    /// Send a message, applying the overflow policy if the mailbox is full
+    pub async fn send(&self, msg: T) -> Result<()> {
+        let msg = match self.overflow.policy {
+            OverflowPolicy::Block => return self.send_blocking(msg).await,
+            _ => match self.sender.try_send(msg) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Closed(msg)) => {
+                    return Err(NodeError::from_send_err(SendError(msg)))
+                }
+                Err(TrySendError::Full(msg)) => msg,
+            },
+        };
+
+        match self.overflow.policy {
+            OverflowPolicy::DropNewest => {
+                self.overflow.reject();
+                Ok(())
+            }
+            OverflowPolicy::Error => {
+                self.overflow.reject();
+                Err(Error::new(
+                    Origin::Node,
+                    Kind::ResourceExhausted,
+                    NodeError::WorkerState(WorkerReason::MailboxFull),
+                ))
+            }
+            OverflowPolicy::DropOldest => self.replace_oldest(msg),
+            OverflowPolicy::Block => unreachable!(),
+        }
+    }
+
+    /// Make room in a full mailbox by dropping its oldest messages,
+    /// without ever waiting for the worker
+    fn replace_oldest(&self, mut msg: T) -> Result<()> {
+        loop {
+            let receiver = match self.receiver.upgrade() {
+                Some(receiver) => receiver,
+                None => return Err(NodeError::from_send_err(SendError(msg))),
+            };
+            // The worker may have made room in the meantime
+            let oldest = receiver.lock().unwrap().try_recv();
+            if oldest.is_ok() {
+                self.overflow.reject();
+            }
+
+            match self.sender.try_send(msg) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Closed(msg)) => {
+                    return Err(NodeError::from_send_err(SendError(msg)))
+                }
+                Err(TrySendError::Full(full)) => msg = full,
+            }
+        }
+    }
+
+    async fn send_blocking(&self, msg: T) -> Result<()> {
+        self.sender
+            .send(msg)
+            .await
+            .map_err(NodeError::from_send_err)
+    }
 }
 
 /// Router sender
-pub type RouterSender<T> = crate::tokio::sync::mpsc::Sender<T>;
+pub type RouterSender<T> = Sender<T>;
 /// Router receiver
-pub type RouterReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
+pub type RouterReceiver<T> = Receiver<T>;
 
 /// Create router channel
 pub fn router_channel<T>() -> (RouterSender<T>, RouterReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(64)
+    router_channel_with_capacity(DEFAULT_ROUTER_CAPACITY)
+}
+
+/// Create router channel with the given capacity
+pub fn router_channel_with_capacity<T>(capacity: usize) -> (RouterSender<T>, RouterReceiver<T>) {
+    channel(capacity)
 }
 
 // TODO: Consider replacing with oneshot
 
 /// Sender for small channels
-pub type SmallSender<T> = crate::tokio::sync::mpsc::Sender<T>;
+pub type SmallSender<T> = Sender<T>;
 /// Receiver for small channels
-pub type SmallReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
+pub type SmallReceiver<T> = Receiver<T>;
 
 /// Create small channel (size 1)
 pub fn small_channel<T>() -> (SmallSender<T>, SmallReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(1)
+    channel(1)
 }
@@ -1,12 +1,15 @@
 use crate::async_drop::AsyncDrop;
-use crate::channel_types::{message_channel, small_channel, SmallReceiver, SmallSender};
+use crate::channel_types::{
+    mailbox_channel, small_channel, MailboxConfig, MailboxReceiver, SmallReceiver, SmallSender,
+};
 use crate::tokio::{self, runtime::Handle, time::timeout};
 use crate::{
     error::*,
+    metrics::MetricsRegistry,
     parser,
     relay::{CtrlSignal, ProcessorRelay, RelayMessage},
     router::SenderPair,
-    Cancel, NodeMessage, ShutdownType, WorkerBuilder,
+    Cancel, NodeMessage, ShutdownType, StopReason, WorkerBuilder, WorkerStopped,
 };
 use core::{
     sync::atomic::{AtomicUsize, Ordering},
@@ -53,9 +56,10 @@ pub struct Context {
     mailboxes: Mailboxes,
     sender: SmallSender<NodeMessage>,
     rt: Handle,
-    receiver: SmallReceiver<RelayMessage>,
+    receiver: MailboxReceiver<RelayMessage>,
     async_drop_sender: Option<AsyncDropSender>,
     mailbox_count: Arc<AtomicUsize>,
+    metrics: MetricsRegistry,
 }
 
 impl Drop for Context {
@@ -82,6 +86,11 @@ impl Context {
         &self.rt
     }
 
+    /// Return the metrics registry of this node
+    pub fn metrics(&self) -> &MetricsRegistry {
+        &self.metrics
+    }
+
     /// Return mailbox_count clone
     pub(crate) fn mailbox_count(&self) -> Arc<AtomicUsize> {
         self.mailbox_count.clone()
@@ -133,10 +142,18 @@ impl Context {
     pub(crate) fn new(
         rt: Handle,
         sender: SmallSender<NodeMessage>,
+        metrics: MetricsRegistry,
         mailboxes: Mailboxes,
+        mailbox_config: MailboxConfig,
         async_drop_sender: Option<AsyncDropSender>,
     ) -> (Self, SenderPair, SmallReceiver<CtrlSignal>) {
-        let (mailbox_tx, receiver) = message_channel();
+        let mailbox_count = Arc::new(AtomicUsize::new(0));
+        let (mailbox_tx, receiver) = mailbox_channel(
+            mailbox_config,
+            mailbox_count.clone(),
+            metrics.clone(),
+            mailboxes.main_address(),
+        );
         let (ctrl_tx, ctrl_rx) = small_channel();
         (
             Self {
@@ -145,7 +162,8 @@ impl Context {
                 mailboxes,
                 receiver,
                 async_drop_sender,
-                mailbox_count: Arc::new(0.into()),
+                mailbox_count,
+                metrics,
             },
             SenderPair {
                 msgs: mailbox_tx,
@@ -225,13 +243,14 @@ impl Context {
         let (ctx, sender, _) = Self::new(
             self.rt.clone(),
             self.sender.clone(),
+            self.metrics.clone(),
             mailboxes,
+            MailboxConfig::default(),
             Some(drop_sender),
         );
 
         // Create a "detached relay" and register it with the router
-        let (msg, mut rx) =
-            NodeMessage::start_worker(addresses, sender, true, Arc::clone(&self.mailbox_count));
+        let (msg, mut rx) = NodeMessage::start_worker(addresses, sender, true, ctx.mailbox_count());
         self.sender
             .send(msg)
             .await
@@ -313,8 +332,14 @@ impl Context {
         let main_mailbox = Mailbox::new(addr, Arc::new(AllowAll)); // TODO FIXME
         let mailboxes = Mailboxes::new(main_mailbox, vec![]);
 
-        let (ctx, senders, ctrl_rx) =
-            Context::new(self.rt.clone(), self.sender.clone(), mailboxes, None);
+        let (ctx, senders, ctrl_rx) = Context::new(
+            self.rt.clone(),
+            self.sender.clone(),
+            self.metrics.clone(),
+            mailboxes,
+            MailboxConfig::default(),
+            None,
+        );
 
         // Initialise the processor relay with the ctrl receiver
         ProcessorRelay::<P>::init(&self.rt, processor, ctx, ctrl_rx);
@@ -597,7 +622,7 @@ impl Context {
         let msg = RelayMessage::new(addr, local_msg, route, needs_wrapping);
 
         // Send the packed user message with associated route
-        sender.send(msg).await.map_err(NodeError::from_send_err)?;
+        sender.send(msg).await?;
 
         Ok(())
     }
@@ -635,7 +660,7 @@ impl Context {
         let msg = RelayMessage::new(addr, local_msg, onward, needs_wrapping);
 
         // Forward the message
-        sender.send(msg).await.map_err(NodeError::from_send_err)?;
+        sender.send(msg).await?;
 
         Ok(())
     }
@@ -763,6 +788,60 @@ impl Context {
             .take_workers()
     }
 
+    /// Get notified once the worker or processor at `addr` stops
+    ///
+    /// When it stops, the worker of this context receives a
+    /// [`WorkerStopped`] message.  Monitoring the same address twice
+    /// only results in a single notification.  Detached contexts can
+    /// not be monitored.
+    ///
+    /// ```rust
+    /// # use ockam_node::{Context, WorkerStopped};
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: &mut Context) -> Result<()> {
+    /// ctx.monitor("echoer").await?;
+    /// ctx.stop_worker("echoer").await?;
+    /// let stopped = ctx.receive::<WorkerStopped>().await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn monitor<A: Into<Address>>(&self, addr: A) -> Result<()> {
+        let (msg, mut rx) = NodeMessage::monitor(addr.into(), self.address());
+        self.sender
+            .send(msg)
+            .await
+            .map_err(NodeError::from_send_err)?;
+        rx.recv()
+            .await
+            .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??
+            .is_ok()
+    }
+
+    /// Send a [`WorkerStopped`] message to everyone monitoring this
+    /// context's worker
+    pub(crate) async fn notify_monitors(&self, reason: StopReason) -> Result<()> {
+        let (msg, mut rx) = NodeMessage::take_monitors(self.address());
+        self.sender
+            .send(msg)
+            .await
+            .map_err(NodeError::from_send_err)?;
+        let monitors = rx
+            .recv()
+            .await
+            .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??
+            .take_monitors()?;
+
+        let msg = WorkerStopped {
+            addr: self.address(),
+            reason,
+        };
+        for monitor in monitors {
+            if let Err(e) = self.send(monitor.clone(), msg.clone()).await {
+                debug!("Failed to notify monitor '{}': {}", monitor, e);
+            }
+        }
+        Ok(())
+    }
+
     /// Register a router for a specific address type
     pub async fn register<A: Into<Address>>(&self, type_: TransportType, addr: A) -> Result<()> {
         self.register_impl(type_, addr.into()).await
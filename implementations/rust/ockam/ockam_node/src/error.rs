@@ -142,6 +142,10 @@ pub enum WorkerReason {
     Faulty,
     /// The worker is otherwise corrupt and can not be recovered
     Corrupt,
+    /// The mailbox of the worker is full
+    MailboxFull,
+    /// The target is a detached context without a relay
+    Detached,
 }
 
 impl fmt::Display for WorkerReason {
@@ -153,6 +157,8 @@ impl fmt::Display for WorkerReason {
                 Self::Shutdown => "target worker is shutting down",
                 Self::Faulty => "target worker is faulty and waiting for supervisor",
                 Self::Corrupt => "target worker is corrupt and can not be recovered",
+                Self::MailboxFull => "target worker's mailbox is full",
+                Self::Detached => "target is a detached context",
             }
         )
     }
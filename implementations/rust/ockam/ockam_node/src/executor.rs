@@ -1,6 +1,7 @@
 // use crate::message::BaseMessage;
 
-use crate::channel_types::SmallSender;
+use crate::channel_types::{SmallSender, DEFAULT_ROUTER_CAPACITY};
+use crate::metrics::MetricsRegistry;
 use crate::{
     router::{Router, SenderPair},
     tokio::runtime::{Handle, Runtime},
@@ -40,8 +41,21 @@ pub struct Executor {
 
 impl Default for Executor {
     fn default() -> Self {
+        Self::with_router_capacity(DEFAULT_ROUTER_CAPACITY)
+    }
+}
+
+impl Executor {
+    /// Create a new Ockam node [`Executor`] instance
+    pub fn new() -> Self {
+        Executor::default()
+    }
+
+    /// Create an [`Executor`] whose router channel holds at most
+    /// `capacity` pending requests
+    pub(crate) fn with_router_capacity(capacity: usize) -> Self {
         let rt = Runtime::new().unwrap();
-        let router = Router::new();
+        let router = Router::new(capacity);
         #[cfg(feature = "metrics")]
         let metrics = Metrics::new(&rt, router.get_metrics_readout());
         Self {
@@ -51,19 +65,17 @@ impl Default for Executor {
             metrics,
         }
     }
-}
-
-impl Executor {
-    /// Create a new Ockam node [`Executor`] instance
-    pub fn new() -> Self {
-        Executor::default()
-    }
 
     /// Get access to the internal message sender
     pub(crate) fn sender(&self) -> SmallSender<NodeMessage> {
         self.router.sender()
     }
 
+    /// Get access to the metrics of this node
+    pub(crate) fn metrics(&self) -> MetricsRegistry {
+        self.router.metrics()
+    }
+
     /// Get access to the underlying async runtime (by default `tokio`)
     pub(crate) fn runtime(&self) -> &Handle {
         self.rt.handle()
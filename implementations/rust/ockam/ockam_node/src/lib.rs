@@ -35,8 +35,7 @@ pub mod compat;
 /// MPSC channel type aliases
 pub mod channel_types;
 
-#[cfg(feature = "metrics")]
-mod metrics;
+pub mod metrics;
 
 /// Access Control
 pub mod access_control;
@@ -52,19 +51,26 @@ mod error;
 mod executor;
 mod local_info;
 mod messages;
+mod monitor;
 mod node;
 mod parser;
 mod relay;
 mod router;
+#[cfg(feature = "std")]
+mod supervisor;
 mod worker_builder;
 
 pub use cancel::*;
+pub use channel_types::OverflowPolicy;
 pub use context::*;
 pub use delayed::*;
 pub use error::*;
 pub use executor::*;
 pub use local_info::*;
 pub use messages::*;
+pub use monitor::{StopReason, WorkerStopped};
+#[cfg(feature = "std")]
+pub use supervisor::{RestartPolicy, RestartStrategy, WorkerFailed};
 pub use worker_builder::WorkerBuilder;
 
 pub use node::{NodeBuilder, NullWorker};
@@ -1,4 +1,4 @@
-use crate::channel_types::{small_channel, MessageSender, SmallReceiver, SmallSender};
+use crate::channel_types::{small_channel, MailboxSender, SmallReceiver, SmallSender};
 use crate::{
     error::{NodeError, NodeReason, RouterReason, WorkerReason},
     relay::RelayMessage,
@@ -48,6 +48,10 @@ pub enum NodeMessage {
     SetReady(Address),
     /// Check whether an address has been marked as "ready"
     CheckReady(Address, SmallSender<NodeReplyResult>),
+    /// Notify the second address once the first one has stopped
+    Monitor(Address, Address, SmallSender<NodeReplyResult>),
+    /// Take the list of monitors of a stopping address
+    TakeMonitors(Address, SmallSender<NodeReplyResult>),
 }
 
 impl fmt::Display for NodeMessage {
@@ -66,6 +70,8 @@ impl fmt::Display for NodeMessage {
             NodeMessage::Router(_, _, _) => write!(f, "Router"),
             NodeMessage::SetReady(_) => write!(f, "SetReady"),
             NodeMessage::CheckReady(_, _) => write!(f, "CheckReady"),
+            NodeMessage::Monitor(_, _, _) => write!(f, "Monitor"),
+            NodeMessage::TakeMonitors(_, _) => write!(f, "TakeMonitors"),
         }
     }
 }
@@ -153,6 +159,18 @@ impl NodeMessage {
         let (tx, rx) = small_channel();
         (Self::CheckReady(addr, tx), rx)
     }
+
+    /// Create a Monitor message and reply receiver
+    pub fn monitor(target: Address, monitor: Address) -> (Self, SmallReceiver<NodeReplyResult>) {
+        let (tx, rx) = small_channel();
+        (Self::Monitor(target, monitor, tx), rx)
+    }
+
+    /// Create a TakeMonitors message and reply receiver
+    pub fn take_monitors(addr: Address) -> (Self, SmallReceiver<NodeReplyResult>) {
+        let (tx, rx) = small_channel();
+        (Self::TakeMonitors(addr, tx), rx)
+    }
 }
 
 /// The reply/result of a Node
@@ -170,13 +188,15 @@ pub enum RouterReply {
         /// The address a message is being sent to
         addr: Address,
         /// The relay sender
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender<RelayMessage>,
         /// Indicate whether the relay message needs to be constructed
         /// with router wrapping.
         wrap: bool,
     },
     /// Indicate the 'ready' state of an address
     State(bool),
+    /// The monitors of an address
+    Monitors(Vec<Address>),
 }
 
 /// Specify the type of node shutdown
@@ -263,17 +283,22 @@ impl RouterReply {
         Ok(Self::Workers(v))
     }
 
+    /// Return [NodeReply::Monitors] for the given addresses
+    pub fn monitors(v: Vec<Address>) -> NodeReplyResult {
+        Ok(Self::Monitors(v))
+    }
+
     /// Return [NodeReply::Sender] for the given information
     pub fn sender(
         addr: Address,
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender<RelayMessage>,
         wrap: bool,
     ) -> NodeReplyResult {
         Ok(RouterReply::Sender { addr, sender, wrap })
     }
 
     /// Consume the wrapper and return [NodeReply::Sender]
-    pub fn take_sender(self) -> Result<(Address, MessageSender<RelayMessage>, bool)> {
+    pub fn take_sender(self) -> Result<(Address, MailboxSender<RelayMessage>, bool)> {
         match self {
             Self::Sender { addr, sender, wrap } => Ok((addr, sender, wrap)),
             _ => Err(NodeError::NodeState(NodeReason::Unknown).internal()),
@@ -288,6 +313,14 @@ impl RouterReply {
         }
     }
 
+    /// Consume the wrapper and return [NodeReply::Monitors]
+    pub fn take_monitors(self) -> Result<Vec<Address>> {
+        match self {
+            Self::Monitors(m) => Ok(m),
+            _ => Err(NodeError::NodeState(NodeReason::Unknown).internal()),
+        }
+    }
+
     /// Consume the wrapper and return [NodeReply::State]
     pub fn take_state(self) -> Result<bool> {
         match self {
@@ -0,0 +1,124 @@
+use crate::tokio::{
+    self,
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use crate::Context;
+use ockam_core::compat::net::SocketAddr;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+
+use super::MetricsRegistry;
+
+/// Largest request head the endpoint accepts
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// A running metrics endpoint, see [`serve_metrics`]
+///
+/// Dropping the handle leaves the endpoint running until the node
+/// shuts down.
+#[derive(Debug)]
+pub struct MetricsServer {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    /// The address the endpoint is bound to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop accepting connections and close the listener
+    pub fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+/// Serve the node's metrics over HTTP
+///
+/// Answers `GET /metrics` with the OpenMetrics text encoding of the
+/// [`MetricsRegistry`] of the node `ctx` belongs to.  The endpoint
+/// runs until it is stopped with the returned [`MetricsServer`] or
+/// the node shuts down.
+pub async fn serve_metrics(ctx: &Context, bind_addr: SocketAddr) -> Result<MetricsServer> {
+    let listener = TcpListener::bind(bind_addr)
+        .await
+        .map_err(|e| Error::new(Origin::Node, Kind::Io, e))?;
+    let local_addr = listener
+        .local_addr()
+        .map_err(|e| Error::new(Origin::Node, Kind::Io, e))?;
+    info!("Serving node metrics at http://{}/metrics", local_addr);
+
+    let registry = ctx.metrics().clone();
+    let handle = ctx.runtime().spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let registry = registry.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = respond(stream, &registry).await {
+                            debug!("Failed to answer metrics request: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!("Metrics endpoint stopped accepting connections: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok(MetricsServer {
+        addr: local_addr,
+        handle,
+    })
+}
+
+/// Read the request line and headers of a request
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() >= MAX_REQUEST_HEAD {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(head)
+}
+
+async fn respond(mut stream: TcpStream, registry: &MetricsRegistry) -> std::io::Result<()> {
+    // Only the request line matters, but the whole head is read so
+    // that the client does not see its request cut off
+    let head = read_request_head(&mut stream).await?;
+    let request = String::from_utf8_lossy(&head);
+    let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+
+    let response = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let body = registry.encode();
+            format!(
+                "HTTP/1.1 200 OK\r\n\
+                 Content-Type: application/openmetrics-text; version=1.0.0; charset=utf-8\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        }
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
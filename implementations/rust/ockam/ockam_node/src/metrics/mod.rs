@@ -0,0 +1,20 @@
+//! Node metrics
+//!
+//! Each node owns a [`MetricsRegistry`], available from any of its
+//! contexts via [`Context::metrics`](crate::Context::metrics).  The
+//! router tracks message counts and worker mailbox depths, and
+//! transports and secure channels register their own counters.
+//!
+//! The metrics can be scraped over HTTP with [`serve_metrics`].
+
+#[cfg(feature = "metrics")]
+mod collector;
+#[cfg(feature = "std")]
+mod http;
+mod registry;
+
+#[cfg(feature = "metrics")]
+pub(crate) use collector::Metrics;
+#[cfg(feature = "std")]
+pub use http::*;
+pub use registry::*;
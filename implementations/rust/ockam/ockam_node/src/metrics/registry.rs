@@ -0,0 +1,320 @@
+use core::fmt::Write;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use ockam_core::compat::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::{Arc, RwLock},
+    vec::Vec,
+};
+use ockam_core::Address;
+
+// Some 32-bit targets lack 64-bit atomics, where the metrics fall
+// back to pointer-sized values
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicI64 as AtomicSigned, AtomicU64 as AtomicUnsigned};
+#[cfg(not(target_has_atomic = "64"))]
+use core::sync::atomic::{AtomicIsize as AtomicSigned, AtomicUsize as AtomicUnsigned};
+
+/// Name of the per-worker mailbox depth gauge
+const MAILBOX_DEPTH: &str = "ockam_worker_mailbox_depth";
+
+/// A metric which only ever goes up
+///
+/// Cloning a `Counter` returns a handle to the same value.
+#[derive(Clone, Debug, Default)]
+pub struct Counter(Arc<AtomicUnsigned>);
+
+// The casts are only needed for the pointer-sized fallback
+#[allow(trivial_numeric_casts, clippy::unnecessary_cast)]
+impl Counter {
+    /// Increment the counter by one
+    pub fn inc(&self) {
+        self.inc_by(1)
+    }
+
+    /// Increment the counter by `n`
+    pub fn inc_by(&self, n: u64) {
+        self.0.fetch_add(n as _, Ordering::Relaxed);
+    }
+
+    /// Current value of the counter
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed) as u64
+    }
+}
+
+/// A metric which can go up and down
+///
+/// Cloning a `Gauge` returns a handle to the same value.
+#[derive(Clone, Debug, Default)]
+pub struct Gauge(Arc<AtomicSigned>);
+
+#[allow(trivial_numeric_casts, clippy::unnecessary_cast)]
+impl Gauge {
+    /// Increment the gauge by one
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrement the gauge by one
+    pub fn dec(&self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Set the gauge to `value`
+    pub fn set(&self, value: i64) {
+        self.0.store(value as _, Ordering::Relaxed);
+    }
+
+    /// Current value of the gauge
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed) as i64
+    }
+
+    /// Increment the gauge now and decrement it again when the
+    /// returned guard is dropped
+    pub fn track(&self) -> GaugeGuard {
+        self.inc();
+        GaugeGuard(self.clone())
+    }
+}
+
+/// Decrements its [`Gauge`] when dropped, see [`Gauge::track`]
+#[derive(Debug)]
+pub struct GaugeGuard(Gauge);
+
+impl Drop for GaugeGuard {
+    fn drop(&mut self) {
+        self.0.dec()
+    }
+}
+
+/// The type of a metric family
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricKind {
+    /// See [`Counter`]
+    Counter,
+    /// See [`Gauge`]
+    Gauge,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+        }
+    }
+}
+
+/// A single value of a metric family at the time of a snapshot
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricSample {
+    /// Name of the metric family
+    pub name: String,
+    /// Label pairs identifying this series within the family
+    pub labels: Vec<(String, String)>,
+    /// Value of the series
+    pub value: i64,
+}
+
+type Labels = Vec<(String, String)>;
+
+enum Value {
+    Counter(Counter),
+    Gauge(Gauge),
+    Depth(Arc<AtomicUsize>),
+}
+
+impl Value {
+    fn get(&self) -> i64 {
+        match self {
+            Value::Counter(c) => c.get() as i64,
+            Value::Gauge(g) => g.get(),
+            Value::Depth(d) => d.load(Ordering::Acquire) as i64,
+        }
+    }
+}
+
+struct Family {
+    help: String,
+    kind: MetricKind,
+    series: BTreeMap<Labels, Value>,
+}
+
+/// The metrics of a single node
+///
+/// Every [`Context`](crate::Context) of a node shares the same
+/// registry, which transports and other components use to register
+/// their metrics.  Cloning a registry returns a handle to the same
+/// metrics.
+///
+/// ```rust
+/// use ockam_node::metrics::MetricsRegistry;
+///
+/// let registry = MetricsRegistry::new();
+/// let sent = registry.counter("bytes_sent_total", "Bytes sent", &[("transport", "tcp")]);
+/// sent.inc_by(42);
+/// let encoded = registry.encode();
+/// assert!(encoded.contains("# TYPE bytes_sent counter"));
+/// assert!(encoded.contains("bytes_sent_total{transport=\"tcp\"} 42"));
+/// ```
+#[derive(Clone, Default)]
+pub struct MetricsRegistry {
+    families: Arc<RwLock<BTreeMap<String, Family>>>,
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get or create the counter `name` with the given labels
+    pub fn counter(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Counter {
+        match self.get_or_insert(name, help, MetricKind::Counter, labels, || {
+            Value::Counter(Counter::default())
+        }) {
+            Some(Value::Counter(c)) => c,
+            _ => Counter::default(),
+        }
+    }
+
+    /// Get or create the gauge `name` with the given labels
+    pub fn gauge(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Gauge {
+        match self.get_or_insert(name, help, MetricKind::Gauge, labels, || {
+            Value::Gauge(Gauge::default())
+        }) {
+            Some(Value::Gauge(g)) => g,
+            _ => Gauge::default(),
+        }
+    }
+
+    /// Track the mailbox depth of a worker
+    pub(crate) fn register_mailbox(&self, addr: &Address, depth: Arc<AtomicUsize>) {
+        let addr = addr.to_string();
+        let mut families = self.families.write().unwrap();
+        let family = families
+            .entry(MAILBOX_DEPTH.to_string())
+            .or_insert_with(|| Family {
+                help: "Messages waiting in a worker's mailbox".to_string(),
+                kind: MetricKind::Gauge,
+                series: BTreeMap::new(),
+            });
+        family
+            .series
+            .insert(vec![("address".to_string(), addr)], Value::Depth(depth));
+    }
+
+    /// Stop tracking the mailbox depth of a worker
+    pub(crate) fn unregister_mailbox(&self, addr: &Address) {
+        let mut families = self.families.write().unwrap();
+        if let Some(family) = families.get_mut(MAILBOX_DEPTH) {
+            family
+                .series
+                .remove(&vec![("address".to_string(), addr.to_string())]);
+        }
+    }
+
+    /// Take a snapshot of all metrics
+    pub fn samples(&self) -> Vec<MetricSample> {
+        let families = self.families.read().unwrap();
+        families
+            .iter()
+            .flat_map(|(name, family)| {
+                family
+                    .series
+                    .iter()
+                    .map(move |(labels, value)| MetricSample {
+                        name: name.clone(),
+                        labels: labels.clone(),
+                        value: value.get(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Encode all metrics in the OpenMetrics text format
+    pub fn encode(&self) -> String {
+        let families = self.families.read().unwrap();
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            // Counter families are named without the `_total` suffix,
+            // which only their samples carry
+            let (family_name, sample_name) = match family.kind {
+                MetricKind::Counter => {
+                    let family_name = name.strip_suffix("_total").unwrap_or(name);
+                    (family_name, format!("{}_total", family_name))
+                }
+                MetricKind::Gauge => (name.as_str(), name.clone()),
+            };
+            let _ = writeln!(out, "# HELP {} {}", family_name, family.help);
+            let _ = writeln!(out, "# TYPE {} {}", family_name, family.kind.as_str());
+            for (labels, value) in family.series.iter() {
+                let _ = writeln!(
+                    out,
+                    "{}{} {}",
+                    sample_name,
+                    encode_labels(labels),
+                    value.get()
+                );
+            }
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+
+    fn get_or_insert(
+        &self,
+        name: &str,
+        help: &str,
+        kind: MetricKind,
+        labels: &[(&str, &str)],
+        new: impl FnOnce() -> Value,
+    ) -> Option<Value> {
+        let labels: Labels = labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let mut families = self.families.write().unwrap();
+        let family = families.entry(name.to_string()).or_insert_with(|| Family {
+            help: help.to_string(),
+            kind,
+            series: BTreeMap::new(),
+        });
+        if family.kind != kind {
+            warn!(
+                "Metric {} is already registered as a {:?}",
+                name, family.kind
+            );
+            return None;
+        }
+
+        let value = family.series.entry(labels).or_insert_with(new);
+        Some(match value {
+            Value::Counter(c) => Value::Counter(c.clone()),
+            Value::Gauge(g) => Value::Gauge(g.clone()),
+            Value::Depth(d) => Value::Depth(d.clone()),
+        })
+    }
+}
+
+/// Encode label pairs as `{k="v",...}`, escaping label values
+fn encode_labels(labels: &Labels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| {
+            let v = v
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", k, v)
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
@@ -0,0 +1,39 @@
+//! Worker monitors
+//!
+//! A worker can ask to be notified when another worker or processor
+//! stops, by calling [`Context::monitor`](crate::Context::monitor).
+//! Once the monitored address has stopped, each monitoring worker
+//! receives a [`WorkerStopped`] message.
+
+use core::fmt;
+use ockam_core::compat::string::String;
+use ockam_core::{Address, Message};
+use serde::{Deserialize, Serialize};
+
+/// Why a monitored worker or processor stopped
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum StopReason {
+    /// It was stopped, or it returned from its run loop
+    Normal,
+    /// It was stopped by its supervisor after failing permanently
+    Failed(String),
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "stopped"),
+            Self::Failed(reason) => write!(f, "failed: {}", reason),
+        }
+    }
+}
+
+/// Sent to the workers monitoring a worker or processor once it has
+/// stopped
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Message)]
+pub struct WorkerStopped {
+    /// Primary address of the stopped worker or processor
+    pub addr: Address,
+    /// Why it stopped
+    pub reason: StopReason,
+}
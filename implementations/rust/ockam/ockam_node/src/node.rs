@@ -1,3 +1,4 @@
+use crate::channel_types::{MailboxConfig, DEFAULT_ROUTER_CAPACITY};
 use crate::{Context, Executor};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{AccessControl, Address, AllowAll, Mailbox, Mailboxes};
@@ -21,6 +22,7 @@ where
 {
     access_control: AC,
     logging: bool,
+    router_capacity: usize,
 }
 
 impl NodeBuilder<AllowAll> {
@@ -29,6 +31,7 @@ impl NodeBuilder<AllowAll> {
         Self {
             access_control: AllowAll,
             logging: true,
+            router_capacity: DEFAULT_ROUTER_CAPACITY,
         }
     }
 }
@@ -42,6 +45,7 @@ where
         Self {
             access_control,
             logging: true,
+            router_capacity: DEFAULT_ROUTER_CAPACITY,
         }
     }
 
@@ -53,6 +57,18 @@ where
         }
     }
 
+    /// Set how many requests the node router buffers before senders
+    /// have to wait
+    ///
+    /// Every message sent between workers passes through the router,
+    /// so busy nodes may want a larger buffer than the default of 64.
+    pub fn with_router_capacity(self, capacity: usize) -> Self {
+        Self {
+            router_capacity: capacity,
+            ..self
+        }
+    }
+
     /// Consume this builder and yield a new Ockam Node
     #[inline]
     pub fn build(self) -> (Context, Executor) {
@@ -65,7 +81,7 @@ where
             self.access_control
         );
 
-        let mut exe = Executor::new();
+        let mut exe = Executor::with_router_capacity(self.router_capacity);
         let addr: Address = "app".into();
 
         // The root application worker needs a mailbox and relay to accept
@@ -73,7 +89,9 @@ where
         let (ctx, sender, _) = Context::new(
             exe.runtime().clone(),
             exe.sender(),
+            exe.metrics(),
             Mailboxes::new(Mailbox::new(addr, Arc::new(self.access_control)), vec![]),
+            MailboxConfig::default(),
             None,
         );
 
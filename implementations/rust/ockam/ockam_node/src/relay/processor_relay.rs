@@ -1,5 +1,5 @@
 use crate::channel_types::SmallReceiver;
-use crate::{relay::CtrlSignal, tokio::runtime::Handle, Context};
+use crate::{relay::CtrlSignal, tokio::runtime::Handle, Context, StopReason};
 use ockam_core::{Processor, Result};
 
 pub struct ProcessorRelay<P>
@@ -88,6 +88,10 @@ where
             }
         }
 
+        if let Err(e) = ctx.notify_monitors(StopReason::Normal).await {
+            warn!("Failed to notify monitors of '{}': {}", ctx_addr, e);
+        }
+
         // Finally send the router a stop ACK -- log errors
         trace!("Sending shutdown ACK");
         if let Err(e) = ctx.send_stop_ack().await {
@@ -1,10 +1,14 @@
 use crate::channel_types::SmallReceiver;
 use crate::relay::CtrlSignal;
 use crate::relay::RelayMessage;
+#[cfg(feature = "std")]
+use crate::supervisor::{Decision, Failure, Supervisor, WorkerFailed};
 use crate::tokio::runtime::Handle;
-use crate::{parser, Context};
+use crate::{parser, Context, StopReason};
 use core::marker::PhantomData;
 use ockam_core::{Message, Result, Routed, Worker};
+#[cfg(feature = "std")]
+use {futures::FutureExt, std::panic::AssertUnwindSafe};
 
 /// Worker relay machinery
 ///
@@ -18,6 +22,10 @@ where
 {
     worker: W,
     ctx: Context,
+    #[cfg(feature = "std")]
+    supervisor: Supervisor<W>,
+    /// Reported to the monitors of this worker once it stopped
+    stop_reason: StopReason,
     _phantom: PhantomData<M>,
 }
 
@@ -30,6 +38,9 @@ where
         Self {
             worker,
             ctx,
+            #[cfg(feature = "std")]
+            supervisor: Supervisor::default(),
+            stop_reason: StopReason::Normal,
             _phantom: PhantomData,
         }
     }
@@ -85,15 +96,110 @@ where
 
         // Call the worker handle function - pass errors up
         let routed = Self::wrap_direct_message(&relay_msg)?;
+        #[cfg(feature = "std")]
+        {
+            let handled = AssertUnwindSafe(self.worker.handle_message(&mut self.ctx, routed))
+                .catch_unwind()
+                .await;
+            let failure = match handled {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(Failure::Error(e)),
+                Err(panic) => Some(Failure::Panic(panic)),
+            };
+            if let Some(failure) = failure {
+                self.handle_failure(failure).await;
+            }
+        }
+        #[cfg(not(feature = "std"))]
         self.worker.handle_message(&mut self.ctx, routed).await?;
 
         // Signal to the outer loop that we would like to run again
         Ok(true)
     }
 
+    /// Run the `initialize` hook of the current worker instance
+    #[cfg(feature = "std")]
+    async fn initialize(&mut self) -> core::result::Result<(), Failure> {
+        match AssertUnwindSafe(self.worker.initialize(&mut self.ctx))
+            .catch_unwind()
+            .await
+        {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(Failure::Error(e)),
+            Err(panic) => Err(Failure::Panic(panic)),
+        }
+    }
+
+    /// Restart, stop or keep running the worker after a failure,
+    /// depending on its supervision
+    #[cfg(feature = "std")]
+    async fn handle_failure(&mut self, mut failure: Failure) {
+        let address = self.ctx.address();
+        loop {
+            error!("Worker '{}' failed: {}", address, failure.reason());
+
+            match self.supervisor.decide(&failure) {
+                Decision::Continue => return,
+                Decision::Unwind => {
+                    if let Failure::Panic(panic) = failure {
+                        std::panic::resume_unwind(panic);
+                    }
+                    return;
+                }
+                Decision::Restart(worker) => {
+                    warn!("Restarting worker '{}'", address);
+                    // Give the failed instance a chance to release
+                    // its resources before it is replaced
+                    if let Err(e) = self.worker.shutdown(&mut self.ctx).await {
+                        error!("Failure during '{}' worker shutdown: {}", address, e);
+                    }
+                    self.worker = worker;
+                    match self.initialize().await {
+                        Ok(()) => return,
+                        Err(f) => failure = f,
+                    }
+                }
+                Decision::Stop => {
+                    self.stop_failed(failure).await;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Notify the linked workers of a permanent failure and stop
+    #[cfg(feature = "std")]
+    async fn stop_failed(&mut self, failure: Failure) {
+        let address = self.ctx.address();
+        error!("Worker '{}' failed permanently, stopping it", address);
+
+        let reason = failure.reason();
+        self.stop_reason = StopReason::Failed(reason.clone());
+        let msg = WorkerFailed {
+            addr: address.clone(),
+            reason,
+        };
+        for link in self.supervisor.links() {
+            if let Err(e) = self.ctx.send(link.clone(), msg.clone()).await {
+                warn!(
+                    "Failed to notify '{}' of failed worker '{}': {}",
+                    link, address, e
+                );
+            }
+        }
+
+        // Closes the mailbox, which ends the run loop
+        if let Err(e) = self.ctx.stop_worker(address.clone()).await {
+            error!("Failed to stop worker '{}': {}", address, e);
+        }
+    }
+
     #[cfg_attr(not(feature = "std"), allow(unused_mut))]
     #[cfg_attr(not(feature = "std"), allow(unused_variables))]
     async fn run(mut self, mut ctrl_rx: SmallReceiver<CtrlSignal>) {
+        #[cfg(feature = "std")]
+        let initialized = self.initialize().await;
+        #[cfg(not(feature = "std"))]
         match self.worker.initialize(&mut self.ctx).await {
             Ok(()) => {}
             Err(e) => {
@@ -111,6 +217,13 @@ where
             error!("Failed to mark worker '{}' as 'ready': {}", address, e);
         }
 
+        // Only handle a failed initialisation once the worker is
+        // marked as ready, so that nobody waits for a stopped worker
+        #[cfg(feature = "std")]
+        if let Err(failure) = initialized {
+            self.handle_failure(failure).await;
+        }
+
         #[cfg(feature = "std")]
         loop {
             crate::tokio::select! {
@@ -165,6 +278,14 @@ where
             }
         }
 
+        if let Err(e) = self.ctx.notify_monitors(self.stop_reason.clone()).await {
+            warn!(
+                "Failed to notify monitors of '{}': {}",
+                self.ctx.address(),
+                e
+            );
+        }
+
         // Finally send the router a stop ACK -- log errors
         trace!("Sending shutdown ACK");
         if let Err(e) = self.ctx.send_stop_ack().await {
@@ -173,8 +294,19 @@ where
     }
 
     /// Build and spawn a new worker relay, returning a send handle to it
-    pub(crate) fn init(rt: &Handle, worker: W, ctx: Context, ctrl_rx: SmallReceiver<CtrlSignal>) {
-        let relay = WorkerRelay::<W, M>::new(worker, ctx);
+    pub(crate) fn init(
+        rt: &Handle,
+        worker: W,
+        ctx: Context,
+        ctrl_rx: SmallReceiver<CtrlSignal>,
+        #[cfg(feature = "std")] supervisor: Supervisor<W>,
+    ) {
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut relay = WorkerRelay::<W, M>::new(worker, ctx);
+        #[cfg(feature = "std")]
+        {
+            relay.supervisor = supervisor;
+        }
         rt.spawn(relay.run(ctrl_rx));
     }
 }
@@ -13,7 +13,10 @@ use std::sync::atomic::AtomicUsize;
 use record::{AddressMeta, AddressRecord, InternalMap};
 use state::{NodeState, RouterState};
 
-use crate::channel_types::{router_channel, MessageSender, RouterReceiver, SmallSender};
+use crate::channel_types::{
+    router_channel_with_capacity, MailboxSender, RouterReceiver, SmallSender,
+};
+use crate::metrics::{Counter, MetricsRegistry};
 use crate::{
     error::{NodeError, NodeReason},
     relay::{CtrlSignal, RelayMessage},
@@ -25,7 +28,7 @@ use ockam_core::{Address, Result, TransportType};
 /// A pair of senders to a worker relay
 #[derive(Debug)]
 pub struct SenderPair {
-    pub msgs: MessageSender<RelayMessage>,
+    pub msgs: MailboxSender<RelayMessage>,
     pub ctrl: SmallSender<CtrlSignal>,
 }
 
@@ -47,6 +50,10 @@ pub struct Router {
     external: BTreeMap<TransportType, Address>,
     /// Receiver for messages from node
     receiver: Option<RouterReceiver<NodeMessage>>,
+    /// Metrics of this node
+    metrics: MetricsRegistry,
+    /// Number of messages routed to local workers
+    messages: Counter,
 }
 
 enum RouteType {
@@ -63,16 +70,29 @@ fn determine_type(next: &Address) -> RouteType {
 }
 
 impl Router {
-    pub fn new() -> Self {
-        let (sender, receiver) = router_channel();
+    pub fn new(capacity: usize) -> Self {
+        let (sender, receiver) = router_channel_with_capacity(capacity);
+        let metrics = MetricsRegistry::new();
+        let messages = metrics.counter(
+            "ockam_router_messages_total",
+            "Messages routed to local workers and transport routers",
+            &[],
+        );
         Self {
             state: RouterState::new(sender),
             map: InternalMap::default(),
             external: BTreeMap::new(),
             receiver: Some(receiver),
+            metrics,
+            messages,
         }
     }
 
+    /// Get the metrics registry of this node
+    pub fn metrics(&self) -> MetricsRegistry {
+        self.metrics.clone()
+    }
+
     #[cfg(feature = "metrics")]
     pub(crate) fn get_metrics_readout(&self) -> (Arc<AtomicUsize>, Arc<AtomicUsize>) {
         self.map.get_metrics()
@@ -225,6 +245,8 @@ impl Router {
                         self.map.addr_map.remove(addr);
                     });
                 }
+                self.map.drop_monitors(&addr);
+                self.metrics.unregister_mailbox(&addr);
             }
 
             StopAck(addr) => {
@@ -280,6 +302,23 @@ impl Router {
                 }
             }
 
+            Monitor(addr, monitor, reply) => {
+                trace!("Monitoring address {} for {}", addr, monitor);
+                let msg = self.map.monitor(&addr, monitor);
+                reply
+                    .send(msg)
+                    .await
+                    .map_err(|_| NodeError::NodeState(NodeReason::Unknown).internal())?;
+            }
+
+            TakeMonitors(addr, reply) => {
+                let monitors = self.map.take_monitors(&addr);
+                reply
+                    .send(RouterReply::monitors(monitors))
+                    .await
+                    .map_err(|_| NodeError::NodeState(NodeReason::Unknown).internal())?;
+            }
+
             // Handle route/ sender requests
             SenderReq(ref addr, ref reply) => match determine_type(addr) {
                 RouteType::Internal(ref addr) => utils::resolve(self, addr, reply, false).await?,
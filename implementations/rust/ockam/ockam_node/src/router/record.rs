@@ -1,7 +1,7 @@
-use crate::channel_types::{MessageSender, SmallSender};
+use crate::channel_types::{MailboxSender, SmallSender};
 use crate::relay::{CtrlSignal, RelayMessage};
 use crate::{
-    error::{NodeError, NodeReason},
+    error::{NodeError, NodeReason, WorkerReason},
     NodeReplyResult, RouterReply,
 };
 use core::sync::atomic::{AtomicUsize, Ordering};
@@ -28,6 +28,8 @@ pub struct InternalMap {
     clusters: BTreeMap<String, BTreeSet<Address>>,
     /// Track stop information
     stopping: BTreeSet<Address>,
+    /// Workers to notify once a primary address has stopped
+    monitors: BTreeMap<Address, Vec<Address>>,
     /// Metrics collection and sharing
     #[cfg(feature = "metrics")]
     metrics: (Arc<AtomicUsize>, Arc<AtomicUsize>),
@@ -142,8 +144,48 @@ impl InternalMap {
             .collect()
     }
 
+    /// Notify `monitor` once the worker behind `addr` has stopped
+    pub(super) fn monitor(&mut self, addr: &Address, monitor: Address) -> NodeReplyResult {
+        let primary = self
+            .addr_map
+            .get(addr)
+            .ok_or_else(|| NodeError::Address(addr.clone()).not_found())?;
+        let rec = self
+            .internal
+            .get(primary)
+            .ok_or_else(|| NodeError::Address(addr.clone()).not_found())?;
+
+        // Detached contexts have no relay which could notify monitors
+        if rec.meta.detached {
+            return RouterReply::worker_rejected(WorkerReason::Detached);
+        }
+
+        let monitors = self.monitors.entry(primary.clone()).or_default();
+        if !monitors.contains(&monitor) {
+            monitors.push(monitor);
+        }
+
+        RouterReply::ok()
+    }
+
+    /// Take the monitors of a stopping address
+    pub(super) fn take_monitors(&mut self, primary: &Address) -> Vec<Address> {
+        self.monitors.remove(primary).unwrap_or_default()
+    }
+
+    /// Forget the monitors of a stopped address and stop notifying it
+    /// about other addresses
+    pub(super) fn drop_monitors(&mut self, primary: &Address) {
+        self.monitors.remove(primary);
+        self.monitors.retain(|_, monitors| {
+            monitors.retain(|m| m != primary);
+            !monitors.is_empty()
+        });
+    }
+
     /// Permanently free all remaining resources associated to a particular address
     pub(super) fn free_address(&mut self, primary: Address) {
+        self.drop_monitors(&primary);
         self.stopping.remove(&primary);
         if let Some(record) = self.internal.remove(&primary) {
             for addr in record.address_set {
@@ -163,7 +205,7 @@ pub struct AddressMeta {
 #[derive(Debug)]
 pub struct AddressRecord {
     address_set: AddressSet,
-    sender: Option<MessageSender<RelayMessage>>,
+    sender: Option<MailboxSender<RelayMessage>>,
     ctrl_tx: SmallSender<CtrlSignal>,
     state: AddressState,
     ready: ReadyState,
@@ -175,7 +217,7 @@ impl AddressRecord {
     pub fn address_set(&self) -> &AddressSet {
         &self.address_set
     }
-    pub fn sender(&self) -> MessageSender<RelayMessage> {
+    pub fn sender(&self) -> MailboxSender<RelayMessage> {
         self.sender.clone().expect("No such sender!")
     }
     pub fn sender_drop(&mut self) {
@@ -183,7 +225,7 @@ impl AddressRecord {
     }
     pub fn new(
         address_set: AddressSet,
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender<RelayMessage>,
         ctrl_tx: SmallSender<CtrlSignal>,
         msg_count: Arc<AtomicUsize>,
         meta: AddressMeta,
@@ -40,6 +40,14 @@ async fn start(
 
     let SenderPair { msgs, ctrl } = senders;
 
+    // Detached contexts are short-lived and numerous, only track
+    // the mailboxes of actual workers
+    if !detached {
+        router
+            .metrics
+            .register_mailbox(&primary_addr, Arc::clone(&metrics));
+    }
+
     // Create an address record and insert it into the internal map
 
     let address_record = AddressRecord::new(
@@ -35,6 +35,7 @@ pub(super) async fn resolve(
         Some(record) if record.check() => {
             trace!("{} OK", base);
             record.increment_msg_count();
+            router.messages.inc();
             reply.send(RouterReply::sender(addr.clone(), record.sender(), wrap))
         }
         Some(_) => {
@@ -0,0 +1,221 @@
+//! Worker supervision
+//!
+//! A worker started with a [`RestartPolicy`] is recreated at the same
+//! address when it fails, and workers linked to it are notified with
+//! a [`WorkerFailed`] message once it fails permanently.
+
+use core::any::Any;
+use core::time::Duration;
+use ockam_core::compat::{
+    boxed::Box,
+    collections::VecDeque,
+    string::{String, ToString},
+    vec::Vec,
+};
+use ockam_core::{Address, Message};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Which failures restart a supervised worker
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Never restart the worker
+    Never,
+    /// Restart the worker when `initialize` or `handle_message`
+    /// return an error, or when it panics
+    OnError,
+    /// Only restart the worker when it panics
+    OnPanic,
+}
+
+/// Restart policy of a supervised worker
+///
+/// Restarts are limited to `max_restarts` within a sliding `window`.
+/// Once a worker exceeds that limit it is stopped for good and its
+/// linked workers are notified.
+///
+/// ```rust
+/// use core::time::Duration;
+/// use ockam_node::RestartPolicy;
+///
+/// let policy = RestartPolicy::on_error().with_max_restarts(5, Duration::from_secs(60));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RestartPolicy {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+impl RestartPolicy {
+    fn new(strategy: RestartStrategy) -> Self {
+        Self {
+            strategy,
+            max_restarts: 3,
+            window: Duration::from_secs(5),
+        }
+    }
+
+    /// Never restart the worker
+    pub fn never() -> Self {
+        Self::new(RestartStrategy::Never)
+    }
+
+    /// Restart the worker on errors and panics, by default at most 3
+    /// times within 5 seconds
+    pub fn on_error() -> Self {
+        Self::new(RestartStrategy::OnError)
+    }
+
+    /// Restart the worker on panics, by default at most 3 times
+    /// within 5 seconds
+    pub fn on_panic() -> Self {
+        Self::new(RestartStrategy::OnPanic)
+    }
+
+    /// Allow at most `max_restarts` restarts within `window`
+    pub fn with_max_restarts(mut self, max_restarts: usize, window: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.window = window;
+        self
+    }
+
+    /// Which failures restart the worker
+    pub fn strategy(&self) -> RestartStrategy {
+        self.strategy
+    }
+}
+
+/// Sent to the workers linked to a supervised worker when it has
+/// failed permanently and was stopped
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Message)]
+pub struct WorkerFailed {
+    /// Address of the failed worker
+    pub addr: Address,
+    /// Description of the last failure
+    pub reason: String,
+}
+
+/// A failure of a worker, reported by its relay
+pub(crate) enum Failure {
+    /// The worker returned an error
+    Error(ockam_core::Error),
+    /// The worker panicked
+    Panic(Box<dyn Any + Send>),
+}
+
+impl Failure {
+    pub(crate) fn reason(&self) -> String {
+        match self {
+            Failure::Error(e) => e.to_string(),
+            Failure::Panic(panic) => {
+                let msg = if let Some(s) = panic.downcast_ref::<&str>() {
+                    s.to_string()
+                } else if let Some(s) = panic.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "unknown panic".to_string()
+                };
+                format!("panicked: {}", msg)
+            }
+        }
+    }
+}
+
+/// What the relay should do about a failure
+pub(crate) enum Decision<W> {
+    /// Log the failure and keep the current worker running
+    Continue,
+    /// Resume the panic of an unsupervised worker, which ends its relay
+    Unwind,
+    /// Replace the worker with a new instance
+    Restart(W),
+    /// Stop the worker and notify its links
+    Stop,
+}
+
+type Factory<W> = Box<dyn Fn() -> W + Send + Sync>;
+
+/// Supervision state of a single worker
+pub(crate) struct Supervisor<W> {
+    policy: RestartPolicy,
+    factory: Option<Factory<W>>,
+    links: Vec<Address>,
+    restarts: VecDeque<Instant>,
+}
+
+impl<W> Default for Supervisor<W> {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicy::never(),
+            factory: None,
+            links: Vec::new(),
+            restarts: VecDeque::new(),
+        }
+    }
+}
+
+impl<W> Supervisor<W> {
+    pub(crate) fn set_policy(&mut self, policy: RestartPolicy, factory: Factory<W>) {
+        self.policy = policy;
+        self.factory = Some(factory);
+    }
+
+    pub(crate) fn link(&mut self, addr: Address) {
+        self.links.push(addr);
+    }
+
+    pub(crate) fn links(&self) -> &[Address] {
+        &self.links
+    }
+
+    /// Whether a restart policy or links were configured
+    fn is_supervised(&self) -> bool {
+        self.factory.is_some() || !self.links.is_empty()
+    }
+
+    /// Decide how to handle a failure
+    ///
+    /// Errors which the policy does not cover are only logged, as
+    /// unsupervised workers keep running after an error.  Panics
+    /// which it does not cover stop a supervised worker and notify
+    /// its links, while unsupervised workers behave as before and
+    /// only lose their relay task.
+    pub(crate) fn decide(&mut self, failure: &Failure) -> Decision<W> {
+        let covered = matches!(
+            (self.policy.strategy, failure),
+            (RestartStrategy::OnError, _) | (RestartStrategy::OnPanic, Failure::Panic(_))
+        );
+        let factory = match &self.factory {
+            Some(factory) if covered => factory,
+            _ => {
+                return match failure {
+                    Failure::Error(_) => Decision::Continue,
+                    Failure::Panic(_) if self.is_supervised() => Decision::Stop,
+                    Failure::Panic(_) => Decision::Unwind,
+                }
+            }
+        };
+
+        let now = Instant::now();
+        while let Some(first) = self.restarts.front() {
+            if now.duration_since(*first) > self.policy.window {
+                self.restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+        if self.restarts.len() >= self.policy.max_restarts {
+            return Decision::Stop;
+        }
+
+        self.restarts.push_back(now);
+        Decision::Restart(factory())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::compat::futures::FutureExt;
-use crate::{Context, NodeBuilder};
+use crate::{Context, NodeBuilder, WorkerBuilder};
 use core::sync::atomic::{AtomicBool, Ordering};
 use core::time::Duration;
 use ockam_core::compat::{
@@ -11,6 +11,7 @@ use ockam_core::{async_trait, Address, Any, Decodable, Message, LOCAL};
 use ockam_core::{route, Processor, Result, Routed, Worker};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicI8, AtomicU32};
+use tokio::sync::Notify;
 use tokio::time::sleep;
 
 #[allow(non_snake_case)]
@@ -560,3 +561,583 @@ async fn starting_worker_with_dup_address_should_fail(ctx: &mut Context) -> Resu
     assert!(ctx.start_worker("dummy_worker", DummyWorker).await.is_err());
     ctx.stop().await
 }
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn router_metrics__messages_and_mailboxes__should_be_tracked(
+    ctx: &mut Context,
+) -> Result<()> {
+    ctx.start_worker("metrics_worker", DummyWorker).await?;
+
+    let reply: String = ctx
+        .send_and_receive("metrics_worker", "Hello".to_string())
+        .await?;
+    assert_eq!(reply, "Hello");
+
+    let samples = ctx.metrics().samples();
+    let messages = samples
+        .iter()
+        .find(|s| s.name == "ockam_router_messages_total")
+        .unwrap();
+    assert!(messages.value >= 2);
+
+    let label = ("address".to_string(), "0#metrics_worker".to_string());
+    let depth = samples
+        .iter()
+        .find(|s| s.name == "ockam_worker_mailbox_depth" && s.labels == vec![label.clone()])
+        .unwrap();
+    assert_eq!(depth.value, 0);
+
+    ctx.stop_worker("metrics_worker").await?;
+    sleep(Duration::from_millis(100)).await;
+    assert!(!ctx
+        .metrics()
+        .samples()
+        .iter()
+        .any(|s| s.labels == vec![label.clone()]));
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn serve_metrics__http_get__should_return_openmetrics_text(ctx: &mut Context) -> Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    ctx.metrics()
+        .counter("test_requests_total", "Test counter", &[("kind", "a")])
+        .inc_by(3);
+    let server = crate::metrics::serve_metrics(ctx, "127.0.0.1:0".parse().unwrap()).await?;
+    let addr = server.addr();
+
+    // Send the request head in two parts
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET /metrics HTTP/1.1\r\n")
+        .await
+        .unwrap();
+    stream.flush().await.unwrap();
+    stream.write_all(b"Host: localhost\r\n\r\n").await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("# TYPE test_requests counter"));
+    assert!(response.contains("test_requests_total{kind=\"a\"} 3"));
+    assert!(response.ends_with("# EOF\n"));
+
+    // The listener closes once the aborted task is dropped
+    server.stop();
+    for _ in 0..10 {
+        if tokio::net::TcpStream::connect(addr).await.is_err() {
+            break;
+        }
+        ctx.sleep(Duration::from_millis(50)).await;
+    }
+    assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+
+    ctx.stop().await
+}
+
+struct GatedWorker {
+    received: Arc<std::sync::Mutex<Vec<u32>>>,
+    started: Arc<Notify>,
+    gate: Arc<Notify>,
+    done: Arc<Notify>,
+    expected: usize,
+}
+
+#[async_trait]
+impl Worker for GatedWorker {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, _ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        let count = {
+            let mut received = self.received.lock().unwrap();
+            received.push(msg.body().parse().unwrap());
+            received.len()
+        };
+        // Keep the worker busy with the first message until the test
+        // has filled its mailbox
+        if count == 1 {
+            self.started.notify_one();
+            self.gate.notified().await;
+        }
+        if count == self.expected {
+            self.done.notify_one();
+        }
+        Ok(())
+    }
+}
+
+struct Gated {
+    received: Arc<std::sync::Mutex<Vec<u32>>>,
+    gate: Arc<Notify>,
+    done: Arc<Notify>,
+}
+
+/// Start a `GatedWorker` which stays busy with its first message
+/// until the gate is opened, so that its mailbox can be filled
+async fn start_gated_worker(
+    ctx: &Context,
+    address: &str,
+    capacity: usize,
+    policy: crate::OverflowPolicy,
+    expected: usize,
+) -> Result<Gated> {
+    let started = Arc::new(Notify::new());
+    let gated = Gated {
+        received: Arc::new(std::sync::Mutex::new(Vec::new())),
+        gate: Arc::new(Notify::new()),
+        done: Arc::new(Notify::new()),
+    };
+    let worker = GatedWorker {
+        received: gated.received.clone(),
+        started: started.clone(),
+        gate: gated.gate.clone(),
+        done: gated.done.clone(),
+        expected,
+    };
+    WorkerBuilder::with_inherited_access_control(ctx, address, worker)
+        .with_mailbox_capacity(capacity)
+        .with_overflow_policy(policy)
+        .start(ctx)
+        .await?;
+
+    ctx.send(address, 1.to_string()).await?;
+    started.notified().await;
+    Ok(gated)
+}
+
+fn dropped(ctx: &Context, address: &str) -> u64 {
+    ctx.metrics()
+        .samples()
+        .into_iter()
+        .find(|s| {
+            s.name == "ockam_worker_mailbox_dropped_total"
+                && s.labels == vec![("address".to_string(), format!("0#{}", address))]
+        })
+        .map_or(0, |s| s.value as u64)
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn bounded_mailbox__block__should_wait_for_room(ctx: &mut Context) -> Result<()> {
+    let gated = start_gated_worker(ctx, "block", 1, crate::OverflowPolicy::Block, 3).await?;
+    ctx.send("block", 2.to_string()).await?;
+
+    let sender = ctx.new_detached("block_sender").await?;
+    let mut blocked = tokio::spawn(async move { sender.send("block", 3.to_string()).await });
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), &mut blocked)
+            .await
+            .is_err()
+    );
+
+    gated.gate.notify_one();
+    blocked.await.unwrap()?;
+    gated.done.notified().await;
+
+    assert_eq!(*gated.received.lock().unwrap(), vec![1, 2, 3]);
+    assert_eq!(dropped(ctx, "block"), 0);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn bounded_mailbox__drop_newest__should_keep_oldest_messages(
+    ctx: &mut Context,
+) -> Result<()> {
+    let gated =
+        start_gated_worker(ctx, "drop_newest", 2, crate::OverflowPolicy::DropNewest, 3).await?;
+    for i in 2..=5u32 {
+        ctx.send("drop_newest", i.to_string()).await?;
+    }
+
+    gated.gate.notify_one();
+    gated.done.notified().await;
+
+    assert_eq!(*gated.received.lock().unwrap(), vec![1, 2, 3]);
+    assert_eq!(dropped(ctx, "drop_newest"), 2);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn bounded_mailbox__drop_oldest__should_keep_newest_messages(
+    ctx: &mut Context,
+) -> Result<()> {
+    let gated =
+        start_gated_worker(ctx, "drop_oldest", 2, crate::OverflowPolicy::DropOldest, 3).await?;
+    for i in 2..=3u32 {
+        ctx.send("drop_oldest", i.to_string()).await?;
+    }
+
+    // Sending to the full mailbox returns while the worker is still busy
+    for i in 4..=5u32 {
+        tokio::time::timeout(
+            Duration::from_millis(200),
+            ctx.send("drop_oldest", i.to_string()),
+        )
+        .await
+        .expect("send to a full mailbox should not block")?;
+    }
+
+    gated.gate.notify_one();
+    gated.done.notified().await;
+
+    assert_eq!(*gated.received.lock().unwrap(), vec![1, 4, 5]);
+    assert_eq!(dropped(ctx, "drop_oldest"), 2);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn bounded_mailbox__error__should_fail_send(ctx: &mut Context) -> Result<()> {
+    let gated = start_gated_worker(ctx, "error", 2, crate::OverflowPolicy::Error, 3).await?;
+    let mut results = vec![];
+    for i in 2..=5u32 {
+        results.push(ctx.send("error", i.to_string()).await);
+    }
+
+    gated.gate.notify_one();
+    gated.done.notified().await;
+
+    assert!(results[0].is_ok() && results[1].is_ok());
+    assert!(results[2].is_err() && results[3].is_err());
+    assert_eq!(*gated.received.lock().unwrap(), vec![1, 2, 3]);
+    assert_eq!(dropped(ctx, "error"), 2);
+
+    ctx.stop().await
+}
+
+struct FlakyWorker {
+    starts: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl Worker for FlakyWorker {
+    type Message = String;
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        let start = self.starts.fetch_add(1, Ordering::Relaxed) + 1;
+        ctx.send("flaky_probe", start.to_string()).await
+    }
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        match msg.as_body().as_str() {
+            "error" => Err(ockam_core::Error::new(
+                ockam_core::errcode::Origin::Node,
+                ockam_core::errcode::Kind::Other,
+                "flaky error",
+            )),
+            "panic" => panic!("flaky panic"),
+            _ => ctx.send(msg.return_route(), msg.body()).await,
+        }
+    }
+}
+
+/// Start a supervised `FlakyWorker`, returning a probe context that
+/// receives the number of every (re)start of the worker
+async fn start_flaky_worker(
+    ctx: &Context,
+    address: &str,
+    policy: crate::RestartPolicy,
+    link: Option<&str>,
+) -> Result<Context> {
+    let mut probe = ctx.new_detached("flaky_probe").await?;
+    let starts = Arc::new(AtomicU32::new(0));
+    let factory_starts = starts.clone();
+    let mut builder =
+        WorkerBuilder::with_inherited_access_control(ctx, address, FlakyWorker { starts })
+            .with_restart_policy(policy, move || FlakyWorker {
+                starts: factory_starts.clone(),
+            });
+    if let Some(link) = link {
+        builder = builder.with_link(link);
+    }
+    builder.start(ctx).await?;
+
+    assert_eq!(probe.receive_timeout::<String>(1).await?.take().body(), "1");
+    Ok(probe)
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn supervised_worker__error__should_restart(ctx: &mut Context) -> Result<()> {
+    let mut probe =
+        start_flaky_worker(ctx, "flaky_error", crate::RestartPolicy::on_error(), None).await?;
+
+    ctx.send("flaky_error", "error".to_string()).await?;
+    assert_eq!(probe.receive_timeout::<String>(1).await?.take().body(), "2");
+
+    let reply: String = ctx
+        .send_and_receive("flaky_error", "Hello".to_string())
+        .await?;
+    assert_eq!(reply, "Hello");
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn supervised_worker__panic__should_restart(ctx: &mut Context) -> Result<()> {
+    let mut probe =
+        start_flaky_worker(ctx, "flaky_panic", crate::RestartPolicy::on_panic(), None).await?;
+
+    // Errors are not covered by an `on_panic` policy
+    ctx.send("flaky_panic", "error".to_string()).await?;
+    ctx.send("flaky_panic", "panic".to_string()).await?;
+    assert_eq!(probe.receive_timeout::<String>(1).await?.take().body(), "2");
+    assert!(probe.receive_timeout::<String>(1).await.is_err());
+
+    let reply: String = ctx
+        .send_and_receive("flaky_panic", "Hello".to_string())
+        .await?;
+    assert_eq!(reply, "Hello");
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn supervised_worker__max_restarts__should_stop_and_notify_links(
+    ctx: &mut Context,
+) -> Result<()> {
+    let mut link = ctx.new_detached("flaky_link").await?;
+    let policy = crate::RestartPolicy::on_error().with_max_restarts(1, Duration::from_secs(60));
+    let mut probe = start_flaky_worker(ctx, "flaky_max", policy, Some("flaky_link")).await?;
+
+    ctx.send("flaky_max", "error".to_string()).await?;
+    assert_eq!(probe.receive_timeout::<String>(1).await?.take().body(), "2");
+
+    ctx.send("flaky_max", "error".to_string()).await?;
+    let failed = link
+        .receive_timeout::<crate::WorkerFailed>(1)
+        .await?
+        .take()
+        .body();
+    assert_eq!(failed.addr, "flaky_max".into());
+    assert_eq!(failed.reason, "flaky error");
+
+    let address: Address = "flaky_max".into();
+    for _ in 0..10 {
+        if !ctx.list_workers().await?.contains(&address) {
+            break;
+        }
+        ctx.sleep(Duration::from_millis(50)).await;
+    }
+    assert!(!ctx.list_workers().await?.contains(&address));
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn monitor__stopped_worker__should_notify_monitors(ctx: &mut Context) -> Result<()> {
+    let mut monitor = ctx.new_detached("monitor").await?;
+    ctx.start_worker("monitored", DummyWorker).await?;
+    monitor.monitor("monitored").await?;
+    // Monitoring twice only results in a single notification
+    monitor.monitor("monitored").await?;
+
+    ctx.stop_worker("monitored").await?;
+    let stopped = monitor
+        .receive_timeout::<crate::WorkerStopped>(1)
+        .await?
+        .take()
+        .body();
+    assert_eq!(stopped.addr, "monitored".into());
+    assert_eq!(stopped.reason, crate::StopReason::Normal);
+    assert!(monitor
+        .receive_duration_timeout::<crate::WorkerStopped>(Duration::from_millis(200))
+        .await
+        .is_err());
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn monitor__stopped_processor__should_notify_monitors(ctx: &mut Context) -> Result<()> {
+    let mut monitor = ctx.new_detached("monitor").await?;
+    ctx.start_processor("monitored", DummyProcessor).await?;
+    monitor.monitor("monitored").await?;
+
+    ctx.stop_processor("monitored").await?;
+    let stopped = monitor
+        .receive_timeout::<crate::WorkerStopped>(1)
+        .await?
+        .take()
+        .body();
+    assert_eq!(stopped.addr, "monitored".into());
+    assert_eq!(stopped.reason, crate::StopReason::Normal);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn monitor__failed_worker__should_report_failure(ctx: &mut Context) -> Result<()> {
+    let mut monitor = ctx.new_detached("monitor").await?;
+    let policy = crate::RestartPolicy::on_error().with_max_restarts(0, Duration::from_secs(60));
+    let _probe = start_flaky_worker(ctx, "flaky_monitored", policy, None).await?;
+    monitor.monitor("flaky_monitored").await?;
+
+    ctx.send("flaky_monitored", "error".to_string()).await?;
+    let stopped = monitor
+        .receive_timeout::<crate::WorkerStopped>(1)
+        .await?
+        .take()
+        .body();
+    assert_eq!(stopped.addr, "flaky_monitored".into());
+    assert_eq!(
+        stopped.reason,
+        crate::StopReason::Failed("flaky error".to_string())
+    );
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn monitor__unknown_or_detached_address__should_fail(ctx: &mut Context) -> Result<()> {
+    let _detached = ctx.new_detached("detached").await?;
+    assert!(ctx.monitor("unknown").await.is_err());
+    assert!(ctx.monitor("detached").await.is_err());
+
+    ctx.stop().await
+}
+
+/// Answers `/count/{n}` with a stream of `n` numbers, `/forever` with
+/// numbers until cancelled, and `/fail` with a failed stream
+struct StreamingWorker;
+
+#[async_trait]
+impl Worker for StreamingWorker {
+    type Context = Context;
+    type Message = Vec<u8>;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Vec<u8>>) -> Result<()> {
+        let mut dec = minicbor::Decoder::new(msg.as_body());
+        let req: ockam_core::api::Request = dec.decode()?;
+        let mut stream = crate::api::respond_stream(ctx, msg.return_route(), &req).await?;
+        let count = match req.path_segments::<3>().as_slice() {
+            ["count", n] => Some(n.parse::<u32>().unwrap()),
+            ["fail"] => {
+                let e = ockam_core::Error::new(
+                    ockam_core::errcode::Origin::Application,
+                    ockam_core::errcode::Kind::NotFound,
+                    "nothing to stream",
+                );
+                return stream.fail(&e).await;
+            }
+            ["silent"] => return Ok(()),
+            ["uncancelled"] => {
+                stream.set_timeout(Duration::from_millis(100));
+                let timed_out = stream.cancelled().await.is_err();
+                stream.send(timed_out).await?;
+                return stream.end().await;
+            }
+            _ => None,
+        };
+        tokio::spawn(async move {
+            let mut i = 0;
+            while count.map_or(true, |n| i < n) && !stream.is_cancelled().await {
+                stream.send(i).await.unwrap();
+                i += 1;
+                sleep(Duration::from_millis(10)).await;
+            }
+            stream.end().await.unwrap();
+        });
+        Ok(())
+    }
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn stream__finite__should_end_after_last_item(ctx: &mut Context) -> Result<()> {
+    use ockam_core::api::Request;
+
+    ctx.start_worker("streaming", StreamingWorker).await?;
+    let req = Request::get("/count/3");
+    let mut stream = crate::api::request_stream(ctx, "count", None, "streaming", req).await?;
+    for i in 0..3 {
+        assert_eq!(Some(i), stream.next::<u32>().await?);
+    }
+    assert_eq!(None, stream.next::<u32>().await?);
+    assert_eq!(None, stream.next::<u32>().await?);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn stream__cancelled__should_end(ctx: &mut Context) -> Result<()> {
+    use ockam_core::api::Request;
+
+    ctx.start_worker("streaming", StreamingWorker).await?;
+    let req = Request::get("/forever");
+    let mut stream = crate::api::request_stream(ctx, "forever", None, "streaming", req).await?;
+    assert_eq!(Some(0), stream.next::<u32>().await?);
+
+    // Items sent before the server noticed the cancellation are
+    // still received
+    stream.cancel().await?;
+    let mut n = 1;
+    while stream.next::<u32>().await?.is_some() {
+        n += 1;
+    }
+    assert!(n < 10);
+    assert_eq!(None, stream.next::<u32>().await?);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn stream__failed__should_return_error(ctx: &mut Context) -> Result<()> {
+    use ockam_core::api::Request;
+
+    ctx.start_worker("streaming", StreamingWorker).await?;
+    let req = Request::get("/fail");
+    let mut stream = crate::api::request_stream(ctx, "fail", None, "streaming", req).await?;
+    let e = stream.next::<u32>().await.unwrap_err();
+    assert_eq!(e.code().kind, ockam_core::errcode::Kind::NotFound);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn stream__silent_server__should_time_out(ctx: &mut Context) -> Result<()> {
+    use ockam_core::api::Request;
+
+    ctx.start_worker("streaming", StreamingWorker).await?;
+    let req = Request::get("/silent");
+    let mut stream = crate::api::request_stream(ctx, "silent", None, "streaming", req).await?;
+    stream.set_timeout(Duration::from_millis(100));
+    let e = stream.next::<u32>().await.unwrap_err();
+    assert_eq!(e.code().kind, ockam_core::errcode::Kind::Timeout);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(crate = "crate")]
+async fn stream__not_cancelled__should_time_out_waiting(ctx: &mut Context) -> Result<()> {
+    use ockam_core::api::Request;
+
+    ctx.start_worker("streaming", StreamingWorker).await?;
+    let req = Request::get("/uncancelled");
+    let mut stream = crate::api::request_stream(ctx, "uncancelled", None, "streaming", req).await?;
+    assert_eq!(Some(true), stream.next::<bool>().await?);
+    assert_eq!(None, stream.next::<bool>().await?);
+
+    ctx.stop().await
+}
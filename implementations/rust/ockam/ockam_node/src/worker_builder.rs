@@ -1,4 +1,7 @@
+use crate::channel_types::{MailboxConfig, OverflowPolicy};
 use crate::error::{NodeError, NodeReason};
+#[cfg(feature = "std")]
+use crate::supervisor::{RestartPolicy, Supervisor};
 use crate::{relay::WorkerRelay, Context, NodeMessage};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{
@@ -17,8 +20,36 @@ use ockam_core::{
 ///
 /// Varying use-cases should use the builder API to customise the
 /// underlying worker that is created.
+///
+/// By default a worker's mailbox holds up to
+/// [`DEFAULT_MAILBOX_CAPACITY`](crate::channel_types::DEFAULT_MAILBOX_CAPACITY)
+/// messages and senders wait while it is full.  A worker which may
+/// fall behind can use [`with_overflow_policy`](Self::with_overflow_policy)
+/// so that it doesn't stall the workers sending to it.
+///
+/// ```rust
+/// # use ockam_node::{Context, OverflowPolicy, WorkerBuilder};
+/// # use ockam_core::{Result, Worker};
+/// # struct MyWorker;
+/// # #[ockam_core::worker]
+/// # impl Worker for MyWorker {
+/// #     type Context = Context;
+/// #     type Message = String;
+/// # }
+/// # async fn start(ctx: &Context) -> Result<()> {
+/// WorkerBuilder::with_inherited_access_control(ctx, "slow_worker", MyWorker)
+///     .with_mailbox_capacity(128)
+///     .with_overflow_policy(OverflowPolicy::DropOldest)
+///     .start(ctx)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct WorkerBuilder<W> {
     mailboxes: Mailboxes,
+    mailbox_config: MailboxConfig,
+    #[cfg(feature = "std")]
+    supervisor: Supervisor<W>,
     worker: W,
 }
 
@@ -34,7 +65,13 @@ where
     {
         let mailboxes = Mailboxes::from_address_set(address_set.into(), Arc::new(AllowAll));
 
-        Self { mailboxes, worker }
+        Self {
+            mailboxes,
+            mailbox_config: MailboxConfig::default(),
+            #[cfg(feature = "std")]
+            supervisor: Supervisor::default(),
+            worker,
+        }
     }
 
     /// Create a worker which inherits access control from the given context
@@ -56,7 +93,13 @@ where
 
         let mailboxes = Mailboxes::from_address_set(address_set, access_control);
 
-        Self { mailboxes, worker }
+        Self {
+            mailboxes,
+            mailbox_config: MailboxConfig::default(),
+            #[cfg(feature = "std")]
+            supervisor: Supervisor::default(),
+            worker,
+        }
     }
 
     /// Create a worker which uses the given access control
@@ -67,13 +110,62 @@ where
     {
         let mailboxes = Mailboxes::main(address.into(), Arc::new(access_control));
 
-        Self { mailboxes, worker }
+        Self {
+            mailboxes,
+            mailbox_config: MailboxConfig::default(),
+            #[cfg(feature = "std")]
+            supervisor: Supervisor::default(),
+            worker,
+        }
     }
 
     /// Create a worker which uses the access control from the given
     /// [`Mailboxes`]
     pub fn with_mailboxes(mailboxes: Mailboxes, worker: W) -> Self {
-        Self { mailboxes, worker }
+        Self {
+            mailboxes,
+            mailbox_config: MailboxConfig::default(),
+            #[cfg(feature = "std")]
+            supervisor: Supervisor::default(),
+            worker,
+        }
+    }
+
+    /// Set the maximum number of messages waiting in the worker's mailbox
+    pub fn with_mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_config.capacity = capacity;
+        self
+    }
+
+    /// Set what happens to messages sent while the worker's mailbox is full
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.mailbox_config.policy = policy;
+        self
+    }
+
+    /// Supervise the worker with the given [`RestartPolicy`]
+    ///
+    /// Every restart replaces the failed worker with a new instance
+    /// created by `factory`, at the same address and with the same
+    /// mailbox.
+    #[cfg(feature = "std")]
+    pub fn with_restart_policy<F>(mut self, policy: RestartPolicy, factory: F) -> Self
+    where
+        F: Fn() -> W + Send + Sync + 'static,
+    {
+        self.supervisor.set_policy(policy, Box::new(factory));
+        self
+    }
+
+    /// Send a [`WorkerFailed`](crate::WorkerFailed) message to the
+    /// worker at `address` if this worker fails permanently
+    ///
+    /// A worker fails permanently when it panics or exceeds the
+    /// restarts allowed by its [`RestartPolicy`].
+    #[cfg(feature = "std")]
+    pub fn with_link(mut self, address: impl Into<Address>) -> Self {
+        self.supervisor.link(address.into());
+        self
     }
 
     /// Consume this builder and start a new Ockam [`Worker`] from the given context
@@ -92,16 +184,25 @@ where
         let (ctx, sender, ctrl_rx) = Context::new(
             context.runtime().clone(),
             context.sender().clone(),
+            context.metrics().clone(),
             mailboxes,
+            self.mailbox_config,
             None,
         );
+        let mailbox_count = ctx.mailbox_count();
 
         // Then initialise the worker message relay
-        WorkerRelay::<W, M>::init(context.runtime(), self.worker, ctx, ctrl_rx);
+        WorkerRelay::<W, M>::init(
+            context.runtime(),
+            self.worker,
+            ctx,
+            ctrl_rx,
+            #[cfg(feature = "std")]
+            self.supervisor,
+        );
 
         // Send start request to router
-        let (msg, mut rx) =
-            NodeMessage::start_worker(addresses, sender, false, context.mailbox_count());
+        let (msg, mut rx) = NodeMessage::start_worker(addresses, sender, false, mailbox_count);
         context
             .sender()
             .send(msg)
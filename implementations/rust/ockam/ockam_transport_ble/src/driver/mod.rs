@@ -13,6 +13,10 @@ pub mod bluetooth_hci;
 ))]
 pub mod btleplug;
 
+/// in-memory simulated BLE link for tests
+#[cfg(feature = "use_simulated")]
+pub mod simulated;
+
 #[cfg(not(feature = "std"))]
 mod mutex;
 mod packet;
@@ -34,7 +38,12 @@ pub const MTU: usize = 23;
 /// Hard-limited for now according to MTU:
 ///
 /// MTU - 5 (packet fields) = 18 bytes of payload
-pub const CHARACTERISTIC_VALUE_LENGTH: usize = MTU - 5;
+pub const CHARACTERISTIC_VALUE_LENGTH: usize = characteristic_value_length(MTU);
+
+/// Maximum length of characteristic values for a given MTU
+pub const fn characteristic_value_length(mtu: usize) -> usize {
+    mtu - 5
+}
 
 /// Maximum length of ockam messages
 pub const MAX_OCKAM_MESSAGE_LENGTH: usize = 1024;
@@ -94,6 +103,12 @@ pub trait BleServerDriver {
 pub trait BleStreamDriver {
     async fn poll<'b>(&mut self, buffer: &'b mut [u8]) -> Result<BleEvent<'b>>;
     async fn write(&mut self, buffer: &[u8]) -> Result<()>;
+
+    /// The MTU negotiated for the connection, outgoing messages are
+    /// fragmented to fit into it
+    fn mtu(&self) -> usize {
+        MTU
+    }
 }
 
 /// A BLE client that initiates GATT commands and requests, and
@@ -137,6 +152,10 @@ where
     async fn write(&mut self, buffer: &[u8]) -> Result<()> {
         self.inner.write(buffer).await
     }
+
+    fn mtu(&self) -> usize {
+        self.inner.mtu()
+    }
 }
 
 /// A BLE server that receives GATT commands and requests, and returns
@@ -180,4 +199,8 @@ where
     async fn write(&mut self, buffer: &[u8]) -> Result<()> {
         self.inner.write(buffer).await
     }
+
+    fn mtu(&self) -> usize {
+        self.inner.mtu()
+    }
 }
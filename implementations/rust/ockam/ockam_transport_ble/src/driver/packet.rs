@@ -1,8 +1,8 @@
 use core::cmp::Ordering;
 
 use crate::driver::BleEvent;
-use crate::driver::CHARACTERISTIC_VALUE_LENGTH;
 use crate::driver::MAX_OCKAM_MESSAGE_LENGTH;
+use crate::driver::{characteristic_value_length, CHARACTERISTIC_VALUE_LENGTH};
 use crate::error::BleError;
 
 use ockam_core::Result;
@@ -38,7 +38,8 @@ impl PacketBuffer {
 
 /// PacketBuffer send implementation
 impl PacketBuffer {
-    pub fn from_packet(packet: &[u8]) -> PacketBuffer {
+    /// Create a PacketBuffer sending fragments that fit into the given MTU
+    pub fn from_packet(packet: &[u8], mtu: usize) -> PacketBuffer {
         if packet.len() > MAX_OCKAM_MESSAGE_LENGTH {
             error!(
                 "Packet too long for PacketBuffer: {} > {} ",
@@ -53,6 +54,7 @@ impl PacketBuffer {
         }
 
         let mut pb = PacketBuffer {
+            fragment_len: characteristic_value_length(mtu),
             packet_len: packet.len(),
             ..Default::default()
         };
@@ -132,8 +134,10 @@ impl PacketBuffer {
     }
 
     pub fn receive_next_fragment(&mut self, fragment: &[u8]) -> Result<Option<&[u8]>> {
+        // e.g. the fragment carrying the packet length was lost
         if self.offset >= self.packet_len {
-            panic!("packet buffer already has enough fragments")
+            error!("Received packet fragment without a packet length");
+            return Err(BleError::ReadError.into());
         }
 
         let fragment_len = fragment.len();
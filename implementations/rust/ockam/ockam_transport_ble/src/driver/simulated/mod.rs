@@ -0,0 +1,245 @@
+//! Driver for tests
+//!
+//! Connects a [`BleClient`](crate::BleClient) and a
+//! [`BleServer`](crate::BleServer) through an in-memory link with a
+//! configurable MTU, latency and packet loss, so the transport can be
+//! exercised without any Bluetooth hardware.
+
+use core::time::Duration;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use ockam_core::compat::rand::prelude::{Rng, SeedableRng};
+use ockam_core::compat::rand::rngs::StdRng;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::vec::Vec;
+use ockam_core::{async_trait, Result};
+
+use crate::driver::{characteristic_value_length, BleEvent, MTU};
+use crate::driver::{BleClientDriver, BleServerDriver, BleStreamDriver};
+use crate::error::BleError;
+use crate::BleAddr;
+
+/// The largest MTU a simulated link can be configured with
+pub const MAX_MTU: usize = 255;
+
+/// Configuration of a simulated link between a BLE client and server
+///
+/// ```rust
+/// use core::time::Duration;
+/// use ockam_transport_ble::driver::simulated::SimulatedLink;
+/// use ockam_transport_ble::{BleClient, BleServer};
+///
+/// let (client, server) = SimulatedLink::default()
+///     .with_mtu(185)
+///     .with_latency(Duration::from_millis(5))
+///     .with_packet_loss(0.01)
+///     .pair();
+///
+/// let ble_client = BleClient::with_adapter(client);
+/// let ble_server = BleServer::with_adapter(server);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SimulatedLink {
+    mtu: usize,
+    latency: Duration,
+    packet_loss: f64,
+    seed: u64,
+}
+
+impl Default for SimulatedLink {
+    fn default() -> Self {
+        Self {
+            mtu: MTU,
+            latency: Duration::ZERO,
+            packet_loss: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl SimulatedLink {
+    /// Set the MTU of the link, clamped between [`MTU`] and [`MAX_MTU`]
+    pub fn with_mtu(mut self, mtu: usize) -> Self {
+        self.mtu = mtu.clamp(MTU, MAX_MTU);
+        self
+    }
+
+    /// Set the time a packet takes to reach the other end of the link
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Set the probability, between 0 and 1, for a packet to be lost
+    pub fn with_packet_loss(mut self, packet_loss: f64) -> Self {
+        self.packet_loss = packet_loss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the seed deciding which packets are lost
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Create the client and server adapters at both ends of the link
+    pub fn pair(self) -> (SimulatedAdapter, SimulatedAdapter) {
+        let state = Arc::new(Mutex::new(LinkState::default()));
+        let client = SimulatedAdapter::new(self.clone(), state.clone(), Side::Client);
+        let server = SimulatedAdapter::new(self, state, Side::Server);
+        (client, server)
+    }
+}
+
+/// State shared by both ends of a link
+#[derive(Default)]
+struct LinkState {
+    /// Name the server is bound to
+    bound: Option<String>,
+    /// Whether the client is connected to the server
+    connected: bool,
+    /// Packets in flight, with the time they reach the client
+    to_client: VecDeque<(Instant, Vec<u8>)>,
+    /// Packets in flight, with the time they reach the server
+    to_server: VecDeque<(Instant, Vec<u8>)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Client,
+    Server,
+}
+
+/// One end of a [`SimulatedLink`]
+pub struct SimulatedAdapter {
+    link: SimulatedLink,
+    state: Arc<Mutex<LinkState>>,
+    side: Side,
+    rng: StdRng,
+    found: bool,
+    connection_reported: bool,
+}
+
+impl SimulatedAdapter {
+    fn new(link: SimulatedLink, state: Arc<Mutex<LinkState>>, side: Side) -> Self {
+        let seed = match side {
+            Side::Client => link.seed,
+            Side::Server => link.seed.wrapping_add(1),
+        };
+        Self {
+            link,
+            state,
+            side,
+            rng: StdRng::seed_from_u64(seed),
+            found: false,
+            connection_reported: false,
+        }
+    }
+}
+
+#[async_trait]
+impl BleClientDriver for SimulatedAdapter {
+    async fn scan(&mut self, ble_addr: &BleAddr) -> Result<()> {
+        let state = self.state.lock().unwrap();
+        if state.bound.as_deref() != Some(ble_addr.to_string().as_str()) {
+            debug!("[simulated] no server bound to: {}", ble_addr);
+            return Err(BleError::NotFound.into());
+        }
+
+        self.found = true;
+        Ok(())
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        if !self.found {
+            return Err(BleError::NotFound.into());
+        }
+
+        self.state.lock().unwrap().connected = true;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BleServerDriver for SimulatedAdapter {
+    async fn bind(&mut self, ble_addr: &BleAddr) -> Result<()> {
+        self.state.lock().unwrap().bound = Some(ble_addr.to_string());
+        Ok(())
+    }
+
+    async fn start_advertising(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BleStreamDriver for SimulatedAdapter {
+    async fn poll<'b>(&mut self, buffer: &'b mut [u8]) -> Result<BleEvent<'b>> {
+        // avoid deadlocking the caller
+        ockam_node::tokio::task::yield_now().await;
+
+        let mut state = self.state.lock().unwrap();
+        if !state.connected {
+            return match self.side {
+                Side::Client => Err(BleError::NotConnected.into()),
+                Side::Server => Ok(BleEvent::None),
+            };
+        }
+
+        if self.side == Side::Server && !self.connection_reported {
+            self.connection_reported = true;
+            return Ok(BleEvent::ConnectionComplete);
+        }
+
+        let incoming = match self.side {
+            Side::Client => &mut state.to_client,
+            Side::Server => &mut state.to_server,
+        };
+        match incoming.front() {
+            Some((deliver_at, _)) if *deliver_at <= Instant::now() => {
+                let (_, data) = incoming.pop_front().unwrap();
+                let len = core::cmp::min(data.len(), buffer.len());
+                buffer[..len].copy_from_slice(&data[..len]);
+                Ok(BleEvent::Received(&buffer[..len]))
+            }
+            _ => Ok(BleEvent::None),
+        }
+    }
+
+    async fn write(&mut self, buffer: &[u8]) -> Result<()> {
+        trace!("[simulated] write {} bytes", buffer.len());
+
+        let mut state = self.state.lock().unwrap();
+        if !state.connected {
+            return Err(BleError::NotConnected.into());
+        }
+
+        if buffer.len() > characteristic_value_length(self.link.mtu) {
+            error!(
+                "[simulated] write of {} bytes exceeds the MTU of {}",
+                buffer.len(),
+                self.link.mtu
+            );
+            return Err(BleError::WriteError.into());
+        }
+
+        if self.rng.gen_bool(self.link.packet_loss) {
+            debug!("[simulated] dropping {} bytes", buffer.len());
+            return Ok(());
+        }
+
+        let outgoing = match self.side {
+            Side::Client => &mut state.to_server,
+            Side::Server => &mut state.to_client,
+        };
+        outgoing.push_back((Instant::now() + self.link.latency, buffer.to_vec()));
+
+        Ok(())
+    }
+
+    fn mtu(&self) -> usize {
+        self.link.mtu
+    }
+}
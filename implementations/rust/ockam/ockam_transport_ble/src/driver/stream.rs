@@ -58,6 +58,11 @@ where
         let mut guard = self.inner.lock().await;
         (*guard).poll(buffer).await
     }
+
+    async fn mtu(&self) -> usize {
+        let guard = self.inner.lock().await;
+        (*guard).mtu()
+    }
 }
 
 /// A Sink for writing data buffers to the Ble adapter
@@ -72,6 +77,10 @@ where
     pub async fn write(&self, buffer: &[u8]) -> Result<()> {
         self.inner.write(buffer).await
     }
+
+    pub async fn mtu(&self) -> usize {
+        self.inner.mtu().await
+    }
 }
 
 /// A Source for reading data buffers from the Ble adapter
@@ -109,7 +109,7 @@ impl Worker for BleRouter {
         let msg_addr = msg.msg_addr();
 
         if msg_addr == self.main_addr {
-            let msg = LocalMessage::decode(msg.payload())?;
+            let msg = msg.into_local_message();
             trace!("handle_message route: {:?}", msg.transport().onward_route);
             self.handle_route(ctx, msg).await?;
         } else if msg_addr == self.api_addr {
@@ -1,5 +1,4 @@
 use core::str::FromStr;
-use core::sync::atomic::{AtomicBool, Ordering};
 
 use ockam_core::compat::boxed::Box;
 use ockam_core::{async_trait, AsyncTryClone, Result};
@@ -15,7 +14,7 @@ use crate::BleAddr;
 /// Be aware that only one `BleTransport` can exist per node, as it
 /// registers itself as a router for the `BLE` address type.  Multiple
 /// calls to [`BleTransport::create`](crate::BleTransport::create)
-/// will panic.
+/// on the same node will fail.
 ///
 /// To register additional connections on an already initialised
 /// `BleTransport`, use
@@ -56,11 +55,6 @@ impl AsyncTryClone for BleTransport {
 impl BleTransport {
     /// Create a new BLE transport and router for the current node
     pub async fn create(ctx: &Context) -> Result<Self> {
-        static CREATED: AtomicBool = AtomicBool::new(false);
-        if CREATED.swap(true, Ordering::SeqCst) {
-            panic!("You may only create one BleTransport per node.");
-        }
-
         let router_handle = BleRouter::register(ctx).await?;
 
         Ok(Self { router_handle })
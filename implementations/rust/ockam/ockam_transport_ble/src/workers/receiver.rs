@@ -93,6 +93,7 @@ where
                 let mut msg = match result {
                     Err(e) => {
                         error!("Error decoding message: {:?}", e);
+                        self.packet_buffer.reset();
                         return Err(e.into());
                     }
                     Ok(msg) => msg,
@@ -120,7 +120,8 @@ where
 
         // create packet buffer
         debug!("creating packet buffer");
-        let mut packet_buffer = PacketBuffer::from_packet(&msg);
+        let mtu = self.tx_stream.as_ref().unwrap().mtu().await;
+        let mut packet_buffer = PacketBuffer::from_packet(&msg, mtu);
 
         // send packet length
         debug!("sending packet length: {}", packet_buffer.packet_len());
@@ -0,0 +1,118 @@
+use core::time::Duration;
+
+use ockam_core::compat::rand::{self, Rng};
+use ockam_core::{route, Result, Routed, Worker};
+use ockam_identity::authenticated_storage::mem::InMemoryStorage;
+use ockam_identity::{Identity, TrustEveryonePolicy};
+use ockam_node::Context;
+use ockam_vault::Vault;
+
+use ockam_transport_ble::driver::simulated::SimulatedLink;
+use ockam_transport_ble::{BleClient, BleServer, BleTransport, BLE};
+
+const SERVER: &str = "ockam_ble_simulated";
+
+/// Create a BLE transport connected to itself over a simulated link
+async fn create_transport(ctx: &Context, link: SimulatedLink) -> Result<BleTransport> {
+    let (client, server) = link.pair();
+
+    let ble = BleTransport::create(ctx).await?;
+    ble.listen(BleServer::with_adapter(server), SERVER).await?;
+    ble.connect(BleClient::with_adapter(client), SERVER).await?;
+
+    Ok(ble)
+}
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+#[ockam_macros::test(timeout = 60000)]
+async fn large_message_is_fragmented(ctx: &mut Context) -> Result<()> {
+    let _ble = create_transport(ctx, SimulatedLink::default()).await?;
+
+    // Spans about 50 fragments at the minimum MTU
+    let msg = random_string(900);
+    ctx.send(route![(BLE, SERVER), ctx.address()], msg.clone())
+        .await?;
+
+    let reply = ctx.receive::<String>().await?;
+    assert_eq!(reply.take().body(), msg);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test(timeout = 60000)]
+async fn secure_channel_over_simulated_link(ctx: &mut Context) -> Result<()> {
+    let link = SimulatedLink::default()
+        .with_mtu(185)
+        .with_latency(Duration::from_millis(5));
+    let _ble = create_transport(ctx, link).await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let vault = Vault::create();
+    let alice = Identity::create(ctx, &vault).await?;
+    let bob = Identity::create(ctx, &vault).await?;
+    let storage = InMemoryStorage::new();
+
+    bob.create_secure_channel_listener("bob_listener", TrustEveryonePolicy, &storage)
+        .await?;
+    let channel = alice
+        .create_secure_channel(
+            route![(BLE, SERVER), "bob_listener"],
+            TrustEveryonePolicy,
+            &storage,
+        )
+        .await?;
+
+    let msg = random_string(256);
+    let reply = ctx
+        .send_and_receive::<_, _, String>(route![channel, "echoer"], msg.clone())
+        .await?;
+    assert_eq!(reply, msg);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test(timeout = 60000)]
+async fn lossy_link_delivers_intact_messages(ctx: &mut Context) -> Result<()> {
+    let link = SimulatedLink::default()
+        .with_mtu(185)
+        .with_packet_loss(0.25)
+        .with_seed(7);
+    let _ble = create_transport(ctx, link).await?;
+
+    let sent: Vec<String> = (0..20).map(|i| format!("message {}", i)).collect();
+    for msg in &sent {
+        ctx.send(route![(BLE, SERVER), ctx.address()], msg.clone())
+            .await?;
+    }
+
+    // Lost fragments drop whole messages, without corrupting the
+    // ones that follow
+    let mut received = vec![];
+    while let Ok(msg) = ctx.receive_timeout::<String>(2).await {
+        received.push(msg.take().body());
+    }
+    assert!(!received.is_empty());
+    assert!(received.len() < sent.len());
+    assert!(received.iter().all(|msg| sent.contains(msg)));
+
+    ctx.stop().await
+}
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
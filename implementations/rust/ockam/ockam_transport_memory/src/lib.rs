@@ -0,0 +1,49 @@
+//! In-memory Transport for Ockam's routing framework
+//!
+//! This crate connects several nodes running in the same process
+//! without any sockets, so that multi-node topologies (forwarders,
+//! secure channels, portals, ...) can be tested quickly and
+//! reliably. Links between nodes can be given some latency or packet
+//! loss, or be partitioned altogether, through the
+//! [`MemoryNetwork`](crate::MemoryNetwork) the nodes are part of.
+//!
+//! ```rust
+//! use ockam_core::{route, Result};
+//! use ockam_node::Context;
+//! use ockam_transport_memory::{MemoryNetwork, MemoryTransport, MEMORY};
+//! # async fn test(mut ctx: Context) -> Result<()> {
+//! let network = MemoryNetwork::new();
+//! MemoryTransport::create(&ctx, &network, "alice").await?;
+//!
+//! // Run a second node, named "bob", on its own thread
+//! let mut bob = network.spawn_node("bob").await?;
+//!
+//! ctx.send(route![(MEMORY, "bob"), "app"], "Hello Bob!".to_string())
+//!     .await?;
+//! let msg = bob.receive::<String>().await?;
+//! # Ok(()) }
+//! ```
+#![deny(unsafe_code)]
+#![warn(
+    missing_docs,
+    dead_code,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+mod network;
+mod receiver;
+mod router;
+mod transport;
+
+pub use network::MemoryNetwork;
+pub use transport::MemoryTransport;
+
+use ockam_core::TransportType;
+
+/// Memory address type constant
+pub const MEMORY: TransportType = TransportType::new(6);
+
+pub(crate) const CLUSTER_NAME: &str = "_internals.transport.memory";
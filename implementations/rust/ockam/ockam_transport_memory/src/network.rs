@@ -0,0 +1,263 @@
+use core::cmp::Ordering;
+use core::time::Duration;
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use ockam_core::compat::rand::prelude::{Rng, SeedableRng};
+use ockam_core::compat::rand::rngs::StdRng;
+use ockam_core::{Result, TransportMessage};
+use ockam_node::{Context, NodeBuilder};
+use ockam_transport_core::TransportError;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::time::Instant;
+use tracing::{debug, trace};
+
+use crate::MemoryTransport;
+
+/// A set of nodes connected to each other by memory transports
+///
+/// Nodes join the network under a unique name with
+/// [`MemoryTransport::create`](crate::MemoryTransport::create), and
+/// reach each other with routes such as `route![(MEMORY, "bob"), "echoer"]`.
+///
+/// Messages sent over a link are delivered in the order they were
+/// sent, even when its latency changes in between. A link between two
+/// nodes can be slowed down, made lossy or partitioned at any time, in
+/// both directions at once. Which messages get lost is decided by a
+/// random generator of each link, derived from the seed given to
+/// [`MemoryNetwork::with_seed`](crate::MemoryNetwork::with_seed), so
+/// the traffic over other links doesn't change it.
+///
+/// Cloning a `MemoryNetwork` yields another handle to the same network.
+#[derive(Clone)]
+pub struct MemoryNetwork {
+    inner: Arc<Mutex<NetworkState>>,
+}
+
+struct NetworkState {
+    /// Inboxes of the nodes, by name
+    nodes: BTreeMap<String, UnboundedSender<Envelope>>,
+    /// Faults injected between two nodes, keyed by their sorted names
+    links: BTreeMap<(String, String), Link>,
+    /// Seeds the random generator of every link
+    seed: u64,
+    /// Orders messages delivered at the same instant by sending order
+    next_seq: u64,
+}
+
+#[derive(Debug)]
+struct Link {
+    latency: Duration,
+    packet_loss: f64,
+    partitioned: bool,
+    /// Decides which messages sent over this link are lost
+    rng: StdRng,
+    /// When the last message sent by each end will be delivered
+    last_delivery: BTreeMap<String, Instant>,
+}
+
+impl Link {
+    fn new(seed: u64, (a, b): &(String, String)) -> Self {
+        // FNV-1a, which unlike `DefaultHasher` is stable across releases
+        let mut hash = seed ^ 0xcbf2_9ce4_8422_2325;
+        for byte in a.bytes().chain([0]).chain(b.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        Self {
+            latency: Duration::ZERO,
+            packet_loss: 0.0,
+            partitioned: false,
+            rng: StdRng::seed_from_u64(hash),
+            last_delivery: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for MemoryNetwork {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl MemoryNetwork {
+    /// Create an empty network
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty network losing messages according to `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        let state = NetworkState {
+            nodes: BTreeMap::new(),
+            links: BTreeMap::new(),
+            seed,
+            next_seq: 0,
+        };
+        Self {
+            inner: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Run a new node on its own thread and join it to this network
+    /// under the given name
+    ///
+    /// The node runs until [`Context::stop`] is called on the returned
+    /// context.
+    pub async fn spawn_node(&self, name: impl Into<String>) -> Result<Context> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (ctx, mut executor) = NodeBuilder::without_access_control().no_logging().build();
+            if tx.send(ctx).is_ok() {
+                let _ = executor.execute(async {});
+            }
+        });
+        let mut ctx = rx.recv().map_err(|_| TransportError::GenericIo)?;
+
+        if let Err(e) = MemoryTransport::create(&ctx, self, name).await {
+            ctx.stop().await?;
+            return Err(e);
+        }
+
+        Ok(ctx)
+    }
+
+    /// The names of the nodes currently part of this network
+    pub fn nodes(&self) -> Vec<String> {
+        self.inner.lock().unwrap().nodes.keys().cloned().collect()
+    }
+
+    /// Delay the messages sent between `a` and `b` by `latency`
+    pub fn set_latency(&self, a: &str, b: &str, latency: Duration) {
+        self.update_link(a, b, |link| link.latency = latency)
+    }
+
+    /// Lose messages sent between `a` and `b` with the given
+    /// probability, between 0 and 1
+    pub fn set_packet_loss(&self, a: &str, b: &str, packet_loss: f64) {
+        self.update_link(a, b, |link| link.packet_loss = packet_loss.clamp(0.0, 1.0))
+    }
+
+    /// Drop all messages sent between `a` and `b` until
+    /// [`heal`](crate::MemoryNetwork::heal) is called
+    pub fn partition(&self, a: &str, b: &str) {
+        self.update_link(a, b, |link| link.partitioned = true)
+    }
+
+    /// Deliver the messages sent between `a` and `b` again
+    pub fn heal(&self, a: &str, b: &str) {
+        self.update_link(a, b, |link| link.partitioned = false)
+    }
+
+    fn update_link(&self, a: &str, b: &str, f: impl FnOnce(&mut Link)) {
+        let mut state = self.inner.lock().unwrap();
+        f(state.link(a, b))
+    }
+
+    /// Add a node to the network, returning its inbox
+    pub(crate) fn join(&self, name: &str) -> Result<UnboundedReceiver<Envelope>> {
+        let mut state = self.inner.lock().unwrap();
+        if state.nodes.contains_key(name) {
+            return Err(TransportError::BindFailed.into());
+        }
+
+        let (tx, rx) = unbounded_channel();
+        state.nodes.insert(name.to_string(), tx);
+        debug!("Node {} joined the memory network", name);
+        Ok(rx)
+    }
+
+    /// Remove a node from the network
+    pub(crate) fn leave(&self, name: &str) {
+        if self.inner.lock().unwrap().nodes.remove(name).is_some() {
+            debug!("Node {} left the memory network", name);
+        }
+    }
+
+    /// Send a message from one node to another
+    ///
+    /// Messages dropped by a lossy or partitioned link are silently
+    /// discarded, as they would be by a real network.
+    pub(crate) fn send(&self, from: &str, to: &str, msg: TransportMessage) -> Result<()> {
+        let mut state = self.inner.lock().unwrap();
+        let link = state.link(from, to);
+
+        if link.partitioned {
+            trace!("Dropping message from {} to {}: partitioned", from, to);
+            return Ok(());
+        }
+        let packet_loss = link.packet_loss;
+        if link.rng.gen_bool(packet_loss) {
+            trace!("Dropping message from {} to {}: lost", from, to);
+            return Ok(());
+        }
+
+        // Never overtake a message sent while the link was slower
+        let mut deliver_at = Instant::now() + link.latency;
+        if let Some(last) = link.last_delivery.get(from) {
+            deliver_at = deliver_at.max(*last);
+        }
+        link.last_delivery.insert(from.to_string(), deliver_at);
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        let envelope = Envelope {
+            deliver_at,
+            seq,
+            from: from.to_string(),
+            msg,
+        };
+
+        let inbox = state.nodes.get(to).ok_or(TransportError::PeerNotFound)?;
+        inbox
+            .send(envelope)
+            .map_err(|_| TransportError::PeerNotFound.into())
+    }
+}
+
+impl NetworkState {
+    /// The link between `a` and `b`, created on first use
+    fn link(&mut self, a: &str, b: &str) -> &mut Link {
+        let seed = self.seed;
+        self.links
+            .entry(link_key(a, b))
+            .or_insert_with_key(|key| Link::new(seed, key))
+    }
+}
+
+fn link_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// A message in flight between two nodes
+pub(crate) struct Envelope {
+    pub(crate) deliver_at: Instant,
+    seq: u64,
+    pub(crate) from: String,
+    pub(crate) msg: TransportMessage,
+}
+
+impl PartialEq for Envelope {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Envelope {}
+
+impl PartialOrd for Envelope {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Envelope {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.deliver_at, self.seq).cmp(&(other.deliver_at, other.seq))
+    }
+}
@@ -0,0 +1,84 @@
+use core::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use ockam_core::{async_trait, Address, LocalMessage, Processor, Result};
+use ockam_node::Context;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::{sleep_until, Instant};
+use tracing::debug;
+
+use crate::network::Envelope;
+use crate::MEMORY;
+
+/// A memory message receiving processor
+///
+/// Messages sent to this node are held until the latency of their
+/// link has elapsed, then forwarded to their destination with the
+/// sending node prepended to their return route.
+pub(crate) struct MemoryRecvProcessor {
+    inbox: UnboundedReceiver<Envelope>,
+    pending: BinaryHeap<Reverse<Envelope>>,
+}
+
+impl MemoryRecvProcessor {
+    pub(crate) fn new(inbox: UnboundedReceiver<Envelope>) -> Self {
+        Self {
+            inbox,
+            pending: BinaryHeap::new(),
+        }
+    }
+
+    /// Forward the first pending message to its destination
+    async fn deliver(&mut self, ctx: &Context) -> Result<()> {
+        let Reverse(envelope) = match self.pending.pop() {
+            Some(envelope) => envelope,
+            None => return Ok(()),
+        };
+
+        let mut msg = envelope.msg;
+        msg.return_route
+            .modify()
+            .prepend(Address::new(MEMORY, envelope.from));
+
+        debug!("Message onward route: {}", msg.onward_route);
+        debug!("Message return route: {}", msg.return_route);
+
+        ctx.forward(LocalMessage::new(msg, Vec::new())).await
+    }
+}
+
+#[async_trait]
+impl Processor for MemoryRecvProcessor {
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await
+    }
+
+    async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
+        let next = self.pending.peek().map(|Reverse(e)| e.deliver_at);
+        let received = match next {
+            Some(deliver_at) if deliver_at <= Instant::now() => {
+                self.deliver(ctx).await?;
+                return Ok(true);
+            }
+            Some(deliver_at) => tokio::select! {
+                received = self.inbox.recv() => received,
+                _ = sleep_until(deliver_at) => {
+                    self.deliver(ctx).await?;
+                    return Ok(true);
+                }
+            },
+            None => self.inbox.recv().await,
+        };
+
+        match received {
+            Some(envelope) => {
+                self.pending.push(Reverse(envelope));
+                Ok(true)
+            }
+            // The node left the network
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use ockam_core::{async_trait, Any, Result, Routed, Worker};
+use ockam_node::Context;
+use ockam_transport_core::TransportError;
+use tracing::trace;
+
+use crate::{MemoryNetwork, MEMORY};
+
+/// A memory address router
+///
+/// Messages routed to an address of `type = 6` are handed to the
+/// network, which delivers them to the node of that name.
+pub(crate) struct MemoryRouter {
+    name: String,
+    network: MemoryNetwork,
+}
+
+impl MemoryRouter {
+    pub(crate) fn new(name: String, network: MemoryNetwork) -> Self {
+        Self { name, network }
+    }
+}
+
+#[async_trait]
+impl Worker for MemoryRouter {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> Result<()> {
+        self.network.leave(&self.name);
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let mut msg = msg.into_local_message().into_transport_message();
+
+        // Remove the address of the next node from the route
+        let peer = msg.onward_route.step()?;
+        if peer.transport_type() != MEMORY {
+            return Err(TransportError::UnknownRoute.into());
+        }
+
+        trace!("Memory route request: {} -> {}", self.name, peer.address());
+        self.network.send(&self.name, peer.address(), msg)
+    }
+}
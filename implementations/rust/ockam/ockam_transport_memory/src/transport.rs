@@ -0,0 +1,75 @@
+use ockam_core::{Address, Result};
+use ockam_node::Context;
+use tracing::trace;
+
+use crate::receiver::MemoryRecvProcessor;
+use crate::router::MemoryRouter;
+use crate::{MemoryNetwork, MEMORY};
+
+/// High level management interface for memory transports
+///
+/// Be aware that only one `MemoryTransport` can exist per node, as it
+/// registers itself as a router for the `MEMORY` address type.
+/// Multiple calls to
+/// [`MemoryTransport::create`](crate::MemoryTransport::create) on the
+/// same node will fail.
+///
+/// ```rust
+/// use ockam_transport_memory::{MemoryNetwork, MemoryTransport};
+/// # use ockam_node::Context;
+/// # use ockam_core::Result;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let network = MemoryNetwork::new();
+/// let memory = MemoryTransport::create(&ctx, &network, "alice").await?;
+/// assert_eq!(network.nodes(), vec!["alice".to_string()]);
+/// # Ok(()) }
+/// ```
+pub struct MemoryTransport {
+    name: String,
+}
+
+impl MemoryTransport {
+    /// Join the current node to `network` under the given name
+    ///
+    /// Fails if another node already uses this name.
+    pub async fn create(
+        ctx: &Context,
+        network: &MemoryNetwork,
+        name: impl Into<String>,
+    ) -> Result<Self> {
+        let name = name.into();
+        let inbox = network.join(&name)?;
+
+        if let Err(e) = Self::start(ctx, network, &name, MemoryRecvProcessor::new(inbox)).await {
+            network.leave(&name);
+            return Err(e);
+        }
+
+        Ok(Self { name })
+    }
+
+    async fn start(
+        ctx: &Context,
+        network: &MemoryNetwork,
+        name: &str,
+        receiver: MemoryRecvProcessor,
+    ) -> Result<()> {
+        let main_addr = Address::random_local();
+        let router = MemoryRouter::new(name.to_string(), network.clone());
+        ctx.start_worker(main_addr.clone(), router).await?;
+        trace!("Registering memory router for type = {}", MEMORY);
+        ctx.register(MEMORY, main_addr).await?;
+
+        ctx.start_processor(Address::random_local(), receiver).await
+    }
+
+    /// The name of the current node on its network
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The address other nodes of the network reach this node at
+    pub fn address(&self) -> Address {
+        Address::new(MEMORY, self.name.clone())
+    }
+}
@@ -0,0 +1,254 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use ockam::authenticated_storage::InMemoryStorage;
+use ockam::identity::{Identity, TrustEveryonePolicy};
+use ockam::remote::RemoteForwarder;
+use ockam::vault::Vault;
+use ockam::{ForwardingService, TcpTransport};
+use ockam_core::{route, Result, Routed, Worker};
+use ockam_node::Context;
+use ockam_transport_memory::{MemoryNetwork, MemoryTransport, MEMORY};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+#[ockam_macros::test]
+async fn send_receive(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+    bob.start_worker("echoer", Echoer).await?;
+
+    let r = route![(MEMORY, "bob"), "echoer"];
+    let reply: String = ctx.send_and_receive(r, "Hello Bob!".to_string()).await?;
+    assert_eq!(reply, "Hello Bob!");
+
+    bob.stop().await?;
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn names_are_unique(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    assert!(network.spawn_node("alice").await.is_err());
+    assert_eq!(network.nodes(), vec!["alice".to_string()]);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn latency_preserves_ordering(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+    bob.start_worker("echoer", Echoer).await?;
+    network.set_latency("alice", "bob", Duration::from_millis(100));
+
+    let start = Instant::now();
+    for i in 0..10 {
+        ctx.send(route![(MEMORY, "bob"), "echoer"], i.to_string())
+            .await?;
+    }
+    for i in 0..10 {
+        let reply = ctx.receive::<String>().await?.take().body();
+        assert_eq!(reply, i.to_string());
+    }
+    // There and back again
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    bob.stop().await?;
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn lowering_latency_preserves_ordering(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+
+    network.set_latency("alice", "bob", Duration::from_millis(200));
+    for i in 0..5 {
+        ctx.send(route![(MEMORY, "bob"), "app"], i.to_string())
+            .await?;
+    }
+    network.set_latency("alice", "bob", Duration::ZERO);
+    for i in 5..10 {
+        ctx.send(route![(MEMORY, "bob"), "app"], i.to_string())
+            .await?;
+    }
+
+    for i in 0..10 {
+        let msg = bob.receive::<String>().await?.take().body();
+        assert_eq!(msg, i.to_string());
+    }
+
+    bob.stop().await?;
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn partition_and_heal(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+    bob.start_worker("echoer", Echoer).await?;
+    let r = route![(MEMORY, "bob"), "echoer"];
+
+    network.partition("bob", "alice");
+    ctx.send(r.clone(), "Lost".to_string()).await?;
+    assert!(ctx.receive_timeout::<String>(1).await.is_err());
+
+    network.heal("alice", "bob");
+    let reply: String = ctx.send_and_receive(r, "Found".to_string()).await?;
+    assert_eq!(reply, "Found");
+
+    bob.stop().await?;
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn packet_loss_drops_some_messages(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::with_seed(42);
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+    network.set_packet_loss("alice", "bob", 0.5);
+
+    for i in 0..50 {
+        ctx.send(route![(MEMORY, "bob"), "app"], i.to_string())
+            .await?;
+    }
+
+    let mut received = vec![];
+    while let Ok(msg) = bob.receive_timeout::<String>(1).await {
+        received.push(msg.take().body().parse::<u32>().unwrap());
+    }
+    assert!(!received.is_empty() && received.len() < 50);
+    assert!(received.windows(2).all(|w| w[0] < w[1]));
+
+    bob.stop().await?;
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn packet_loss_ignores_other_links(ctx: &mut Context) -> Result<()> {
+    let quiet = received_by_bob(false).await?;
+    let busy = received_by_bob(true).await?;
+    assert_eq!(quiet, busy);
+
+    ctx.stop().await
+}
+
+/// The messages which make it over a lossy link from Alice to Bob,
+/// while Alice sends as many messages to Carol if `busy`
+async fn received_by_bob(busy: bool) -> Result<Vec<u32>> {
+    let network = MemoryNetwork::with_seed(42);
+    let mut alice = network.spawn_node("alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+    let mut carol = network.spawn_node("carol").await?;
+    network.set_packet_loss("alice", "bob", 0.5);
+    network.set_packet_loss("alice", "carol", 0.5);
+
+    for i in 0..50 {
+        alice
+            .send(route![(MEMORY, "bob"), "app"], i.to_string())
+            .await?;
+        if busy {
+            alice
+                .send(route![(MEMORY, "carol"), "app"], i.to_string())
+                .await?;
+        }
+    }
+
+    let mut received = vec![];
+    while let Ok(msg) = bob.receive_timeout::<String>(1).await {
+        received.push(msg.take().body().parse::<u32>().unwrap());
+    }
+
+    alice.stop().await?;
+    bob.stop().await?;
+    carol.stop().await?;
+    Ok(received)
+}
+
+#[ockam_macros::test]
+async fn secure_channel_through_forwarder(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut relay = network.spawn_node("relay").await?;
+    let mut bob = network.spawn_node("bob").await?;
+    ForwardingService::create(&relay).await?;
+
+    // Bob is only reachable through his forwarder at the relay
+    let bob_identity = Identity::create(&bob, &Vault::create()).await?;
+    bob_identity
+        .create_secure_channel_listener("listener", TrustEveryonePolicy, &InMemoryStorage::new())
+        .await?;
+    bob.start_worker("echoer", Echoer).await?;
+    let forwarder = RemoteForwarder::create(&bob, route![(MEMORY, "relay")]).await?;
+
+    let alice = Identity::create(ctx, &Vault::create()).await?;
+    let channel = alice
+        .create_secure_channel(
+            route![(MEMORY, "relay"), forwarder.remote_address(), "listener"],
+            TrustEveryonePolicy,
+            &InMemoryStorage::new(),
+        )
+        .await?;
+
+    let r = route![channel, "echoer"];
+    let reply: String = ctx.send_and_receive(r, "Hello Bob!".to_string()).await?;
+    assert_eq!(reply, "Hello Bob!");
+
+    bob.stop().await?;
+    relay.stop().await?;
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn portal_between_nodes(ctx: &mut Context) -> Result<()> {
+    let network = MemoryNetwork::new();
+    MemoryTransport::create(ctx, &network, "alice").await?;
+    let mut bob = network.spawn_node("bob").await?;
+
+    // Bob exposes a TCP echo server through an outlet
+    let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = server.accept().await.unwrap();
+        let mut buf = [0; 64];
+        let n = stream.read(&mut buf).await.unwrap();
+        stream.write_all(&buf[..n]).await.unwrap();
+    });
+    let bob_tcp = TcpTransport::create(&bob).await?;
+    bob_tcp
+        .create_outlet("outlet", server_addr.to_string())
+        .await?;
+
+    // Alice reaches it through an inlet
+    let tcp = TcpTransport::create(ctx).await?;
+    let (_, inlet_addr) = tcp
+        .create_inlet("127.0.0.1:0", route![(MEMORY, "bob"), "outlet"])
+        .await?;
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    stream.write_all(b"Hello through the portal").await.unwrap();
+    let mut buf = [0; 64];
+    let n = stream.read(&mut buf).await.unwrap();
+    assert_eq!(&buf[..n], b"Hello through the portal");
+
+    bob.stop().await?;
+    ctx.stop().await
+}
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
@@ -33,9 +33,15 @@ pub(crate) use portal::*;
 pub(crate) use router::*;
 pub(crate) use workers::*;
 
+mod reconnect;
 mod transport;
+#[cfg(unix)]
+mod uds;
 
+pub use reconnect::*;
 pub use transport::*;
+#[cfg(unix)]
+pub use uds::{UdsTransport, UDS};
 
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::{Result, TransportType};
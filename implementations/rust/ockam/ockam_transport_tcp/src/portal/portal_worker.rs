@@ -3,6 +3,7 @@ use core::time::Duration;
 use ockam_core::compat::{boxed::Box, net::SocketAddr};
 use ockam_core::{async_trait, AccessControl, AllowAll, Decodable, Mailbox, Mailboxes};
 use ockam_core::{Address, Any, Result, Route, Routed, Worker};
+use ockam_node::metrics::GaugeGuard;
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use std::sync::Arc;
@@ -49,6 +50,8 @@ pub(crate) struct TcpPortalWorker {
     remote_route: Option<Route>,
     is_disconnecting: bool,
     type_name: TypeName,
+    /// Counts this worker as an open portal connection while it lives
+    _connection: GaugeGuard,
 }
 
 impl TcpPortalWorker {
@@ -115,6 +118,19 @@ impl TcpPortalWorker {
             None => (None, None),
         };
 
+        let type_label = match type_name {
+            TypeName::Inlet => "inlet",
+            TypeName::Outlet => "outlet",
+        };
+        let connection = ctx
+            .metrics()
+            .gauge(
+                "ockam_portal_connections",
+                "Open TCP portal connections",
+                &[("type", type_label)],
+            )
+            .track();
+
         let sender = Self {
             state,
             tx,
@@ -126,6 +142,7 @@ impl TcpPortalWorker {
             receiver_address,
             is_disconnecting: false,
             type_name,
+            _connection: connection,
         };
 
         let main_internal_mailbox = Mailbox::new(
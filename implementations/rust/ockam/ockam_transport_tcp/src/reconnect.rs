@@ -0,0 +1,159 @@
+use core::time::Duration;
+use ockam_core::{route, Address, TransportMessage};
+use serde::{Deserialize, Serialize};
+
+/// Onward address of the frame which opens every connection made
+/// with a [`ReconnectPolicy`]
+const RESUME_ADDRESS: &str = "ockam.tcp.resume";
+
+/// Reconnection policy for outgoing TCP connections
+///
+/// When a connection created with
+/// [`TcpTransport::connect_with_reconnect`](crate::TcpTransport::connect_with_reconnect)
+/// drops, the connection worker keeps its address registered in the
+/// TCP router and tries to re-establish the connection, resolving
+/// the peer again and waiting twice as long after every failed
+/// attempt.  Messages sent while disconnected are buffered up to
+/// [`buffer_size`](Self::with_buffer_size) and flushed once the
+/// connection is back.  Further messages are dropped, and the worker
+/// reports a [`Capacity`](ockam_transport_core::TransportError::Capacity)
+/// error for each of them.
+///
+/// Every connection made with a policy starts with a frame carrying
+/// a connection id that stays the same across reconnections.  The
+/// listening side uses it to bind the new connection to the peer
+/// address of the dropped one, so return routes through it keep
+/// working there too.
+///
+/// The policy only applies once the first connection attempt has
+/// succeeded.  If the peer is unreachable to begin with, the worker
+/// stops just like a connection without a policy.
+///
+/// ```rust
+/// use core::time::Duration;
+/// use ockam_transport_tcp::ReconnectPolicy;
+///
+/// let policy = ReconnectPolicy::new()
+///     .with_initial_delay(Duration::from_millis(50))
+///     .with_max_delay(Duration::from_secs(10))
+///     .with_max_attempts(5)
+///     .with_buffer_size(16);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_attempts: Option<u32>,
+    buffer_size: usize,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            max_attempts: Some(10),
+            buffer_size: 32,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Create a policy with the default settings: 100ms initial delay,
+    /// 30s maximum delay, 10 attempts and 32 buffered messages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay before the first reconnection attempt
+    pub fn with_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Upper bound for the delay between two attempts
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Give up after `attempts` consecutive failed attempts
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Never give up reconnecting
+    pub fn with_unlimited_attempts(mut self) -> Self {
+        self.max_attempts = None;
+        self
+    }
+
+    /// Number of messages to buffer while disconnected, `0` rejects
+    /// all messages until the connection is back
+    pub fn with_buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size;
+        self
+    }
+
+    /// Number of messages to buffer while disconnected
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Delay before the given (zero-based) attempt
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |d| d.min(self.max_delay))
+    }
+
+    /// Whether another attempt may follow `attempts` failed ones
+    pub(crate) fn should_retry(&self, attempts: u32) -> bool {
+        self.max_attempts.map_or(true, |max| attempts < max)
+    }
+}
+
+/// Create the frame announcing the id of a resumable connection
+pub(crate) fn resume_message(connection_id: &[u8]) -> TransportMessage {
+    TransportMessage::v1(route![RESUME_ADDRESS], route![], connection_id.to_vec())
+}
+
+/// Return the connection id if `msg` announces a resumable connection
+pub(crate) fn resumed_connection(msg: &TransportMessage) -> Option<&[u8]> {
+    let mut hops = msg.onward_route.iter();
+    match (hops.next(), hops.next()) {
+        (Some(addr), None) if addr == &Address::from_string(RESUME_ADDRESS) => Some(&msg.payload),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ReconnectPolicy;
+    use core::time::Duration;
+
+    #[test]
+    fn delay_backs_off_exponentially_up_to_max() {
+        let policy = ReconnectPolicy::new()
+            .with_initial_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_secs(1));
+
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(800));
+        assert_eq!(policy.delay(4), Duration::from_secs(1));
+        assert_eq!(policy.delay(40), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn should_retry_respects_max_attempts() {
+        let policy = ReconnectPolicy::new().with_max_attempts(2);
+        assert!(policy.should_retry(1));
+        assert!(!policy.should_retry(2));
+
+        let policy = policy.with_unlimited_attempts();
+        assert!(policy.should_retry(u32::MAX));
+    }
+}
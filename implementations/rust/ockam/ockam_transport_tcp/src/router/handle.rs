@@ -1,6 +1,6 @@
 use crate::{
-    parse_socket_addr, TcpInletListenProcessor, TcpListenProcessor, TcpRouterRequest,
-    TcpRouterResponse, WorkerPair, TCP,
+    parse_socket_addr, ReconnectPolicy, TcpInletListenProcessor, TcpListenProcessor,
+    TcpRouterRequest, TcpRouterResponse, WorkerPair, TCP,
 };
 use ockam_core::compat::net::{SocketAddr, ToSocketAddrs};
 use ockam_core::{async_trait, compat::boxed::Box, AccessControl};
@@ -45,13 +45,18 @@ impl TcpRouterHandle {
     }
 
     /// Establish an outgoing TCP connection on an existing transport
-    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
+    pub async fn connect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        reconnect: Option<ReconnectPolicy>,
+    ) -> Result<Address> {
         let response = self
             .ctx
             .send_and_receive(
                 self.api_addr.clone(),
                 TcpRouterRequest::Connect {
                     peer: peer.as_ref().to_string(),
+                    reconnect,
                 },
             )
             .await?;
@@ -131,6 +136,33 @@ impl TcpRouterHandle {
         }
     }
 
+    /// Bind a resumed connection to the peer address of the first
+    /// connection with the same id, returning that address
+    pub(crate) async fn resume(
+        &self,
+        connection_id: Vec<u8>,
+        peer: Address,
+        self_addr: Address,
+    ) -> Result<Address> {
+        let response = self
+            .ctx
+            .send_and_receive(
+                self.api_addr.clone(),
+                TcpRouterRequest::Resume {
+                    connection_id,
+                    peer,
+                    self_addr,
+                },
+            )
+            .await?;
+
+        if let TcpRouterResponse::Resume(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
+
     /// Resolve the given peer to a [`SocketAddr`](std::net::SocketAddr)
     pub(crate) fn resolve_peer(peer: impl Into<String>) -> Result<(SocketAddr, Vec<String>)> {
         let peer_str = peer.into();
@@ -1,3 +1,4 @@
+use crate::ReconnectPolicy;
 use ockam_core::{Address, Message, Result};
 use serde::{Deserialize, Serialize};
 
@@ -10,8 +11,20 @@ pub enum TcpRouterRequest {
         /// The clients own worker bus address.
         self_addr: Address,
     },
-    /// Connect
-    Connect { peer: String },
+    /// Connect, optionally re-establishing the connection when it drops
+    Connect {
+        peer: String,
+        reconnect: Option<ReconnectPolicy>,
+    },
+    /// Bind a connection announcing the given id to the peer address
+    /// of the first connection with that id
+    Resume {
+        connection_id: Vec<u8>,
+        /// Peer address of this connection
+        peer: Address,
+        /// The connection's own worker bus address.
+        self_addr: Address,
+    },
     /// Connect
     Disconnect { peer: String },
     /// Unregister (usually, after disconnection)
@@ -27,4 +40,5 @@ pub enum TcpRouterResponse {
     Connect(Result<Address>),
     Disconnect(Result<()>),
     Unregister(Result<()>),
+    Resume(Result<Address>),
 }
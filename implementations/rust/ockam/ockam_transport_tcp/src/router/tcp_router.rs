@@ -1,4 +1,6 @@
-use crate::{TcpRouterHandle, TcpRouterRequest, TcpRouterResponse, TcpSendWorker, TCP};
+use crate::{
+    ReconnectPolicy, TcpRouterHandle, TcpRouterRequest, TcpRouterResponse, TcpSendWorker, TCP,
+};
 use core::ops::Deref;
 use ockam_core::{async_trait, Any};
 use ockam_core::{Address, Decodable, LocalMessage, Result, Routed, Worker};
@@ -20,6 +22,8 @@ pub(crate) struct TcpRouter {
     main_addr: Address,
     api_addr: Address,
     map: BTreeMap<Address, Address>,
+    /// Peer addresses of resumable connections, by connection id
+    connections: BTreeMap<Vec<u8>, Address>,
     allow_auto_connection: bool,
 }
 
@@ -37,6 +41,7 @@ impl TcpRouter {
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
             map: BTreeMap::new(),
+            connections: BTreeMap::new(),
             allow_auto_connection: true,
         };
 
@@ -95,6 +100,30 @@ impl TcpRouter {
 
         Ok(())
     }
+
+    /// Handle any [`TcpRouterRequest::Resume`] messages received by
+    /// this node's worker
+    ///
+    /// The first connection with a given id keeps its peer address,
+    /// later ones take that address over, so that routes through a
+    /// dropped connection lead to the one replacing it.  Connection ids
+    /// are remembered for as long as the router runs.
+    async fn handle_resume(
+        &mut self,
+        connection_id: Vec<u8>,
+        peer: Address,
+        self_addr: Address,
+    ) -> Result<Address> {
+        let peer = self
+            .connections
+            .entry(connection_id)
+            .or_insert(peer)
+            .clone();
+        trace!("TCP resume request: {} => {}", peer, self_addr);
+        self.map.insert(peer.clone(), self_addr);
+
+        Ok(peer)
+    }
 }
 
 impl TcpRouter {
@@ -104,16 +133,26 @@ impl TcpRouter {
     /// This handler starts a `(TcpSendWorker, TcpRecvProcessor)` pair
     /// that open and manage a connection to the given peer and
     /// finally register the given peer with this `TcpRouter`.
-    async fn handle_connect(&mut self, peer: String) -> Result<Address> {
+    async fn handle_connect(
+        &mut self,
+        peer: String,
+        reconnect: Option<ReconnectPolicy>,
+    ) -> Result<Address> {
         // Resolve peer address
         let (peer_addr, hostnames) = TcpRouterHandle::resolve_peer(peer)?;
 
         // Start a new `WorkerPair` for the given peer containing a
         // `TcpSendWorker` and `TcpRecvprocessor`
         let router_handle = self.create_self_handle().await?;
-        let pair =
-            TcpSendWorker::start_pair(&self.ctx, router_handle, None, peer_addr, hostnames.clone())
-                .await?;
+        let pair = TcpSendWorker::start_pair(
+            &self.ctx,
+            router_handle,
+            None,
+            peer_addr,
+            hostnames.clone(),
+            reconnect,
+        )
+        .await?;
 
         // Send this `TcpRouter` a `TcpRouterRequest::Register` message
         // containing the registration request
@@ -199,7 +238,7 @@ impl TcpRouter {
 
         // No existing connection
         if self.allow_auto_connection {
-            self.handle_connect(peer).await
+            self.handle_connect(peer, None).await
         } else {
             error!(
                 "Failed to resolve route, no existing connection to peer: {}",
@@ -241,8 +280,18 @@ impl Worker for TcpRouter {
                     ctx.send(return_route, TcpRouterResponse::Unregister(res))
                         .await?;
                 }
-                TcpRouterRequest::Connect { peer } => {
-                    let res = self.handle_connect(peer).await;
+                TcpRouterRequest::Resume {
+                    connection_id,
+                    peer,
+                    self_addr,
+                } => {
+                    let res = self.handle_resume(connection_id, peer, self_addr).await;
+
+                    ctx.send(return_route, TcpRouterResponse::Resume(res))
+                        .await?;
+                }
+                TcpRouterRequest::Connect { peer, reconnect } => {
+                    let res = self.handle_connect(peer, reconnect).await;
 
                     ctx.send(return_route, TcpRouterResponse::Connect(res))
                         .await?;
@@ -4,7 +4,9 @@ use ockam_core::{Address, AllowAll, AsyncTryClone, Result, Route};
 use ockam_node::Context;
 use std::sync::Arc;
 
-use crate::{parse_socket_addr, TcpOutletListenWorker, TcpRouter, TcpRouterHandle};
+use crate::{
+    parse_socket_addr, ReconnectPolicy, TcpOutletListenWorker, TcpRouter, TcpRouterHandle,
+};
 
 /// High level management interface for TCP transports
 ///
@@ -84,7 +86,35 @@ impl TcpTransport {
     /// # Ok(()) }
     /// ```
     pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
-        self.router_handle.connect(peer.as_ref()).await
+        self.router_handle.connect(peer.as_ref(), None).await
+    }
+
+    /// Establish an outgoing TCP connection which re-establishes
+    /// itself when it drops
+    ///
+    /// The returned worker address stays registered while
+    /// reconnecting, so routes through it (including secure channels
+    /// layered on top) keep working once the peer is reachable again.
+    /// See [`ReconnectPolicy`] for the backoff and buffering behaviour.
+    ///
+    /// ```rust
+    /// use ockam_transport_tcp::{ReconnectPolicy, TcpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let tcp = TcpTransport::create(&ctx).await?;
+    /// tcp.connect_with_reconnect("127.0.0.1:5000", ReconnectPolicy::new())
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect_with_reconnect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        policy: ReconnectPolicy,
+    ) -> Result<Address> {
+        self.router_handle
+            .connect(peer.as_ref(), Some(policy))
+            .await
     }
 
     /// Disconnect from peer
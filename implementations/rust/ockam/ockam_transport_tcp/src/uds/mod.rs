@@ -0,0 +1,23 @@
+//! Unix domain socket transport
+//!
+//! Connections over a Unix domain socket are only reachable by local
+//! processes with access to the socket file, which makes them a good
+//! fit for node administration.  Unlike the TCP transport there is no
+//! router: [`UdsTransport::connect`] returns the address of the
+//! connection worker, which is used directly in routes.
+
+mod transport;
+mod workers;
+
+pub use transport::*;
+pub(crate) use workers::*;
+
+use ockam_core::TransportType;
+
+/// Unix domain socket address type constant
+///
+/// It tags messages received from a Unix domain socket, there is no
+/// router for this address type.
+pub const UDS: TransportType = TransportType::new(5);
+
+pub(crate) const CLUSTER_NAME: &str = "_internals.transport.uds";
@@ -0,0 +1,71 @@
+use crate::uds::{UdsListenProcessor, UdsSendWorker};
+use ockam_core::{Address, AsyncTryClone, Result};
+use ockam_node::Context;
+use ockam_transport_core::TransportError;
+use std::path::Path;
+use tokio::net::UnixStream;
+use tracing::debug;
+
+/// High level management interface for Unix domain socket transports
+///
+/// Any number of `UdsTransport`s can exist per node.  Connections are
+/// not registered with a router, the address returned by
+/// [`connect`](UdsTransport::connect) is the first hop of the route to
+/// the other side.
+///
+/// ```rust
+/// use ockam_core::route;
+/// use ockam_transport_tcp::UdsTransport;
+/// # use ockam_node::Context;
+/// # use ockam_core::Result;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let uds = UdsTransport::create(&ctx).await?;
+/// let conn = uds.connect("/tmp/ockam/api.sock").await?;
+/// let route = route![conn, "echoer"];
+/// # Ok(()) }
+/// ```
+#[derive(AsyncTryClone)]
+#[async_try_clone(crate = "ockam_core")]
+pub struct UdsTransport {
+    ctx: Context,
+}
+
+impl UdsTransport {
+    /// Create a new Unix domain socket transport for the current node
+    pub async fn create(ctx: &Context) -> Result<Self> {
+        let ctx = ctx.new_detached(Address::random_local()).await?;
+        Ok(Self { ctx })
+    }
+
+    /// Connect to the Unix domain socket at the given path
+    ///
+    /// Returns the address of the connection worker.
+    pub async fn connect(&self, path: impl AsRef<Path>) -> Result<Address> {
+        let path = path.as_ref();
+        debug!(path = %path.display(), "Connecting");
+        let stream = UnixStream::connect(path)
+            .await
+            .map_err(TransportError::from)?;
+        UdsSendWorker::start(&self.ctx, stream, path.display().to_string()).await
+    }
+
+    /// Close a connection created with [`connect`](UdsTransport::connect)
+    pub async fn disconnect(&self, addr: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_worker(addr).await
+    }
+
+    /// Start listening for incoming connections on the given path
+    ///
+    /// A stale socket file left at the path is replaced.  The socket
+    /// is only accessible by the user running the node, and is removed
+    /// again when the listener stops.  Returns the address of the
+    /// listener processor.
+    pub async fn listen(&self, path: impl AsRef<Path>) -> Result<Address> {
+        UdsListenProcessor::start(&self.ctx, path.as_ref()).await
+    }
+
+    /// Stop a listener started with [`listen`](UdsTransport::listen)
+    pub async fn stop_listener(&self, addr: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_processor(addr).await
+    }
+}
@@ -0,0 +1,272 @@
+use crate::prepare_message;
+use crate::uds::UDS;
+use ockam_core::{async_trait, Any, Decodable, LocalMessage, Message, Processor, Worker};
+use ockam_core::{Address, Result, Routed, TransportMessage};
+use ockam_node::metrics::Counter;
+use ockam_node::{Context, ExternalLocalInfo};
+use ockam_transport_core::TransportError;
+use serde::{Deserialize, Serialize};
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{debug, error, info, trace, warn};
+
+#[derive(Serialize, Deserialize, Message, Clone)]
+pub(crate) enum UdsSendWorkerMsg {
+    ConnectionClosed,
+}
+
+/// A Unix domain socket sending message worker
+///
+/// Messages sent to its transmit address are written to the socket,
+/// while a [`UdsRecvProcessor`] relays the incoming ones.  Both stop
+/// when the connection is closed.
+pub(crate) struct UdsSendWorker {
+    tx: OwnedWriteHalf,
+    rx: Option<OwnedReadHalf>,
+    peer: String,
+    tx_addr: Address,
+    internal_addr: Address,
+    rx_addr: Option<Address>,
+    bytes_sent: Counter,
+}
+
+impl UdsSendWorker {
+    /// Start a `(UdsSendWorker, UdsRecvProcessor)` pair for the given
+    /// stream and return the transmit address
+    pub(crate) async fn start(ctx: &Context, stream: UnixStream, peer: String) -> Result<Address> {
+        let (rx, tx) = stream.into_split();
+        let tx_addr = Address::random_local();
+        let internal_addr = Address::random_local();
+        let worker = Self {
+            tx,
+            rx: Some(rx),
+            peer,
+            tx_addr: tx_addr.clone(),
+            internal_addr: internal_addr.clone(),
+            rx_addr: None,
+            bytes_sent: ctx.metrics().counter(
+                "ockam_transport_bytes_sent_total",
+                "Bytes sent by transports",
+                &[("transport", "uds")],
+            ),
+        };
+        ctx.start_worker(vec![tx_addr.clone(), internal_addr], worker)
+            .await?;
+        Ok(tx_addr)
+    }
+}
+
+#[async_trait]
+impl Worker for UdsSendWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        ctx.set_cluster(crate::uds::CLUSTER_NAME).await?;
+
+        let rx = self.rx.take().ok_or(TransportError::GenericIo)?;
+        let rx_addr = Address::random_local();
+        let receiver = UdsRecvProcessor {
+            rx,
+            peer: self.peer.clone(),
+            tx_addr: self.tx_addr.clone(),
+            sender_internal_address: self.internal_addr.clone(),
+            bytes_received: Counter::default(),
+        };
+        ctx.start_processor(rx_addr.clone(), receiver).await?;
+        self.rx_addr = Some(rx_addr);
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        if let Some(rx_addr) = self.rx_addr.take() {
+            let _ = ctx.stop_processor(rx_addr).await;
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        if msg.msg_addr() == self.internal_addr {
+            match UdsSendWorkerMsg::decode(msg.payload())? {
+                UdsSendWorkerMsg::ConnectionClosed => {
+                    debug!("Connection to {} was closed", self.peer);
+                    // The receiver stops itself after notifying us
+                    self.rx_addr = None;
+                    ctx.stop_worker(self.tx_addr.clone()).await?;
+                }
+            }
+            return Ok(());
+        }
+
+        let mut msg = msg.into_transport_message();
+        // Remove our own address from the route so the other end
+        // knows what to do with the incoming message
+        msg.onward_route.step()?;
+        let msg = prepare_message(msg)?;
+
+        if self.tx.write_all(&msg).await.is_err() {
+            warn!("Failed to send message to {}", self.peer);
+            ctx.stop_worker(self.tx_addr.clone()).await?;
+            return Err(TransportError::ConnectionDrop.into());
+        }
+        self.bytes_sent.inc_by(msg.len() as u64);
+
+        Ok(())
+    }
+}
+
+/// A Unix domain socket receiving message processor
+///
+/// Started by a [`UdsSendWorker`] for the read half of its stream.
+pub(crate) struct UdsRecvProcessor {
+    rx: OwnedReadHalf,
+    peer: String,
+    tx_addr: Address,
+    sender_internal_address: Address,
+    bytes_received: Counter,
+}
+
+#[async_trait]
+impl Processor for UdsRecvProcessor {
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        self.bytes_received = ctx.metrics().counter(
+            "ockam_transport_bytes_received_total",
+            "Bytes received by transports",
+            &[("transport", "uds")],
+        );
+        ctx.set_cluster(crate::uds::CLUSTER_NAME).await
+    }
+
+    async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
+        let len = match self.rx.read_u16().await {
+            Ok(len) => len,
+            Err(_e) => {
+                info!("Connection to '{}' was closed; dropping stream", self.peer);
+                ctx.send(
+                    self.sender_internal_address.clone(),
+                    UdsSendWorkerMsg::ConnectionClosed,
+                )
+                .await?;
+                return Ok(false);
+            }
+        };
+
+        let mut buf = vec![0; len as usize];
+        if self.rx.read_exact(&mut buf).await.is_err() {
+            error!("Failed to receive message of length: {}", len);
+            return Ok(true);
+        }
+        self.bytes_received.inc_by(2 + len as u64);
+
+        let mut msg = TransportMessage::decode(&buf).map_err(|_| TransportError::RecvBadMessage)?;
+
+        // Replies are routed back through the sender of this connection
+        msg.return_route.modify().prepend(self.tx_addr.clone());
+
+        trace!("Message onward route: {}", msg.onward_route);
+        trace!("Message return route: {}", msg.return_route);
+
+        let local_info = ExternalLocalInfo::new(UDS).to_local_info()?;
+        ctx.forward(LocalMessage::new(msg, vec![local_info]))
+            .await?;
+
+        Ok(true)
+    }
+}
+
+/// A Unix domain socket listen processor
+///
+/// Created by [`UdsTransport::listen`](crate::UdsTransport::listen).
+pub(crate) struct UdsListenProcessor {
+    inner: UnixListener,
+    path: PathBuf,
+}
+
+impl UdsListenProcessor {
+    pub(crate) async fn start(ctx: &Context, path: &Path) -> Result<Address> {
+        debug!("Binding UnixListener to {}", path.display());
+        remove_stale_socket(path)?;
+        let inner = bind_private(path).map_err(TransportError::from)?;
+
+        let addr = Address::random_local();
+        let processor = Self {
+            inner,
+            path: path.to_path_buf(),
+        };
+        ctx.start_processor(addr.clone(), processor).await?;
+
+        Ok(addr)
+    }
+}
+
+/// Bind a listener which is only accessible by the current user
+///
+/// The socket is created inside a new directory only the current user
+/// can enter, and only moved to its path once its permissions are
+/// restricted.  It is never reachable with the default permissions.
+fn bind_private(path: &Path) -> io::Result<UnixListener> {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let dir = parent.join(format!(".uds-{:08x}", rand::random::<u32>()));
+    fs::DirBuilder::new().mode(0o700).create(&dir)?;
+    let tmp = dir.join("socket");
+    let res = UnixListener::bind(&tmp).and_then(|listener| {
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+        fs::rename(&tmp, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_dir_all(&dir);
+    res
+}
+
+/// Remove a socket file left behind by a previous listener
+fn remove_stale_socket(path: &Path) -> Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(m) if m.file_type().is_socket() => {
+            fs::remove_file(path).map_err(TransportError::from)?;
+            Ok(())
+        }
+        Ok(_) => {
+            error!("Refusing to replace {}, it is not a socket", path.display());
+            Err(TransportError::BindFailed.into())
+        }
+        Err(_) => Ok(()),
+    }
+}
+
+#[async_trait]
+impl Processor for UdsListenProcessor {
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::uds::CLUSTER_NAME).await
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> Result<()> {
+        let _ = fs::remove_file(&self.path);
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let (stream, _) = self.inner.accept().await.map_err(TransportError::from)?;
+        let peer = self.path.display().to_string();
+        let tx_addr = UdsSendWorker::start(ctx, stream, peer).await?;
+        debug!(%tx_addr, "Unix domain socket connection accepted");
+
+        Ok(true)
+    }
+}
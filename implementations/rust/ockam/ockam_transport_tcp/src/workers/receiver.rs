@@ -1,10 +1,11 @@
-use crate::{TcpSendWorkerMsg, TCP};
+use crate::{resumed_connection, TcpRouterHandle, TcpSendWorkerMsg, TCP};
 use ockam_core::async_trait;
 use ockam_core::{Address, Decodable, LocalMessage, Processor, Result, TransportMessage};
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, ExternalLocalInfo};
 use ockam_transport_core::TransportError;
 use tokio::{io::AsyncReadExt, net::tcp::OwnedReadHalf};
-use tracing::{error, info, trace};
+use tracing::{debug, error, info, trace};
 
 /// A TCP receiving message processor
 ///
@@ -17,16 +18,28 @@ use tracing::{error, info, trace};
 pub(crate) struct TcpRecvProcessor {
     rx: OwnedReadHalf,
     peer_addr: Address,
+    sender_address: Address,
     sender_internal_address: Address,
+    router_handle: TcpRouterHandle,
+    bytes_received: Counter,
 }
 
 impl TcpRecvProcessor {
     /// Create a new `TcpRecvProcessor`
-    pub fn new(rx: OwnedReadHalf, peer_addr: Address, sender_internal_address: Address) -> Self {
+    pub fn new(
+        rx: OwnedReadHalf,
+        peer_addr: Address,
+        sender_address: Address,
+        sender_internal_address: Address,
+        router_handle: TcpRouterHandle,
+    ) -> Self {
         Self {
             rx,
             peer_addr,
+            sender_address,
             sender_internal_address,
+            router_handle,
+            bytes_received: Counter::default(),
         }
     }
 }
@@ -36,6 +49,11 @@ impl Processor for TcpRecvProcessor {
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        self.bytes_received = ctx.metrics().counter(
+            "ockam_transport_bytes_received_total",
+            "Bytes received by transports",
+            &[("transport", "tcp")],
+        );
         ctx.set_cluster(crate::CLUSTER_NAME).await
     }
 
@@ -85,10 +103,26 @@ impl Processor for TcpRecvProcessor {
                 return Ok(true);
             }
         }
+        self.bytes_received.inc_by(2 + len as u64);
 
         // Deserialize the message now
         let mut msg = TransportMessage::decode(&buf).map_err(|_| TransportError::RecvBadMessage)?;
 
+        // A reconnected peer takes over the address of its previous
+        // connection, which return routes may still point to
+        if let Some(connection_id) = resumed_connection(&msg) {
+            self.peer_addr = self
+                .router_handle
+                .resume(
+                    connection_id.to_vec(),
+                    self.peer_addr.clone(),
+                    self.sender_address.clone(),
+                )
+                .await?;
+            debug!("Connection from {} resumed", self.peer_addr);
+            return Ok(true);
+        }
+
         // Heartbeat message
         if msg.onward_route.next().is_err() {
             trace!("Got heartbeat message from: {}", self.peer_addr);
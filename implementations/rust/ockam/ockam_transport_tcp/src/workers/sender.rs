@@ -1,7 +1,10 @@
-use crate::{TcpRecvProcessor, TcpRouterHandle};
+use crate::{resume_message, ReconnectPolicy, TcpRecvProcessor, TcpRouterHandle};
 use core::time::Duration;
+use ockam_core::compat::collections::VecDeque;
 use ockam_core::{async_trait, compat::net::SocketAddr, route, Any, Decodable, LocalMessage};
+use ockam_core::{compat::rand, AsyncTryClone};
 use ockam_core::{Address, Encodable, Message, Result, Routed, TransportMessage, Worker};
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, DelayedEvent};
 use ockam_transport_core::TransportError;
 use serde::{Deserialize, Serialize};
@@ -39,6 +42,21 @@ impl WorkerPair {
 pub(crate) enum TcpSendWorkerMsg {
     Heartbeat,
     ConnectionClosed,
+    Reconnect,
+}
+
+/// State of a connection which re-establishes itself after dropping
+struct Reconnect {
+    policy: ReconnectPolicy,
+    /// Peer as given by the user, resolved again on every attempt
+    peer: String,
+    /// Announced at the start of every connection
+    connection_id: Vec<u8>,
+    /// Failed attempts since the connection dropped
+    attempts: u32,
+    /// Prepared messages waiting for the connection to come back
+    buffer: VecDeque<Vec<u8>>,
+    event: DelayedEvent<TcpSendWorkerMsg>,
 }
 
 /// A TCP sending message worker
@@ -54,20 +72,27 @@ pub(crate) struct TcpSendWorker {
     rx: Option<OwnedReadHalf>,
     tx: Option<OwnedWriteHalf>,
     peer: SocketAddr,
+    tx_addr: Address,
     internal_addr: Address,
     rx_addr: Option<Address>,
     heartbeat: DelayedEvent<TcpSendWorkerMsg>,
     heartbeat_interval: Option<Duration>,
+    reconnect: Option<Reconnect>,
+    bytes_sent: Counter,
 }
 
 impl TcpSendWorker {
     /// Create a new `TcpSendWorker`
+    #[allow(clippy::too_many_arguments)]
     fn new(
         router_handle: TcpRouterHandle,
         stream: Option<TcpStream>,
         peer: SocketAddr,
+        tx_addr: Address,
         internal_addr: Address,
         heartbeat: DelayedEvent<TcpSendWorkerMsg>,
+        reconnect: Option<Reconnect>,
+        bytes_sent: Counter,
     ) -> Self {
         let (rx, tx) = match stream {
             Some(s) => {
@@ -82,10 +107,13 @@ impl TcpSendWorker {
             rx,
             tx,
             peer,
+            tx_addr,
             internal_addr,
             rx_addr: None,
             heartbeat,
             heartbeat_interval: Some(Duration::from_secs(5 * 60)),
+            reconnect,
+            bytes_sent,
         }
     }
 
@@ -100,15 +128,38 @@ impl TcpSendWorker {
         stream: Option<TcpStream>,
         peer: SocketAddr,
         hostnames: Vec<String>,
+        reconnect: Option<ReconnectPolicy>,
     ) -> Result<(Self, WorkerPair)> {
         let tx_addr = Address::random_local();
         let int_addr = Address::random_local();
+        let reconnect = match reconnect {
+            Some(policy) => Some(Reconnect {
+                policy,
+                peer: hostnames
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| peer.to_string()),
+                connection_id: rand::random::<[u8; 16]>().to_vec(),
+                attempts: 0,
+                buffer: VecDeque::new(),
+                event: DelayedEvent::create(ctx, int_addr.clone(), TcpSendWorkerMsg::Reconnect)
+                    .await?,
+            }),
+            None => None,
+        };
         let sender = TcpSendWorker::new(
             router_handle,
             stream,
             peer,
+            tx_addr.clone(),
             int_addr.clone(),
             DelayedEvent::create(ctx, int_addr.clone(), TcpSendWorkerMsg::Heartbeat).await?,
+            reconnect,
+            ctx.metrics().counter(
+                "ockam_transport_bytes_sent_total",
+                "Bytes sent by transports",
+                &[("transport", "tcp")],
+            ),
         );
         Ok((
             sender,
@@ -122,15 +173,20 @@ impl TcpSendWorker {
 
     /// Start a `(TcpSendWorker, TcpRecvProcessor)` pair that opens and
     /// manages the connection with the given peer
+    ///
+    /// With a [`ReconnectPolicy`] the worker re-establishes the
+    /// connection when it drops, instead of stopping.
     pub(crate) async fn start_pair(
         ctx: &Context,
         router_handle: TcpRouterHandle,
         stream: Option<TcpStream>,
         peer: SocketAddr,
         hostnames: Vec<String>,
+        reconnect: Option<ReconnectPolicy>,
     ) -> Result<WorkerPair> {
         trace!("Creating new TCP worker pair");
-        let (worker, pair) = Self::new_pair(ctx, router_handle, stream, peer, hostnames).await?;
+        let (worker, pair) =
+            Self::new_pair(ctx, router_handle, stream, peer, hostnames, reconnect).await?;
         ctx.start_worker(vec![pair.tx_addr(), worker.internal_addr().clone()], worker)
             .await?;
         Ok(pair)
@@ -153,6 +209,158 @@ impl TcpSendWorker {
 
         Ok(())
     }
+
+    /// Start the `TcpRecvProcessor` for the read half of the connection
+    async fn start_receiver(&mut self, ctx: &Context, rx: OwnedReadHalf) -> Result<()> {
+        let rx_addr = Address::random_local();
+        let receiver = TcpRecvProcessor::new(
+            rx,
+            format!("{}#{}", crate::TCP, self.peer).into(),
+            self.tx_addr.clone(),
+            self.internal_addr.clone(),
+            self.router_handle.async_try_clone().await?,
+        );
+        ctx.start_processor(rx_addr.clone(), receiver).await?;
+
+        self.rx_addr = Some(rx_addr);
+
+        Ok(())
+    }
+
+    /// Handle a dropped connection
+    ///
+    /// Without a reconnect policy the worker stops, otherwise it keeps
+    /// its address registered and schedules the next attempt.
+    async fn connection_lost(&mut self, ctx: &Context) -> Result<()> {
+        self.heartbeat.cancel();
+        self.tx = None;
+        if let Some(rx_addr) = self.rx_addr.take() {
+            let _ = ctx.stop_processor(rx_addr).await;
+        }
+
+        match &mut self.reconnect {
+            Some(reconnect) => {
+                let delay = reconnect.policy.delay(reconnect.attempts);
+                debug!(addr = %self.peer, ?delay, "Scheduling reconnection");
+                reconnect.event.schedule(delay).await
+            }
+            None => self.stop_and_unregister(ctx).await,
+        }
+    }
+
+    /// Attempt to re-establish a dropped connection
+    async fn try_reconnect(&mut self, ctx: &Context) -> Result<()> {
+        if self.tx.is_some() {
+            return Ok(());
+        }
+
+        debug!(addr = %self.peer, "Reconnecting");
+        match self.connect_again().await {
+            Ok(stream) => {
+                debug!(addr = %self.peer, "Reconnected");
+                let (rx, tx) = stream.into_split();
+                self.tx = Some(tx);
+                self.start_receiver(ctx, rx).await?;
+                if let Some(reconnect) = &mut self.reconnect {
+                    reconnect.attempts = 0;
+                }
+                if !self.announce(ctx).await? {
+                    return Ok(());
+                }
+
+                // Flush the buffer one message at a time, so that
+                // messages stay buffered if the connection drops again
+                while let Some(msg) = self.reconnect.as_mut().and_then(|r| r.buffer.pop_front()) {
+                    if !self.write(ctx, &msg).await? {
+                        if let Some(reconnect) = &mut self.reconnect {
+                            reconnect.buffer.push_front(msg);
+                        }
+                        return Ok(());
+                    }
+                }
+
+                self.schedule_heartbeat().await
+            }
+            Err(e) => {
+                let reconnect = match &mut self.reconnect {
+                    Some(reconnect) => reconnect,
+                    None => return self.stop_and_unregister(ctx).await,
+                };
+                reconnect.attempts += 1;
+                if !reconnect.policy.should_retry(reconnect.attempts) {
+                    warn!(
+                        addr = %self.peer, err = %e,
+                        "Giving up reconnecting after {} attempts", reconnect.attempts
+                    );
+                    return self.stop_and_unregister(ctx).await;
+                }
+
+                let delay = reconnect.policy.delay(reconnect.attempts);
+                debug!(addr = %self.peer, err = %e, ?delay, "Failed to reconnect");
+                reconnect.event.schedule(delay).await
+            }
+        }
+    }
+
+    /// Resolve the original peer again and connect to it
+    async fn connect_again(&self) -> Result<TcpStream> {
+        let peer_addr = match &self.reconnect {
+            Some(reconnect) => TcpRouterHandle::resolve_peer(reconnect.peer.clone())?.0,
+            None => self.peer,
+        };
+        Ok(TcpStream::connect(peer_addr)
+            .await
+            .map_err(TransportError::from)?)
+    }
+
+    /// Announce the connection id of a resumable connection to the peer
+    ///
+    /// Returns `false` if the connection dropped while writing.
+    async fn announce(&mut self, ctx: &Context) -> Result<bool> {
+        let msg = match &self.reconnect {
+            Some(reconnect) => prepare_message(resume_message(&reconnect.connection_id))?,
+            None => return Ok(true),
+        };
+        self.write(ctx, &msg).await
+    }
+
+    /// Keep a prepared message until the connection is back
+    ///
+    /// Fails if the buffer is full.
+    fn buffer(&mut self, msg: Vec<u8>) -> Result<()> {
+        let reconnect = self
+            .reconnect
+            .as_mut()
+            .ok_or(TransportError::PeerNotFound)?;
+
+        if reconnect.buffer.len() >= reconnect.policy.buffer_size() {
+            warn!("Dropping message for disconnected peer {}", self.peer);
+            return Err(TransportError::Capacity.into());
+        }
+
+        trace!("Buffering message for disconnected peer {}", self.peer);
+        reconnect.buffer.push_back(msg);
+        Ok(())
+    }
+
+    /// Write a prepared message to the peer
+    ///
+    /// Returns `false` if the connection dropped while writing.
+    async fn write(&mut self, ctx: &Context, msg: &[u8]) -> Result<bool> {
+        let tx = match &mut self.tx {
+            Some(tx) => tx,
+            None => return Err(TransportError::PeerNotFound.into()),
+        };
+
+        if tx.write_all(msg).await.is_err() {
+            warn!("Failed to send message to peer {}", self.peer);
+            self.connection_lost(ctx).await?;
+            return Ok(false);
+        }
+        self.bytes_sent.inc_by(msg.len() as u64);
+
+        Ok(true)
+    }
 }
 
 #[async_trait]
@@ -165,6 +373,8 @@ impl Worker for TcpSendWorker {
 
         if self.tx.is_none() {
             debug!(addr = %self.peer, "Connecting");
+            // The reconnect policy only applies to an established
+            // connection, so a failed first attempt stops the worker
             let connection = match TcpStream::connect(self.peer).await {
                 Ok(c) => {
                     debug!(addr = %self.peer, "Connected");
@@ -183,16 +393,11 @@ impl Worker for TcpSendWorker {
         }
 
         let rx = self.rx.take().ok_or(TransportError::GenericIo)?;
+        self.start_receiver(ctx, rx).await?;
 
-        let rx_addr = Address::random_local();
-        let receiver = TcpRecvProcessor::new(
-            rx,
-            format!("{}#{}", crate::TCP, self.peer).into(),
-            self.internal_addr.clone(),
-        );
-        ctx.start_processor(rx_addr.clone(), receiver).await?;
-
-        self.rx_addr = Some(rx_addr);
+        if !self.announce(ctx).await? {
+            return Ok(());
+        }
 
         self.schedule_heartbeat().await?;
 
@@ -216,38 +421,43 @@ impl Worker for TcpSendWorker {
     ) -> Result<()> {
         self.heartbeat.cancel();
 
-        let tx = match &mut self.tx {
-            Some(tx) => tx,
-            None => return Err(TransportError::PeerNotFound.into()),
-        };
-
         let recipient = msg.msg_addr();
         if recipient == self.internal_addr {
             let msg = TcpSendWorkerMsg::decode(msg.payload())?;
 
             match msg {
                 TcpSendWorkerMsg::Heartbeat => {
+                    // Nothing to keep alive while reconnecting
+                    if self.tx.is_none() {
+                        return Ok(());
+                    }
+
                     let msg = TransportMessage::v1(route![], route![], vec![]);
                     let msg = prepare_message(msg)?;
                     // Sending empty heartbeat
-                    if tx.write_all(&msg).await.is_err() {
-                        warn!("Failed to send heartbeat to peer {}", self.peer);
-                        self.stop_and_unregister(ctx).await?;
-
+                    if !self.write(ctx, &msg).await? {
                         return Ok(());
                     }
 
                     debug!("Sent heartbeat to peer {}", self.peer);
                 }
                 TcpSendWorkerMsg::ConnectionClosed => {
-                    warn!("Stopping sender due to closed connection {}", self.peer);
+                    // Already handled the drop of this connection
+                    if self.tx.is_none() {
+                        return Ok(());
+                    }
+
+                    warn!("Connection to peer {} was closed", self.peer);
                     // No need to stop Receiver as it notified us about connection drop and will
                     // stop itself
                     self.rx_addr = None;
-                    self.stop_and_unregister(ctx).await?;
+                    self.connection_lost(ctx).await?;
 
                     return Ok(());
                 }
+                TcpSendWorkerMsg::Reconnect => {
+                    return self.try_reconnect(ctx).await;
+                }
             }
         } else {
             let mut msg = LocalMessage::decode(msg.payload())?.into_transport_message();
@@ -257,10 +467,14 @@ impl Worker for TcpSendWorker {
             // Create a message buffer with pre-pended length
             let msg = prepare_message(msg)?;
 
-            if tx.write_all(msg.as_slice()).await.is_err() {
-                warn!("Failed to send message to peer {}", self.peer);
-                self.stop_and_unregister(ctx).await?;
+            if self.tx.is_none() {
+                return self.buffer(msg);
+            }
 
+            if !self.write(ctx, &msg).await? {
+                if self.reconnect.is_some() {
+                    return self.buffer(msg);
+                }
                 return Ok(());
             }
         }
@@ -276,7 +490,7 @@ impl Worker for TcpSendWorker {
 ///
 /// The length-prefix is encoded as a big-endian 16-bit unsigned
 /// integer.
-fn prepare_message(msg: TransportMessage) -> Result<Vec<u8>> {
+pub(crate) fn prepare_message(msg: TransportMessage) -> Result<Vec<u8>> {
     let mut msg_buf = msg.encode().map_err(|_| TransportError::SendBadMessage)?;
 
     // Create a buffer that includes the message length in big endian
@@ -0,0 +1,173 @@
+use core::time::Duration;
+use ockam_core::{route, Address, Decodable, Result, TransportMessage};
+use ockam_identity::authenticated_storage::mem::InMemoryStorage;
+use ockam_identity::{Identity, TrustEveryonePolicy};
+use ockam_node::Context;
+use ockam_vault::Vault;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use ockam_transport_tcp::{ReconnectPolicy, TcpTransport, TCP};
+
+/// Read a single length-prefixed transport message from a raw stream
+async fn read_message(stream: &mut TcpStream) -> TransportMessage {
+    let len = stream.read_u16().await.unwrap();
+    let mut buf = vec![0; len as usize];
+    stream.read_exact(&mut buf).await.unwrap();
+    TransportMessage::decode(&buf).unwrap()
+}
+
+/// Wait until the workers of the node satisfy `check`
+async fn wait_for_workers(ctx: &Context, check: impl Fn(&[Address]) -> bool) -> Result<bool> {
+    for _ in 0..50 {
+        if check(&ctx.list_workers().await?) {
+            return Ok(true);
+        }
+        ctx.sleep(Duration::from_millis(20)).await;
+    }
+    Ok(false)
+}
+
+/// Forward every connection accepted on `listener` to `target`,
+/// handing out the task serving it so that tests can drop it
+fn start_proxy(listener: TcpListener, target: String) -> mpsc::UnboundedReceiver<JoinHandle<()>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((mut inbound, _)) = listener.accept().await {
+            let mut outbound = TcpStream::connect(&target).await.unwrap();
+            let connection = tokio::spawn(async move {
+                let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+            });
+            if tx.send(connection).is_err() {
+                return;
+            }
+        }
+    });
+    rx
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn tcp_reconnect__peer_drops__buffered_message_delivered(ctx: &mut Context) -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let peer = listener.local_addr().unwrap().to_string();
+
+    let transport = TcpTransport::create(ctx).await?;
+    let policy = ReconnectPolicy::new()
+        .with_initial_delay(Duration::from_millis(500))
+        .with_max_attempts(3);
+    transport.connect_with_reconnect(&peer, policy).await?;
+
+    // Drop the first connection from the peer side, and wait for the
+    // worker to stop its receiver
+    let (mut first, _) = listener.accept().await.unwrap();
+    let announced = read_message(&mut first).await;
+    let connected = ctx.list_workers().await?.len();
+    drop(first);
+    assert!(wait_for_workers(ctx, |workers| workers.len() < connected).await?);
+
+    // Sent while disconnected, through the same route
+    let msg = "Hello again".to_string();
+    ctx.send(route![(TCP, peer.clone()), "echoer"], msg.clone())
+        .await?;
+
+    // The new connection announces the same connection id first
+    let (mut second, _) = listener.accept().await.unwrap();
+    assert_eq!(read_message(&mut second).await, announced);
+    let received = read_message(&mut second).await;
+    assert_eq!(received.onward_route, route!["echoer"]);
+    assert_eq!(String::decode(&received.payload)?, msg);
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn tcp_reconnect__peer_gone__gives_up_after_max_attempts(ctx: &mut Context) -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let peer = listener.local_addr().unwrap().to_string();
+
+    let transport = TcpTransport::create(ctx).await?;
+    let policy = ReconnectPolicy::new()
+        .with_initial_delay(Duration::from_millis(10))
+        .with_max_attempts(2);
+    let tx_addr = transport.connect_with_reconnect(&peer, policy).await?;
+
+    // Close the connection and stop listening, so that every
+    // reconnection attempt fails
+    let (first, _) = listener.accept().await.unwrap();
+    drop(listener);
+    drop(first);
+
+    assert!(wait_for_workers(ctx, |workers| !workers.contains(&tx_addr)).await?);
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn tcp_reconnect__peer_drops__secure_channel_survives(ctx: &mut Context) -> Result<()> {
+    let transport = TcpTransport::create(ctx).await?;
+    let server = transport.listen("127.0.0.1:0").await?;
+
+    // Connect through a proxy, which drops connections on request
+    let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let peer = proxy.local_addr().unwrap().to_string();
+    let mut connections = start_proxy(proxy, server.to_string());
+
+    let policy = ReconnectPolicy::new().with_initial_delay(Duration::from_millis(100));
+    transport.connect_with_reconnect(&peer, policy).await?;
+
+    let vault = Vault::create();
+    let storage = InMemoryStorage::new();
+    let alice = Identity::create(ctx, &vault).await?;
+    let bob = Identity::create(ctx, &vault).await?;
+    bob.create_secure_channel_listener("bob_listener", TrustEveryonePolicy, &storage)
+        .await?;
+    let channel = alice
+        .create_secure_channel(
+            route![(TCP, peer.clone()), "bob_listener"],
+            TrustEveryonePolicy,
+            &storage,
+        )
+        .await?;
+
+    // Round trip, replying along the return route the listening side
+    // saw for the first connection
+    ctx.send(route![channel.clone(), ctx.address()], "before".to_string())
+        .await?;
+    let msg = ctx.receive::<String>().await?.take();
+    let return_route = msg.return_route();
+    assert_eq!(msg.body(), "before");
+
+    // Drop the connection on both sides, and wait for the reconnection
+    let first = connections.recv().await.unwrap();
+    first.abort();
+    let _ = first.await;
+    let _second = connections.recv().await.unwrap();
+
+    ctx.send(route![channel, ctx.address()], "after".to_string())
+        .await?;
+    let msg = ctx.receive::<String>().await?.take();
+    assert_eq!(msg.body(), "after");
+
+    ctx.send(return_route, "reply".to_string()).await?;
+    let msg = ctx.receive::<String>().await?.take();
+    assert_eq!(msg.body(), "reply");
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+#![cfg(unix)]
+
+use ockam_core::compat::rand::{self, Rng};
+use ockam_core::{route, Result, Routed, Worker};
+use ockam_node::Context;
+use std::os::unix::fs::PermissionsExt;
+
+use ockam_transport_tcp::UdsTransport;
+
+#[ockam_macros::test]
+async fn uds_send_receive(ctx: &mut Context) -> Result<()> {
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let dir = std::env::temp_dir().join(format!("ockam-uds-{}", rand::random::<u32>()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("api.sock");
+
+    let transport = UdsTransport::create(ctx).await?;
+    let listener = transport.listen(&path).await?;
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(
+        mode & 0o777,
+        0o600,
+        "Socket should only be accessible by its owner"
+    );
+    assert_eq!(
+        std::fs::read_dir(&dir).unwrap().count(),
+        1,
+        "Only the socket should be left in its directory"
+    );
+
+    let conn = transport.connect(&path).await?;
+    let msg: String = rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(256)
+        .map(char::from)
+        .collect();
+    let reply = ctx
+        .send_and_receive::<_, _, String>(route![conn.clone(), "echoer"], msg.clone())
+        .await?;
+    assert_eq!(reply, msg, "Should receive the same message");
+
+    transport.disconnect(conn).await?;
+    transport.stop_listener(listener).await?;
+    ctx.sleep(core::time::Duration::from_millis(100)).await;
+    assert!(!path.exists(), "Socket should be removed with its listener");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
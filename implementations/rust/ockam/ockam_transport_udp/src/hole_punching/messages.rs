@@ -0,0 +1,49 @@
+use ockam_core::{Address, Message};
+use serde::{Deserialize, Serialize};
+
+/// Requests handled by a [`RendezvousService`](crate::RendezvousService)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Message)]
+pub enum RendezvousRequest {
+    /// Record the observed public address of the sender under `name`
+    Register {
+        /// Name the sender wants to be known as
+        name: String,
+    },
+    /// Ask for a simultaneous hole punch between `name` and `peer`
+    ///
+    /// The punch is coordinated once both peers have asked to be
+    /// connected to each other.
+    Connect {
+        /// Name of the requesting peer
+        name: String,
+        /// Name of the peer to connect to
+        peer: String,
+    },
+}
+
+/// Messages received by a peer taking part in a hole punch
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Message)]
+pub enum PunchMessage {
+    /// Registration response, carrying the public address observed
+    /// by the rendezvous service
+    Registered {
+        /// Observed public socket address of the registering peer
+        public_addr: String,
+    },
+    /// Start punching towards the given peer
+    Punch {
+        /// Public socket address of the peer
+        peer_addr: String,
+        /// Address of the worker waiting for punches on the peer node
+        peer_worker: Address,
+    },
+    /// A punch sent directly between peers
+    Ping,
+    /// Acknowledgement of a received [`PunchMessage::Ping`]
+    Pong,
+    /// The rendezvous service could not handle a request
+    Rejected {
+        /// Why the request was rejected
+        reason: String,
+    },
+}
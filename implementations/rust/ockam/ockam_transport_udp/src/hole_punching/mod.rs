@@ -0,0 +1,5 @@
+pub use messages::*;
+pub use rendezvous::*;
+
+mod messages;
+mod rendezvous;
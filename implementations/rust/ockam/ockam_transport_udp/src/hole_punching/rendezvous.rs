@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use ockam_core::{async_trait, Result, Route, Routed, Worker};
+use ockam_node::Context;
+use ockam_transport_core::TransportError;
+use tracing::{debug, trace, warn};
+
+use crate::hole_punching::{PunchMessage, RendezvousRequest};
+use crate::UDP;
+
+/// Default address of the rendezvous service
+pub const RENDEZVOUS_SERVICE: &str = "rendezvous";
+
+/// How long peers and pending connection requests are remembered
+/// after their latest request
+const RENDEZVOUS_TTL: Duration = Duration::from_secs(60);
+/// Maximum number of pending connection requests
+const MAX_PENDING: usize = 1024;
+
+/// A peer known to the rendezvous service
+struct Peer {
+    /// Public socket address, as observed on incoming datagrams
+    public_addr: String,
+    /// Route back to the worker which sent the latest request
+    route: Route,
+    /// Time of the latest request
+    last_seen: Instant,
+}
+
+/// UDP rendezvous service
+///
+/// Peers behind NAT register with this service over UDP. The service
+/// records the public address each registration arrived from and,
+/// once two peers asked to be connected to each other, tells both of
+/// them to start punching towards the other one at the same time.
+///
+/// A name stays bound to the public address which registered it,
+/// until that peer has been silent for a minute. Connection requests
+/// which are not answered by the other peer within that time are
+/// dropped.
+///
+/// The service can run on any node which has a
+/// [`UdpTransport`](crate::UdpTransport) listening on a reachable
+/// address. See [`UdpTransport::punch_hole`](crate::UdpTransport::punch_hole)
+/// for the client side.
+#[derive(Default)]
+pub struct RendezvousService {
+    peers: BTreeMap<String, Peer>,
+    /// Pending `(name, peer)` connection requests, by time of request
+    pending: BTreeMap<(String, String), Instant>,
+}
+
+impl RendezvousService {
+    /// Create a new, empty rendezvous service
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a rendezvous service at [`RENDEZVOUS_SERVICE`]
+    pub async fn create(ctx: &Context) -> Result<()> {
+        ctx.start_worker(RENDEZVOUS_SERVICE, Self::new()).await
+    }
+
+    /// Extract the public socket address from a return route
+    fn observed_addr(route: &Route) -> Result<String> {
+        route
+            .iter()
+            .find(|a| a.transport_type() == UDP)
+            .map(|a| a.address().to_string())
+            .ok_or_else(|| TransportError::InvalidAddress.into())
+    }
+
+    /// Forget peers and connection requests which have expired
+    fn expire(&mut self, now: Instant) {
+        self.peers
+            .retain(|_, peer| now.duration_since(peer.last_seen) < RENDEZVOUS_TTL);
+        self.pending
+            .retain(|_, requested| now.duration_since(*requested) < RENDEZVOUS_TTL);
+    }
+
+    fn update_peer(&mut self, name: String, route: Route, now: Instant) -> Result<String> {
+        let public_addr = Self::observed_addr(&route)?;
+        if let Some(peer) = self.peers.get(&name) {
+            if peer.public_addr != public_addr {
+                warn!(
+                    "Rendezvous: {} is already registered from {}, rejecting {}",
+                    name, peer.public_addr, public_addr
+                );
+                return Err(TransportError::AlreadyConnected.into());
+            }
+        }
+
+        trace!("Rendezvous peer {} observed at {}", name, public_addr);
+        self.peers.insert(
+            name,
+            Peer {
+                public_addr: public_addr.clone(),
+                route,
+                last_seen: now,
+            },
+        );
+        Ok(public_addr)
+    }
+
+    async fn handle_request(
+        &mut self,
+        ctx: &Context,
+        request: RendezvousRequest,
+        return_route: Route,
+        now: Instant,
+    ) -> Result<()> {
+        match request {
+            RendezvousRequest::Register { name } => {
+                let public_addr = self.update_peer(name, return_route.clone(), now)?;
+                ctx.send(return_route, PunchMessage::Registered { public_addr })
+                    .await?;
+            }
+            RendezvousRequest::Connect { name, peer } => {
+                self.update_peer(name.clone(), return_route, now)?;
+
+                // Wait until the other side asks for the same connection
+                if self.pending.remove(&(peer.clone(), name.clone())).is_none() {
+                    let request = (name, peer);
+                    if !self.pending.contains_key(&request) && self.pending.len() >= MAX_PENDING {
+                        warn!("Rendezvous: too many pending connection requests");
+                        return Err(TransportError::Capacity.into());
+                    }
+                    debug!("Rendezvous: {} is waiting for {}", request.0, request.1);
+                    self.pending.insert(request, now);
+                    return Ok(());
+                }
+
+                let (a, b) = match (self.peers.get(&name), self.peers.get(&peer)) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => return Err(TransportError::PeerNotFound.into()),
+                };
+                debug!("Rendezvous: coordinating punch {} <-> {}", name, peer);
+                Self::send_punch(ctx, a, b).await?;
+                Self::send_punch(ctx, b, a).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn send_punch(ctx: &Context, to: &Peer, peer: &Peer) -> Result<()> {
+        let msg = PunchMessage::Punch {
+            peer_addr: peer.public_addr.clone(),
+            peer_worker: peer.route.recipient(),
+        };
+        ctx.send(to.route.clone(), msg).await
+    }
+}
+
+#[async_trait]
+impl Worker for RendezvousService {
+    type Message = RendezvousRequest;
+    type Context = Context;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<RendezvousRequest>,
+    ) -> Result<()> {
+        let return_route = msg.return_route();
+        let now = Instant::now();
+        self.expire(now);
+
+        // Tell the sender why its request failed, rather than letting
+        // it wait for a timeout
+        let res = self
+            .handle_request(ctx, msg.body(), return_route.clone(), now)
+            .await;
+        if let Err(e) = res {
+            let reason = e.to_string();
+            ctx.send(return_route, PunchMessage::Rejected { reason })
+                .await?;
+        }
+
+        Ok(())
+    }
+}
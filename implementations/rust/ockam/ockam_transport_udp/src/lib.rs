@@ -1,9 +1,11 @@
 use std::net::SocketAddr;
 
+pub use hole_punching::*;
 use ockam_core::{Result, TransportType};
 use ockam_transport_core::TransportError;
 pub use transport::*;
 
+mod hole_punching;
 mod router;
 mod transport;
 mod workers;
@@ -1,22 +1,30 @@
 use std::{
     net::{SocketAddr, ToSocketAddrs},
     str::FromStr,
+    time::Duration,
 };
 
-use futures_util::stream::StreamExt;
-use ockam_core::{async_trait, Address, AsyncTryClone, Result};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, route, Address, AsyncTryClone, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
-use tokio::net::UdpSocket;
-use tokio_util::udp::UdpFramed;
+use tracing::{debug, warn};
 
 use crate::{
-    parse_socket_addr,
-    workers::{TransportMessageCodec, UdpListenProcessor, UdpSendWorker},
-    UdpAddress,
+    hole_punching::{PunchMessage, RendezvousRequest, RENDEZVOUS_SERVICE},
+    parse_socket_addr, UdpAddress, UdpConnection,
 };
 
-use super::UdpRouterMessage;
+use super::{UdpRouterMessage, UdpRouterResponse};
+
+/// How many pings are sent to a peer while punching a hole
+const PUNCH_ATTEMPTS: usize = 20;
+/// Delay between two pings sent to a peer while punching a hole
+const PUNCH_INTERVAL: Duration = Duration::from_millis(250);
+/// How long to wait for the rendezvous service to answer a registration
+const REGISTER_TIMEOUT_SECS: u64 = 5;
+/// How long to wait for the other peer to show up at the rendezvous service
+const PUNCH_TIMEOUT_SECS: u64 = 30;
 
 /// A handle to connect to a UdpRouter
 ///
@@ -68,23 +76,90 @@ impl UdpRouterHandle {
     }
 
     /// Bind a listener with given address for this router
-    pub async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<()> {
-        let socket = UdpSocket::bind(addr.into())
-            .await
-            .map_err(TransportError::from)?;
-        let (sink, stream) = UdpFramed::new(socket, TransportMessageCodec).split();
+    ///
+    /// Returns the bound socket address and the address of the sender
+    /// worker owning the write half of the socket.
+    pub async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<(SocketAddr, Address)> {
+        let bind_addr = addr.into();
+        match self.request(UdpRouterMessage::Bind { bind_addr }).await? {
+            UdpRouterResponse::Bind(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
 
-        let tx_addr = Address::random_local();
-        let sender = UdpSendWorker::new(sink);
-        self.ctx.start_worker(tx_addr.clone(), sender).await?;
-        UdpListenProcessor::start(&self.ctx, stream, tx_addr, self.async_try_clone().await?)
-            .await?;
+    /// Bind a socket which is not listed as a listener
+    ///
+    /// The socket is closed once the last peer routed through it is
+    /// disconnected. Returns the address of its sender worker.
+    pub(crate) async fn open(&self, addr: impl Into<SocketAddr>) -> Result<Address> {
+        let bind_addr = addr.into();
+        match self.request(UdpRouterMessage::Open { bind_addr }).await? {
+            UdpRouterResponse::Open(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
 
-        Ok(())
+    /// Close the socket of the given sender worker, forgetting the peers
+    /// routed through it
+    pub(crate) async fn close(&self, tx_addr: Address) -> Result<()> {
+        match self.request(UdpRouterMessage::Close { tx_addr }).await? {
+            UdpRouterResponse::Close(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
     }
 
-    /// Register a new worker with this router
-    pub(crate) async fn register(&self, tx_addr: Address, peer: impl Into<String>) -> Result<()> {
+    /// Stop the listener bound to the given address
+    pub async fn stop_listener(&self, addr: impl Into<SocketAddr>) -> Result<()> {
+        let bind_addr = addr.into();
+        match self
+            .request(UdpRouterMessage::StopListener { bind_addr })
+            .await?
+        {
+            UdpRouterResponse::StopListener(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
+
+    /// Bind a new socket dedicated to the given peer
+    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
+        let peer = peer.as_ref().to_string();
+        match self.request(UdpRouterMessage::Connect { peer }).await? {
+            UdpRouterResponse::Connect(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
+
+    /// Forget the given peer
+    pub async fn disconnect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
+        let peer = peer.as_ref().to_string();
+        match self.request(UdpRouterMessage::Disconnect { peer }).await? {
+            UdpRouterResponse::Disconnect(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
+
+    /// List all peers known to this router
+    pub async fn list_connections(&self) -> Result<Vec<UdpConnection>> {
+        match self.request(UdpRouterMessage::ListConnections).await? {
+            UdpRouterResponse::ListConnections(res) => Ok(res),
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
+
+    /// Send a request to the router and wait for its response
+    async fn request(&self, req: UdpRouterMessage) -> Result<UdpRouterResponse> {
+        let mut child_ctx = self.ctx.new_detached(Address::random_local()).await?;
+        child_ctx.send(self.api_addr.clone(), req).await?;
+
+        Ok(child_ctx
+            .receive::<UdpRouterResponse>()
+            .await?
+            .take()
+            .body())
+    }
+
+    /// The addresses under which the given peer is routed
+    pub(crate) fn peer_accepts(peer: impl Into<String>) -> Result<Vec<Address>> {
         let (peer, hostnames) = Self::resolve_peer(peer.into())?;
         let mut accepts = vec![UdpAddress::from(peer).into()];
         accepts.extend(
@@ -93,6 +168,15 @@ impl UdpRouterHandle {
                 .filter_map(|s| UdpAddress::from_str(s).ok())
                 .map(|addr| addr.into()),
         );
+        Ok(accepts)
+    }
+
+    /// Register a new worker with this router
+    ///
+    /// Peers which are already routed through another socket are
+    /// left untouched.
+    pub(crate) async fn register(&self, tx_addr: Address, peer: impl Into<String>) -> Result<()> {
+        let accepts = Self::peer_accepts(peer)?;
 
         // TODO: should we send a router request instead
         // and see if worker is already registered?
@@ -106,4 +190,132 @@ impl UdpRouterHandle {
             )
             .await
     }
+
+    /// Route the given peer through a socket, replacing its existing
+    /// route if there is one
+    pub(crate) async fn reroute(&self, tx_addr: Address, peer: impl Into<String>) -> Result<()> {
+        let accepts = Self::peer_accepts(peer)?;
+        match self
+            .request(UdpRouterMessage::Reroute {
+                accepts,
+                self_addr: tx_addr,
+            })
+            .await?
+        {
+            UdpRouterResponse::Reroute(res) => res,
+            _ => Err(TransportError::InvalidRouterResponseType.into()),
+        }
+    }
+
+    /// Punch a hole towards `peer`, coordinated by a rendezvous service
+    ///
+    /// A dedicated socket is bound for this, so that the public address
+    /// observed by the rendezvous service is also the one the peer
+    /// sees our punches coming from.
+    ///
+    /// The socket is closed again if the punch fails.
+    pub async fn punch_hole(
+        &self,
+        rendezvous_addr: SocketAddr,
+        name: String,
+        peer: String,
+    ) -> Result<Address> {
+        let tx_addr = self.open(parse_socket_addr("0.0.0.0:0")?).await?;
+        let res = self
+            .punch_hole_through(&tx_addr, rendezvous_addr, name, peer)
+            .await;
+        if res.is_err() {
+            if let Err(e) = self.close(tx_addr).await {
+                debug!("Failed to close punching socket: {}", e);
+            }
+        }
+        res
+    }
+
+    async fn punch_hole_through(
+        &self,
+        tx_addr: &Address,
+        rendezvous_addr: SocketAddr,
+        name: String,
+        peer: String,
+    ) -> Result<Address> {
+        self.reroute(tx_addr.clone(), rendezvous_addr.to_string())
+            .await?;
+
+        let mut child_ctx = self.ctx.new_detached(Address::random_local()).await?;
+        let rendezvous = route![UdpAddress::from(rendezvous_addr), RENDEZVOUS_SERVICE];
+
+        child_ctx
+            .send(
+                rendezvous.clone(),
+                RendezvousRequest::Register { name: name.clone() },
+            )
+            .await?;
+        let response = child_ctx
+            .receive_timeout::<PunchMessage>(REGISTER_TIMEOUT_SECS)
+            .await?
+            .take()
+            .body();
+        match response {
+            PunchMessage::Registered { public_addr } => {
+                debug!("Registered as {} with public address {}", name, public_addr)
+            }
+            PunchMessage::Rejected { reason } => return Err(Self::rejected(reason)),
+            _ => return Err(TransportError::Protocol.into()),
+        }
+
+        child_ctx
+            .send(rendezvous, RendezvousRequest::Connect { name, peer })
+            .await?;
+        let response = child_ctx
+            .receive_timeout::<PunchMessage>(PUNCH_TIMEOUT_SECS)
+            .await?
+            .take()
+            .body();
+        let (peer_addr, peer_worker) = match response {
+            PunchMessage::Punch {
+                peer_addr,
+                peer_worker,
+            } => (peer_addr, peer_worker),
+            PunchMessage::Rejected { reason } => return Err(Self::rejected(reason)),
+            _ => return Err(TransportError::Protocol.into()),
+        };
+
+        // Route traffic to the peer through the punching socket, even
+        // if it was reachable through another socket before
+        self.reroute(tx_addr.clone(), peer_addr.clone()).await?;
+        let peer_udp: Address = UdpAddress::from_str(&peer_addr)?.into();
+        let peer_route = route![peer_udp.clone(), peer_worker];
+
+        // Our first pings may be dropped by the peer's NAT, until the
+        // peer's own pings open it up
+        for _ in 0..PUNCH_ATTEMPTS {
+            child_ctx
+                .send(peer_route.clone(), PunchMessage::Ping)
+                .await?;
+            let msg = match child_ctx
+                .receive_duration_timeout::<PunchMessage>(PUNCH_INTERVAL)
+                .await
+            {
+                Ok(msg) => msg.take().body(),
+                Err(_) => continue,
+            };
+            match msg {
+                PunchMessage::Ping => {
+                    child_ctx.send(peer_route, PunchMessage::Pong).await?;
+                    return Ok(peer_udp);
+                }
+                PunchMessage::Pong => return Ok(peer_udp),
+                _ => continue,
+            }
+        }
+
+        Err(TransportError::PeerNotFound.into())
+    }
+
+    /// Error for a request rejected by the rendezvous service
+    fn rejected(reason: String) -> ockam_core::Error {
+        warn!("Rendezvous service rejected request: {}", reason);
+        ockam_core::Error::new(Origin::Transport, Kind::Invalid, reason)
+    }
 }
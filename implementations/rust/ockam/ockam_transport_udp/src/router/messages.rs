@@ -1,6 +1,10 @@
-use ockam_core::{Address, Message};
+use std::net::SocketAddr;
+
+use ockam_core::{Address, Message, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::UdpConnection;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, Ord, PartialOrd, Eq, PartialEq, Message)]
 pub(crate) enum UdpRouterMessage {
     /// Register a new client to this routing scope.
@@ -10,4 +14,40 @@ pub(crate) enum UdpRouterMessage {
         /// The clients own worker bus address.
         self_addr: Address,
     },
+    /// Route the given addresses through a socket, replacing any
+    /// existing route, answered with [`UdpRouterResponse::Reroute`]
+    Reroute {
+        accepts: Vec<Address>,
+        self_addr: Address,
+    },
+    /// Bind a new socket, answered with [`UdpRouterResponse::Bind`]
+    Bind { bind_addr: SocketAddr },
+    /// Bind a new socket which is not a listener, answered with
+    /// [`UdpRouterResponse::Open`]
+    Open { bind_addr: SocketAddr },
+    /// Close the socket of the given sender worker, forgetting the
+    /// peers routed through it, answered with [`UdpRouterResponse::Close`]
+    Close { tx_addr: Address },
+    /// Stop the socket bound to the given address
+    StopListener { bind_addr: SocketAddr },
+    /// Bind a new socket dedicated to the given peer
+    Connect { peer: String },
+    /// Forget the given peer, closing its socket if it isn't shared
+    Disconnect { peer: String },
+    /// List all known peers
+    ListConnections,
+}
+
+#[derive(Serialize, Deserialize, Debug, Message)]
+pub(crate) enum UdpRouterResponse {
+    Reroute(Result<()>),
+    /// Bound socket address and the address of its sender worker
+    Bind(Result<(SocketAddr, Address)>),
+    /// Address of the sender worker of the opened socket
+    Open(Result<Address>),
+    Close(Result<()>),
+    StopListener(Result<()>),
+    Connect(Result<Address>),
+    Disconnect(Result<()>),
+    ListConnections(Vec<UdpConnection>),
 }
@@ -1,5 +1,6 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
 use std::ops::Deref;
-use std::{collections::BTreeMap, str::FromStr};
 
 use futures_util::StreamExt;
 use ockam_core::{async_trait, Address, Any, Decodable, LocalMessage, Result, Routed, Worker};
@@ -10,8 +11,9 @@ use tokio::net::UdpSocket;
 use tokio_util::udp::UdpFramed;
 use tracing::{error, trace};
 
-use crate::router::{UdpRouterHandle, UdpRouterMessage};
-use crate::transport::UdpAddress;
+use crate::parse_socket_addr;
+use crate::router::{UdpRouterHandle, UdpRouterMessage, UdpRouterResponse};
+use crate::transport::UdpConnection;
 use crate::workers::{TransportMessageCodec, UdpListenProcessor, UdpSendWorker};
 
 /// A UDP address router and listener
@@ -27,6 +29,10 @@ pub(crate) struct UdpRouter {
     main_addr: Address,
     api_addr: Address,
     map: BTreeMap<Address, Address>,
+    /// Receiving processors, by the address of their socket's sender worker
+    sockets: BTreeMap<Address, Address>,
+    /// Sender workers of the sockets bound with `Bind`, by bound address
+    listeners: BTreeMap<SocketAddr, Address>,
     allow_auto_connection: bool,
 }
 
@@ -43,6 +49,8 @@ impl UdpRouter {
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
             map: BTreeMap::new(),
+            sockets: BTreeMap::new(),
+            listeners: BTreeMap::new(),
             allow_auto_connection: true,
         };
 
@@ -119,36 +127,127 @@ impl UdpRouter {
         Ok(())
     }
 
-    async fn connect(&mut self, peer: String) -> Result<Address> {
-        let socket = UdpSocket::bind("127.0.0.1:0")
+    async fn handle_reroute(&mut self, accepts: Vec<Address>, self_addr: Address) -> Result<()> {
+        let mut previous = Vec::new();
+        for accept in accepts {
+            trace!("UDP reroute request: {} => {}", accept, self_addr);
+            if let Some(tx_addr) = self.map.insert(accept, self_addr.clone()) {
+                if tx_addr != self_addr && !previous.contains(&tx_addr) {
+                    previous.push(tx_addr);
+                }
+            }
+        }
+
+        for tx_addr in previous {
+            self.close_if_unused(&tx_addr).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Bind a new socket and start its sender worker and receiving processor
+    async fn bind_socket(&mut self, bind_addr: SocketAddr) -> Result<(SocketAddr, Address)> {
+        let socket = UdpSocket::bind(bind_addr)
             .await
             .map_err(TransportError::from)?;
-        let (sink, stream) = UdpFramed::new(socket, TransportMessageCodec).split();
+        let local_addr = socket.local_addr().map_err(TransportError::from)?;
+        let (sink, stream) =
+            UdpFramed::new(socket, TransportMessageCodec::new(self.ctx.metrics())).split();
 
         let tx_addr = Address::random_local();
         let sender = UdpSendWorker::new(sink);
         self.ctx.start_worker(tx_addr.clone(), sender).await?;
-        UdpListenProcessor::start(
+        let rx_addr = UdpListenProcessor::start(
             &self.ctx,
             stream,
             tx_addr.clone(),
             self.create_self_handle(&self.ctx).await?,
         )
         .await?;
+        self.sockets.insert(tx_addr.clone(), rx_addr);
 
-        let (peer, hostnames) = UdpRouterHandle::resolve_peer(peer)?;
-        let mut accepts: Vec<Address> = vec![UdpAddress::from(peer).into()];
-        accepts.extend(
-            hostnames
-                .iter()
-                .filter_map(|s| UdpAddress::from_str(s).ok())
-                .map(|addr| addr.into()),
-        );
+        Ok((local_addr, tx_addr))
+    }
+
+    /// Stop the sender worker and receiving processor of a socket
+    async fn close_socket(&mut self, tx_addr: &Address) -> Result<()> {
+        self.map.retain(|_, self_addr| self_addr != tx_addr);
+        if let Some(rx_addr) = self.sockets.remove(tx_addr) {
+            self.ctx.stop_processor(rx_addr).await?;
+        }
+        self.ctx.stop_worker(tx_addr.clone()).await
+    }
+
+    /// Close a socket unless it is a listener or still used by peers
+    async fn close_if_unused(&mut self, tx_addr: &Address) -> Result<()> {
+        let is_listener = self.listeners.values().any(|a| a == tx_addr);
+        let is_used = self.map.values().any(|a| a == tx_addr);
+        if !is_listener && !is_used {
+            self.close_socket(tx_addr).await?;
+        }
+        Ok(())
+    }
+
+    async fn connect(&mut self, peer: String) -> Result<Address> {
+        let accepts = UdpRouterHandle::peer_accepts(peer)?;
+        let (_, tx_addr) = self.bind_socket(parse_socket_addr("127.0.0.1:0")?).await?;
 
         self.handle_register(accepts, tx_addr.clone()).await?;
 
         Ok(tx_addr)
     }
+
+    async fn handle_connect(&mut self, peer: String) -> Result<Address> {
+        let accepts = UdpRouterHandle::peer_accepts(peer.clone())?;
+        if accepts.iter().any(|a| self.map.contains_key(a)) {
+            return Err(TransportError::AlreadyConnected.into());
+        }
+        self.connect(peer).await
+    }
+
+    async fn handle_disconnect(&mut self, peer: String) -> Result<()> {
+        let accepts = UdpRouterHandle::peer_accepts(peer)?;
+        let tx_addr = match self.map.get(&accepts[0]) {
+            Some(tx_addr) => tx_addr.clone(),
+            None => {
+                error!("Failed to disconnect, peer not found: {}", accepts[0]);
+                return Err(TransportError::PeerNotFound.into());
+            }
+        };
+        for accept in &accepts {
+            self.map.remove(accept);
+        }
+
+        self.close_if_unused(&tx_addr).await
+    }
+
+    async fn handle_bind(&mut self, bind_addr: SocketAddr) -> Result<(SocketAddr, Address)> {
+        let (local_addr, tx_addr) = self.bind_socket(bind_addr).await?;
+        self.listeners.insert(local_addr, tx_addr.clone());
+        Ok((local_addr, tx_addr))
+    }
+
+    async fn handle_open(&mut self, bind_addr: SocketAddr) -> Result<Address> {
+        let (_, tx_addr) = self.bind_socket(bind_addr).await?;
+        Ok(tx_addr)
+    }
+
+    async fn handle_stop_listener(&mut self, bind_addr: SocketAddr) -> Result<()> {
+        match self.listeners.remove(&bind_addr) {
+            Some(tx_addr) => self.close_socket(&tx_addr).await,
+            None => {
+                error!("Failed to stop listener, not found: {}", bind_addr);
+                Err(TransportError::PeerNotFound.into())
+            }
+        }
+    }
+
+    fn list_connections(&self) -> Vec<UdpConnection> {
+        self.map
+            .iter()
+            .map(|(peer, worker)| UdpConnection::new(peer.clone(), worker.clone()))
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -167,13 +266,39 @@ impl Worker for UdpRouter {
         if msg_addr == self.main_addr {
             self.handle_route(ctx, msg.into_local_message()).await?;
         } else if msg_addr == self.api_addr {
+            let return_route = msg.return_route();
             let msg = UdpRouterMessage::decode(msg.payload())?;
-            match msg {
+            let response = match msg {
                 UdpRouterMessage::Register { accepts, self_addr } => {
                     trace!("handle_message register: {:?} => {:?}", accepts, self_addr);
-                    self.handle_register(accepts, self_addr).await?;
+                    return self.handle_register(accepts, self_addr).await;
+                }
+                UdpRouterMessage::Reroute { accepts, self_addr } => {
+                    UdpRouterResponse::Reroute(self.handle_reroute(accepts, self_addr).await)
+                }
+                UdpRouterMessage::Bind { bind_addr } => {
+                    UdpRouterResponse::Bind(self.handle_bind(bind_addr).await)
+                }
+                UdpRouterMessage::Open { bind_addr } => {
+                    UdpRouterResponse::Open(self.handle_open(bind_addr).await)
+                }
+                UdpRouterMessage::Close { tx_addr } => {
+                    UdpRouterResponse::Close(self.close_socket(&tx_addr).await)
+                }
+                UdpRouterMessage::StopListener { bind_addr } => {
+                    UdpRouterResponse::StopListener(self.handle_stop_listener(bind_addr).await)
+                }
+                UdpRouterMessage::Connect { peer } => {
+                    UdpRouterResponse::Connect(self.handle_connect(peer).await)
+                }
+                UdpRouterMessage::Disconnect { peer } => {
+                    UdpRouterResponse::Disconnect(self.handle_disconnect(peer).await)
+                }
+                UdpRouterMessage::ListConnections => {
+                    UdpRouterResponse::ListConnections(self.list_connections())
                 }
             };
+            ctx.send(return_route, response).await?;
         } else {
             return Err(TransportError::InvalidAddress.into());
         }
@@ -3,6 +3,7 @@ use std::{net::SocketAddr, str::FromStr};
 
 use ockam_core::{Address, Result};
 use ockam_node::Context;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     parse_socket_addr,
@@ -23,12 +24,112 @@ impl UdpTransport {
     }
 
     /// Start listening to incoming datagrams on an existing transport
-    pub async fn listen<S: AsRef<str>>(&self, bind_addr: S) -> Result<()> {
+    ///
+    /// Returns the local address that this transport is bound to.
+    ///
+    /// This can be useful, for example, when binding to port 0 to figure out
+    /// which port was actually bound.
+    pub async fn listen<S: AsRef<str>>(&self, bind_addr: S) -> Result<SocketAddr> {
         let bind_addr = parse_socket_addr(bind_addr)?;
-        self.router_handle.bind(bind_addr).await
+        let (local_addr, _) = self.router_handle.bind(bind_addr).await?;
+        Ok(local_addr)
     }
 
-    // TODO: connect method for manually connecting.
+    /// Stop listening on the given address
+    ///
+    /// The address must be the one returned by
+    /// [`listen`](crate::UdpTransport::listen). Peers which were only
+    /// reachable through this socket are forgotten.
+    pub async fn stop_listener<S: AsRef<str>>(&self, bind_addr: S) -> Result<()> {
+        let bind_addr = parse_socket_addr(bind_addr)?;
+        self.router_handle.stop_listener(bind_addr).await
+    }
+
+    /// Manually bind a socket dedicated to the given peer
+    ///
+    /// This step is optional because the underlying UdpRouter is capable of
+    /// lazily binding a socket upon arrival of the initial message.
+    /// Returns the address of the worker sending datagrams to the peer.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::UdpTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// udp.connect("127.0.0.1:5000").await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
+        self.router_handle.connect(peer).await
+    }
+
+    /// Disconnect from peer
+    ///
+    /// The peer's socket is closed, unless it is a listener or is
+    /// shared with other peers.
+    pub async fn disconnect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
+        self.router_handle.disconnect(peer).await
+    }
+
+    /// List all peers known to this transport
+    pub async fn list_connections(&self) -> Result<Vec<UdpConnection>> {
+        self.router_handle.list_connections().await
+    }
+
+    /// Punch a hole through NAT towards the peer registered as `peer`
+    ///
+    /// The punch is coordinated by the
+    /// [`RendezvousService`](crate::RendezvousService) listening on
+    /// `rendezvous_addr`. The peer has to call this function with the
+    /// names swapped at roughly the same time. On success the returned
+    /// [`Address`] can be used as the first hop of a route to the peer
+    /// node.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::UdpTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::{route, Result};
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// let peer = udp.punch_hole("203.0.113.1:4000", "alice", "bob").await?;
+    /// ctx.send(route![peer, "echoer"], "Hello Bob!".to_string()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn punch_hole<S: AsRef<str>>(
+        &self,
+        rendezvous_addr: S,
+        name: impl Into<String>,
+        peer: impl Into<String>,
+    ) -> Result<Address> {
+        let rendezvous_addr = parse_socket_addr(rendezvous_addr)?;
+        self.router_handle
+            .punch_hole(rendezvous_addr, name.into(), peer.into())
+            .await
+    }
+}
+
+/// A peer known to a [`UdpTransport`]
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub struct UdpConnection {
+    peer: Address,
+    worker: Address,
+}
+
+impl UdpConnection {
+    pub(crate) fn new(peer: Address, worker: Address) -> Self {
+        Self { peer, worker }
+    }
+
+    /// The `UDP` address of the peer
+    pub fn peer(&self) -> &Address {
+        &self.peer
+    }
+
+    /// The address of the worker sending datagrams to the peer
+    pub fn worker(&self) -> &Address {
+        &self.worker
+    }
 }
 
 #[derive(Clone)]
@@ -1,10 +1,33 @@
 use bytes::{Buf, BufMut, BytesMut};
 use ockam_core::TransportMessage;
 use ockam_core::{Decodable, Encodable};
+use ockam_node::metrics::{Counter, MetricsRegistry};
 use ockam_transport_core::TransportError;
 use tokio_util::codec::{Decoder, Encoder};
 
-pub(crate) struct TransportMessageCodec;
+/// Length-prefixed [`TransportMessage`] codec, counting the bytes
+/// it encodes and decodes
+pub(crate) struct TransportMessageCodec {
+    bytes_sent: Counter,
+    bytes_received: Counter,
+}
+
+impl TransportMessageCodec {
+    pub(crate) fn new(metrics: &MetricsRegistry) -> Self {
+        Self {
+            bytes_sent: metrics.counter(
+                "ockam_transport_bytes_sent_total",
+                "Bytes sent by transports",
+                &[("transport", "udp")],
+            ),
+            bytes_received: metrics.counter(
+                "ockam_transport_bytes_received_total",
+                "Bytes received by transports",
+                &[("transport", "udp")],
+            ),
+        }
+    }
+}
 
 impl Encoder<TransportMessage> for TransportMessageCodec {
     type Error = TransportError;
@@ -13,6 +36,7 @@ impl Encoder<TransportMessage> for TransportMessageCodec {
         let len = msg_buf.len();
         dst.put_u16(len as u16);
         dst.put(&msg_buf[..]);
+        self.bytes_sent.inc_by(2 + len as u64);
         Ok(())
     }
 }
@@ -28,6 +52,7 @@ impl Decoder for TransportMessageCodec {
         let len = src.get_u16() as usize;
         let msg = TransportMessage::decode(&src.split_to(len)[..])
             .map_err(|_| TransportError::RecvBadMessage)?;
+        self.bytes_received.inc_by(2 + len as u64);
 
         Ok(Some(msg))
     }
@@ -30,15 +30,15 @@ impl UdpListenProcessor {
         stream: SplitStream<UdpFramed<TransportMessageCodec>>,
         tx_addr: Address,
         router_handle: UdpRouterHandle,
-    ) -> Result<()> {
+    ) -> Result<Address> {
         let processor = Self {
             stream,
             tx_addr,
             router_handle,
         };
-        ctx.start_processor(Address::random_local(), processor)
-            .await?;
-        Ok(())
+        let addr = Address::random_local();
+        ctx.start_processor(addr.clone(), processor).await?;
+        Ok(addr)
     }
 }
 
@@ -0,0 +1,130 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ockam_core::{route, Result, Routed, Worker};
+use ockam_node::{Context, NodeBuilder};
+use ockam_transport_udp::{
+    PunchMessage, RendezvousRequest, RendezvousService, UdpTransport, RENDEZVOUS_SERVICE, UDP,
+};
+use tokio::sync::oneshot;
+
+/// Run `f` on a fresh node in its own thread
+///
+/// Each peer needs its own node, since all sockets of a node share
+/// a single UDP router.
+fn spawn_node<F, Fut>(f: F) -> thread::JoinHandle<()>
+where
+    F: FnOnce(Context) -> Fut + Send + 'static,
+    Fut: core::future::Future<Output = Result<()>> + Send + 'static,
+{
+    thread::spawn(move || {
+        let (ctx, mut executor) = NodeBuilder::without_access_control().no_logging().build();
+        executor
+            .execute(async move { f(ctx).await.unwrap() })
+            .unwrap();
+    })
+}
+
+#[test]
+fn punch_hole_between_two_nodes() {
+    let (rendezvous_ready_tx, rendezvous_ready_rx) = mpsc::channel();
+    let (rendezvous_done_tx, rendezvous_done_rx) = oneshot::channel::<()>();
+    let (bob_done_tx, bob_done_rx) = oneshot::channel::<()>();
+
+    let rendezvous = spawn_node(move |mut ctx| async move {
+        let transport = UdpTransport::create(&ctx).await?;
+        let bind_addr = transport.listen("127.0.0.1:0").await?;
+        RendezvousService::create(&ctx).await?;
+        rendezvous_ready_tx.send(bind_addr.to_string()).unwrap();
+
+        rendezvous_done_rx.await.unwrap();
+        ctx.stop().await
+    });
+    let rendezvous_addr = rendezvous_ready_rx.recv().unwrap();
+
+    let addr = rendezvous_addr.clone();
+    let bob = spawn_node(move |mut ctx| async move {
+        let transport = UdpTransport::create(&ctx).await?;
+        ctx.start_worker("echoer", Echoer).await?;
+        transport.punch_hole(addr, "bob", "alice").await?;
+
+        bob_done_rx.await.unwrap();
+        ctx.stop().await
+    });
+
+    let alice = spawn_node(move |mut ctx| async move {
+        let transport = UdpTransport::create(&ctx).await?;
+        let bob = transport
+            .punch_hole(rendezvous_addr, "alice", "bob")
+            .await?;
+
+        let msg = "Hello through the hole!".to_string();
+        let reply: String = ctx
+            .send_and_receive(route![bob, "echoer"], msg.clone())
+            .await?;
+        assert_eq!(reply, msg, "Should receive the same message");
+
+        bob_done_tx.send(()).unwrap();
+        rendezvous_done_tx.send(()).unwrap();
+        ctx.stop().await
+    });
+
+    alice.join().unwrap();
+    bob.join().unwrap();
+    rendezvous.join().unwrap();
+}
+
+#[test]
+fn punch_hole_rejected_by_rendezvous() {
+    let (rendezvous_ready_tx, rendezvous_ready_rx) = mpsc::channel();
+    let (rendezvous_done_tx, rendezvous_done_rx) = oneshot::channel::<()>();
+
+    let rendezvous = spawn_node(move |mut ctx| async move {
+        let transport = UdpTransport::create(&ctx).await?;
+        let bind_addr = transport.listen("127.0.0.1:0").await?;
+        RendezvousService::create(&ctx).await?;
+        rendezvous_ready_tx.send(bind_addr.to_string()).unwrap();
+
+        rendezvous_done_rx.await.unwrap();
+        ctx.stop().await
+    });
+    let rendezvous_addr = rendezvous_ready_rx.recv().unwrap();
+
+    let alice = spawn_node(move |mut ctx| async move {
+        let transport = UdpTransport::create(&ctx).await?;
+
+        // Register "bob" from another socket than the punching one
+        let rendezvous = route![(UDP, rendezvous_addr.clone()), RENDEZVOUS_SERVICE];
+        let register = RendezvousRequest::Register { name: "bob".into() };
+        let reply: PunchMessage = ctx.send_and_receive(rendezvous, register).await?;
+        assert!(matches!(reply, PunchMessage::Registered { .. }));
+
+        // The name is taken, which is reported without waiting for a timeout
+        let started = Instant::now();
+        let res = transport.punch_hole(rendezvous_addr, "bob", "alice").await;
+        assert!(res.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // The punching socket is closed again
+        assert!(transport.list_connections().await?.is_empty());
+
+        rendezvous_done_tx.send(()).unwrap();
+        ctx.stop().await
+    });
+
+    alice.join().unwrap();
+    rendezvous.join().unwrap();
+}
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
@@ -39,6 +39,37 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[ockam_macros::test]
+async fn connect_list_disconnect(ctx: &mut Context) -> Result<()> {
+    let transport = UdpTransport::create(ctx).await?;
+    let bind_address = transport.listen("127.0.0.1:0").await?.to_string();
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let sender_address = transport.connect(&bind_address).await?;
+    assert!(transport.connect(&bind_address).await.is_err());
+
+    let r = route![(UDP, bind_address.as_str()), "echoer"];
+    let reply: String = ctx.send_and_receive(r, "Hello".to_string()).await?;
+    assert_eq!(reply, "Hello");
+
+    // The listener learned about the sender's socket
+    let connections = transport.list_connections().await?;
+    assert_eq!(connections.len(), 2);
+    assert!(connections.iter().any(|c| c.worker() == &sender_address));
+
+    transport.disconnect(&bind_address).await?;
+    let connections = transport.list_connections().await?;
+    assert!(connections.iter().all(|c| c.worker() != &sender_address));
+
+    transport.stop_listener(&bind_address).await?;
+    assert!(transport.list_connections().await?.is_empty());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+    Ok(())
+}
+
 pub struct Echoer;
 
 #[ockam_core::worker]
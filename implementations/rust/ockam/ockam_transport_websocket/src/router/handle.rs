@@ -5,9 +5,9 @@ use ockam_core::{async_trait, Address, AsyncTryClone, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
-use crate::router::WebSocketRouterMessage;
-use crate::workers::{WebSocketListenProcessor, WorkerPair};
-use crate::{parse_socket_addr, WebSocketAddress};
+use crate::router::{WebSocketRouterRequest, WebSocketRouterResponse};
+use crate::workers::WorkerPair;
+use crate::{parse_socket_addr, WebSocketAddress, WebSocketConnection};
 
 /// A handle to connect to a WebSocketRouter.
 ///
@@ -41,18 +41,81 @@ impl WebSocketRouterHandle {
                 .map(|addr| addr.into()),
         );
         let self_addr = pair.tx_addr();
-        self.ctx
-            .send(
-                self.api_addr.clone(),
-                WebSocketRouterMessage::Register { accepts, self_addr },
-            )
-            .await
+        let response = self
+            .request(WebSocketRouterRequest::Register { accepts, self_addr })
+            .await?;
+
+        if let WebSocketRouterResponse::Register(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
+
+    /// Unregister the connection worker for the given `Address`.
+    pub(crate) async fn unregister(&self, self_addr: Address) -> Result<()> {
+        let response = self
+            .request(WebSocketRouterRequest::Unregister { self_addr })
+            .await?;
+
+        if let WebSocketRouterResponse::Unregister(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
     }
 
     /// Bind an incoming connection listener for this router.
     pub(crate) async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<SocketAddr> {
-        let socket_addr = addr.into();
-        WebSocketListenProcessor::start(&self.ctx, self.async_try_clone().await?, socket_addr).await
+        let bind_addr = addr.into();
+        let response = self
+            .request(WebSocketRouterRequest::Listen { bind_addr })
+            .await?;
+
+        if let WebSocketRouterResponse::Listen(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
+
+    /// Stop the connection listener bound to the given address.
+    pub(crate) async fn stop_listener(&self, addr: impl Into<SocketAddr>) -> Result<()> {
+        let bind_addr = addr.into();
+        let response = self
+            .request(WebSocketRouterRequest::StopListener { bind_addr })
+            .await?;
+
+        if let WebSocketRouterResponse::StopListener(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
+
+    /// List all connections established on this router.
+    pub(crate) async fn list_connections(&self) -> Result<Vec<WebSocketConnection>> {
+        let response = self
+            .request(WebSocketRouterRequest::ListConnections)
+            .await?;
+
+        if let WebSocketRouterResponse::ListConnections(res) = response {
+            Ok(res)
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
+
+    /// Send a request to the router and wait for its response.
+    async fn request(&self, req: WebSocketRouterRequest) -> Result<WebSocketRouterResponse> {
+        let mut child_ctx = self.ctx.new_detached(Address::random_local()).await?;
+        child_ctx.send(self.api_addr.clone(), req).await?;
+
+        Ok(child_ctx
+            .receive::<WebSocketRouterResponse>()
+            .await?
+            .take()
+            .body())
     }
 
     /// Return the peer's `SocketAddr` and `hostnames` given a plain `String` address.
@@ -84,15 +147,32 @@ impl WebSocketRouterHandle {
     }
 
     /// Establish an outgoing WS connection on an existing transport.
-    pub(crate) async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
-        // Get peer address and connect to it.
-        let (peer_addr, hostnames) = Self::resolve_peer(peer.as_ref())?;
+    pub(crate) async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
+        let response = self
+            .request(WebSocketRouterRequest::Connect {
+                peer: peer.as_ref().to_string(),
+            })
+            .await?;
 
-        // Create a new `WorkerPair` for the given peer, initializing a new pair
-        // of sender worker and receiver processor.
-        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames).await?;
+        if let WebSocketRouterResponse::Connect(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
 
-        // Handle node's register request.
-        self.register(&pair).await
+    /// Disconnect an outgoing WS connection on an existing transport.
+    pub(crate) async fn disconnect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
+        let response = self
+            .request(WebSocketRouterRequest::Disconnect {
+                peer: peer.as_ref().to_string(),
+            })
+            .await?;
+
+        if let WebSocketRouterResponse::Disconnect(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
     }
 }
@@ -0,0 +1,43 @@
+use std::net::SocketAddr;
+
+use ockam_core::{Address, Message, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::WebSocketConnection;
+
+#[derive(Serialize, Deserialize, Debug, Message)]
+pub(crate) enum WebSocketRouterRequest {
+    /// Register a new client to this routing scope.
+    Register {
+        /// Specify an accept scope for this client.
+        accepts: Vec<Address>,
+        /// The clients own worker bus address.
+        self_addr: Address,
+    },
+    /// Unregister (usually, after disconnection)
+    Unregister {
+        /// The clients own worker bus address.
+        self_addr: Address,
+    },
+    /// Connect to a remote peer
+    Connect { peer: String },
+    /// Disconnect from a remote peer
+    Disconnect { peer: String },
+    /// Start listening for incoming connections
+    Listen { bind_addr: SocketAddr },
+    /// Stop the listener bound to the given address
+    StopListener { bind_addr: SocketAddr },
+    /// List all established connections
+    ListConnections,
+}
+
+#[derive(Serialize, Deserialize, Debug, Message)]
+pub(crate) enum WebSocketRouterResponse {
+    Register(Result<()>),
+    Unregister(Result<()>),
+    Connect(Result<Address>),
+    Disconnect(Result<()>),
+    Listen(Result<SocketAddr>),
+    StopListener(Result<()>),
+    ListConnections(Vec<WebSocketConnection>),
+}
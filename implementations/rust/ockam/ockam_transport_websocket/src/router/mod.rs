@@ -1,30 +1,19 @@
 use core::str::FromStr;
 use std::collections::BTreeMap;
+use std::net::SocketAddr;
 use std::ops::Deref;
 
 pub(crate) use handle::WebSocketRouterHandle;
-use ockam_core::{
-    async_trait, Address, Any, Decodable, LocalMessage, Message, Result, Routed, Worker,
-};
+pub(crate) use messages::*;
+use ockam_core::{async_trait, Address, Any, Decodable, LocalMessage, Result, Routed, Worker};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
-use crate::workers::WorkerPair;
-use crate::{WebSocketAddress, WS};
-use serde::{Deserialize, Serialize};
+use crate::workers::{WebSocketListenProcessor, WorkerPair};
+use crate::{WebSocketAddress, WebSocketConnection, WS};
 
 mod handle;
-
-#[derive(Serialize, Deserialize, Debug, Clone, Hash, Ord, PartialOrd, Eq, PartialEq, Message)]
-pub(crate) enum WebSocketRouterMessage {
-    /// Register a new client to this routing scope.
-    Register {
-        /// Specify an accept scope for this client.
-        accepts: Vec<Address>,
-        /// The clients own worker bus address.
-        self_addr: Address,
-    },
-}
+mod messages;
 
 /// A WebSocket address router and connection listener.
 ///
@@ -39,6 +28,10 @@ pub(crate) struct WebSocketRouter {
     main_addr: Address,
     api_addr: Address,
     map: BTreeMap<Address, Address>,
+    /// Established connections, by sender worker address
+    connections: BTreeMap<Address, Address>,
+    /// Listener processors, by bound socket address
+    listeners: BTreeMap<SocketAddr, Address>,
     allow_auto_connection: bool,
 }
 
@@ -61,6 +54,8 @@ impl WebSocketRouter {
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
             map: BTreeMap::new(),
+            connections: BTreeMap::new(),
+            listeners: BTreeMap::new(),
             allow_auto_connection: true,
         };
 
@@ -92,18 +87,42 @@ impl Worker for WebSocketRouter {
     }
 
     async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let return_route = msg.return_route();
         let msg_addr = msg.msg_addr();
 
         if msg_addr == self.main_addr {
             self.handle_route(ctx, msg.into_local_message()).await?;
         } else if msg_addr == self.api_addr {
-            let msg = WebSocketRouterMessage::decode(msg.payload())?;
-            match msg {
-                WebSocketRouterMessage::Register { accepts, self_addr } => {
+            let msg = WebSocketRouterRequest::decode(msg.payload())?;
+            let response = match msg {
+                WebSocketRouterRequest::Register { accepts, self_addr } => {
                     trace!("handle_message register: {:?} => {:?}", accepts, self_addr);
-                    self.handle_register(accepts, self_addr).await?;
+                    WebSocketRouterResponse::Register(
+                        self.handle_register(accepts, self_addr).await,
+                    )
+                }
+                WebSocketRouterRequest::Unregister { self_addr } => {
+                    WebSocketRouterResponse::Unregister(self.handle_unregister(self_addr).await)
+                }
+                WebSocketRouterRequest::Connect { peer } => {
+                    WebSocketRouterResponse::Connect(self.connect(peer).await)
+                }
+                WebSocketRouterRequest::Disconnect { peer } => {
+                    WebSocketRouterResponse::Disconnect(self.handle_disconnect(peer).await)
+                }
+                WebSocketRouterRequest::Listen { bind_addr } => {
+                    WebSocketRouterResponse::Listen(self.handle_listen(bind_addr).await)
+                }
+                WebSocketRouterRequest::StopListener { bind_addr } => {
+                    WebSocketRouterResponse::StopListener(
+                        self.handle_stop_listener(bind_addr).await,
+                    )
+                }
+                WebSocketRouterRequest::ListConnections => {
+                    WebSocketRouterResponse::ListConnections(self.list_connections())
                 }
             };
+            ctx.send(return_route, response).await?;
         } else {
             return Err(TransportError::InvalidAddress.into());
         }
@@ -173,6 +192,10 @@ impl WebSocketRouter {
             }
         }
 
+        // The first address is always the peer's socket address.
+        self.connections
+            .insert(self_addr.clone(), accepts[0].clone());
+
         // Add a new entry for each hostname/address pair.
         for accept in accepts {
             self.map.insert(accept.clone(), self_addr.clone());
@@ -181,22 +204,78 @@ impl WebSocketRouter {
         Ok(())
     }
 
+    async fn handle_unregister(&mut self, self_addr: Address) -> Result<()> {
+        trace!("WS unregistration request: {}", &self_addr);
+
+        self.map.retain(|_, self_addr_i| self_addr_i != &self_addr);
+        self.connections.remove(&self_addr);
+
+        Ok(())
+    }
+
+    async fn handle_disconnect(&mut self, peer: String) -> Result<()> {
+        let (peer_addr, _hostnames) = WebSocketRouterHandle::resolve_peer(peer)?;
+        let ws_address: Address = WebSocketAddress::from(peer_addr).into();
+
+        let self_addr = if let Some(self_addr) = self.map.get(&ws_address) {
+            self_addr.clone()
+        } else {
+            error!("Failed to disconnect, peer not found: {}", ws_address);
+            return Err(TransportError::PeerNotFound.into());
+        };
+
+        self.handle_unregister(self_addr.clone()).await?;
+        self.ctx.stop_worker(self_addr).await
+    }
+
+    async fn handle_listen(&mut self, bind_addr: SocketAddr) -> Result<SocketAddr> {
+        let handle = self.create_self_handle(&self.ctx).await?;
+        let (socket_addr, processor_addr) =
+            WebSocketListenProcessor::start(&self.ctx, handle, bind_addr).await?;
+        self.listeners.insert(socket_addr, processor_addr);
+        Ok(socket_addr)
+    }
+
+    async fn handle_stop_listener(&mut self, bind_addr: SocketAddr) -> Result<()> {
+        match self.listeners.remove(&bind_addr) {
+            Some(processor_addr) => self.ctx.stop_processor(processor_addr).await,
+            None => {
+                error!("Failed to stop listener, not found: {}", bind_addr);
+                Err(TransportError::PeerNotFound.into())
+            }
+        }
+    }
+
+    fn list_connections(&self) -> Vec<WebSocketConnection> {
+        self.connections
+            .iter()
+            .map(|(worker, peer)| WebSocketConnection::new(peer.clone(), worker.clone()))
+            .collect()
+    }
+
     async fn connect(&mut self, peer: String) -> Result<Address> {
         // Get peer address and connect to it.
         let (peer_addr, hostnames) = WebSocketRouterHandle::resolve_peer(peer)?;
 
-        // Create a new `WorkerPair` for the given peer, initializing a new pair
-        // of sender worker and receiver processor.
-        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames).await?;
-
-        // Handle node's register request.
-        let mut accepts = vec![pair.peer()];
+        let mut accepts: Vec<Address> = vec![WebSocketAddress::from(peer_addr).into()];
         accepts.extend(
-            pair.hostnames()
+            hostnames
                 .iter()
                 .filter_map(|x| WebSocketAddress::from_str(x).ok())
                 .map(|addr| addr.into()),
         );
+
+        // Do not spawn a worker pair which the router would reject.
+        if accepts.iter().any(|accept| self.map.contains_key(accept)) {
+            return Err(TransportError::AlreadyConnected.into());
+        }
+
+        // Create a new `WorkerPair` for the given peer, initializing a new pair
+        // of sender worker and receiver processor.
+        let router_handle = self.create_self_handle(&self.ctx).await?;
+        let pair = WorkerPair::from_client(&self.ctx, router_handle, peer_addr, hostnames).await?;
+
+        // Handle node's register request.
         let self_addr = pair.tx_addr();
         self.handle_register(accepts, self_addr.clone()).await?;
 
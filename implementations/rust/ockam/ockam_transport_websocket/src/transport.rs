@@ -4,6 +4,7 @@ use std::str::FromStr;
 
 use ockam_core::{Address, Result};
 use ockam_node::Context;
+use serde::{Deserialize, Serialize};
 
 use crate::{parse_socket_addr, WebSocketRouter, WebSocketRouterHandle, WS};
 
@@ -77,10 +78,15 @@ impl WebSocketTransport {
     /// ws.connect("127.0.0.1:5000").await?; // and connect to port 5000
     /// # Ok(()) }
     /// ```
-    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
+    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
         self.router_handle.connect(peer).await
     }
 
+    /// Disconnect from peer
+    pub async fn disconnect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
+        self.router_handle.disconnect(peer).await
+    }
+
     /// Start listening to incoming connections on an existing transport.
     ///
     /// Returns the local address that this transport is bound to.
@@ -100,6 +106,55 @@ impl WebSocketTransport {
         let bind_addr = parse_socket_addr(bind_addr)?;
         self.router_handle.bind(bind_addr).await
     }
+
+    /// Stop listening on the given address
+    ///
+    /// The address must be the one returned by
+    /// [`listen`](crate::WebSocketTransport::listen). Connections already
+    /// accepted by this listener are kept open.
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::WebSocketTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// let bind_addr = ws.listen("127.0.0.1:0").await?;
+    /// ws.stop_listener(bind_addr.to_string()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn stop_listener<S: AsRef<str>>(&self, bind_addr: S) -> Result<()> {
+        let bind_addr = parse_socket_addr(bind_addr)?;
+        self.router_handle.stop_listener(bind_addr).await
+    }
+
+    /// List all connections, both incoming and outgoing, of this transport
+    pub async fn list_connections(&self) -> Result<Vec<WebSocketConnection>> {
+        self.router_handle.list_connections().await
+    }
+}
+
+/// An established WebSocket connection
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketConnection {
+    peer: Address,
+    worker: Address,
+}
+
+impl WebSocketConnection {
+    pub(crate) fn new(peer: Address, worker: Address) -> Self {
+        Self { peer, worker }
+    }
+
+    /// The `WS` address of the remote peer
+    pub fn peer(&self) -> &Address {
+        &self.peer
+    }
+
+    /// The address of the worker sending messages to the peer
+    pub fn worker(&self) -> &Address {
+        &self.worker
+    }
 }
 
 #[derive(Clone)]
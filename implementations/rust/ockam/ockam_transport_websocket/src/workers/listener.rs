@@ -2,7 +2,7 @@ use std::net::SocketAddr;
 
 use tokio::net::TcpListener;
 
-use ockam_core::{async_trait, Address, Processor, Result};
+use ockam_core::{async_trait, Address, AsyncTryClone, Processor, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
@@ -20,11 +20,13 @@ pub(crate) struct WebSocketListenProcessor {
 
 impl WebSocketListenProcessor {
     /// Create and start a new instance bound to the given `addr`.
+    ///
+    /// Returns the bound socket address and the processor's address.
     pub(crate) async fn start(
         ctx: &Context,
         router_handle: WebSocketRouterHandle,
         addr: SocketAddr,
-    ) -> Result<SocketAddr> {
+    ) -> Result<(SocketAddr, Address)> {
         debug!("Binding WebSocketListener to {}", addr);
         let inner = TcpListener::bind(addr)
             .await
@@ -35,8 +37,8 @@ impl WebSocketListenProcessor {
             router_handle,
         };
         let waddr = Address::random_local();
-        ctx.start_processor(waddr, processor).await?;
-        Ok(saddr)
+        ctx.start_processor(waddr.clone(), processor).await?;
+        Ok((saddr, waddr))
     }
 }
 
@@ -59,10 +61,16 @@ impl Processor for WebSocketListenProcessor {
         debug!("TCP connection accepted");
 
         // Spawn a connection worker for it
-        let pair = WorkerPair::from_server(ctx, ws_stream, peer, vec![]).await?;
+        let handle = self.router_handle.async_try_clone().await?;
+        let pair = WorkerPair::from_server(ctx, handle, ws_stream, peer, vec![]).await?;
 
-        // Register the connection with the local TcpRouter
-        self.router_handle.register(&pair).await?;
+        // Register the connection with the local TcpRouter, and drop
+        // it if the router rejects it
+        if let Err(e) = self.router_handle.register(&pair).await {
+            warn!("Failed to register connection from {}: {}", peer, e);
+            ctx.stop_worker(pair.tx_addr()).await?;
+            return Ok(true);
+        }
         debug!("TCP connection registered");
 
         Ok(true)
@@ -4,10 +4,11 @@ use futures_util::stream::SplitStream;
 use futures_util::StreamExt;
 use tokio_tungstenite::WebSocketStream;
 
-use crate::WebSocketAddress;
+use crate::{WebSocketAddress, WebSocketRouterHandle};
 use ockam_core::{
     async_trait, Address, Decodable, LocalMessage, Processor, Result, TransportMessage,
 };
+use ockam_node::metrics::Counter;
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
@@ -24,16 +25,42 @@ where
 {
     ws_stream: SplitStream<WebSocketStream<S>>,
     peer_addr: Address,
+    router_handle: WebSocketRouterHandle,
+    sender_addr: Address,
+    bytes_received: Counter,
 }
 
 impl<S> WebSocketRecvProcessor<S>
 where
     S: AsyncStream,
 {
-    pub(crate) fn new(ws_stream: SplitStream<WebSocketStream<S>>, peer: SocketAddr) -> Self {
+    pub(crate) fn new(
+        ws_stream: SplitStream<WebSocketStream<S>>,
+        peer: SocketAddr,
+        router_handle: WebSocketRouterHandle,
+        sender_addr: Address,
+    ) -> Self {
         Self {
             ws_stream,
             peer_addr: WebSocketAddress::from(peer).into(),
+            router_handle,
+            sender_addr,
+            bytes_received: Counter::default(),
+        }
+    }
+
+    /// Forget the closed connection and stop its sender, which in
+    /// turn stops this processor
+    async fn close(&self, ctx: &Context) {
+        if let Err(e) = self
+            .router_handle
+            .unregister(self.sender_addr.clone())
+            .await
+        {
+            warn!("Failed to unregister peer '{}': {}", self.peer_addr, e);
+        }
+        if let Err(e) = ctx.stop_worker(self.sender_addr.clone()).await {
+            debug!("Sender of peer '{}' already stopped: {}", self.peer_addr, e);
         }
     }
 }
@@ -46,6 +73,11 @@ where
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        self.bytes_received = ctx.metrics().counter(
+            "ockam_transport_bytes_received_total",
+            "Bytes received by transports",
+            &[("transport", "websocket")],
+        );
         ctx.set_cluster(crate::CLUSTER_NAME).await
     }
 
@@ -62,6 +94,7 @@ where
                         "Connection to peer '{}' was closed; dropping stream",
                         self.peer_addr
                     );
+                    self.close(ctx).await;
                     return Ok(false);
                 }
             },
@@ -70,12 +103,14 @@ where
                     "Stream connected to peer '{}' is exhausted; dropping stream",
                     self.peer_addr
                 );
+                self.close(ctx).await;
                 return Ok(false);
             }
         };
 
         // Extract message payload
         let encoded_msg = ws_msg.into_data();
+        self.bytes_received.inc_by(encoded_msg.len() as u64);
 
         // Deserialize the message
         let mut msg =
@@ -7,16 +7,17 @@ use tokio_tungstenite::tungstenite::protocol::Message as WebSocketMessage;
 
 use crate::error::WebSocketError;
 use ockam_core::{
-    async_trait, route, Address, Any, Decodable, Encodable, LocalMessage, Result, Routed,
-    TransportMessage, Worker,
+    async_trait, route, Address, Any, AsyncTryClone, Decodable, Encodable, LocalMessage, Result,
+    Routed, TransportMessage, Worker,
 };
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, DelayedEvent};
 use ockam_transport_core::TransportError;
 
 use crate::workers::{
     AsyncStream, TcpClientStream, TcpServerStream, WebSocketRecvProcessor, WebSocketStream,
 };
-use crate::WebSocketAddress;
+use crate::{WebSocketAddress, WebSocketRouterHandle};
 
 /// Transmit and receive peers of a WebSocket connection.
 #[derive(Debug)]
@@ -43,6 +44,7 @@ impl WorkerPair {
     /// The WebSocket stream is created when the `WebSocketSendWorker` is initialized.
     pub(crate) async fn from_client(
         ctx: &Context,
+        router_handle: WebSocketRouterHandle,
         peer: SocketAddr,
         hostnames: Vec<String>,
     ) -> Result<WorkerPair> {
@@ -50,6 +52,7 @@ impl WorkerPair {
 
         let internal_addr = Address::random_local();
         let sender = WebSocketSendWorker::<TcpClientStream>::new(
+            router_handle,
             peer,
             internal_addr.clone(),
             DelayedEvent::create(ctx, internal_addr.clone(), vec![]).await?,
@@ -71,6 +74,7 @@ impl WorkerPair {
     /// returns a `WorkerPair` instance that will be registered by the `WebSocketRouter`.
     pub(crate) async fn from_server(
         ctx: &Context,
+        router_handle: WebSocketRouterHandle,
         stream: WebSocketStream<TcpServerStream>,
         peer: SocketAddr,
         hostnames: Vec<String>,
@@ -79,6 +83,7 @@ impl WorkerPair {
 
         let internal_addr = Address::random_local();
         let sender = WebSocketSendWorker::<TcpServerStream>::new(
+            router_handle,
             stream,
             peer,
             internal_addr.clone(),
@@ -109,10 +114,13 @@ where
 {
     ws_stream: Option<SplitStream<WebSocketStream<S>>>,
     ws_sink: Option<SplitSink<WebSocketStream<S>, WebSocketMessage>>,
+    router_handle: WebSocketRouterHandle,
+    rx_addr: Option<Address>,
     peer: SocketAddr,
     internal_addr: Address,
     heartbeat: DelayedEvent<Vec<u8>>,
     heartbeat_interval: Option<Duration>,
+    bytes_sent: Counter,
 }
 
 impl<S> WebSocketSendWorker<S>
@@ -122,12 +130,23 @@ where
     async fn handle_initialize(&mut self, ctx: &mut Context) -> Result<()> {
         if let Some(ws_stream) = self.ws_stream.take() {
             let rx_addr = Address::random_local();
-            let receiver = WebSocketRecvProcessor::new(ws_stream, self.peer);
+            let receiver = WebSocketRecvProcessor::new(
+                ws_stream,
+                self.peer,
+                self.router_handle.async_try_clone().await?,
+                ctx.address(),
+            );
             ctx.start_processor(rx_addr.clone(), receiver).await?;
+            self.rx_addr = Some(rx_addr);
         } else {
             return Err(TransportError::GenericIo.into());
         }
 
+        self.bytes_sent = ctx.metrics().counter(
+            "ockam_transport_bytes_sent_total",
+            "Bytes sent by transports",
+            &[("transport", "websocket")],
+        );
         ctx.set_cluster(crate::CLUSTER_NAME).await?;
         self.schedule_heartbeat().await?;
         Ok(())
@@ -142,6 +161,18 @@ where
         self.heartbeat.schedule(heartbeat_interval).await
     }
 
+    async fn stop_and_unregister(&self, ctx: &Context) -> Result<()> {
+        self.router_handle.unregister(ctx.address()).await?;
+        ctx.stop_worker(ctx.address()).await
+    }
+
+    async fn handle_shutdown(&mut self, ctx: &mut Context) -> Result<()> {
+        if let Some(rx_addr) = self.rx_addr.take() {
+            let _ = ctx.stop_processor(rx_addr).await;
+        }
+        Ok(())
+    }
+
     /// Receive messages from the `WebSocketRouter` to send
     /// across the `WebSocketStream` to the next remote peer.
     async fn handle_msg(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
@@ -155,18 +186,16 @@ where
 
         let recipient = msg.msg_addr();
         if recipient == self.internal_addr {
-            let msg = TransportMessage::v1(route![], route![], vec![]);
+            let msg = TransportMessage::v1(route![], route![], vec![]).encode()?;
+            let len = msg.len();
             // Sending empty heartbeat
-            if ws_sink
-                .send(WebSocketMessage::from(msg.encode()?))
-                .await
-                .is_err()
-            {
+            if ws_sink.send(WebSocketMessage::from(msg)).await.is_err() {
                 warn!("Failed to send heartbeat to peer {}", self.peer);
-                ctx.stop_worker(ctx.address()).await?;
+                self.stop_and_unregister(ctx).await?;
 
                 return Ok(());
             }
+            self.bytes_sent.inc_by(len as u64);
             debug!("Sent heartbeat to peer {}", self.peer);
         } else {
             let mut msg = LocalMessage::decode(msg.payload())?.into_transport_message();
@@ -175,12 +204,14 @@ where
             // knows what to do with the incoming message
             msg.onward_route.step()?;
 
-            let msg = WebSocketMessage::from(msg.encode()?);
-            if ws_sink.send(msg).await.is_err() {
+            let msg = msg.encode()?;
+            let len = msg.len();
+            if ws_sink.send(WebSocketMessage::from(msg)).await.is_err() {
                 warn!("Failed to send message to peer {}", self.peer);
-                ctx.stop_worker(ctx.address()).await?;
+                self.stop_and_unregister(ctx).await?;
                 return Ok(());
             }
+            self.bytes_sent.inc_by(len as u64);
             debug!("Sent message to peer {}", self.peer);
         }
 
@@ -192,6 +223,7 @@ where
 
 impl WebSocketSendWorker<TcpServerStream> {
     fn new(
+        router_handle: WebSocketRouterHandle,
         stream: WebSocketStream<TcpServerStream>,
         peer: SocketAddr,
         internal_addr: Address,
@@ -201,23 +233,34 @@ impl WebSocketSendWorker<TcpServerStream> {
         Self {
             ws_sink: Some(ws_sink),
             ws_stream: Some(ws_stream),
+            router_handle,
+            rx_addr: None,
             peer,
             internal_addr,
             heartbeat,
             heartbeat_interval: None,
+            bytes_sent: Counter::default(),
         }
     }
 }
 
 impl WebSocketSendWorker<TcpClientStream> {
-    fn new(peer: SocketAddr, internal_addr: Address, heartbeat: DelayedEvent<Vec<u8>>) -> Self {
+    fn new(
+        router_handle: WebSocketRouterHandle,
+        peer: SocketAddr,
+        internal_addr: Address,
+        heartbeat: DelayedEvent<Vec<u8>>,
+    ) -> Self {
         Self {
             ws_stream: None,
             ws_sink: None,
+            router_handle,
+            rx_addr: None,
             peer,
             internal_addr,
             heartbeat,
             heartbeat_interval: None,
+            bytes_sent: Counter::default(),
         }
     }
 
@@ -245,6 +288,10 @@ impl Worker for WebSocketSendWorker<TcpServerStream> {
         Ok(())
     }
 
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.handle_shutdown(ctx).await
+    }
+
     async fn handle_message(
         &mut self,
         ctx: &mut Context,
@@ -265,6 +312,10 @@ impl Worker for WebSocketSendWorker<TcpClientStream> {
         Ok(())
     }
 
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.handle_shutdown(ctx).await
+    }
+
     async fn handle_message(
         &mut self,
         ctx: &mut Context,
@@ -1,3 +1,4 @@
+use core::time::Duration;
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, Result, Routed, Worker};
 use ockam_node::Context;
@@ -29,6 +30,43 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[ockam_macros::test]
+async fn connect_list_disconnect(ctx: &mut Context) -> Result<()> {
+    let transport = WebSocketTransport::create(ctx).await?;
+    let listener_address = transport.listen("127.0.0.1:0").await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let sender_address = transport.connect(listener_address.to_string()).await?;
+
+    // Both the outgoing and the accepted connection are listed
+    ctx.sleep(Duration::from_millis(100)).await;
+    let connections = transport.list_connections().await?;
+    assert_eq!(connections.len(), 2);
+    assert!(connections.iter().any(|c| c.worker() == &sender_address));
+
+    let r = route![(WS, listener_address.to_string()), "echoer"];
+    let reply: String = ctx.send_and_receive(r, "Hello".to_string()).await?;
+    assert_eq!(reply, "Hello");
+
+    transport.disconnect(listener_address.to_string()).await?;
+    let connections = transport.list_connections().await?;
+    assert!(connections.iter().all(|c| c.worker() != &sender_address));
+
+    transport
+        .stop_listener(listener_address.to_string())
+        .await?;
+    assert!(transport
+        .stop_listener(listener_address.to_string())
+        .await
+        .is_err());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
 pub struct Echoer;
 
 #[ockam_core::worker]